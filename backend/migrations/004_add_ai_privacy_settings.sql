@@ -0,0 +1,12 @@
+-- Migration: Add per-user opt-out from sending CV data to external AI providers
+-- Existing users keep the current behaviour (sharing enabled)
+
+DO $$ 
+BEGIN
+    IF NOT EXISTS (
+        SELECT 1 FROM information_schema.columns 
+        WHERE table_name = 'users' AND column_name = 'share_cv_with_ai'
+    ) THEN
+        ALTER TABLE users ADD COLUMN share_cv_with_ai BOOLEAN NOT NULL DEFAULT TRUE;
+    END IF;
+END $$;
@@ -0,0 +1,36 @@
+-- Migration: Add structured CV parse tables
+-- The deterministic parser writes here on upload; users review and correct the result
+
+-- Create cv_parses table (structured CV data, one per user, reviewable)
+CREATE TABLE IF NOT EXISTS cv_parses (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    full_name VARCHAR(255),
+    email VARCHAR(255),
+    phone VARCHAR(50),
+    location TEXT,
+    links TEXT[] NOT NULL DEFAULT '{}',
+    summary TEXT,
+    skills TEXT[] NOT NULL DEFAULT '{}',
+    reviewed_at TIMESTAMP WITH TIME ZONE,
+    enriched_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Create cv_parse_entries table (education, experience, project and certification entries)
+CREATE TABLE IF NOT EXISTS cv_parse_entries (
+    id SERIAL PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES cv_parses(user_id) ON DELETE CASCADE,
+    section VARCHAR(20) NOT NULL CHECK (section IN ('education', 'experience', 'project', 'certification')),
+    position INTEGER NOT NULL DEFAULT 0,
+    title TEXT NOT NULL,
+    organization TEXT,
+    location TEXT,
+    start_date DATE,
+    end_date DATE,
+    is_current BOOLEAN NOT NULL DEFAULT FALSE,
+    description TEXT,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_cv_parse_entries_user_section ON cv_parse_entries(user_id, section, position);
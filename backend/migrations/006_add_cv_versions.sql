@@ -0,0 +1,35 @@
+-- Migration: Keep original CV files with versioning
+-- Every upload becomes a version; exactly one version per user can be active
+
+-- Create cv_versions table (uploaded files, stored through the configured storage backend)
+CREATE TABLE IF NOT EXISTS cv_versions (
+    id SERIAL PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    version_number INTEGER NOT NULL,
+    file_name VARCHAR(255) NOT NULL,
+    content_type VARCHAR(100) NOT NULL,
+    format VARCHAR(20) NOT NULL,
+    size_bytes BIGINT NOT NULL,
+    sha256 CHAR(64) NOT NULL,
+    storage_backend VARCHAR(20) NOT NULL,
+    storage_key TEXT NOT NULL,
+    extracted_text TEXT NOT NULL,
+    skills TEXT[] NOT NULL DEFAULT '{}',
+    is_active BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (user_id, version_number)
+);
+
+CREATE INDEX IF NOT EXISTS idx_cv_versions_user_sha256 ON cv_versions(user_id, sha256);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_cv_versions_one_active ON cv_versions(user_id) WHERE is_active;
+
+-- Link the structured parse to the version it came from
+DO $$
+BEGIN
+    IF NOT EXISTS (
+        SELECT 1 FROM information_schema.columns
+        WHERE table_name = 'cv_parses' AND column_name = 'cv_version_id'
+    ) THEN
+        ALTER TABLE cv_parses ADD COLUMN cv_version_id INTEGER REFERENCES cv_versions(id) ON DELETE SET NULL;
+    END IF;
+END $$;
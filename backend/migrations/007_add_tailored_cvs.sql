@@ -0,0 +1,19 @@
+-- Migration: Job-tailored CVs
+-- Each tailored CV is kept as a snapshot so users can see exactly what they sent with an application
+
+-- Create tailored_cvs table (document is the renderer-neutral CV as JSON)
+CREATE TABLE IF NOT EXISTS tailored_cvs (
+    id SERIAL PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    job_id INTEGER NOT NULL REFERENCES jobs(id) ON DELETE CASCADE,
+    application_id INTEGER REFERENCES application_tracking(id) ON DELETE SET NULL,
+    document JSONB NOT NULL,
+    template VARCHAR(20) NOT NULL,
+    matched_skills TEXT[] NOT NULL DEFAULT '{}',
+    missing_skills TEXT[] NOT NULL DEFAULT '{}',
+    ai_rewritten BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_tailored_cvs_user_job ON tailored_cvs(user_id, job_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_tailored_cvs_application ON tailored_cvs(application_id);
@@ -0,0 +1,17 @@
+-- Migration: Public profiles
+-- Opt-in shareable profile pages at a user-chosen slug, with per-field visibility
+
+-- Create public_profiles table
+CREATE TABLE IF NOT EXISTS public_profiles (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    slug VARCHAR(40) NOT NULL,
+    is_public BOOLEAN NOT NULL DEFAULT FALSE,
+    show_email BOOLEAN NOT NULL DEFAULT FALSE,
+    show_cv BOOLEAN NOT NULL DEFAULT FALSE,
+    show_skills BOOLEAN NOT NULL DEFAULT TRUE,
+    show_learning BOOLEAN NOT NULL DEFAULT TRUE,
+    show_roadmaps BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT public_profiles_slug_key UNIQUE (slug)
+);
@@ -0,0 +1,22 @@
+-- Migration: Scheduled account deletion
+-- Accounts are purged by a background task once the grace period has passed
+
+DO $$ 
+BEGIN
+    IF NOT EXISTS (
+        SELECT 1 FROM information_schema.columns 
+        WHERE table_name = 'users' AND column_name = 'deletion_requested_at'
+    ) THEN
+        ALTER TABLE users ADD COLUMN deletion_requested_at TIMESTAMP WITH TIME ZONE;
+    END IF;
+
+    IF NOT EXISTS (
+        SELECT 1 FROM information_schema.columns 
+        WHERE table_name = 'users' AND column_name = 'deletion_scheduled_for'
+    ) THEN
+        ALTER TABLE users ADD COLUMN deletion_scheduled_for TIMESTAMP WITH TIME ZONE;
+    END IF;
+END $$;
+
+CREATE INDEX IF NOT EXISTS idx_users_deletion_scheduled_for
+    ON users(deletion_scheduled_for) WHERE deletion_scheduled_for IS NOT NULL;
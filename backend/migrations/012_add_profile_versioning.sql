@@ -0,0 +1,59 @@
+-- Migration: Profile versioning and change history
+-- profile_version backs the ETag used for optimistic concurrency on PUT /api/profile;
+-- profile_history records every change to skills and target roles so it can be reverted
+
+DO $$ 
+BEGIN
+    IF NOT EXISTS (
+        SELECT 1 FROM information_schema.columns 
+        WHERE table_name = 'users' AND column_name = 'profile_version'
+    ) THEN
+        ALTER TABLE users ADD COLUMN profile_version INTEGER NOT NULL DEFAULT 1;
+    END IF;
+END $$;
+
+-- Create profile_history table
+CREATE TABLE IF NOT EXISTS profile_history (
+    id SERIAL PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    field VARCHAR(20) NOT NULL CHECK (field IN ('skills', 'target_roles')),
+    old_value TEXT[] NOT NULL DEFAULT '{}',
+    new_value TEXT[] NOT NULL DEFAULT '{}',
+    profile_version INTEGER NOT NULL,
+    changed_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_profile_history_user_id ON profile_history(user_id, changed_at DESC);
+
+-- Bumps profile_version and records skill/target role changes, whichever
+-- endpoint makes them (profile updates, CV review, AI skill extraction)
+CREATE OR REPLACE FUNCTION track_profile_changes()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF ROW(NEW.full_name, NEW.education_level, NEW.experience_level, NEW.preferred_track,
+           NEW.skills, NEW.projects, NEW.target_roles, NEW.raw_cv_text)
+       IS DISTINCT FROM
+       ROW(OLD.full_name, OLD.education_level, OLD.experience_level, OLD.preferred_track,
+           OLD.skills, OLD.projects, OLD.target_roles, OLD.raw_cv_text) THEN
+        NEW.profile_version = OLD.profile_version + 1;
+    END IF;
+
+    IF NEW.skills IS DISTINCT FROM OLD.skills THEN
+        INSERT INTO profile_history (user_id, field, old_value, new_value, profile_version)
+        VALUES (NEW.id, 'skills', OLD.skills, NEW.skills, NEW.profile_version);
+    END IF;
+
+    IF NEW.target_roles IS DISTINCT FROM OLD.target_roles THEN
+        INSERT INTO profile_history (user_id, field, old_value, new_value, profile_version)
+        VALUES (NEW.id, 'target_roles', OLD.target_roles, NEW.target_roles, NEW.profile_version);
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS track_profile_changes ON users;
+CREATE TRIGGER track_profile_changes
+    BEFORE UPDATE ON users
+    FOR EACH ROW
+    EXECUTE FUNCTION track_profile_changes();
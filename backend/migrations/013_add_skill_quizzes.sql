@@ -0,0 +1,60 @@
+-- Migration: Skill self-ratings and quizzes
+-- skill_assessments records every rating over time, either a self-rating or a
+-- graded quiz; quiz_questions holds the question banks (loaded from files or
+-- AI-generated and reviewed) and quiz_attempts the server-side quiz sessions
+
+DO $$ 
+BEGIN
+    IF NOT EXISTS (
+        SELECT 1 FROM information_schema.columns 
+        WHERE table_name = 'skill_assessments' AND column_name = 'source'
+    ) THEN
+        ALTER TABLE skill_assessments ADD COLUMN source VARCHAR(10) NOT NULL DEFAULT 'self'
+            CHECK (source IN ('self', 'quiz'));
+    END IF;
+END $$;
+
+-- Create quiz_questions table
+CREATE TABLE IF NOT EXISTS quiz_questions (
+    id SERIAL PRIMARY KEY,
+    skill_name VARCHAR(255) NOT NULL,
+    question TEXT NOT NULL,
+    options TEXT[] NOT NULL,
+    correct_option INTEGER NOT NULL,
+    explanation TEXT,
+    source VARCHAR(10) NOT NULL CHECK (source IN ('file', 'ai')),
+    status VARCHAR(10) NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'approved', 'rejected')),
+    reviewed_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    CHECK (correct_option >= 0 AND correct_option < cardinality(options))
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_quiz_questions_unique ON quiz_questions(LOWER(skill_name), md5(question));
+CREATE INDEX IF NOT EXISTS idx_quiz_questions_skill ON quiz_questions(LOWER(skill_name), status);
+
+-- Create quiz_attempts table
+CREATE TABLE IF NOT EXISTS quiz_attempts (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    skill_name VARCHAR(255) NOT NULL,
+    question_ids INTEGER[] NOT NULL,
+    started_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    submitted_at TIMESTAMP WITH TIME ZONE,
+    correct_count INTEGER,
+    proficiency_level INTEGER
+);
+
+CREATE INDEX IF NOT EXISTS idx_quiz_attempts_user_skill ON quiz_attempts(user_id, LOWER(skill_name), started_at DESC);
+
+DO $$ 
+BEGIN
+    IF NOT EXISTS (
+        SELECT 1 FROM information_schema.columns 
+        WHERE table_name = 'skill_assessments' AND column_name = 'quiz_attempt_id'
+    ) THEN
+        ALTER TABLE skill_assessments ADD COLUMN quiz_attempt_id UUID REFERENCES quiz_attempts(id) ON DELETE SET NULL;
+    END IF;
+END $$;
+
+CREATE INDEX IF NOT EXISTS idx_skill_assessments_user_skill ON skill_assessments(user_id, LOWER(skill_name), assessed_at DESC);
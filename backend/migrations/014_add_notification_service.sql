@@ -0,0 +1,62 @@
+-- Migration: Notification service
+-- Notifications get a link and a dedup key so background checks can run
+-- repeatedly without notifying twice; users can turn each type off;
+-- user_progress gets updated_at to detect stalled learning
+
+UPDATE notifications SET is_read = FALSE WHERE is_read IS NULL;
+UPDATE notifications SET created_at = CURRENT_TIMESTAMP WHERE created_at IS NULL;
+ALTER TABLE notifications ALTER COLUMN is_read SET NOT NULL;
+ALTER TABLE notifications ALTER COLUMN created_at SET NOT NULL;
+
+DO $$ 
+BEGIN
+    IF NOT EXISTS (
+        SELECT 1 FROM information_schema.columns 
+        WHERE table_name = 'notifications' AND column_name = 'link'
+    ) THEN
+        ALTER TABLE notifications ADD COLUMN link VARCHAR(255);
+    END IF;
+
+    IF NOT EXISTS (
+        SELECT 1 FROM information_schema.columns 
+        WHERE table_name = 'notifications' AND column_name = 'dedup_key'
+    ) THEN
+        ALTER TABLE notifications ADD COLUMN dedup_key VARCHAR(255);
+    END IF;
+
+    IF NOT EXISTS (
+        SELECT 1 FROM information_schema.columns 
+        WHERE table_name = 'user_progress' AND column_name = 'updated_at'
+    ) THEN
+        ALTER TABLE user_progress ADD COLUMN updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP;
+        UPDATE user_progress SET updated_at = COALESCE(completed_at, started_at, CURRENT_TIMESTAMP);
+    END IF;
+END $$;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_notifications_dedup ON notifications(user_id, dedup_key) WHERE dedup_key IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_notifications_user_created ON notifications(user_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_notifications_unread ON notifications(user_id) WHERE NOT is_read;
+
+-- Create notification_preferences table (no row means the type is enabled)
+CREATE TABLE IF NOT EXISTS notification_preferences (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    type VARCHAR(50) NOT NULL,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (user_id, type)
+);
+
+-- Function to update updated_at timestamp for learning progress
+CREATE OR REPLACE FUNCTION update_progress_timestamp()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = CURRENT_TIMESTAMP;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS update_progress_timestamp ON user_progress;
+CREATE TRIGGER update_progress_timestamp
+    BEFORE UPDATE ON user_progress
+    FOR EACH ROW
+    EXECUTE FUNCTION update_progress_timestamp();
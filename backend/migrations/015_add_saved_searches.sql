@@ -0,0 +1,47 @@
+-- Migration: Saved job searches
+-- Users save job filters with a minimum match score; a background check
+-- records new jobs matching them, alerts in-app and sends optional daily or
+-- weekly email digests
+
+CREATE TABLE IF NOT EXISTS saved_searches (
+    id SERIAL PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    keywords VARCHAR(200),
+    location VARCHAR(100),
+    experience_level experience_level,
+    job_type job_type,
+    skills TEXT[] NOT NULL DEFAULT '{}',
+    min_match_score INTEGER NOT NULL DEFAULT 0 CHECK (min_match_score >= 0 AND min_match_score <= 100),
+    include_external BOOLEAN NOT NULL DEFAULT TRUE,
+    alerts_enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    digest_frequency VARCHAR(10) NOT NULL DEFAULT 'none' CHECK (digest_frequency IN ('none', 'daily', 'weekly')),
+    last_checked_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_digest_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_saved_searches_user ON saved_searches(user_id);
+
+-- Jobs found by a saved search; job_key is 'job:<id>' or 'external:<id>'
+CREATE TABLE IF NOT EXISTS saved_search_matches (
+    id SERIAL PRIMARY KEY,
+    saved_search_id INTEGER NOT NULL REFERENCES saved_searches(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    job_key VARCHAR(255) NOT NULL,
+    job_id INTEGER REFERENCES jobs(id) ON DELETE CASCADE,
+    title VARCHAR(255) NOT NULL,
+    company VARCHAR(255) NOT NULL,
+    location VARCHAR(255),
+    url TEXT,
+    source VARCHAR(100) NOT NULL,
+    match_score DOUBLE PRECISION NOT NULL,
+    matched_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    emailed_at TIMESTAMP WITH TIME ZONE,
+    UNIQUE (saved_search_id, job_key)
+);
+
+CREATE INDEX IF NOT EXISTS idx_saved_search_matches_search ON saved_search_matches(saved_search_id, matched_at DESC);
+CREATE INDEX IF NOT EXISTS idx_saved_search_matches_unsent ON saved_search_matches(saved_search_id) WHERE emailed_at IS NULL;
+CREATE INDEX IF NOT EXISTS idx_jobs_created_at ON jobs(created_at);
@@ -0,0 +1,38 @@
+-- Migration: Background job queue
+-- Work that should not run inside a request, and periodic work, is queued as
+-- rows claimed by workers with FOR UPDATE SKIP LOCKED. Failed jobs are retried
+-- with backoff and marked dead after their last attempt.
+
+CREATE TABLE IF NOT EXISTS background_jobs (
+    id BIGSERIAL PRIMARY KEY,
+    kind VARCHAR(50) NOT NULL,
+    payload JSONB NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'running', 'succeeded', 'dead')),
+    attempts INTEGER NOT NULL DEFAULT 0,
+    max_attempts INTEGER NOT NULL DEFAULT 5 CHECK (max_attempts > 0),
+    run_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    locked_at TIMESTAMP WITH TIME ZONE,
+    locked_by VARCHAR(100),
+    last_error TEXT,
+    -- At most one pending or running job per key (e.g. one run of a schedule)
+    dedup_key VARCHAR(255),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    finished_at TIMESTAMP WITH TIME ZONE
+);
+
+CREATE INDEX IF NOT EXISTS idx_background_jobs_due ON background_jobs(run_at, id) WHERE status = 'pending';
+CREATE INDEX IF NOT EXISTS idx_background_jobs_running ON background_jobs(locked_at) WHERE status = 'running';
+CREATE INDEX IF NOT EXISTS idx_background_jobs_status ON background_jobs(status, updated_at DESC);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_background_jobs_dedup ON background_jobs(dedup_key)
+    WHERE status IN ('pending', 'running');
+
+-- Periodic jobs; an instance enqueues a run by moving next_run_at forward,
+-- so each run is enqueued once however many instances are running
+CREATE TABLE IF NOT EXISTS job_schedules (
+    name VARCHAR(100) PRIMARY KEY,
+    schedule VARCHAR(100) NOT NULL,
+    next_run_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    last_run_at TIMESTAMP WITH TIME ZONE,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,35 @@
+-- Migration: Stored external job listings
+-- A periodic import stores listings from external sources (ReliefWeb,
+-- government portals, local job boards) instead of fetching them on every
+-- request. dedup_key (normalized title, company and city) finds the same
+-- posting from another source; listings expire when no longer seen.
+
+CREATE TABLE IF NOT EXISTS external_jobs (
+    id SERIAL PRIMARY KEY,
+    source VARCHAR(100) NOT NULL,
+    source_id VARCHAR(255) NOT NULL,
+    category VARCHAR(20) NOT NULL CHECK (category IN ('ngo', 'government', 'local')),
+    dedup_key VARCHAR(1000) NOT NULL,
+    title VARCHAR(500) NOT NULL,
+    company VARCHAR(255) NOT NULL,
+    location VARCHAR(255) NOT NULL,
+    description TEXT NOT NULL DEFAULT '',
+    url TEXT NOT NULL,
+    job_type VARCHAR(100),
+    experience_level VARCHAR(100),
+    skills TEXT[] NOT NULL DEFAULT '{}',
+    salary VARCHAR(255),
+    -- Posting date as given by the source, and parsed when possible
+    posted_date VARCHAR(100) NOT NULL DEFAULT '',
+    posted_at TIMESTAMP WITH TIME ZONE,
+    first_seen_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_seen_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (source, source_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_external_jobs_dedup_key ON external_jobs(dedup_key);
+CREATE INDEX IF NOT EXISTS idx_external_jobs_expires_at ON external_jobs(expires_at);
+CREATE INDEX IF NOT EXISTS idx_external_jobs_category ON external_jobs(category, posted_at DESC);
+CREATE INDEX IF NOT EXISTS idx_external_jobs_first_seen ON external_jobs(first_seen_at);
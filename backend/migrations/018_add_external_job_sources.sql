@@ -0,0 +1,22 @@
+-- Migration: External job source health
+-- One row per source in the sources file, updated by every import: when it
+-- was last fetched, when a fetch last succeeded and the last error. Job
+-- listings report each source as live, cached or unavailable from this.
+
+CREATE TABLE IF NOT EXISTS external_job_sources (
+    name VARCHAR(100) PRIMARY KEY,
+    kind VARCHAR(20) NOT NULL,
+    category VARCHAR(20) NOT NULL CHECK (category IN ('ngo', 'government', 'local')),
+    -- Enabled in the sources file of the most recently started instance
+    configured BOOLEAN NOT NULL DEFAULT TRUE,
+    last_attempt_at TIMESTAMP WITH TIME ZONE,
+    last_success_at TIMESTAMP WITH TIME ZONE,
+    -- Error of the last fetch; NULL when it succeeded
+    last_error TEXT,
+    -- Listings returned by the last successful fetch
+    last_fetched INTEGER,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_external_jobs_source ON external_jobs(source);
@@ -0,0 +1,61 @@
+-- Migration: Normalized external job fields
+-- job_type, experience_level and salary keep the values published by the
+-- source; the import also stores them mapped to the internal job_type and
+-- experience_level types, the salary parsed into a range, and the skills
+-- found in the description. Listings are normalized when next imported.
+
+DO $$
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM pg_type WHERE typname = 'salary_period') THEN
+        CREATE TYPE salary_period AS ENUM ('hour', 'day', 'week', 'month', 'year');
+    END IF;
+
+    IF NOT EXISTS (
+        SELECT 1 FROM information_schema.columns 
+        WHERE table_name = 'external_jobs' AND column_name = 'normalized_job_type'
+    ) THEN
+        ALTER TABLE external_jobs ADD COLUMN normalized_job_type job_type;
+    END IF;
+
+    IF NOT EXISTS (
+        SELECT 1 FROM information_schema.columns 
+        WHERE table_name = 'external_jobs' AND column_name = 'normalized_experience_level'
+    ) THEN
+        ALTER TABLE external_jobs ADD COLUMN normalized_experience_level experience_level;
+    END IF;
+
+    IF NOT EXISTS (
+        SELECT 1 FROM information_schema.columns 
+        WHERE table_name = 'external_jobs' AND column_name = 'salary_min'
+    ) THEN
+        ALTER TABLE external_jobs ADD COLUMN salary_min INTEGER;
+    END IF;
+
+    IF NOT EXISTS (
+        SELECT 1 FROM information_schema.columns 
+        WHERE table_name = 'external_jobs' AND column_name = 'salary_max'
+    ) THEN
+        ALTER TABLE external_jobs ADD COLUMN salary_max INTEGER;
+    END IF;
+
+    IF NOT EXISTS (
+        SELECT 1 FROM information_schema.columns 
+        WHERE table_name = 'external_jobs' AND column_name = 'salary_currency'
+    ) THEN
+        ALTER TABLE external_jobs ADD COLUMN salary_currency VARCHAR(3);
+    END IF;
+
+    IF NOT EXISTS (
+        SELECT 1 FROM information_schema.columns 
+        WHERE table_name = 'external_jobs' AND column_name = 'salary_period'
+    ) THEN
+        ALTER TABLE external_jobs ADD COLUMN salary_period salary_period;
+    END IF;
+
+    IF NOT EXISTS (
+        SELECT 1 FROM information_schema.columns 
+        WHERE table_name = 'external_jobs' AND column_name = 'extracted_skills'
+    ) THEN
+        ALTER TABLE external_jobs ADD COLUMN extracted_skills TEXT[] NOT NULL DEFAULT '{}';
+    END IF;
+END $$;
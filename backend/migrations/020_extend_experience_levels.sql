@@ -0,0 +1,69 @@
+-- Migration: Experience levels above mid and years of experience
+-- senior, lead and manager follow mid in the experience_level type, so its
+-- order is the order of seniority. Users and jobs gain an optional range of
+-- years of experience (had, or required).
+
+ALTER TYPE experience_level ADD VALUE IF NOT EXISTS 'senior' AFTER 'mid';
+ALTER TYPE experience_level ADD VALUE IF NOT EXISTS 'lead' AFTER 'senior';
+ALTER TYPE experience_level ADD VALUE IF NOT EXISTS 'manager' AFTER 'lead';
+
+DO $$
+BEGIN
+    IF NOT EXISTS (
+        SELECT 1 FROM information_schema.columns 
+        WHERE table_name = 'users' AND column_name = 'experience_years_min'
+    ) THEN
+        ALTER TABLE users ADD COLUMN experience_years_min INTEGER CHECK (experience_years_min >= 0);
+    END IF;
+
+    IF NOT EXISTS (
+        SELECT 1 FROM information_schema.columns 
+        WHERE table_name = 'users' AND column_name = 'experience_years_max'
+    ) THEN
+        ALTER TABLE users ADD COLUMN experience_years_max INTEGER
+            CHECK (experience_years_max >= experience_years_min);
+    END IF;
+
+    IF NOT EXISTS (
+        SELECT 1 FROM information_schema.columns 
+        WHERE table_name = 'jobs' AND column_name = 'experience_years_min'
+    ) THEN
+        ALTER TABLE jobs ADD COLUMN experience_years_min INTEGER CHECK (experience_years_min >= 0);
+    END IF;
+
+    IF NOT EXISTS (
+        SELECT 1 FROM information_schema.columns 
+        WHERE table_name = 'jobs' AND column_name = 'experience_years_max'
+    ) THEN
+        ALTER TABLE jobs ADD COLUMN experience_years_max INTEGER
+            CHECK (experience_years_max >= experience_years_min);
+    END IF;
+END $$;
+
+-- A change to the years of experience is a profile change too
+CREATE OR REPLACE FUNCTION track_profile_changes()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF ROW(NEW.full_name, NEW.education_level, NEW.experience_level, NEW.preferred_track,
+           NEW.experience_years_min, NEW.experience_years_max,
+           NEW.skills, NEW.projects, NEW.target_roles, NEW.raw_cv_text)
+       IS DISTINCT FROM
+       ROW(OLD.full_name, OLD.education_level, OLD.experience_level, OLD.preferred_track,
+           OLD.experience_years_min, OLD.experience_years_max,
+           OLD.skills, OLD.projects, OLD.target_roles, OLD.raw_cv_text) THEN
+        NEW.profile_version = OLD.profile_version + 1;
+    END IF;
+
+    IF NEW.skills IS DISTINCT FROM OLD.skills THEN
+        INSERT INTO profile_history (user_id, field, old_value, new_value, profile_version)
+        VALUES (NEW.id, 'skills', OLD.skills, NEW.skills, NEW.profile_version);
+    END IF;
+
+    IF NEW.target_roles IS DISTINCT FROM OLD.target_roles THEN
+        INSERT INTO profile_history (user_id, field, old_value, new_value, profile_version)
+        VALUES (NEW.id, 'target_roles', OLD.target_roles, NEW.target_roles, NEW.profile_version);
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
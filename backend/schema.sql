@@ -1,8 +1,9 @@
 -- Create custom enum types
-CREATE TYPE experience_level AS ENUM ('fresher', 'junior', 'mid');
+CREATE TYPE experience_level AS ENUM ('fresher', 'junior', 'mid', 'senior', 'lead', 'manager');
 CREATE TYPE career_track AS ENUM ('web_development', 'data', 'design', 'marketing');
 CREATE TYPE job_type AS ENUM ('internship', 'part_time', 'full_time', 'freelance');
 CREATE TYPE cost_indicator AS ENUM ('free', 'paid');
+CREATE TYPE salary_period AS ENUM ('hour', 'day', 'week', 'month', 'year');
 
 -- Create users table
 CREATE TABLE users (
@@ -15,12 +16,18 @@ CREATE TABLE users (
     avatar_url TEXT,
     education_level VARCHAR(255),
     experience_level experience_level,
+    experience_years_min INTEGER CHECK (experience_years_min >= 0),
+    experience_years_max INTEGER CHECK (experience_years_max >= experience_years_min),
     preferred_track career_track,
     skills TEXT[] NOT NULL DEFAULT '{}',
     projects TEXT[] NOT NULL DEFAULT '{}',
     target_roles TEXT[] NOT NULL DEFAULT '{}',
     profile_completed BOOLEAN DEFAULT FALSE,
     raw_cv_text TEXT,
+    share_cv_with_ai BOOLEAN NOT NULL DEFAULT TRUE,
+    deletion_requested_at TIMESTAMP WITH TIME ZONE,
+    deletion_scheduled_for TIMESTAMP WITH TIME ZONE,
+    profile_version INTEGER NOT NULL DEFAULT 1,
     created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
     updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
 );
@@ -34,6 +41,8 @@ CREATE TABLE jobs (
     job_description TEXT NOT NULL,
     required_skills TEXT[] NOT NULL DEFAULT '{}',
     experience_level experience_level NOT NULL,
+    experience_years_min INTEGER CHECK (experience_years_min >= 0),
+    experience_years_max INTEGER CHECK (experience_years_max >= experience_years_min),
     job_type job_type NOT NULL,
     salary_min INTEGER,
     salary_max INTEGER,
@@ -73,6 +82,7 @@ CREATE TABLE user_progress (
     completion_percentage INTEGER DEFAULT 0,
     started_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
     completed_at TIMESTAMP WITH TIME ZONE,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
     UNIQUE(user_id, resource_id)
 );
 
@@ -83,8 +93,10 @@ CREATE TABLE notifications (
     title VARCHAR(255) NOT NULL,
     message TEXT NOT NULL,
     type VARCHAR(50) NOT NULL,
-    is_read BOOLEAN DEFAULT FALSE,
-    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+    is_read BOOLEAN NOT NULL DEFAULT FALSE,
+    link VARCHAR(255),
+    dedup_key VARCHAR(255),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
 );
 
 -- Create skill_assessments table
@@ -93,19 +105,26 @@ CREATE TABLE skill_assessments (
     user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
     skill_name VARCHAR(255) NOT NULL,
     proficiency_level INTEGER CHECK (proficiency_level >= 1 AND proficiency_level <= 10),
+    source VARCHAR(10) NOT NULL DEFAULT 'self' CHECK (source IN ('self', 'quiz')),
+    quiz_attempt_id UUID,
     assessed_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
 );
 
 -- Create indexes
 CREATE INDEX idx_users_email ON users(email);
 CREATE UNIQUE INDEX idx_users_oauth ON users(oauth_provider, oauth_id) WHERE oauth_provider IS NOT NULL;
+CREATE INDEX idx_users_deletion_scheduled_for ON users(deletion_scheduled_for) WHERE deletion_scheduled_for IS NOT NULL;
 CREATE INDEX idx_jobs_experience_level ON jobs(experience_level);
 CREATE INDEX idx_jobs_job_type ON jobs(job_type);
+CREATE INDEX idx_jobs_created_at ON jobs(created_at);
 CREATE INDEX idx_application_tracking_user_id ON application_tracking(user_id);
 CREATE INDEX idx_application_tracking_status ON application_tracking(status);
 CREATE INDEX idx_user_progress_user_id ON user_progress(user_id);
 CREATE INDEX idx_notifications_user_id ON notifications(user_id);
 CREATE INDEX idx_notifications_is_read ON notifications(is_read);
+CREATE UNIQUE INDEX idx_notifications_dedup ON notifications(user_id, dedup_key) WHERE dedup_key IS NOT NULL;
+CREATE INDEX idx_notifications_user_created ON notifications(user_id, created_at DESC);
+CREATE INDEX idx_notifications_unread ON notifications(user_id) WHERE NOT is_read;
 CREATE INDEX idx_skill_assessments_user_id ON skill_assessments(user_id);
 
 -- Career roadmaps table for AI-generated learning paths (Part 2, Point 4)
@@ -148,3 +167,396 @@ CREATE TRIGGER update_roadmap_timestamp
     BEFORE UPDATE ON career_roadmaps
     FOR EACH ROW
     EXECUTE FUNCTION update_roadmap_timestamp();
+
+-- Create cv_versions table (original CV uploads, one active version per user)
+CREATE TABLE cv_versions (
+    id SERIAL PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    version_number INTEGER NOT NULL,
+    file_name VARCHAR(255) NOT NULL,
+    content_type VARCHAR(100) NOT NULL,
+    format VARCHAR(20) NOT NULL,
+    size_bytes BIGINT NOT NULL,
+    sha256 CHAR(64) NOT NULL,
+    storage_backend VARCHAR(20) NOT NULL,
+    storage_key TEXT NOT NULL,
+    extracted_text TEXT NOT NULL,
+    skills TEXT[] NOT NULL DEFAULT '{}',
+    is_active BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (user_id, version_number)
+);
+
+CREATE INDEX idx_cv_versions_user_sha256 ON cv_versions(user_id, sha256);
+CREATE UNIQUE INDEX idx_cv_versions_one_active ON cv_versions(user_id) WHERE is_active;
+
+-- Create cv_parses table (structured CV data, one per user, reviewable)
+CREATE TABLE cv_parses (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    full_name VARCHAR(255),
+    email VARCHAR(255),
+    phone VARCHAR(50),
+    location TEXT,
+    links TEXT[] NOT NULL DEFAULT '{}',
+    summary TEXT,
+    skills TEXT[] NOT NULL DEFAULT '{}',
+    reviewed_at TIMESTAMP WITH TIME ZONE,
+    enriched_at TIMESTAMP WITH TIME ZONE,
+    cv_version_id INTEGER REFERENCES cv_versions(id) ON DELETE SET NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Create cv_parse_entries table (education, experience, project and certification entries)
+CREATE TABLE cv_parse_entries (
+    id SERIAL PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES cv_parses(user_id) ON DELETE CASCADE,
+    section VARCHAR(20) NOT NULL CHECK (section IN ('education', 'experience', 'project', 'certification')),
+    position INTEGER NOT NULL DEFAULT 0,
+    title TEXT NOT NULL,
+    organization TEXT,
+    location TEXT,
+    start_date DATE,
+    end_date DATE,
+    is_current BOOLEAN NOT NULL DEFAULT FALSE,
+    description TEXT,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_cv_parse_entries_user_section ON cv_parse_entries(user_id, section, position);
+
+-- Create tailored_cvs table (job-tailored CV snapshots, linked to the application they were sent with)
+CREATE TABLE tailored_cvs (
+    id SERIAL PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    job_id INTEGER NOT NULL REFERENCES jobs(id) ON DELETE CASCADE,
+    application_id INTEGER REFERENCES application_tracking(id) ON DELETE SET NULL,
+    document JSONB NOT NULL,
+    template VARCHAR(20) NOT NULL,
+    matched_skills TEXT[] NOT NULL DEFAULT '{}',
+    missing_skills TEXT[] NOT NULL DEFAULT '{}',
+    ai_rewritten BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_tailored_cvs_user_job ON tailored_cvs(user_id, job_id, created_at DESC);
+CREATE INDEX idx_tailored_cvs_application ON tailored_cvs(application_id);
+
+-- Create cover_letters table (one editable cover letter per application)
+CREATE TABLE cover_letters (
+    id SERIAL PRIMARY KEY,
+    application_id INTEGER NOT NULL UNIQUE REFERENCES application_tracking(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    content TEXT NOT NULL,
+    ai_provider VARCHAR(50),
+    edited_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_cover_letters_user_id ON cover_letters(user_id);
+
+-- Create education_entries table
+CREATE TABLE education_entries (
+    id SERIAL PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    institution VARCHAR(255) NOT NULL,
+    degree VARCHAR(255),
+    field_of_study VARCHAR(255),
+    start_date DATE,
+    end_date DATE,
+    is_current BOOLEAN NOT NULL DEFAULT FALSE,
+    grade VARCHAR(100),
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Create work_experiences table
+CREATE TABLE work_experiences (
+    id SERIAL PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    employer VARCHAR(255) NOT NULL,
+    title VARCHAR(255) NOT NULL,
+    location VARCHAR(255),
+    start_date DATE,
+    end_date DATE,
+    is_current BOOLEAN NOT NULL DEFAULT FALSE,
+    bullet_points TEXT[] NOT NULL DEFAULT '{}',
+    skills_used TEXT[] NOT NULL DEFAULT '{}',
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Create projects table
+CREATE TABLE projects (
+    id SERIAL PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    title VARCHAR(255) NOT NULL,
+    description TEXT,
+    url TEXT,
+    tech_stack TEXT[] NOT NULL DEFAULT '{}',
+    start_date DATE,
+    end_date DATE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_education_entries_user_id ON education_entries(user_id);
+CREATE INDEX idx_work_experiences_user_id ON work_experiences(user_id);
+CREATE INDEX idx_projects_user_id ON projects(user_id);
+
+-- Create public_profiles table
+CREATE TABLE public_profiles (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    slug VARCHAR(40) NOT NULL,
+    is_public BOOLEAN NOT NULL DEFAULT FALSE,
+    show_email BOOLEAN NOT NULL DEFAULT FALSE,
+    show_cv BOOLEAN NOT NULL DEFAULT FALSE,
+    show_skills BOOLEAN NOT NULL DEFAULT TRUE,
+    show_learning BOOLEAN NOT NULL DEFAULT TRUE,
+    show_roadmaps BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT public_profiles_slug_key UNIQUE (slug)
+);
+
+-- Create profile_history table (changes to skills and target roles)
+CREATE TABLE profile_history (
+    id SERIAL PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    field VARCHAR(20) NOT NULL CHECK (field IN ('skills', 'target_roles')),
+    old_value TEXT[] NOT NULL DEFAULT '{}',
+    new_value TEXT[] NOT NULL DEFAULT '{}',
+    profile_version INTEGER NOT NULL,
+    changed_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_profile_history_user_id ON profile_history(user_id, changed_at DESC);
+
+-- Function to bump profile_version and record skill/target role changes
+CREATE OR REPLACE FUNCTION track_profile_changes()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF ROW(NEW.full_name, NEW.education_level, NEW.experience_level, NEW.preferred_track,
+           NEW.experience_years_min, NEW.experience_years_max,
+           NEW.skills, NEW.projects, NEW.target_roles, NEW.raw_cv_text)
+       IS DISTINCT FROM
+       ROW(OLD.full_name, OLD.education_level, OLD.experience_level, OLD.preferred_track,
+           OLD.experience_years_min, OLD.experience_years_max,
+           OLD.skills, OLD.projects, OLD.target_roles, OLD.raw_cv_text) THEN
+        NEW.profile_version = OLD.profile_version + 1;
+    END IF;
+
+    IF NEW.skills IS DISTINCT FROM OLD.skills THEN
+        INSERT INTO profile_history (user_id, field, old_value, new_value, profile_version)
+        VALUES (NEW.id, 'skills', OLD.skills, NEW.skills, NEW.profile_version);
+    END IF;
+
+    IF NEW.target_roles IS DISTINCT FROM OLD.target_roles THEN
+        INSERT INTO profile_history (user_id, field, old_value, new_value, profile_version)
+        VALUES (NEW.id, 'target_roles', OLD.target_roles, NEW.target_roles, NEW.profile_version);
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Trigger to version profile updates
+CREATE TRIGGER track_profile_changes
+    BEFORE UPDATE ON users
+    FOR EACH ROW
+    EXECUTE FUNCTION track_profile_changes();
+
+-- Quiz question banks, loaded from files or AI-generated and reviewed
+CREATE TABLE quiz_questions (
+    id SERIAL PRIMARY KEY,
+    skill_name VARCHAR(255) NOT NULL,
+    question TEXT NOT NULL,
+    options TEXT[] NOT NULL,
+    correct_option INTEGER NOT NULL,
+    explanation TEXT,
+    source VARCHAR(10) NOT NULL CHECK (source IN ('file', 'ai')),
+    status VARCHAR(10) NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'approved', 'rejected')),
+    reviewed_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    CHECK (correct_option >= 0 AND correct_option < cardinality(options))
+);
+
+CREATE UNIQUE INDEX idx_quiz_questions_unique ON quiz_questions(LOWER(skill_name), md5(question));
+CREATE INDEX idx_quiz_questions_skill ON quiz_questions(LOWER(skill_name), status);
+
+-- Quiz sessions; answers are graded server-side and each attempt is submitted once
+CREATE TABLE quiz_attempts (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    skill_name VARCHAR(255) NOT NULL,
+    question_ids INTEGER[] NOT NULL,
+    started_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    submitted_at TIMESTAMP WITH TIME ZONE,
+    correct_count INTEGER,
+    proficiency_level INTEGER
+);
+
+CREATE INDEX idx_quiz_attempts_user_skill ON quiz_attempts(user_id, LOWER(skill_name), started_at DESC);
+
+ALTER TABLE skill_assessments
+    ADD CONSTRAINT skill_assessments_quiz_attempt_id_fkey
+    FOREIGN KEY (quiz_attempt_id) REFERENCES quiz_attempts(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_skill_assessments_user_skill ON skill_assessments(user_id, LOWER(skill_name), assessed_at DESC);
+
+-- Per-type notification preferences (no row means the type is enabled)
+CREATE TABLE notification_preferences (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    type VARCHAR(50) NOT NULL,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (user_id, type)
+);
+
+-- Function to update updated_at timestamp for learning progress
+CREATE OR REPLACE FUNCTION update_progress_timestamp()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = CURRENT_TIMESTAMP;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Trigger to detect stalled learning progress
+CREATE TRIGGER update_progress_timestamp
+    BEFORE UPDATE ON user_progress
+    FOR EACH ROW
+    EXECUTE FUNCTION update_progress_timestamp();
+
+-- Saved job searches with alerts and email digests
+CREATE TABLE saved_searches (
+    id SERIAL PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    keywords VARCHAR(200),
+    location VARCHAR(100),
+    experience_level experience_level,
+    job_type job_type,
+    skills TEXT[] NOT NULL DEFAULT '{}',
+    min_match_score INTEGER NOT NULL DEFAULT 0 CHECK (min_match_score >= 0 AND min_match_score <= 100),
+    include_external BOOLEAN NOT NULL DEFAULT TRUE,
+    alerts_enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    digest_frequency VARCHAR(10) NOT NULL DEFAULT 'none' CHECK (digest_frequency IN ('none', 'daily', 'weekly')),
+    last_checked_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_digest_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_saved_searches_user ON saved_searches(user_id);
+
+-- Jobs found by a saved search; job_key is 'job:<id>' or 'external:<id>'
+CREATE TABLE saved_search_matches (
+    id SERIAL PRIMARY KEY,
+    saved_search_id INTEGER NOT NULL REFERENCES saved_searches(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    job_key VARCHAR(255) NOT NULL,
+    job_id INTEGER REFERENCES jobs(id) ON DELETE CASCADE,
+    title VARCHAR(255) NOT NULL,
+    company VARCHAR(255) NOT NULL,
+    location VARCHAR(255),
+    url TEXT,
+    source VARCHAR(100) NOT NULL,
+    match_score DOUBLE PRECISION NOT NULL,
+    matched_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    emailed_at TIMESTAMP WITH TIME ZONE,
+    UNIQUE (saved_search_id, job_key)
+);
+
+CREATE INDEX idx_saved_search_matches_search ON saved_search_matches(saved_search_id, matched_at DESC);
+CREATE INDEX idx_saved_search_matches_unsent ON saved_search_matches(saved_search_id) WHERE emailed_at IS NULL;
+
+-- Background job queue; workers claim due jobs with FOR UPDATE SKIP LOCKED
+CREATE TABLE background_jobs (
+    id BIGSERIAL PRIMARY KEY,
+    kind VARCHAR(50) NOT NULL,
+    payload JSONB NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'running', 'succeeded', 'dead')),
+    attempts INTEGER NOT NULL DEFAULT 0,
+    max_attempts INTEGER NOT NULL DEFAULT 5 CHECK (max_attempts > 0),
+    run_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    locked_at TIMESTAMP WITH TIME ZONE,
+    locked_by VARCHAR(100),
+    last_error TEXT,
+    dedup_key VARCHAR(255),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    finished_at TIMESTAMP WITH TIME ZONE
+);
+
+CREATE INDEX idx_background_jobs_due ON background_jobs(run_at, id) WHERE status = 'pending';
+CREATE INDEX idx_background_jobs_running ON background_jobs(locked_at) WHERE status = 'running';
+CREATE INDEX idx_background_jobs_status ON background_jobs(status, updated_at DESC);
+CREATE UNIQUE INDEX idx_background_jobs_dedup ON background_jobs(dedup_key)
+    WHERE status IN ('pending', 'running');
+
+-- Periodic jobs; each run is enqueued by the instance that moves next_run_at
+CREATE TABLE job_schedules (
+    name VARCHAR(100) PRIMARY KEY,
+    schedule VARCHAR(100) NOT NULL,
+    next_run_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    last_run_at TIMESTAMP WITH TIME ZONE,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- External job listings stored by the periodic import; dedup_key finds
+-- the same posting from another source
+CREATE TABLE external_jobs (
+    id SERIAL PRIMARY KEY,
+    source VARCHAR(100) NOT NULL,
+    source_id VARCHAR(255) NOT NULL,
+    category VARCHAR(20) NOT NULL CHECK (category IN ('ngo', 'government', 'local')),
+    dedup_key VARCHAR(1000) NOT NULL,
+    title VARCHAR(500) NOT NULL,
+    company VARCHAR(255) NOT NULL,
+    location VARCHAR(255) NOT NULL,
+    description TEXT NOT NULL DEFAULT '',
+    url TEXT NOT NULL,
+    job_type VARCHAR(100),
+    experience_level VARCHAR(100),
+    skills TEXT[] NOT NULL DEFAULT '{}',
+    salary VARCHAR(255),
+    -- Published values mapped to the internal types, and skills found in the description
+    normalized_job_type job_type,
+    normalized_experience_level experience_level,
+    salary_min INTEGER,
+    salary_max INTEGER,
+    salary_currency VARCHAR(3),
+    salary_period salary_period,
+    extracted_skills TEXT[] NOT NULL DEFAULT '{}',
+    posted_date VARCHAR(100) NOT NULL DEFAULT '',
+    posted_at TIMESTAMP WITH TIME ZONE,
+    first_seen_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_seen_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (source, source_id)
+);
+
+CREATE INDEX idx_external_jobs_dedup_key ON external_jobs(dedup_key);
+CREATE INDEX idx_external_jobs_expires_at ON external_jobs(expires_at);
+CREATE INDEX idx_external_jobs_category ON external_jobs(category, posted_at DESC);
+CREATE INDEX idx_external_jobs_first_seen ON external_jobs(first_seen_at);
+
+-- Health of each external job source, updated by every import
+CREATE TABLE external_job_sources (
+    name VARCHAR(100) PRIMARY KEY,
+    kind VARCHAR(20) NOT NULL,
+    category VARCHAR(20) NOT NULL CHECK (category IN ('ngo', 'government', 'local')),
+    configured BOOLEAN NOT NULL DEFAULT TRUE,
+    last_attempt_at TIMESTAMP WITH TIME ZONE,
+    last_success_at TIMESTAMP WITH TIME ZONE,
+    last_error TEXT,
+    last_fetched INTEGER,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_external_jobs_source ON external_jobs(source);
@@ -0,0 +1,142 @@
+//! Account deletion.
+//!
+//! Deleting an account is a two-step process: the user requests it and the
+//! account is scheduled for deletion after a grace period, during which it
+//! can be restored. A background task then purges accounts whose grace
+//! period has passed: stored CV files, queued background jobs carrying the
+//! user's data, and finally the user row, which cascades to every other
+//! table, AI-generated content (roadmaps, cover letters, tailored CVs,
+//! parsed CVs) included.
+
+use crate::cv::storage::{DocumentStorage, StorageError};
+use sqlx::{PgConnection, PgPool};
+use std::time::Duration;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Days an account can still be restored after deletion is requested.
+const DEFAULT_GRACE_DAYS: i64 = 30;
+
+/// How often the purge task looks for accounts to delete.
+const DEFAULT_PURGE_INTERVAL_SECS: u64 = 3600;
+
+/// Accounts purged per sweep, so one sweep cannot hold the pool for long.
+const PURGE_BATCH_SIZE: i64 = 50;
+
+/// Grace period from `ACCOUNT_DELETION_GRACE_DAYS` (default 30, `0` purges
+/// at the next sweep).
+pub fn deletion_grace_period() -> chrono::Duration {
+    let days = std::env::var("ACCOUNT_DELETION_GRACE_DAYS")
+        .ok()
+        .and_then(|v| v.parse::<i64>().ok())
+        .filter(|days| *days >= 0)
+        .unwrap_or(DEFAULT_GRACE_DAYS);
+    chrono::Duration::days(days)
+}
+
+/// Purge interval from `ACCOUNT_PURGE_INTERVAL_SECS` (default one hour).
+pub fn purge_interval() -> Duration {
+    let secs = std::env::var("ACCOUNT_PURGE_INTERVAL_SECS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|secs| *secs > 0)
+        .unwrap_or(DEFAULT_PURGE_INTERVAL_SECS);
+    Duration::from_secs(secs)
+}
+
+/// Permanently deletes accounts whose grace period has passed.
+///
+/// Returns how many accounts were deleted. Each account is locked and its
+/// deletion re-checked before anything is removed, so a restore that races
+/// the sweep either wins or waits until the account is gone. An account
+/// whose CV files cannot all be removed from storage is kept and retried on
+/// the next sweep, without the versions of the files already removed, so no
+/// file is orphaned and no version points at a missing file.
+///
+/// # Errors
+///
+/// Returns an error if a database operation fails.
+pub async fn purge_due_accounts(pool: &PgPool, storage: &dyn DocumentStorage) -> Result<usize, sqlx::Error> {
+    let due: Vec<Uuid> = sqlx::query_scalar!(
+        r#"
+        SELECT id FROM users
+        WHERE deletion_scheduled_for IS NOT NULL AND deletion_scheduled_for <= CURRENT_TIMESTAMP
+        ORDER BY deletion_scheduled_for
+        LIMIT $1
+        "#,
+        PURGE_BATCH_SIZE
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut purged = 0;
+    for user_id in due {
+        let mut tx = pool.begin().await?;
+        // Claim the account; a restore waits on the lock until we are done
+        let claimed = sqlx::query_scalar!(
+            r#"
+            SELECT id FROM users
+            WHERE id = $1 AND deletion_scheduled_for <= CURRENT_TIMESTAMP
+            FOR UPDATE
+            "#,
+            user_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        if claimed.is_none() {
+            tx.rollback().await?;
+            continue;
+        }
+
+        if !delete_stored_files(&mut tx, storage, user_id).await? {
+            tx.commit().await?;
+            continue;
+        }
+
+        // Job payloads hold the user's ID and request details (target
+        // role, cover letter instructions) but have no foreign key
+        sqlx::query!(
+            "DELETE FROM background_jobs WHERE payload->'payload'->>'user_id' = $1::UUID::TEXT",
+            user_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!("DELETE FROM users WHERE id = $1", user_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        info!("Purged account {}", user_id);
+        purged += 1;
+    }
+
+    Ok(purged)
+}
+
+/// Removes the user's CV files from storage, and the versions of the files
+/// removed. Returns `false` if any could not be removed.
+async fn delete_stored_files(
+    conn: &mut PgConnection,
+    storage: &dyn DocumentStorage,
+    user_id: Uuid,
+) -> Result<bool, sqlx::Error> {
+    let versions = sqlx::query!("SELECT id, storage_key FROM cv_versions WHERE user_id = $1", user_id)
+        .fetch_all(&mut *conn)
+        .await?;
+
+    let mut all_deleted = true;
+    for version in versions {
+        match storage.delete(&version.storage_key).await {
+            Ok(()) | Err(StorageError::NotFound) => {
+                sqlx::query!("DELETE FROM cv_versions WHERE id = $1", version.id)
+                    .execute(&mut *conn)
+                    .await?;
+            }
+            Err(e) => {
+                warn!("Could not delete CV file {} of account {}: {}", version.storage_key, user_id, e);
+                all_deleted = false;
+            }
+        }
+    }
+    Ok(all_deleted)
+}
@@ -6,11 +6,13 @@
 pub mod types;
 pub mod gemini;
 pub mod groq;
+pub mod redaction;
 
 use crate::errors::AppError;
 use types::*;
 use gemini::GeminiClient;
 use groq::GroqClient;
+use redaction::Redactor;
 
 /// AI service that abstracts over multiple providers
 pub struct AIService {
@@ -35,7 +37,31 @@ impl AIService {
     }
 
     /// Process an AI action request
+    ///
+    /// PII detected by the default [`Redactor`] is masked before the request
+    /// reaches the provider and restored in the response.
     pub async fn process_action(&self, request: AIActionRequest) -> Result<AIActionResponse, AppError> {
+        self.process_action_with(request, &Redactor::default()).await
+    }
+
+    /// Process an AI action request, masking PII with the given redactor
+    ///
+    /// The input and every string in `parameters` are redacted with a shared
+    /// placeholder table, so the provider never sees the original values.
+    pub async fn process_action_with(
+        &self,
+        mut request: AIActionRequest,
+        redactor: &Redactor,
+    ) -> Result<AIActionResponse, AppError> {
+        let mut redaction = redactor.redact(&request.input);
+        request.input = std::mem::take(&mut redaction.text);
+        if let Some(parameters) = request.parameters.as_mut() {
+            redactor.redact_json(parameters, &mut redaction);
+        }
+        if !redaction.is_empty() {
+            tracing::debug!("Redacted {} PII value(s) before AI request", redaction.len());
+        }
+
         // Select the appropriate client based on provider
         let result = match request.provider {
             AIProvider::Gemini => {
@@ -53,12 +79,15 @@ impl AIService {
         };
 
         match result {
-            Ok(data) => Ok(AIActionResponse {
-                success: true,
-                data,
-                provider: request.provider,
-                message: None,
-            }),
+            Ok(mut data) => {
+                redaction.restore_json(&mut data);
+                Ok(AIActionResponse {
+                    success: true,
+                    data,
+                    provider: request.provider,
+                    message: None,
+                })
+            }
             Err(e) => Ok(AIActionResponse {
                 success: false,
                 data: serde_json::json!({"error": e.to_string()}),
@@ -0,0 +1,333 @@
+//! PII redaction for text sent to external AI providers.
+//!
+//! CV text and profile context regularly contain phone numbers, email
+//! addresses, national ID / passport numbers, street addresses and links to
+//! personal profiles. Before any prompt leaves the server those values are
+//! replaced with stable placeholders such as `[EMAIL_1]`; the placeholders can
+//! be swapped back into the provider's response afterwards.
+
+use regex::Regex;
+use serde_json::Value;
+use std::sync::LazyLock;
+
+/// Category of personally identifiable information that was masked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PiiKind {
+    /// Email address
+    Email,
+    /// Phone number (Bangladeshi mobile or international format)
+    Phone,
+    /// National ID card number
+    NationalId,
+    /// Passport number
+    Passport,
+    /// Street or postal address
+    Address,
+    /// Link to a personal profile (LinkedIn, GitHub, portfolio hosts, ...)
+    ProfileUrl,
+    /// A value supplied by the caller, e.g. the user's full name
+    Name,
+}
+
+impl PiiKind {
+    fn label(self) -> &'static str {
+        match self {
+            PiiKind::Email => "EMAIL",
+            PiiKind::Phone => "PHONE",
+            PiiKind::NationalId => "NID",
+            PiiKind::Passport => "PASSPORT",
+            PiiKind::Address => "ADDRESS",
+            PiiKind::ProfileUrl => "PROFILE_URL",
+            PiiKind::Name => "NAME",
+        }
+    }
+}
+
+/// A detection rule: the regex and the capture group holding the sensitive part.
+///
+/// Labelled patterns (e.g. `Passport No: EB0123456`) only mask the value so the
+/// model still understands what kind of information was there.
+struct Pattern {
+    kind: PiiKind,
+    regex: Regex,
+    group: usize,
+}
+
+/// Detection rules, applied in order. URLs and emails go first so the digits
+/// inside them are not picked up as phone or ID numbers.
+static PATTERNS: LazyLock<Vec<Pattern>> = LazyLock::new(|| {
+    let rule = |kind, pattern: &str, group| Pattern {
+        kind,
+        regex: Regex::new(pattern).expect("invalid PII pattern"),
+        group,
+    };
+
+    vec![
+        rule(
+            PiiKind::ProfileUrl,
+            r"(?i)\b(?:https?://)?(?:www\.)?(?:linkedin\.com|github\.com|gitlab\.com|facebook\.com|fb\.com|twitter\.com|x\.com|instagram\.com|behance\.net|dribbble\.com|medium\.com|stackoverflow\.com/users)/[^\s,;()<>]+",
+            0,
+        ),
+        rule(
+            PiiKind::ProfileUrl,
+            r"(?i)\b(?:https?://)?[a-z0-9-]+\.(?:github\.io|vercel\.app|netlify\.app|wordpress\.com|blogspot\.com)(?:/[^\s,;()<>]*)?",
+            0,
+        ),
+        rule(
+            PiiKind::Email,
+            r"(?i)\b[a-z0-9._%+-]+@[a-z0-9.-]+\.[a-z]{2,}\b",
+            0,
+        ),
+        rule(
+            PiiKind::Passport,
+            r"(?i)\bpassport\s*(?:no\.?|number|#)?\s*[:\-]?\s*([a-z]{1,2}\d{6,8})\b",
+            1,
+        ),
+        // Bangladeshi machine-readable passport numbers, e.g. EB0123456
+        rule(PiiKind::Passport, r"\b[A-Z]{2}\d{7}\b", 0),
+        rule(
+            PiiKind::NationalId,
+            r"(?i)\b(?:nid|national\s+id(?:entity)?(?:\s+card)?)\s*(?:no\.?|number|#)?\s*[:\-]?\s*(\d[\d\s-]{8,22}\d)",
+            1,
+        ),
+        // Unlabelled 13 or 17 digit numbers are old-format NIDs
+        rule(PiiKind::NationalId, r"\b(?:\d{17}|\d{13})\b", 0),
+        rule(
+            PiiKind::Phone,
+            r"(?:\+?880[\s-]?|\b0)1[3-9]\d{2}[\s-]?\d{6}\b",
+            0,
+        ),
+        rule(
+            PiiKind::Phone,
+            r"\+\d{1,3}[\s-]?\(?\d{1,4}\)?(?:[\s-]?\d{2,4}){2,4}\b",
+            0,
+        ),
+        rule(
+            PiiKind::Address,
+            r"(?im)\b(?:present|permanent|mailing|home|postal)?\s*address\s*[:\-]\s*([^\n]+)",
+            1,
+        ),
+        rule(
+            PiiKind::Address,
+            r"(?i)\b(?:house|flat|apt|apartment|holding|road|rd\.?|lane|street|sector)\s*(?:no\.?|#)?\s*[:\-]?\s*\d+[a-z]?\b[^\n]*",
+            0,
+        ),
+    ]
+});
+
+/// Result of redacting a piece of text.
+#[derive(Debug, Clone, Default)]
+pub struct Redaction {
+    /// The text with every detected value replaced by a placeholder
+    pub text: String,
+    /// `(placeholder, original value, kind)` in the order they were introduced
+    replacements: Vec<(String, String, PiiKind)>,
+}
+
+impl Redaction {
+    /// Number of distinct values that were masked.
+    pub fn len(&self) -> usize {
+        self.replacements.len()
+    }
+
+    /// Whether nothing was masked.
+    pub fn is_empty(&self) -> bool {
+        self.replacements.is_empty()
+    }
+
+    /// Kinds of PII that were found, one entry per masked value.
+    pub fn kinds(&self) -> Vec<PiiKind> {
+        self.replacements.iter().map(|(_, _, kind)| *kind).collect()
+    }
+
+    /// Puts the original values back into `text`.
+    ///
+    /// Placeholders are expanded newest first, so a value that was masked inside
+    /// an already-redacted span (an email inside an address line) comes back too.
+    pub fn restore(&self, text: &str) -> String {
+        self.replacements
+            .iter()
+            .rev()
+            .fold(text.to_string(), |acc, (placeholder, original, _)| {
+                acc.replace(placeholder, original)
+            })
+    }
+
+    /// Restores placeholders in every string inside a JSON value.
+    pub fn restore_json(&self, value: &mut Value) {
+        if self.is_empty() {
+            return;
+        }
+        match value {
+            Value::String(s) => *s = self.restore(s),
+            Value::Array(items) => items.iter_mut().for_each(|v| self.restore_json(v)),
+            Value::Object(map) => map.values_mut().for_each(|v| self.restore_json(v)),
+            _ => {}
+        }
+    }
+}
+
+/// Detects and masks PII in outbound text.
+///
+/// The default redactor only uses pattern detection. Values that cannot be
+/// recognised by shape, like the user's name, can be added with
+/// [`Redactor::with_known_value`].
+///
+/// # Example
+///
+/// ```
+/// use backend::ai::redaction::Redactor;
+///
+/// let redactor = Redactor::default().with_known_value("Rahim Uddin");
+/// let redaction = redactor.redact(
+///     "Rahim Uddin\nrahim@example.com | +880 1712-345678\nRust, Axum, PostgreSQL",
+/// );
+///
+/// assert!(!redaction.text.contains("rahim@example.com"));
+/// assert!(!redaction.text.contains("1712"));
+/// assert!(!redaction.text.contains("Rahim"));
+/// assert!(redaction.text.contains("Rust, Axum, PostgreSQL"));
+/// assert_eq!(redaction.restore("Hello [NAME_1]"), "Hello Rahim Uddin");
+///
+/// // Known values are only masked as whole words
+/// let redactor = Redactor::default().with_known_value("Ana");
+/// let redaction = redactor.redact("Ana is a Data Analyst");
+/// assert_eq!(redaction.text, "[NAME_1] is a Data Analyst");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Redactor {
+    known_values: Vec<String>,
+}
+
+impl Redactor {
+    /// Builds a redactor that also masks the given user's name and email.
+    pub fn for_user(full_name: &str, email: &str) -> Self {
+        Self::default()
+            .with_known_value(full_name)
+            .with_known_value(email)
+    }
+
+    /// Adds a literal value (matched case-insensitively, as whole words) that
+    /// must never be sent out.
+    pub fn with_known_value(mut self, value: &str) -> Self {
+        let value = value.trim();
+        // Very short values would mask unrelated words
+        if value.chars().count() >= 3 {
+            self.known_values.push(value.to_string());
+        }
+        self
+    }
+
+    /// Replaces every detected PII value in `text` with a placeholder.
+    pub fn redact(&self, text: &str) -> Redaction {
+        let mut redaction = Redaction {
+            text: text.to_string(),
+            replacements: Vec::new(),
+        };
+
+        for value in &self.known_values {
+            let kind = if value.contains('@') { PiiKind::Email } else { PiiKind::Name };
+            let regex = known_value_regex(value);
+            redaction.text = mask(&redaction.text, &regex, 0, kind, &mut redaction.replacements);
+        }
+
+        for pattern in PATTERNS.iter() {
+            redaction.text = mask(
+                &redaction.text,
+                &pattern.regex,
+                pattern.group,
+                pattern.kind,
+                &mut redaction.replacements,
+            );
+        }
+
+        redaction
+    }
+
+    /// Redacts every string inside a JSON value, sharing one placeholder table.
+    pub fn redact_json(&self, value: &mut Value, redaction: &mut Redaction) {
+        match value {
+            Value::String(s) => {
+                let mut partial = self.redact(s);
+                // Re-key placeholders so they stay unique across the whole document
+                for (placeholder, original, kind) in partial.replacements.drain(..) {
+                    let existing = redaction
+                        .replacements
+                        .iter()
+                        .find(|(_, o, _)| *o == original)
+                        .map(|(p, _, _)| p.clone());
+                    let target = existing.unwrap_or_else(|| {
+                        let p = next_placeholder(kind, &redaction.replacements);
+                        redaction.replacements.push((p.clone(), original, kind));
+                        p
+                    });
+                    partial.text = partial.text.replace(&placeholder, &target);
+                }
+                *s = partial.text;
+            }
+            Value::Array(items) => items.iter_mut().for_each(|v| self.redact_json(v, redaction)),
+            Value::Object(map) => map.values_mut().for_each(|v| self.redact_json(v, redaction)),
+            _ => {}
+        }
+    }
+}
+
+/// Builds the next free placeholder for `kind`, e.g. `[PHONE_2]`.
+fn next_placeholder(kind: PiiKind, replacements: &[(String, String, PiiKind)]) -> String {
+    let n = replacements.iter().filter(|(_, _, k)| *k == kind).count() + 1;
+    format!("[{}_{}]", kind.label(), n)
+}
+
+/// Case-insensitive regex matching `value` only where it is not part of a
+/// longer word.
+fn known_value_regex(value: &str) -> Regex {
+    let is_word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+    let start = if is_word(value.chars().next()) { r"\b" } else { "" };
+    let end = if is_word(value.chars().last()) { r"\b" } else { "" };
+    Regex::new(&format!("(?i){}{}{}", start, regex::escape(value), end))
+        .expect("escaped literal is a valid regex")
+}
+
+/// Replaces the matched group of every `regex` match with a placeholder.
+///
+/// The same original value always maps to the same placeholder.
+fn mask(
+    text: &str,
+    regex: &Regex,
+    group: usize,
+    kind: PiiKind,
+    replacements: &mut Vec<(String, String, PiiKind)>,
+) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0;
+
+    for caps in regex.captures_iter(text) {
+        let Some(m) = caps.get(group) else { continue };
+        let original = m.as_str().trim();
+        if original.is_empty() {
+            continue;
+        }
+        // Don't re-mask a span that is nothing but an existing placeholder
+        if original.starts_with('[') && original.ends_with(']') && !original[1..].contains('[') {
+            continue;
+        }
+
+        let placeholder = match replacements.iter().find(|(_, o, _)| o == original) {
+            Some((p, _, _)) => p.clone(),
+            None => {
+                let p = next_placeholder(kind, replacements);
+                replacements.push((p.clone(), original.to_string(), kind));
+                p
+            }
+        };
+
+        let start = m.start() + (m.as_str().len() - m.as_str().trim_start().len());
+        let end = start + original.len();
+        out.push_str(&text[last..start]);
+        out.push_str(&placeholder);
+        last = end;
+    }
+
+    out.push_str(&text[last..]);
+    out
+}
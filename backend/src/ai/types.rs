@@ -3,7 +3,7 @@
 use serde::{Deserialize, Serialize};
 
 /// AI provider to use for processing
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum AIProvider {
     /// Google Gemini API
@@ -40,6 +40,42 @@ pub struct AIActionRequest {
     pub parameters: Option<serde_json::Value>,
 }
 
+impl AIActionRequest {
+    /// Whether the request carries CV or profile data, which users can opt
+    /// out of sharing with AI providers: CV text to extract skills from,
+    /// career content (cover letters, CV sections) written from the
+    /// profile, current skills for a roadmap, or profile context for a
+    /// question.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use backend::ai::types::{AIActionRequest, AIProvider, ActionType};
+    /// use serde_json::json;
+    ///
+    /// let request = |action, parameters| AIActionRequest {
+    ///     action,
+    ///     provider: AIProvider::Gemini,
+    ///     input: "Backend Developer".to_string(),
+    ///     parameters,
+    /// };
+    /// assert!(request(ActionType::ExtractSkills, None).includes_cv_data());
+    /// assert!(request(ActionType::GenerateContent, None).includes_cv_data());
+    /// assert!(request(ActionType::GenerateRoadmap, Some(json!({ "current_skills": "Rust" }))).includes_cv_data());
+    /// assert!(!request(ActionType::GenerateRoadmap, Some(json!({ "timeframe_months": 6 }))).includes_cv_data());
+    /// assert!(request(ActionType::AskQuestion, Some(json!({ "context": "3 years of Java" }))).includes_cv_data());
+    /// assert!(!request(ActionType::AskQuestion, None).includes_cv_data());
+    /// ```
+    pub fn includes_cv_data(&self) -> bool {
+        let has = |key: &str| self.parameters.as_ref().and_then(|p| p.get(key)).is_some_and(|v| !v.is_null());
+        match self.action {
+            ActionType::ExtractSkills | ActionType::GenerateContent => true,
+            ActionType::GenerateRoadmap => has("current_skills"),
+            ActionType::AskQuestion => has("context"),
+        }
+    }
+}
+
 fn default_provider() -> AIProvider {
     AIProvider::Gemini
 }
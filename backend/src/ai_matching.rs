@@ -1,6 +1,22 @@
+use crate::models::ExperienceLevel;
 use serde::{Deserialize, Serialize};
 use tracing::{info, warn};
+use sqlx::PgPool;
+use std::collections::HashMap;
 use std::env;
+use uuid::Uuid;
+
+/// Proficiency level (1-10) from which a matched skill gets full credit.
+const FULL_CREDIT_LEVEL: i32 = 7;
+
+/// Matched skills assessed below this level are suggested for practice.
+pub const WEAK_SKILL_LEVEL: i32 = 5;
+
+/// Experience alignment lost per level the user is below the job.
+const UNDERQUALIFIED_PENALTY: f64 = 20.0;
+
+/// Experience alignment lost per level the user is above the job.
+const OVERQUALIFIED_PENALTY: f64 = 30.0;
 
 /// Enhanced match analysis with AI-generated explanations
 #[derive(Debug, Serialize, Deserialize)]
@@ -22,8 +38,13 @@ pub struct EnhancedMatchAnalysis {
 }
 
 /// Calculate enhanced match score using heuristic algorithm
+///
+/// `skill_levels` maps lowercase skill names to the user's assessed
+/// proficiency (1-10); see [`proficiency_weight`]. Unassessed skills count
+/// fully.
 pub fn calculate_enhanced_match(
     user_skills: &[String],
+    skill_levels: &HashMap<String, i32>,
     job_skills: &[String],
     user_experience: Option<&str>,
     job_experience: &str,
@@ -31,7 +52,7 @@ pub fn calculate_enhanced_match(
     job_title: &str,
 ) -> EnhancedMatchAnalysis {
     // 1. Skill Overlap (60% weight) - Most important factor
-    let skill_overlap = calculate_skill_overlap(user_skills, job_skills);
+    let skill_overlap = calculate_skill_overlap(user_skills, skill_levels, job_skills);
     
     // 2. Experience Alignment (20% weight)
     let experience_alignment = calculate_experience_alignment(user_experience, job_experience);
@@ -43,7 +64,7 @@ pub fn calculate_enhanced_match(
     let match_score = (skill_overlap * 0.6) + (experience_alignment * 0.2) + (track_alignment * 0.2);
     
     // Generate explanation
-    let (explanation, strengths, improvements) = generate_match_explanation(
+    let (explanation, strengths, mut improvements) = generate_match_explanation(
         user_skills,
         job_skills,
         user_experience,
@@ -55,6 +76,17 @@ pub fn calculate_enhanced_match(
         track_alignment,
         match_score,
     );
+
+    let weak: Vec<String> = matched_skills(user_skills, job_skills)
+        .into_iter()
+        .filter_map(|skill| {
+            let level = *skill_levels.get(&skill.to_lowercase())?;
+            (level < WEAK_SKILL_LEVEL).then(|| format!("{} ({}/10)", skill, level))
+        })
+        .collect();
+    if !weak.is_empty() {
+        improvements.push(format!("Strengthen: {}", weak.join(", ")));
+    }
     
     EnhancedMatchAnalysis {
         match_score,
@@ -68,18 +100,17 @@ pub fn calculate_enhanced_match(
 }
 
 /// Calculate skill overlap score (0-100)
-fn calculate_skill_overlap(user_skills: &[String], job_skills: &[String]) -> f64 {
+fn calculate_skill_overlap(user_skills: &[String], skill_levels: &HashMap<String, i32>, job_skills: &[String]) -> f64 {
     if job_skills.is_empty() {
         return 100.0; // No skills required = perfect match
     }
     
-    let user_skills_set: std::collections::HashSet<_> = 
-        user_skills.iter().map(|s| s.to_lowercase()).collect();
-    let job_skills_set: std::collections::HashSet<_> = 
-        job_skills.iter().map(|s| s.to_lowercase()).collect();
-    
-    let matched = user_skills_set.intersection(&job_skills_set).count();
-    let overlap = (matched as f64 / job_skills.len() as f64) * 100.0;
+    // Matched skills count by proficiency, so a skill rated 2/10 is not a full match
+    let matched: f64 = matched_skills(user_skills, job_skills)
+        .iter()
+        .map(|skill| proficiency_weight(skill_levels.get(&skill.to_lowercase()).copied()))
+        .sum();
+    let overlap = (matched / job_skills.len() as f64) * 100.0;
     
     // Bonus for having more skills than required
     let bonus = if user_skills.len() > job_skills.len() {
@@ -91,31 +122,187 @@ fn calculate_skill_overlap(user_skills: &[String], job_skills: &[String]) -> f64
     (overlap + bonus).min(100.0)
 }
 
-/// Calculate experience level alignment (0-100)
-fn calculate_experience_alignment(user_exp: Option<&str>, job_exp: &str) -> f64 {
-    let user_exp = match user_exp {
-        Some(exp) => exp.to_lowercase(),
-        None => return 50.0, // Unknown = neutral
-    };
-    
-    let job_exp = job_exp.to_lowercase();
-    
-    // Exact match
-    if user_exp == job_exp {
-        return 100.0;
+/// Credit (0-1) a matched skill gets in the skill overlap, by the user's
+/// proficiency level (1-10).
+///
+/// Unassessed skills and levels from 7 up get full credit; lower levels get
+/// proportionally less, down to 0.4.
+///
+/// # Example
+///
+/// ```
+/// use backend::ai_matching::proficiency_weight;
+///
+/// assert_eq!(proficiency_weight(None), 1.0);
+/// assert_eq!(proficiency_weight(Some(9)), 1.0);
+/// assert!((proficiency_weight(Some(0)) - 0.4).abs() < 1e-9);
+/// assert!(proficiency_weight(Some(3)) < proficiency_weight(Some(5)));
+/// ```
+pub fn proficiency_weight(level: Option<i32>) -> f64 {
+    match level {
+        None => 1.0,
+        Some(level) => (0.4 + 0.6 * level.clamp(0, FULL_CREDIT_LEVEL) as f64 / FULL_CREDIT_LEVEL as f64).min(1.0),
     }
-    
-    // Experience hierarchy: fresher < junior < mid
-    match (user_exp.as_str(), job_exp.as_str()) {
-        ("fresher", "junior") | ("junior", "mid") => 80.0, // Close match
-        ("fresher", "mid") => 60.0, // Somewhat aligned
-        ("junior", "fresher") | ("mid", "junior") => 70.0, // Overqualified but okay
-        ("mid", "fresher") => 40.0, // Overqualified
-        _ => 50.0, // Unknown
+}
+
+/// Current skill levels of every user, keyed by lowercase skill name, for
+/// matching many users at once: the latest quiz result, else the latest
+/// self-rating.
+///
+/// # Errors
+///
+/// Returns an error if a database operation fails.
+pub async fn load_skill_levels_by_user(pool: &PgPool) -> Result<HashMap<Uuid, HashMap<String, i32>>, sqlx::Error> {
+    let levels = sqlx::query!(
+        r#"
+        SELECT DISTINCT ON (user_id, LOWER(skill_name)) user_id, LOWER(skill_name) AS "skill!",
+               proficiency_level AS "proficiency_level!"
+        FROM skill_assessments
+        WHERE proficiency_level IS NOT NULL
+        ORDER BY user_id, LOWER(skill_name), source = 'quiz' DESC, assessed_at DESC
+        "#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut levels_by_user: HashMap<Uuid, HashMap<String, i32>> = HashMap::new();
+    for row in levels {
+        levels_by_user.entry(row.user_id).or_default().insert(row.skill, row.proficiency_level);
     }
+    Ok(levels_by_user)
+}
+
+/// Job skills the user has, compared case-insensitively.
+///
+/// Returned in the job's order, without duplicates.
+///
+/// # Example
+///
+/// ```
+/// use backend::ai_matching::matched_skills;
+///
+/// let user = vec!["rust".to_string(), "SQL".to_string()];
+/// let job = vec!["SQL".to_string(), "Docker".to_string(), "Rust".to_string()];
+/// assert_eq!(matched_skills(&user, &job), vec!["SQL", "Rust"]);
+/// ```
+pub fn matched_skills(user_skills: &[String], job_skills: &[String]) -> Vec<String> {
+    let user_skills_set: std::collections::HashSet<_> =
+        user_skills.iter().map(|s| s.to_lowercase()).collect();
+    let mut seen = std::collections::HashSet::new();
+
+    job_skills
+        .iter()
+        .filter(|s| {
+            let lower = s.to_lowercase();
+            user_skills_set.contains(&lower) && seen.insert(lower)
+        })
+        .cloned()
+        .collect()
+}
+
+/// Job skills the user lacks, compared case-insensitively.
+///
+/// Returned in the job's order, without duplicates.
+///
+/// # Example
+///
+/// ```
+/// use backend::ai_matching::missing_skills;
+///
+/// let user = vec!["rust".to_string(), "SQL".to_string()];
+/// let job = vec!["SQL".to_string(), "Docker".to_string(), "docker".to_string()];
+/// assert_eq!(missing_skills(&user, &job), vec!["Docker"]);
+/// ```
+pub fn missing_skills(user_skills: &[String], job_skills: &[String]) -> Vec<String> {
+    let user_skills_set: std::collections::HashSet<_> =
+        user_skills.iter().map(|s| s.to_lowercase()).collect();
+    let mut seen = std::collections::HashSet::new();
+
+    job_skills
+        .iter()
+        .filter(|s| {
+            let lower = s.to_lowercase();
+            !user_skills_set.contains(&lower) && seen.insert(lower)
+        })
+        .cloned()
+        .collect()
+}
+
+/// Job skills mentioned as whole words in free text, such as a project
+/// description.
+///
+/// # Example
+///
+/// ```
+/// use backend::ai_matching::skills_mentioned;
+///
+/// let job = vec!["Go".to_string(), "Node.js".to_string(), "C++".to_string()];
+/// let text = "Built a Node.js API and a C++ parser, good to go";
+/// assert_eq!(skills_mentioned(text, &job), vec!["Go", "Node.js", "C++"]);
+/// assert!(skills_mentioned("Google Sheets", &job[..1]).is_empty());
+/// ```
+pub fn skills_mentioned(text: &str, job_skills: &[String]) -> Vec<String> {
+    let text = text.to_lowercase();
+    let mut seen = std::collections::HashSet::new();
+
+    job_skills
+        .iter()
+        .filter(|skill| {
+            let skill = skill.trim().to_lowercase();
+            !skill.is_empty() && contains_word(&text, &skill) && seen.insert(skill)
+        })
+        .cloned()
+        .collect()
+}
+
+/// Whether `needle` occurs in `haystack` not surrounded by letters or digits.
+pub(crate) fn contains_word(haystack: &str, needle: &str) -> bool {
+    haystack.match_indices(needle).any(|(start, _)| {
+        let before = haystack[..start].chars().next_back();
+        let after = haystack[start + needle.len()..].chars().next();
+        !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+    })
+}
+
+/// Calculate experience level alignment (0-100)
+///
+/// Scored by how many levels apart the user and the job are in the order
+/// fresher, junior, mid, senior, lead, manager: each level the user is below
+/// the job costs 20 points, each level above it (overqualified) 30. Unknown
+/// levels score a neutral 50.
+///
+/// # Example
+///
+/// ```
+/// use backend::ai_matching::calculate_experience_alignment;
+///
+/// assert_eq!(calculate_experience_alignment(Some("senior"), "senior"), 100.0);
+/// assert_eq!(calculate_experience_alignment(Some("junior"), "mid"), 80.0);
+/// assert_eq!(calculate_experience_alignment(Some("fresher"), "mid"), 60.0);
+/// assert_eq!(calculate_experience_alignment(Some("lead"), "senior"), 70.0);
+/// assert_eq!(calculate_experience_alignment(Some("manager"), "junior"), 0.0);
+/// assert_eq!(calculate_experience_alignment(None, "mid"), 50.0);
+/// assert_eq!(calculate_experience_alignment(Some("mid"), ""), 50.0);
+/// ```
+pub fn calculate_experience_alignment(user_exp: Option<&str>, job_exp: &str) -> f64 {
+    let (Some(user_level), Ok(job_level)) = (
+        user_exp.and_then(|exp| exp.parse::<ExperienceLevel>().ok()),
+        job_exp.parse::<ExperienceLevel>(),
+    ) else {
+        return 50.0; // Unknown = neutral
+    };
+
+    let distance = job_level.rank() - user_level.rank();
+    let penalty = if distance >= 0 {
+        UNDERQUALIFIED_PENALTY * distance as f64
+    } else {
+        OVERQUALIFIED_PENALTY * -distance as f64
+    };
+    (100.0 - penalty).max(0.0)
 }
 
 /// Calculate track alignment (0-100)
+#[allow(clippy::collapsible_match)]
 fn calculate_track_alignment(user_track: Option<&str>, job_title: &str) -> f64 {
     let user_track = match user_track {
         Some(track) => track.to_lowercase(),
@@ -165,6 +352,7 @@ fn calculate_track_alignment(user_track: Option<&str>, job_title: &str) -> f64 {
 }
 
 /// Generate human-readable match explanation
+#[allow(clippy::too_many_arguments)]
 fn generate_match_explanation(
     user_skills: &[String],
     job_skills: &[String],
@@ -246,6 +434,7 @@ fn generate_match_explanation(
     (full_explanation, strengths, improvements)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn generate_ai_explanation_hf(
     user_skills: &[String],
     job_skills: &[String],
@@ -288,6 +477,8 @@ Format: Start with overall assessment, then mention key strengths, then areas fo
         job_description.chars().take(200).collect::<String>(), // Truncate description
         match_score
     );
+    // Job descriptions can carry recruiter phone numbers and emails
+    let prompt = crate::ai::redaction::Redactor::default().redact(&prompt).text;
     
     let client = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(30))
@@ -296,7 +487,7 @@ Format: Start with overall assessment, then mention key strengths, then areas fo
     
     info!("Calling Hugging Face API for job match explanation");
     let response = client
-        .post(&format!("https://router.huggingface.co/hf-inference/models/{}", model))
+        .post(format!("https://router.huggingface.co/hf-inference/models/{}", model))
         .header("Authorization", format!("Bearer {}", api_key))
         .header("Content-Type", "application/json")
         .json(&serde_json::json!({
@@ -356,6 +547,7 @@ Format: Start with overall assessment, then mention key strengths, then areas fo
 
 /// Generate AI explanation with fallback to heuristic
 /// Tries Hugging Face API first, falls back to heuristic if it fails
+#[allow(clippy::too_many_arguments)]
 pub async fn generate_ai_explanation(
     user_skills: &[String],
     job_skills: &[String],
@@ -23,6 +23,9 @@ pub struct Claims {
     pub email: String,
     /// Expiration timestamp (Unix epoch)
     pub exp: i64,
+    /// Issue timestamp (Unix epoch); 0 for tokens issued before it was added
+    #[serde(default)]
+    pub iat: i64,
 }
 
 impl Claims {
@@ -33,7 +36,8 @@ impl Claims {
     /// * `user_id` - The user's UUID
     /// * `email` - The user's email address
     pub fn new(user_id: Uuid, email: String) -> Self {
-        let expiration = Utc::now()
+        let now = Utc::now();
+        let expiration = now
             .checked_add_signed(Duration::hours(24))
             .expect("Invalid timestamp")
             .timestamp();
@@ -42,6 +46,7 @@ impl Claims {
             sub: user_id.to_string(),
             email,
             exp: expiration,
+            iat: now.timestamp(),
         }
     }
 }
@@ -102,8 +107,11 @@ pub struct AuthUser {
     /// The authenticated user's ID
     pub user_id: Uuid,
     /// The authenticated user's email
-    #[allow(dead_code)]
     pub email: String,
+    /// When the token was issued (Unix epoch), for actions that need a recent sign-in
+    pub issued_at: i64,
+    /// When the token expires (Unix epoch), for connections outliving the request
+    pub expires_at: i64,
 }
 
 impl<S> FromRequestParts<S> for AuthUser
@@ -112,6 +120,7 @@ where
 {
     type Rejection = AppError;
 
+    #[allow(clippy::manual_async_fn)]
     fn from_request_parts(
         parts: &mut Parts,
         _state: &S,
@@ -129,16 +138,75 @@ where
                 .strip_prefix("Bearer ")
                 .ok_or(AppError::Unauthorized)?;
 
-            // Verify the token
-            let claims = verify_jwt(token)?;
-            
-            let user_id = Uuid::parse_str(&claims.sub)
-                .map_err(|_| AppError::Unauthorized)?;
+            AuthUser::from_token(token)
+        }
+    }
+}
+
+impl AuthUser {
+    /// Authenticates a bearer token passed some other way than the
+    /// `Authorization` header, e.g. by clients that cannot set headers.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AppError::Unauthorized`] if the token is invalid or expired.
+    pub fn from_token(token: &str) -> AppResult<Self> {
+        let claims = verify_jwt(token)?;
+
+        let user_id = Uuid::parse_str(&claims.sub)
+            .map_err(|_| AppError::Unauthorized)?;
+
+        Ok(AuthUser {
+            user_id,
+            email: claims.email,
+            issued_at: claims.iat,
+            expires_at: claims.exp,
+        })
+    }
+}
+
+/// Whether `email` is listed in `ADMIN_EMAILS` (comma-separated,
+/// case-insensitive).
+///
+/// # Example
+///
+/// ```
+/// use backend::auth::is_admin_email;
+///
+/// unsafe { std::env::set_var("ADMIN_EMAILS", "ops@example.com, Lead@Example.com") };
+/// assert!(is_admin_email("lead@example.com"));
+/// assert!(!is_admin_email("someone@example.com"));
+/// ```
+pub fn is_admin_email(email: &str) -> bool {
+    std::env::var("ADMIN_EMAILS").is_ok_and(|admins| {
+        admins
+            .split(',')
+            .map(str::trim)
+            .any(|admin| !admin.is_empty() && admin.eq_ignore_ascii_case(email))
+    })
+}
+
+/// Authenticated administrator, for moderation endpoints.
+///
+/// Rejects with 401 like [`AuthUser`] when the token is missing or invalid,
+/// and with 403 when the user's email is not in `ADMIN_EMAILS`.
+#[derive(Debug, Clone)]
+pub struct AdminUser(pub AuthUser);
 
-            Ok(AuthUser {
-                user_id,
-                email: claims.email,
-            })
+impl<S> FromRequestParts<S> for AdminUser
+where
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        let user = AuthUser::from_request_parts(parts, state).await?;
+        if !is_admin_email(&user.email) {
+            return Err(AppError::Forbidden("Administrator access required".to_string()));
         }
+        Ok(AdminUser(user))
     }
 }
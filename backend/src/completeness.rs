@@ -0,0 +1,327 @@
+//! Profile completeness scoring.
+//!
+//! Scores a profile out of 100 across seven sections and lists the next
+//! actions that would raise the score the most. Section weights depend on the
+//! user's career track (a designer's portfolio matters more than a data
+//! analyst's, whose skills matter more), and the scoring is fully
+//! deterministic, so it works without any AI provider configured.
+
+use crate::models::CareerTrack;
+use serde::Serialize;
+
+/// A scored part of the profile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProfileSection {
+    /// Phone, location and profile links
+    Contact,
+    /// Education history
+    Education,
+    /// Work experience and internships
+    Experience,
+    /// Skills and how proficient the user is in them
+    Skills,
+    /// Projects and portfolio work
+    Projects,
+    /// Uploaded CV file
+    Cv,
+    /// Roles the user is aiming for
+    TargetRoles,
+}
+
+/// What is known about a profile, gathered from the user record, structured
+/// entries and the parsed CV.
+#[derive(Debug, Clone, Default)]
+pub struct ProfileFacts {
+    /// A phone number is on the CV
+    pub has_phone: bool,
+    /// A location is on the CV
+    pub has_location: bool,
+    /// At least one LinkedIn, GitHub or portfolio link is on the CV
+    pub has_links: bool,
+    /// Education entries naming an institution
+    pub education_entries: usize,
+    /// Only an education level (e.g. "BSc") is known
+    pub has_education_level: bool,
+    /// Work experience entries
+    pub experience_entries: usize,
+    /// Work experience entries with at least one bullet point
+    pub experience_with_bullets: usize,
+    /// Skills listed on the profile
+    pub skills: usize,
+    /// Listed skills with a proficiency rating
+    pub rated_skills: usize,
+    /// Projects with a description or tech stack
+    pub detailed_projects: usize,
+    /// Projects with a title only
+    pub other_projects: usize,
+    /// A CV file has been uploaded
+    pub has_cv: bool,
+    /// Target roles chosen
+    pub target_roles: usize,
+}
+
+/// Score for one section.
+#[derive(Debug, Clone, Serialize)]
+pub struct SectionScore {
+    /// Section being scored
+    pub section: ProfileSection,
+    /// Points the section is worth for the user's track
+    pub weight: u32,
+    /// Points earned
+    pub points: u32,
+    /// Whether every point in the section has been earned
+    pub complete: bool,
+}
+
+/// A suggested next step.
+#[derive(Debug, Clone, Serialize)]
+pub struct ChecklistItem {
+    /// Section the action improves
+    pub section: ProfileSection,
+    /// What to do
+    pub action: String,
+    /// Points the score rises by once done
+    pub points: u32,
+    /// Endpoint that performs the action, if there is one
+    pub endpoint: Option<String>,
+}
+
+/// Overall completeness with per-section scores and a prioritized checklist.
+#[derive(Debug, Clone, Serialize)]
+pub struct CompletenessReport {
+    /// Track the weights were chosen for (balanced weights when `None`)
+    pub track: Option<CareerTrack>,
+    /// Score out of 100
+    pub score: u32,
+    /// Scores in display order
+    pub sections: Vec<SectionScore>,
+    /// Next actions, biggest gain first
+    pub checklist: Vec<ChecklistItem>,
+}
+
+/// One scored item within a section.
+struct Component {
+    /// Share of the section's weight, components of a section sum to 1
+    share: f64,
+    /// How much of it is done, from 0 to 1
+    done: f64,
+    action: String,
+    endpoint: Option<&'static str>,
+}
+
+impl Component {
+    fn new(share: f64, done: f64, action: impl Into<String>, endpoint: Option<&'static str>) -> Self {
+        Self {
+            share,
+            done: done.clamp(0.0, 1.0),
+            action: action.into(),
+            endpoint,
+        }
+    }
+}
+
+/// Section weights for a career track, summing to 100.
+///
+/// # Example
+///
+/// ```
+/// use backend::completeness::{ProfileSection, section_weights};
+/// use backend::models::CareerTrack;
+///
+/// let weight = |track| {
+///     section_weights(track)
+///         .into_iter()
+///         .find(|(section, _)| *section == ProfileSection::Projects)
+///         .map(|(_, weight)| weight)
+/// };
+/// assert!(weight(Some(CareerTrack::Design)) > weight(Some(CareerTrack::Marketing)));
+/// assert_eq!(section_weights(None).iter().map(|(_, w)| w).sum::<u32>(), 100);
+/// ```
+pub fn section_weights(track: Option<CareerTrack>) -> [(ProfileSection, u32); 7] {
+    use ProfileSection::*;
+    let [contact, education, experience, skills, projects, cv, target_roles] = match track {
+        Some(CareerTrack::WebDevelopment) => [10, 10, 15, 20, 25, 10, 10],
+        Some(CareerTrack::Data) => [10, 15, 15, 25, 20, 10, 5],
+        Some(CareerTrack::Design) => [15, 5, 15, 15, 30, 10, 10],
+        Some(CareerTrack::Marketing) => [15, 10, 25, 15, 15, 10, 10],
+        None => [15, 15, 15, 20, 15, 10, 10],
+    };
+    [
+        (Contact, contact),
+        (Education, education),
+        (Experience, experience),
+        (Skills, skills),
+        (Projects, projects),
+        (Cv, cv),
+        (TargetRoles, target_roles),
+    ]
+}
+
+/// Scores a profile and builds its checklist.
+///
+/// # Example
+///
+/// ```
+/// use backend::completeness::{ProfileFacts, ProfileSection, evaluate};
+/// use backend::models::CareerTrack;
+///
+/// let facts = ProfileFacts {
+///     has_phone: true,
+///     has_location: true,
+///     has_links: true,
+///     education_entries: 1,
+///     skills: 5,
+///     has_cv: true,
+///     target_roles: 1,
+///     ..ProfileFacts::default()
+/// };
+/// let report = evaluate(&facts, Some(CareerTrack::Design));
+///
+/// assert_eq!(report.score, 49);
+/// // A designer's portfolio is the biggest gap
+/// assert_eq!(report.checklist[0].section, ProfileSection::Projects);
+/// assert_eq!(report.checklist[0].points, 18);
+///
+/// let empty = evaluate(&ProfileFacts::default(), None);
+/// assert_eq!(empty.score, 0);
+/// assert!(empty.sections.iter().all(|s| !s.complete));
+/// ```
+pub fn evaluate(facts: &ProfileFacts, track: Option<CareerTrack>) -> CompletenessReport {
+    let mut sections = Vec::new();
+    let mut checklist = Vec::new();
+
+    for (section, weight) in section_weights(track) {
+        let components = components(section, facts, track);
+        let earned: f64 = components.iter().map(|c| c.share * c.done).sum();
+        let points = (weight as f64 * earned).round() as u32;
+
+        for component in components.into_iter().filter(|c| c.done < 1.0) {
+            checklist.push(ChecklistItem {
+                section,
+                action: component.action,
+                points: (weight as f64 * component.share * (1.0 - component.done)).round() as u32,
+                endpoint: component.endpoint.map(str::to_string),
+            });
+        }
+
+        sections.push(SectionScore {
+            section,
+            weight,
+            points,
+            complete: points == weight,
+        });
+    }
+
+    // Stable, so equal gains keep section order
+    checklist.retain(|item| item.points > 0);
+    checklist.sort_by_key(|item| std::cmp::Reverse(item.points));
+
+    CompletenessReport {
+        track,
+        score: sections.iter().map(|s| s.points).sum(),
+        sections,
+        checklist,
+    }
+}
+
+fn components(section: ProfileSection, facts: &ProfileFacts, track: Option<CareerTrack>) -> Vec<Component> {
+    const CV_DETAILS: Option<&str> = Some("PUT /api/profile/cv/parsed");
+    const PROFILE: Option<&str> = Some("PUT /api/profile");
+
+    let done = |yes: bool| if yes { 1.0 } else { 0.0 };
+    let ratio = |count: usize, of: usize| if of == 0 { 0.0 } else { count as f64 / of as f64 };
+
+    match section {
+        ProfileSection::Contact => {
+            let links = match track {
+                Some(CareerTrack::Design) => "Add a link to your portfolio (Behance, Dribbble or a personal site)",
+                Some(CareerTrack::WebDevelopment) => "Add your GitHub and LinkedIn profile links",
+                _ => "Add your LinkedIn or portfolio link",
+            };
+            vec![
+                Component::new(0.35, done(facts.has_phone), "Add a phone number", CV_DETAILS),
+                Component::new(0.3, done(facts.has_location), "Add your city or location", CV_DETAILS),
+                Component::new(0.35, done(facts.has_links), links, CV_DETAILS),
+            ]
+        }
+        ProfileSection::Education => {
+            let progress = if facts.education_entries > 0 {
+                1.0
+            } else if facts.has_education_level {
+                0.5
+            } else {
+                0.0
+            };
+            vec![Component::new(
+                1.0,
+                progress,
+                "Add your institution and degree to your education history",
+                Some("POST /api/profile/education"),
+            )]
+        }
+        ProfileSection::Experience => vec![
+            Component::new(
+                0.7,
+                done(facts.experience_entries > 0),
+                "Add your work experience, including internships and volunteering",
+                Some("POST /api/profile/experience"),
+            ),
+            Component::new(
+                0.3,
+                ratio(facts.experience_with_bullets, facts.experience_entries.max(1)),
+                "Describe what you achieved in each role with bullet points",
+                Some("PUT /api/profile/experience/{id}"),
+            ),
+        ],
+        ProfileSection::Skills => vec![
+            Component::new(0.6, ratio(facts.skills.min(5), 5), "List at least 5 skills", PROFILE),
+            Component::new(
+                0.4,
+                ratio(facts.rated_skills, facts.skills),
+                "Rate your proficiency in each of your skills",
+                Some("POST /api/skills/ratings"),
+            ),
+        ],
+        ProfileSection::Projects => {
+            let (first, second) = match track {
+                Some(CareerTrack::Design) => (
+                    "Add a portfolio project with a description of your design process",
+                    "Add a second portfolio project",
+                ),
+                _ => (
+                    "Add a project with a description and tech stack",
+                    "Add a second project",
+                ),
+            };
+            let first_done = if facts.detailed_projects > 0 {
+                1.0
+            } else if facts.other_projects > 0 {
+                0.3
+            } else {
+                0.0
+            };
+            vec![
+                Component::new(0.6, first_done, first, Some("POST /api/profile/projects")),
+                Component::new(
+                    0.4,
+                    done(facts.detailed_projects > 1),
+                    second,
+                    Some("POST /api/profile/projects"),
+                ),
+            ]
+        }
+        ProfileSection::Cv => vec![Component::new(
+            1.0,
+            done(facts.has_cv),
+            "Upload your CV",
+            Some("POST /api/profile/cv/upload"),
+        )],
+        ProfileSection::TargetRoles => vec![Component::new(
+            1.0,
+            done(facts.target_roles > 0),
+            "Choose the roles you are targeting",
+            PROFILE,
+        )],
+    }
+}
@@ -0,0 +1,95 @@
+//! Renderer-neutral CV content.
+//!
+//! A [`CvDocument`] combines the user's profile with their reviewed CV parse
+//! and is what the PDF generator (and other output formats) render from.
+
+use super::parser::{CvEntry, ParsedCv};
+use serde::{Deserialize, Serialize};
+
+/// Visual template used when rendering a CV.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CvTemplate {
+    /// Serif typeface, centered header and ruled section headings
+    #[default]
+    Classic,
+    /// Sans-serif typeface with an accent-colored header band
+    Modern,
+    /// Plain single-column layout without graphics, for applicant tracking systems
+    Ats,
+}
+
+/// Content of a generated CV.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CvDocument {
+    /// Line shown under the name, e.g. the target roles
+    pub headline: Option<String>,
+    /// Sections; `cv.contact.full_name` is the name printed at the top
+    #[serde(flatten)]
+    pub cv: ParsedCv,
+}
+
+impl CvDocument {
+    /// Name printed at the top of the CV.
+    pub fn name(&self) -> &str {
+        self.cv.contact.full_name.as_deref().unwrap_or("Curriculum Vitae")
+    }
+
+    /// Contact details in display order: email, phone, location, links.
+    pub fn contact_items(&self) -> Vec<&str> {
+        let contact = &self.cv.contact;
+        contact
+            .email
+            .iter()
+            .chain(contact.phone.iter())
+            .chain(contact.location.iter())
+            .chain(contact.links.iter())
+            .map(|s| s.as_str())
+            .filter(|s| !s.trim().is_empty())
+            .collect()
+    }
+}
+
+/// Formats the date range of an entry, e.g. `Jan 2021 – Present`.
+///
+/// # Example
+///
+/// ```
+/// use backend::cv::{CvEntry, document::date_range};
+/// use chrono::NaiveDate;
+///
+/// let entry = CvEntry {
+///     start_date: NaiveDate::from_ymd_opt(2021, 1, 1),
+///     is_current: true,
+///     ..CvEntry::default()
+/// };
+/// assert_eq!(date_range(&entry).as_deref(), Some("Jan 2021 – Present"));
+/// assert_eq!(date_range(&CvEntry::default()), None);
+/// ```
+pub fn date_range(entry: &CvEntry) -> Option<String> {
+    let start = entry.start_date.map(|d| d.format("%b %Y").to_string());
+    let end = if entry.is_current {
+        Some("Present".to_string())
+    } else {
+        entry.end_date.map(|d| d.format("%b %Y").to_string())
+    };
+
+    match (start, end) {
+        (Some(start), Some(end)) if start != end => Some(format!("{} – {}", start, end)),
+        (Some(date), _) | (None, Some(date)) => Some(date),
+        (None, None) => None,
+    }
+}
+
+/// Splits an entry description into bullet points, dropping bullet markers.
+pub fn description_points(entry: &CvEntry) -> Vec<String> {
+    entry
+        .description
+        .as_deref()
+        .unwrap_or_default()
+        .lines()
+        .map(|line| line.trim().trim_start_matches(['-', '*', '•', '·', '▪', '◦', '‣']).trim())
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
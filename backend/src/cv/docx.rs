@@ -0,0 +1,302 @@
+//! Word (DOCX) rendering of a CV.
+//!
+//! Writes a minimal WordprocessingML package by hand: built-in heading and
+//! list styles keep the document editable and readable by applicant tracking
+//! systems, and Bengali text uses the complex-script font (Nirmala UI /
+//! Vrinda on Windows) so it displays without manual font changes.
+
+use super::document::{CvDocument, CvTemplate, date_range, description_points};
+use super::parser::CvEntry;
+use std::io::{Cursor, Write};
+use zip::write::SimpleFileOptions;
+
+/// A4 content width in twentieths of a point, with 2 cm margins.
+const CONTENT_WIDTH: u32 = 11906 - 2 * 1134;
+
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Override PartName="/word/document.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"/><Override PartName="/word/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.styles+xml"/><Override PartName="/word/numbering.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.numbering+xml"/><Override PartName="/docProps/core.xml" ContentType="application/vnd.openxmlformats-package.core-properties+xml"/></Types>"#;
+
+const PACKAGE_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/document.xml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/package/2006/relationships/metadata/core-properties" Target="docProps/core.xml"/></Relationships>"#;
+
+const DOCUMENT_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/numbering" Target="numbering.xml"/></Relationships>"#;
+
+const NUMBERING: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:numbering xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:abstractNum w:abstractNumId="0"><w:multiLevelType w:val="singleLevel"/><w:lvl w:ilvl="0"><w:start w:val="1"/><w:numFmt w:val="bullet"/><w:lvlText w:val="•"/><w:lvlJc w:val="left"/><w:pPr><w:ind w:left="360" w:hanging="270"/></w:pPr></w:lvl></w:abstractNum><w:num w:numId="1"><w:abstractNumId w:val="0"/></w:num></w:numbering>"#;
+
+/// Fonts and colors of a template.
+struct DocxTheme {
+    font: &'static str,
+    /// Body size in half-points
+    body_size: u32,
+    name_size: u32,
+    heading_size: u32,
+    accent: &'static str,
+    muted: &'static str,
+    center_header: bool,
+    header_band: bool,
+    uppercase_headings: bool,
+    heading_rule: bool,
+}
+
+impl DocxTheme {
+    fn for_template(template: CvTemplate) -> Self {
+        match template {
+            CvTemplate::Classic => DocxTheme {
+                font: "Georgia",
+                body_size: 21,
+                name_size: 44,
+                heading_size: 24,
+                accent: "1A1A1A",
+                muted: "595959",
+                center_header: true,
+                header_band: false,
+                uppercase_headings: true,
+                heading_rule: true,
+            },
+            CvTemplate::Modern => DocxTheme {
+                font: "Calibri",
+                body_size: 21,
+                name_size: 48,
+                heading_size: 26,
+                accent: "175C9E",
+                muted: "666E7A",
+                center_header: false,
+                header_band: true,
+                uppercase_headings: false,
+                heading_rule: true,
+            },
+            CvTemplate::Ats => DocxTheme {
+                font: "Arial",
+                body_size: 21,
+                name_size: 32,
+                heading_size: 24,
+                accent: "000000",
+                muted: "000000",
+                center_header: false,
+                header_band: false,
+                uppercase_headings: true,
+                heading_rule: false,
+            },
+        }
+    }
+
+    fn styles(&self) -> String {
+        let rule = if self.heading_rule {
+            format!(r#"<w:pBdr><w:bottom w:val="single" w:sz="6" w:space="1" w:color="{}"/></w:pBdr>"#, self.accent)
+        } else {
+            String::new()
+        };
+        let caps = if self.uppercase_headings { "<w:caps/>" } else { "" };
+        let header_jc = if self.center_header { r#"<w:jc w:val="center"/>"# } else { "" };
+
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:docDefaults><w:rPrDefault><w:rPr><w:rFonts w:ascii="{font}" w:hAnsi="{font}" w:eastAsia="{font}" w:cs="Nirmala UI"/><w:sz w:val="{body}"/><w:szCs w:val="{body}"/><w:lang w:val="en-US" w:bidi="bn-BD"/></w:rPr></w:rPrDefault><w:pPrDefault><w:pPr><w:spacing w:after="40" w:line="264" w:lineRule="auto"/></w:pPr></w:pPrDefault></w:docDefaults><w:style w:type="paragraph" w:default="1" w:styleId="Normal"><w:name w:val="Normal"/><w:qFormat/></w:style><w:style w:type="paragraph" w:styleId="Title"><w:name w:val="Title"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/><w:pPr>{jc}<w:spacing w:after="0"/></w:pPr><w:rPr><w:b/><w:bCs/><w:sz w:val="{name}"/><w:szCs w:val="{name}"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="Subtitle"><w:name w:val="Subtitle"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/><w:pPr>{jc}</w:pPr><w:rPr><w:color w:val="{muted}"/><w:sz w:val="{sub}"/><w:szCs w:val="{sub}"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="Contact"><w:name w:val="Contact"/><w:basedOn w:val="Normal"/><w:pPr>{jc}<w:spacing w:after="120"/></w:pPr><w:rPr><w:color w:val="{muted}"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="Heading1"><w:name w:val="heading 1"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/><w:pPr><w:keepNext/>{rule}<w:spacing w:before="240" w:after="80"/><w:outlineLvl w:val="0"/></w:pPr><w:rPr><w:b/><w:bCs/>{caps}<w:color w:val="{accent}"/><w:sz w:val="{heading}"/><w:szCs w:val="{heading}"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="Heading2"><w:name w:val="heading 2"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/><w:pPr><w:keepNext/><w:tabs><w:tab w:val="right" w:pos="{width}"/></w:tabs><w:spacing w:before="120" w:after="0"/><w:outlineLvl w:val="1"/></w:pPr><w:rPr><w:b/><w:bCs/></w:rPr></w:style><w:style w:type="character" w:styleId="Muted"><w:name w:val="Muted"/><w:rPr><w:b w:val="0"/><w:bCs w:val="0"/><w:color w:val="{muted}"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="ListBullet"><w:name w:val="List Bullet"/><w:basedOn w:val="Normal"/><w:pPr><w:numPr><w:numId w:val="1"/></w:numPr><w:spacing w:after="20"/></w:pPr></w:style></w:styles>"#,
+            font = self.font,
+            body = self.body_size,
+            name = self.name_size,
+            sub = self.body_size + 3,
+            heading = self.heading_size,
+            accent = self.accent,
+            muted = self.muted,
+            jc = header_jc,
+            rule = rule,
+            caps = caps,
+            width = CONTENT_WIDTH,
+        )
+    }
+}
+
+/// Escapes text for XML, dropping control characters XML 1.0 does not allow.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\t' | '\n' | '\r' => out.push(' '),
+            c if (c as u32) < 0x20 || c == '\u{FFFE}' || c == '\u{FFFF}' => {}
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// A text run with an optional character style.
+fn run(text: &str, style: Option<&str>) -> String {
+    let props = style
+        .map(|s| format!(r#"<w:rPr><w:rStyle w:val="{}"/></w:rPr>"#, s))
+        .unwrap_or_default();
+    // Line breaks inside a value become <w:br/> within the same paragraph
+    text.lines()
+        .map(|line| format!(r#"<w:r>{}<w:t xml:space="preserve">{}</w:t></w:r>"#, props, escape(line)))
+        .collect::<Vec<_>>()
+        .join("<w:r><w:br/></w:r>")
+}
+
+/// A paragraph with a paragraph style and extra properties.
+fn paragraph(style: &str, extra_props: &str, runs: &str) -> String {
+    format!(r#"<w:p><w:pPr><w:pStyle w:val="{}"/>{}</w:pPr>{}</w:p>"#, style, extra_props, runs)
+}
+
+/// Renders a CV as a Word document.
+///
+/// # Example
+///
+/// ```
+/// use backend::cv::document::{CvDocument, CvTemplate};
+/// use backend::cv::extract::extract_docx;
+/// use backend::cv::{ContactInfo, CvEntry, ParsedCv};
+///
+/// let doc = CvDocument {
+///     cv: ParsedCv {
+///         contact: ContactInfo { full_name: Some("Ayesha Rahman".to_string()), ..ContactInfo::default() },
+///         experience: vec![CvEntry {
+///             title: "Junior Developer".to_string(),
+///             description: Some("- Built APIs\n- Wrote tests".to_string()),
+///             ..CvEntry::default()
+///         }],
+///         ..ParsedCv::default()
+///     },
+///     ..CvDocument::default()
+/// };
+///
+/// let docx = backend::cv::docx::render_docx(&doc, CvTemplate::Ats).unwrap();
+/// let text = extract_docx(&docx).unwrap();
+/// assert!(text.starts_with("Ayesha Rahman\n"));
+/// assert!(text.contains("Junior Developer\nBuilt APIs\nWrote tests"));
+/// ```
+pub fn render_docx(document: &CvDocument, template: CvTemplate) -> zip::result::ZipResult<Vec<u8>> {
+    let theme = DocxTheme::for_template(template);
+    let cv = &document.cv;
+    let mut body = String::new();
+
+    // Modern: white text on an accent-shaded header block
+    let band = if theme.header_band {
+        format!(r#"<w:shd w:val="clear" w:color="auto" w:fill="{}"/><w:ind w:left="113" w:right="113"/>"#, theme.accent)
+    } else {
+        String::new()
+    };
+    let header_run = |text: &str| {
+        if theme.header_band {
+            format!(
+                r#"<w:r><w:rPr><w:color w:val="FFFFFF"/></w:rPr><w:t xml:space="preserve">{}</w:t></w:r>"#,
+                escape(text)
+            )
+        } else {
+            run(text, None)
+        }
+    };
+
+    body.push_str(&paragraph("Title", &band, &header_run(document.name())));
+    if let Some(headline) = &document.headline {
+        body.push_str(&paragraph("Subtitle", &band, &header_run(headline)));
+    }
+    let contact = document.contact_items();
+    if template == CvTemplate::Ats {
+        // One field per line so parsers pick up each contact detail
+        for item in contact {
+            body.push_str(&paragraph("Normal", "", &run(item, None)));
+        }
+    } else if !contact.is_empty() {
+        body.push_str(&paragraph("Contact", &band, &header_run(&contact.join("  |  "))));
+    }
+
+    if let Some(summary) = cv.summary.as_deref().filter(|s| !s.trim().is_empty()) {
+        let title = if template == CvTemplate::Ats { "Summary" } else { "Profile" };
+        body.push_str(&paragraph("Heading1", "", &run(title, None)));
+        body.push_str(&paragraph("Normal", "", &run(summary.trim(), None)));
+    }
+    if !cv.skills.is_empty() {
+        body.push_str(&paragraph("Heading1", "", &run("Skills", None)));
+        body.push_str(&paragraph("Normal", "", &run(&cv.skills.join(", "), None)));
+    }
+
+    let sections: [(&str, &Vec<CvEntry>); 4] = [
+        (if template == CvTemplate::Ats { "Work Experience" } else { "Experience" }, &cv.experience),
+        ("Projects", &cv.projects),
+        ("Education", &cv.education),
+        ("Certifications", &cv.certifications),
+    ];
+    for (title, entries) in sections {
+        if entries.is_empty() {
+            continue;
+        }
+        body.push_str(&paragraph("Heading1", "", &run(title, None)));
+        for entry in entries {
+            body.push_str(&entry_xml(entry, template));
+        }
+    }
+
+    let document_xml = format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>{}<w:sectPr><w:pgSz w:w="11906" w:h="16838"/><w:pgMar w:top="1134" w:right="1134" w:bottom="1134" w:left="1134" w:header="567" w:footer="567" w:gutter="0"/></w:sectPr></w:body></w:document>"#,
+        body
+    );
+    let core_xml = format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<cp:coreProperties xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/core-properties" xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>{0} - CV</dc:title><dc:creator>{0}</dc:creator></cp:coreProperties>"#,
+        escape(document.name())
+    );
+
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    let parts = [
+        ("[Content_Types].xml", CONTENT_TYPES.to_string()),
+        ("_rels/.rels", PACKAGE_RELS.to_string()),
+        ("docProps/core.xml", core_xml),
+        ("word/_rels/document.xml.rels", DOCUMENT_RELS.to_string()),
+        ("word/document.xml", document_xml),
+        ("word/styles.xml", theme.styles()),
+        ("word/numbering.xml", NUMBERING.to_string()),
+    ];
+    for (name, content) in parts {
+        zip.start_file(name, options)?;
+        zip.write_all(content.as_bytes())?;
+    }
+    Ok(zip.finish()?.into_inner())
+}
+
+/// Title with right-aligned dates, organization line and bullet points.
+fn entry_xml(entry: &CvEntry, template: CvTemplate) -> String {
+    let mut xml = String::new();
+    let dates = date_range(entry);
+    let place = [entry.organization.as_deref(), entry.location.as_deref()]
+        .into_iter()
+        .flatten()
+        .filter(|s| !s.trim().is_empty())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    if template == CvTemplate::Ats {
+        xml.push_str(&paragraph("Heading2", "", &run(&entry.title, None)));
+        let details: Vec<&str> = [place.as_str(), dates.as_deref().unwrap_or_default()]
+            .into_iter()
+            .filter(|s| !s.is_empty())
+            .collect();
+        if !details.is_empty() {
+            xml.push_str(&paragraph("Normal", "", &run(&details.join(" | "), None)));
+        }
+    } else {
+        let mut runs = run(&entry.title, None);
+        if let Some(dates) = &dates {
+            runs.push_str("<w:r><w:tab/></w:r>");
+            runs.push_str(&run(dates, Some("Muted")));
+        }
+        xml.push_str(&paragraph("Heading2", "", &runs));
+        if !place.is_empty() {
+            xml.push_str(&paragraph("Normal", "", &run(&place, Some("Muted"))));
+        }
+    }
+
+    let points = description_points(entry);
+    let list_style = if points.len() > 1 { "ListBullet" } else { "Normal" };
+    for point in points {
+        xml.push_str(&paragraph(list_style, "", &run(&point, None)));
+    }
+    xml
+}
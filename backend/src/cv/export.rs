@@ -0,0 +1,138 @@
+//! CV export formats and content negotiation.
+//!
+//! Every format renders the same [`CvDocument`]: PDF for printing, DOCX for
+//! portals that require Word files, HTML and Markdown for pasting into other
+//! sites, and JSON Resume for moving between resume tools.
+
+use super::document::{CvDocument, CvTemplate};
+use serde::Deserialize;
+use thiserror::Error;
+
+/// Output format of an exported CV.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// A4 PDF
+    #[default]
+    Pdf,
+    /// Word document
+    Docx,
+    /// Standalone HTML page
+    Html,
+    /// Markdown
+    #[serde(alias = "md")]
+    Markdown,
+    /// JSON Resume
+    #[serde(alias = "json-resume", alias = "jsonresume")]
+    Json,
+}
+
+/// Errors raised while rendering an export.
+#[derive(Debug, Error)]
+pub enum ExportError {
+    /// PDF layout or font embedding failed
+    #[error("PDF rendering failed: {0}")]
+    Pdf(#[from] printpdf::Error),
+    /// The DOCX package could not be written
+    #[error("DOCX packaging failed: {0}")]
+    Docx(#[from] zip::result::ZipError),
+    /// JSON serialization failed
+    #[error("JSON serialization failed: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+impl ExportFormat {
+    /// Media type sent in `Content-Type`.
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            ExportFormat::Pdf => "application/pdf",
+            ExportFormat::Docx => {
+                "application/vnd.openxmlformats-officedocument.wordprocessingml.document"
+            }
+            ExportFormat::Html => "text/html; charset=utf-8",
+            ExportFormat::Markdown => "text/markdown; charset=utf-8",
+            ExportFormat::Json => "application/json",
+        }
+    }
+
+    /// File extension of the download.
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Pdf => "pdf",
+            ExportFormat::Docx => "docx",
+            ExportFormat::Html => "html",
+            ExportFormat::Markdown => "md",
+            ExportFormat::Json => "json",
+        }
+    }
+
+    fn from_media_type(media_type: &str) -> Option<Self> {
+        match media_type {
+            "application/pdf" => Some(ExportFormat::Pdf),
+            "application/vnd.openxmlformats-officedocument.wordprocessingml.document" => {
+                Some(ExportFormat::Docx)
+            }
+            "text/html" | "application/xhtml+xml" => Some(ExportFormat::Html),
+            "text/markdown" | "text/x-markdown" => Some(ExportFormat::Markdown),
+            "application/json" | "application/vnd.jsonresume+json" => Some(ExportFormat::Json),
+            _ => None,
+        }
+    }
+
+    /// Picks the format from an `Accept` header.
+    ///
+    /// Media types are tried by descending quality, in header order for ties.
+    /// Returns `None` when no listed type is supported; wildcards also return
+    /// `None` so the caller's default applies.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use backend::cv::export::ExportFormat;
+    ///
+    /// let accept = "text/html;q=0.5, application/vnd.openxmlformats-officedocument.wordprocessingml.document";
+    /// assert_eq!(ExportFormat::from_accept(accept), Some(ExportFormat::Docx));
+    /// assert_eq!(ExportFormat::from_accept("text/markdown"), Some(ExportFormat::Markdown));
+    /// assert_eq!(ExportFormat::from_accept("*/*"), None);
+    /// ```
+    pub fn from_accept(accept: &str) -> Option<Self> {
+        let mut candidates: Vec<(f32, usize, &str)> = accept
+            .split(',')
+            .enumerate()
+            .filter_map(|(i, item)| {
+                let mut parts = item.split(';');
+                let media_type = parts.next()?.trim();
+                let quality = parts
+                    .filter_map(|p| p.trim().strip_prefix("q="))
+                    .find_map(|q| q.trim().parse::<f32>().ok())
+                    .unwrap_or(1.0);
+                (quality > 0.0).then_some((quality, i, media_type))
+            })
+            .collect();
+        candidates.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)));
+
+        candidates
+            .into_iter()
+            .find_map(|(_, _, media_type)| Self::from_media_type(&media_type.to_ascii_lowercase()))
+    }
+}
+
+/// Renders a CV document in the given format.
+///
+/// The template applies to PDF, DOCX and HTML; Markdown and JSON Resume have
+/// no visual styling.
+pub fn render(
+    document: &CvDocument,
+    format: ExportFormat,
+    template: CvTemplate,
+) -> Result<Vec<u8>, ExportError> {
+    Ok(match format {
+        ExportFormat::Pdf => super::pdf::render_pdf(document, template)?,
+        ExportFormat::Docx => super::docx::render_docx(document, template)?,
+        ExportFormat::Html => super::html::render_html(document, template).into_bytes(),
+        ExportFormat::Markdown => super::html::render_markdown(document).into_bytes(),
+        ExportFormat::Json => {
+            serde_json::to_vec_pretty(&super::json_resume::to_json_resume(document))?
+        }
+    })
+}
@@ -0,0 +1,652 @@
+//! Text extraction for uploaded CV files.
+//!
+//! The file type is detected from the content (magic bytes and, for zip
+//! containers, the entries inside), never from the filename or the
+//! client-supplied content type. Each format has its own extractor:
+//!
+//! - PDF via `pdf-extract`
+//! - DOCX (`word/document.xml`) and ODT (`content.xml`) by walking the XML
+//! - Plain text (UTF-8 or UTF-16 with BOM) and Markdown
+//! - Images through an [`OcrEngine`], e.g. a locally installed Tesseract
+
+use quick_xml::Reader;
+use quick_xml::events::Event;
+use serde::{Deserialize, Serialize};
+use std::io::{Cursor, Read, Write};
+use std::path::PathBuf;
+use std::process::Command;
+use thiserror::Error;
+
+/// Largest XML part read from a DOCX/ODT container.
+const MAX_XML_PART_BYTES: u64 = 20 * 1024 * 1024;
+
+/// CV file formats the extractor understands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CvFormat {
+    /// Portable Document Format
+    Pdf,
+    /// Office Open XML word processing document
+    Docx,
+    /// OpenDocument text document
+    Odt,
+    /// Plain text
+    PlainText,
+    /// Markdown
+    Markdown,
+    /// Raster image (photo or scan), read through OCR
+    Image(ImageFormat),
+}
+
+/// Image formats accepted for OCR.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImageFormat {
+    Png,
+    Jpeg,
+    Tiff,
+    Webp,
+    Bmp,
+}
+
+impl CvFormat {
+    /// Short name used in API responses and logs.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CvFormat::Pdf => "pdf",
+            CvFormat::Docx => "docx",
+            CvFormat::Odt => "odt",
+            CvFormat::PlainText => "txt",
+            CvFormat::Markdown => "markdown",
+            CvFormat::Image(ImageFormat::Png) => "png",
+            CvFormat::Image(ImageFormat::Jpeg) => "jpeg",
+            CvFormat::Image(ImageFormat::Tiff) => "tiff",
+            CvFormat::Image(ImageFormat::Webp) => "webp",
+            CvFormat::Image(ImageFormat::Bmp) => "bmp",
+        }
+    }
+
+    /// File extension used when storing the original upload.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            CvFormat::Markdown => "md",
+            CvFormat::Image(ImageFormat::Jpeg) => "jpg",
+            CvFormat::Image(ImageFormat::Tiff) => "tif",
+            other => other.as_str(),
+        }
+    }
+
+    /// MIME type sent when the original upload is downloaded.
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            CvFormat::Pdf => "application/pdf",
+            CvFormat::Docx => {
+                "application/vnd.openxmlformats-officedocument.wordprocessingml.document"
+            }
+            CvFormat::Odt => "application/vnd.oasis.opendocument.text",
+            CvFormat::PlainText => "text/plain; charset=utf-8",
+            CvFormat::Markdown => "text/markdown; charset=utf-8",
+            CvFormat::Image(ImageFormat::Png) => "image/png",
+            CvFormat::Image(ImageFormat::Jpeg) => "image/jpeg",
+            CvFormat::Image(ImageFormat::Tiff) => "image/tiff",
+            CvFormat::Image(ImageFormat::Webp) => "image/webp",
+            CvFormat::Image(ImageFormat::Bmp) => "image/bmp",
+        }
+    }
+}
+
+/// Errors raised while detecting or extracting a CV file.
+#[derive(Debug, Error, Serialize, Deserialize)]
+pub enum ExtractError {
+    /// Content doesn't match any supported format
+    #[error("Unsupported file type")]
+    UnsupportedFormat,
+    /// Format was recognised but the file could not be read
+    #[error("File is corrupted or unreadable: {0}")]
+    Corrupt(String),
+    /// File was read but contains no text
+    #[error("File contains no extractable text")]
+    Empty,
+    /// An image was uploaded but no OCR engine is configured
+    #[error("OCR is not available on this server")]
+    OcrUnavailable,
+    /// The OCR engine failed
+    #[error("OCR failed: {0}")]
+    Ocr(String),
+}
+
+/// Text recognition engine for image CVs.
+pub trait OcrEngine: Send + Sync {
+    /// Engine name for logs
+    fn name(&self) -> &str;
+
+    /// Returns the text found in an encoded image (PNG, JPEG, ...).
+    fn recognize(&self, image: &[u8]) -> Result<String, ExtractError>;
+}
+
+/// OCR through a locally installed `tesseract` binary.
+#[derive(Debug, Clone)]
+pub struct TesseractOcr {
+    command: PathBuf,
+    languages: String,
+}
+
+impl TesseractOcr {
+    /// Creates an engine using the given binary and language list (e.g. `eng+ben`).
+    pub fn new(command: impl Into<PathBuf>, languages: impl Into<String>) -> Self {
+        Self {
+            command: command.into(),
+            languages: languages.into(),
+        }
+    }
+
+    /// Configures the engine from `TESSERACT_CMD` (default `tesseract`) and
+    /// `OCR_LANGUAGES` (default `eng+ben`).
+    ///
+    /// Returns `None` when the binary can't be executed.
+    pub fn from_env() -> Option<Self> {
+        let command = std::env::var("TESSERACT_CMD").unwrap_or_else(|_| "tesseract".to_string());
+        let languages = std::env::var("OCR_LANGUAGES").unwrap_or_else(|_| "eng+ben".to_string());
+
+        let available = Command::new(&command)
+            .arg("--version")
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false);
+
+        available.then(|| Self::new(command, languages))
+    }
+}
+
+impl OcrEngine for TesseractOcr {
+    fn name(&self) -> &str {
+        "tesseract"
+    }
+
+    fn recognize(&self, image: &[u8]) -> Result<String, ExtractError> {
+        let mut file = tempfile::NamedTempFile::new().map_err(|e| ExtractError::Ocr(e.to_string()))?;
+        file.write_all(image)
+            .and_then(|_| file.flush())
+            .map_err(|e| ExtractError::Ocr(e.to_string()))?;
+
+        let output = Command::new(&self.command)
+            .arg(file.path())
+            .arg("stdout")
+            .arg("-l")
+            .arg(&self.languages)
+            .output()
+            .map_err(|e| ExtractError::Ocr(format!("failed to run {}: {}", self.command.display(), e)))?;
+
+        if !output.status.success() {
+            return Err(ExtractError::Ocr(
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+/// Text extracted from a CV together with the detected format.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtractedText {
+    /// Detected file format
+    pub format: CvFormat,
+    /// Extracted text, with `\n` line endings
+    pub text: String,
+}
+
+/// Detects the CV format from the file content.
+///
+/// # Example
+///
+/// ```
+/// use backend::cv::extract::{CvFormat, ImageFormat, detect_format};
+///
+/// assert_eq!(detect_format(b"%PDF-1.7\n..."), Some(CvFormat::Pdf));
+/// assert_eq!(detect_format(b"\x89PNG\r\n\x1a\n...."), Some(CvFormat::Image(ImageFormat::Png)));
+/// assert_eq!(detect_format(b"Jane Doe\nSkills: Rust"), Some(CvFormat::PlainText));
+/// assert_eq!(detect_format(b"# Jane Doe\n\n## Skills\n- Rust"), Some(CvFormat::Markdown));
+/// assert_eq!(detect_format(b"MZ\x90\x00\x03\x00\x00\x00"), None);
+/// ```
+pub fn detect_format(bytes: &[u8]) -> Option<CvFormat> {
+    if bytes.starts_with(b"%PDF") {
+        return Some(CvFormat::Pdf);
+    }
+    if bytes.starts_with(b"PK\x03\x04") {
+        return detect_zip_format(bytes);
+    }
+    if let Some(image) = detect_image_format(bytes) {
+        return Some(CvFormat::Image(image));
+    }
+
+    let text = decode_text(bytes)?;
+    Some(if looks_like_markdown(&text) {
+        CvFormat::Markdown
+    } else {
+        CvFormat::PlainText
+    })
+}
+
+fn detect_image_format(bytes: &[u8]) -> Option<ImageFormat> {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some(ImageFormat::Png)
+    } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some(ImageFormat::Jpeg)
+    } else if bytes.starts_with(b"II*\x00") || bytes.starts_with(b"MM\x00*") {
+        Some(ImageFormat::Tiff)
+    } else if bytes.len() >= 12 && bytes.starts_with(b"RIFF") && &bytes[8..12] == b"WEBP" {
+        Some(ImageFormat::Webp)
+    } else if bytes.starts_with(b"BM")
+        && bytes.len() > 26
+        // DIB header size, so text starting with "BM" isn't mistaken for a bitmap
+        && matches!(u32::from_le_bytes([bytes[14], bytes[15], bytes[16], bytes[17]]), 12 | 40 | 52 | 56 | 108 | 124)
+    {
+        Some(ImageFormat::Bmp)
+    } else {
+        None
+    }
+}
+
+/// Tells DOCX and ODT apart by the entries inside the zip container.
+fn detect_zip_format(bytes: &[u8]) -> Option<CvFormat> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).ok()?;
+
+    if archive.by_name("word/document.xml").is_ok() {
+        return Some(CvFormat::Docx);
+    }
+
+    // ODF stores its media type uncompressed in the first entry
+    let mut mimetype = String::new();
+    if let Ok(entry) = archive.by_name("mimetype") {
+        entry.take(128).read_to_string(&mut mimetype).ok()?;
+    }
+    if mimetype.trim() == "application/vnd.oasis.opendocument.text" {
+        return Some(CvFormat::Odt);
+    }
+
+    None
+}
+
+/// Decodes UTF-8 or BOM-marked UTF-16 text; returns `None` for binary data.
+fn decode_text(bytes: &[u8]) -> Option<String> {
+    let text = if let Some(rest) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+        String::from_utf8(rest.to_vec()).ok()?
+    } else if let Some(rest) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+        decode_utf16(rest, u16::from_le_bytes)?
+    } else if let Some(rest) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+        decode_utf16(rest, u16::from_be_bytes)?
+    } else {
+        String::from_utf8(bytes.to_vec()).ok()?
+    };
+
+    let control = text
+        .chars()
+        .filter(|c| c.is_control() && !matches!(c, '\n' | '\r' | '\t' | '\u{c}'))
+        .count();
+    if text.contains('\0') || control * 20 > text.chars().count() {
+        return None;
+    }
+
+    Some(text.replace("\r\n", "\n").replace('\r', "\n"))
+}
+
+fn decode_utf16(bytes: &[u8], convert: fn([u8; 2]) -> u16) -> Option<String> {
+    let units: Vec<u16> = bytes.chunks_exact(2).map(|c| convert([c[0], c[1]])).collect();
+    String::from_utf16(&units).ok()
+}
+
+fn looks_like_markdown(text: &str) -> bool {
+    let heading = text.lines().any(|l| {
+        let hashes = l.chars().take_while(|c| *c == '#').count();
+        (1..=6).contains(&hashes) && l[hashes..].starts_with(' ')
+    });
+    heading || text.contains("**") || text.contains("](")
+}
+
+/// Detects the format and extracts the text of a CV file.
+///
+/// `ocr` is only used for images; without it image uploads fail with
+/// [`ExtractError::OcrUnavailable`].
+pub fn extract_text(bytes: &[u8], ocr: Option<&dyn OcrEngine>) -> Result<ExtractedText, ExtractError> {
+    let format = detect_format(bytes).ok_or(ExtractError::UnsupportedFormat)?;
+
+    let text = match format {
+        CvFormat::Pdf => extract_pdf(bytes)?,
+        CvFormat::Docx => extract_docx(bytes)?,
+        CvFormat::Odt => extract_odt(bytes)?,
+        CvFormat::PlainText => extract_plain_text(bytes)?,
+        CvFormat::Markdown => extract_markdown(bytes)?,
+        CvFormat::Image(_) => extract_image(bytes, ocr.ok_or(ExtractError::OcrUnavailable)?)?,
+    };
+
+    if text.trim().is_empty() {
+        return Err(ExtractError::Empty);
+    }
+
+    Ok(ExtractedText { format, text })
+}
+
+/// Extracts the text of a PDF.
+///
+/// # Example
+///
+/// ```
+/// use backend::cv::extract::extract_pdf;
+///
+/// let pdf = include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/cv/sample.pdf"));
+/// let text = extract_pdf(pdf).unwrap();
+/// assert!(text.contains("Ayesha Rahman"));
+/// assert!(text.contains("Rust, PostgreSQL, Docker"));
+/// ```
+pub fn extract_pdf(bytes: &[u8]) -> Result<String, ExtractError> {
+    pdf_extract::extract_text_from_mem(bytes).map_err(|e| ExtractError::Corrupt(e.to_string()))
+}
+
+/// Extracts the text of a DOCX document, including headers and footers.
+///
+/// Paragraphs become lines, `<w:tab/>` a tab and `<w:br/>` a line break.
+///
+/// # Example
+///
+/// ```
+/// use backend::cv::extract::extract_docx;
+///
+/// let docx = include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/cv/sample.docx"));
+/// let text = extract_docx(docx).unwrap();
+/// assert!(text.starts_with("Ayesha Rahman\n"));
+/// assert!(text.contains("ayesha@example.com\t01712345678\n"));
+/// assert!(text.contains("Rust, PostgreSQL & Docker\n"));
+/// assert!(text.contains("BRAC University\n2017 - 2021"));
+/// ```
+pub fn extract_docx(bytes: &[u8]) -> Result<String, ExtractError> {
+    let mut archive =
+        zip::ZipArchive::new(Cursor::new(bytes)).map_err(|e| ExtractError::Corrupt(e.to_string()))?;
+
+    let mut parts: Vec<String> = archive
+        .file_names()
+        .filter(|n| {
+            (n.starts_with("word/header") || n.starts_with("word/footer")) && n.ends_with(".xml")
+        })
+        .map(str::to_string)
+        .collect();
+    parts.sort();
+    // Headers usually hold the contact block, so they go first
+    let footers = parts.iter().position(|n| n.starts_with("word/footer")).unwrap_or(parts.len());
+    parts.insert(footers, "word/document.xml".to_string());
+
+    let mut text = String::new();
+    for part in parts {
+        let xml = read_zip_entry(&mut archive, &part)?;
+        text.push_str(&xml_to_text(&xml, |name| match name {
+            b"w:t" => XmlTag::Text,
+            b"w:tab" => XmlTag::Tab,
+            b"w:br" | b"w:cr" => XmlTag::LineBreak,
+            b"w:p" => XmlTag::Paragraph,
+            b"w:pPr" | b"w:rPr" => XmlTag::Properties,
+            _ => XmlTag::Other,
+        })?);
+    }
+
+    Ok(text)
+}
+
+/// Extracts the text of an ODT document.
+///
+/// Handles `<text:s text:c="n"/>` runs of spaces, tabs and line breaks.
+///
+/// # Example
+///
+/// ```
+/// use backend::cv::extract::extract_odt;
+///
+/// let odt = include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/cv/sample.odt"));
+/// let text = extract_odt(odt).unwrap();
+/// assert!(text.starts_with("Ayesha Rahman\n"));
+/// assert!(text.contains("ayesha@example.com\t01712345678\n"));
+/// assert!(text.contains("Rust, PostgreSQL  & Docker\n"));
+/// assert!(text.contains("BRAC University\n2017 - 2021"));
+/// ```
+pub fn extract_odt(bytes: &[u8]) -> Result<String, ExtractError> {
+    let mut archive =
+        zip::ZipArchive::new(Cursor::new(bytes)).map_err(|e| ExtractError::Corrupt(e.to_string()))?;
+    let xml = read_zip_entry(&mut archive, "content.xml")?;
+
+    xml_to_text(&xml, |name| match name {
+        b"text:p" | b"text:h" => XmlTag::TextParagraph,
+        b"text:tab" => XmlTag::Tab,
+        b"text:line-break" => XmlTag::LineBreak,
+        b"text:s" => XmlTag::Spaces,
+        _ => XmlTag::Other,
+    })
+}
+
+/// Decodes a plain text CV (UTF-8, or UTF-16 with BOM).
+///
+/// # Example
+///
+/// ```
+/// use backend::cv::extract::extract_plain_text;
+///
+/// let txt = include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/cv/sample.txt"));
+/// assert!(extract_plain_text(txt).unwrap().contains("Rust, PostgreSQL, Docker"));
+///
+/// // UTF-16 LE with BOM, as saved by Windows Notepad
+/// let utf16: Vec<u8> = [0xFF, 0xFE].into_iter()
+///     .chain("Skills\r\nRust".encode_utf16().flat_map(u16::to_le_bytes))
+///     .collect();
+/// assert_eq!(extract_plain_text(&utf16).unwrap(), "Skills\nRust");
+/// ```
+pub fn extract_plain_text(bytes: &[u8]) -> Result<String, ExtractError> {
+    decode_text(bytes).ok_or_else(|| ExtractError::Corrupt("not valid text".to_string()))
+}
+
+/// Converts a Markdown CV to plain text.
+///
+/// Heading markers, emphasis and code markers are removed; links keep both
+/// the label and the URL. List bullets are kept for the section parser.
+///
+/// # Example
+///
+/// ```
+/// use backend::cv::extract::extract_markdown;
+///
+/// let md = include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/cv/sample.md"));
+/// let text = extract_markdown(md).unwrap();
+/// assert!(text.starts_with("Ayesha Rahman\n"));
+/// assert!(text.contains("Email: ayesha@example.com | GitHub (https://github.com/ayesha)"));
+/// assert!(text.contains("\nSkills\n"));
+/// assert!(text.contains("- Rust, PostgreSQL, Docker"));
+/// ```
+pub fn extract_markdown(bytes: &[u8]) -> Result<String, ExtractError> {
+    let text = extract_plain_text(bytes)?;
+    let mut out = String::with_capacity(text.len());
+
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        // Horizontal rules
+        if trimmed.len() >= 3 && trimmed.chars().all(|c| matches!(c, '-' | '*' | '_' | ' ')) {
+            out.push('\n');
+            continue;
+        }
+
+        let line = trimmed.trim_start_matches('#').trim_start_matches('>').trim_start();
+        out.push_str(&strip_inline_markdown(line));
+        out.push('\n');
+    }
+
+    Ok(out)
+}
+
+fn strip_inline_markdown(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let chars: Vec<char> = line.chars().collect();
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            // ![alt](url) and [label](url)
+            '!' if chars.get(i + 1) == Some(&'[') => i += 1,
+            '[' => {
+                let close = chars[i..].iter().position(|c| *c == ']').map(|p| p + i);
+                match close {
+                    Some(close) if chars.get(close + 1) == Some(&'(') => {
+                        let end = chars[close..].iter().position(|c| *c == ')').map(|p| p + close);
+                        let label: String = chars[i + 1..close].iter().collect();
+                        let url: String = end
+                            .map(|end| chars[close + 2..end].iter().collect())
+                            .unwrap_or_default();
+                        out.push_str(&strip_inline_markdown(&label));
+                        if !url.is_empty() && url != label {
+                            out.push_str(&format!(" ({url})"));
+                        }
+                        i = end.map(|e| e + 1).unwrap_or(chars.len());
+                    }
+                    _ => {
+                        out.push('[');
+                        i += 1;
+                    }
+                }
+            }
+            '`' => i += 1,
+            // Emphasis markers, but keep "* " list bullets and lone underscores in words
+            '*' | '_' => {
+                let is_bullet = i == 0 && chars.get(1) == Some(&' ');
+                let in_word = chars[i] == '_'
+                    && i > 0
+                    && chars[i - 1].is_alphanumeric()
+                    && chars.get(i + 1).is_some_and(|c| c.is_alphanumeric());
+                if is_bullet || in_word {
+                    out.push(chars[i]);
+                }
+                i += 1;
+            }
+            c => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    out
+}
+
+/// Reads an image CV through the OCR engine.
+///
+/// # Example
+///
+/// ```
+/// use backend::cv::extract::{ExtractError, OcrEngine, extract_image};
+///
+/// struct FixedOcr;
+///
+/// impl OcrEngine for FixedOcr {
+///     fn name(&self) -> &str { "fixed" }
+///     fn recognize(&self, image: &[u8]) -> Result<String, ExtractError> {
+///         assert!(image.starts_with(b"\x89PNG"));
+///         Ok("Ayesha Rahman\r\nSkills: Rust\r\n".to_string())
+///     }
+/// }
+///
+/// let png = include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/cv/sample.png"));
+/// assert_eq!(extract_image(png, &FixedOcr).unwrap(), "Ayesha Rahman\nSkills: Rust\n");
+/// ```
+pub fn extract_image(bytes: &[u8], ocr: &dyn OcrEngine) -> Result<String, ExtractError> {
+    if detect_image_format(bytes).is_none() {
+        return Err(ExtractError::UnsupportedFormat);
+    }
+    let text = ocr.recognize(bytes)?;
+    Ok(text.replace("\r\n", "\n").replace('\u{c}', "\n"))
+}
+
+fn read_zip_entry(
+    archive: &mut zip::ZipArchive<Cursor<&[u8]>>,
+    name: &str,
+) -> Result<Vec<u8>, ExtractError> {
+    let entry = archive
+        .by_name(name)
+        .map_err(|_| ExtractError::Corrupt(format!("missing {}", name)))?;
+    let mut buf = Vec::new();
+    entry
+        .take(MAX_XML_PART_BYTES)
+        .read_to_end(&mut buf)
+        .map_err(|e| ExtractError::Corrupt(e.to_string()))?;
+    Ok(buf)
+}
+
+/// How an XML element contributes to the extracted text.
+enum XmlTag {
+    /// Element whose character data is kept (`w:t`)
+    Text,
+    /// Block element, ends with a newline
+    Paragraph,
+    /// Block element whose character data is kept (`text:p`)
+    TextParagraph,
+    Tab,
+    LineBreak,
+    /// ODF `text:s`, one or `text:c` spaces
+    Spaces,
+    /// Formatting properties; everything inside is ignored (`w:pPr` tab stops)
+    Properties,
+    /// Any other element
+    Other,
+}
+
+/// Walks an XML part and collects its text according to `classify`.
+fn xml_to_text(xml: &[u8], classify: fn(&[u8]) -> XmlTag) -> Result<String, ExtractError> {
+    let mut reader = Reader::from_reader(xml);
+    let mut buf = Vec::new();
+    let mut out = String::new();
+    // Nesting depth of elements whose character data is kept / ignored
+    let mut text_depth = 0usize;
+    let mut skip_depth = 0usize;
+
+    loop {
+        let event = reader
+            .read_event_into(&mut buf)
+            .map_err(|e| ExtractError::Corrupt(e.to_string()))?;
+
+        match event {
+            Event::Start(e) => match classify(e.name().as_ref()) {
+                XmlTag::Properties => skip_depth += 1,
+                XmlTag::Text | XmlTag::TextParagraph => text_depth += 1,
+                _ => {}
+            },
+            Event::End(e) => match classify(e.name().as_ref()) {
+                XmlTag::Properties => skip_depth = skip_depth.saturating_sub(1),
+                XmlTag::Text => text_depth = text_depth.saturating_sub(1),
+                XmlTag::TextParagraph => {
+                    text_depth = text_depth.saturating_sub(1);
+                    out.push('\n');
+                }
+                XmlTag::Paragraph => out.push('\n'),
+                _ => {}
+            },
+            Event::Empty(_) if skip_depth > 0 => {}
+            Event::Empty(e) => match classify(e.name().as_ref()) {
+                XmlTag::Tab => out.push('\t'),
+                XmlTag::LineBreak | XmlTag::Paragraph | XmlTag::TextParagraph => out.push('\n'),
+                XmlTag::Spaces => {
+                    let count = e
+                        .try_get_attribute("text:c")
+                        .ok()
+                        .flatten()
+                        .and_then(|a| std::str::from_utf8(&a.value).ok()?.parse::<usize>().ok())
+                        .unwrap_or(1);
+                    out.push_str(&" ".repeat(count.min(100)));
+                }
+                _ => {}
+            },
+            Event::Text(e) if text_depth > 0 && skip_depth == 0 => {
+                let text = e.unescape().map_err(|err| ExtractError::Corrupt(err.to_string()))?;
+                out.push_str(&text);
+            }
+            Event::CData(e) if text_depth > 0 && skip_depth == 0 => {
+                out.push_str(&String::from_utf8_lossy(&e))
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(out)
+}
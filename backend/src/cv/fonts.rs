@@ -0,0 +1,134 @@
+//! Fonts used for generated CVs.
+//!
+//! DejaVu Sans and DejaVu Serif are compiled into the binary and cover Latin,
+//! Greek and Cyrillic text. Bengali text needs Noto Sans Bengali, which is
+//! loaded at startup from the first directory that contains it:
+//!
+//! - `CV_FONT_DIR`
+//! - `/usr/share/fonts/truetype/noto` (Debian/Ubuntu `fonts-noto-core`)
+//! - `/usr/share/fonts/noto`, `/usr/share/fonts/google-noto` (other distributions)
+//!
+//! Without it, Bengali characters render as missing-glyph boxes.
+
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use tracing::{info, warn};
+
+const SANS_REGULAR: &[u8] = include_bytes!("../../assets/fonts/DejaVuSans.ttf");
+const SANS_BOLD: &[u8] = include_bytes!("../../assets/fonts/DejaVuSans-Bold.ttf");
+const SERIF_REGULAR: &[u8] = include_bytes!("../../assets/fonts/DejaVuSerif.ttf");
+const SERIF_BOLD: &[u8] = include_bytes!("../../assets/fonts/DejaVuSerif-Bold.ttf");
+
+const BENGALI_REGULAR_FILE: &str = "NotoSansBengali-Regular.ttf";
+const BENGALI_BOLD_FILE: &str = "NotoSansBengali-Bold.ttf";
+
+const SYSTEM_FONT_DIRS: [&str; 3] = [
+    "/usr/share/fonts/truetype/noto",
+    "/usr/share/fonts/noto",
+    "/usr/share/fonts/google-noto",
+];
+
+static GLOBAL: LazyLock<FontLibrary> = LazyLock::new(FontLibrary::from_env);
+
+/// Typeface family of a template.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Family {
+    /// DejaVu Sans
+    Sans,
+    /// DejaVu Serif
+    Serif,
+}
+
+/// Font weight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Weight {
+    /// Body text
+    Regular,
+    /// Names, headings and entry titles
+    Bold,
+}
+
+/// Font files available to the PDF renderer.
+#[derive(Debug, Default)]
+pub struct FontLibrary {
+    bengali_regular: Option<&'static [u8]>,
+    bengali_bold: Option<&'static [u8]>,
+}
+
+impl FontLibrary {
+    /// Fonts loaded once per process from `CV_FONT_DIR` and the system font directories.
+    pub fn global() -> &'static FontLibrary {
+        &GLOBAL
+    }
+
+    fn from_env() -> Self {
+        let mut dirs: Vec<PathBuf> = std::env::var("CV_FONT_DIR")
+            .ok()
+            .filter(|d| !d.is_empty())
+            .map(PathBuf::from)
+            .into_iter()
+            .collect();
+        dirs.extend(SYSTEM_FONT_DIRS.iter().map(PathBuf::from));
+
+        let library = Self::load(&dirs);
+        if library.bengali_regular.is_none() {
+            warn!(
+                "{} not found in CV_FONT_DIR or system font directories; Bengali text in generated CVs will not render",
+                BENGALI_REGULAR_FILE
+            );
+        }
+        library
+    }
+
+    /// Loads the Bengali fonts from the first of `dirs` that contains them.
+    pub fn load(dirs: &[PathBuf]) -> Self {
+        let Some(dir) = dirs.iter().find(|d| d.join(BENGALI_REGULAR_FILE).is_file()) else {
+            return Self::default();
+        };
+        info!("Loading Bengali CV fonts from {}", dir.display());
+
+        Self {
+            bengali_regular: read_font(&dir.join(BENGALI_REGULAR_FILE)),
+            bengali_bold: read_font(&dir.join(BENGALI_BOLD_FILE)),
+        }
+    }
+
+    /// Latin font for a family and weight.
+    pub fn latin(&self, family: Family, weight: Weight) -> &'static [u8] {
+        match (family, weight) {
+            (Family::Sans, Weight::Regular) => SANS_REGULAR,
+            (Family::Sans, Weight::Bold) => SANS_BOLD,
+            (Family::Serif, Weight::Regular) => SERIF_REGULAR,
+            (Family::Serif, Weight::Bold) => SERIF_BOLD,
+        }
+    }
+
+    /// Bengali font for a weight; bold falls back to regular when missing.
+    pub fn bengali(&self, weight: Weight) -> Option<&'static [u8]> {
+        match weight {
+            Weight::Regular => self.bengali_regular,
+            Weight::Bold => self.bengali_bold.or(self.bengali_regular),
+        }
+    }
+}
+
+/// Reads a font file, keeping it for the lifetime of the process.
+fn read_font(path: &Path) -> Option<&'static [u8]> {
+    match std::fs::read(path) {
+        Ok(bytes) if ttf_parser_ok(&bytes) => Some(Vec::leak(bytes)),
+        Ok(_) => {
+            warn!("Ignoring invalid font file {}", path.display());
+            None
+        }
+        Err(e) => {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                warn!("Failed to read font {}: {}", path.display(), e);
+            }
+            None
+        }
+    }
+}
+
+fn ttf_parser_ok(bytes: &[u8]) -> bool {
+    rustybuzz::ttf_parser::Face::parse(bytes, 0).is_ok()
+}
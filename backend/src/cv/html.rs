@@ -0,0 +1,290 @@
+//! Standalone HTML and Markdown renderings of a CV.
+//!
+//! The HTML page has its styles inlined so it can be saved, opened offline
+//! or printed; the Markdown output is meant for pasting into LinkedIn and
+//! job portals.
+
+use super::document::{CvDocument, CvTemplate, date_range, description_points};
+use super::parser::CvEntry;
+use std::fmt::Write;
+
+const BASE_CSS: &str = r#"
+*{box-sizing:border-box}
+body{margin:0;padding:24px;background:#f3f4f6;line-height:1.45}
+main{max-width:210mm;margin:0 auto;padding:18mm 20mm;background:#fff}
+h1{margin:0 0 2px}
+h2{margin:18px 0 8px}
+h3{margin:0;font-size:1em}
+p{margin:4px 0}
+ul{margin:4px 0 0;padding-left:20px}
+.entry{margin:10px 0}
+.entry-head{display:flex;justify-content:space-between;gap:12px}
+.dates,.meta{color:var(--muted);white-space:nowrap}
+.meta{white-space:normal}
+.headline{margin:2px 0;color:var(--muted);font-size:1.1em}
+.contact{color:var(--muted)}
+@page{size:A4;margin:15mm}
+@media print{body{padding:0;background:none}main{padding:0;max-width:none}}
+"#;
+
+/// Font stacks end with Bengali-capable fonts so Bangla text renders everywhere.
+fn template_css(template: CvTemplate) -> &'static str {
+    match template {
+        CvTemplate::Classic => {
+            r#"
+:root{--muted:#555}
+body{font-family:Georgia,"DejaVu Serif","Times New Roman","Noto Serif Bengali","Noto Sans Bengali",serif;color:#1a1a1a}
+header{text-align:center;border-bottom:2px solid #1a1a1a;padding-bottom:10px}
+h2{text-transform:uppercase;font-size:1.05em;letter-spacing:.05em;border-bottom:1px solid #1a1a1a;padding-bottom:2px}
+"#
+        }
+        CvTemplate::Modern => {
+            r#"
+:root{--muted:#666e7a;--accent:#175c9e}
+body{font-family:"Segoe UI",Roboto,"DejaVu Sans",Arial,"Noto Sans Bengali",sans-serif;color:#21262e}
+main{padding-top:0}
+header{margin:0 -20mm;padding:14mm 20mm 8mm;background:var(--accent);color:#fff}
+header .headline{color:#d9e8f7}
+header .contact{color:#fff}
+h2{color:var(--accent);font-size:1.15em;border-bottom:1.5px solid var(--accent);padding-bottom:2px}
+"#
+        }
+        CvTemplate::Ats => {
+            r#"
+:root{--muted:#000}
+body{font-family:Arial,"DejaVu Sans","Noto Sans Bengali",sans-serif;color:#000;background:#fff}
+main{padding:0}
+h1{font-size:1.5em}
+h2{text-transform:uppercase;font-size:1.05em}
+"#
+        }
+    }
+}
+
+/// Escapes text for HTML element content and attribute values.
+pub(crate) fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Section titles in display order, shared by the HTML and Markdown output.
+fn entry_sections(document: &CvDocument, template: CvTemplate) -> [(&'static str, &Vec<CvEntry>); 4] {
+    let cv = &document.cv;
+    [
+        (if template == CvTemplate::Ats { "Work Experience" } else { "Experience" }, &cv.experience),
+        ("Projects", &cv.projects),
+        ("Education", &cv.education),
+        ("Certifications", &cv.certifications),
+    ]
+}
+
+/// Organization and location of an entry, comma separated.
+fn place(entry: &CvEntry) -> String {
+    [entry.organization.as_deref(), entry.location.as_deref()]
+        .into_iter()
+        .flatten()
+        .filter(|s| !s.trim().is_empty())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Renders a CV as a standalone HTML page.
+///
+/// # Example
+///
+/// ```
+/// use backend::cv::document::{CvDocument, CvTemplate};
+/// use backend::cv::{ContactInfo, ParsedCv};
+///
+/// let doc = CvDocument {
+///     cv: ParsedCv {
+///         contact: ContactInfo { full_name: Some("Tom & Jerry".to_string()), ..ContactInfo::default() },
+///         ..ParsedCv::default()
+///     },
+///     ..CvDocument::default()
+/// };
+/// let html = backend::cv::html::render_html(&doc, CvTemplate::Modern);
+/// assert!(html.contains("<h1>Tom &amp; Jerry</h1>"));
+/// ```
+pub fn render_html(document: &CvDocument, template: CvTemplate) -> String {
+    let cv = &document.cv;
+    let name = escape(document.name());
+    let mut html = String::new();
+
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{} - CV</title>\n<style>{}{}</style>\n</head>\n<body>\n<main>\n<header>\n<h1>{}</h1>\n",
+        name,
+        BASE_CSS,
+        template_css(template),
+        name
+    );
+    if let Some(headline) = &document.headline {
+        let _ = writeln!(html, "<p class=\"headline\">{}</p>", escape(headline));
+    }
+    let contact = document.contact_items();
+    if !contact.is_empty() {
+        let items: Vec<String> = contact
+            .iter()
+            .map(|item| {
+                let escaped = escape(item);
+                if item.contains('@') && !item.contains('/') {
+                    format!("<a href=\"mailto:{0}\">{0}</a>", escaped)
+                } else if item.starts_with("http://") || item.starts_with("https://") {
+                    format!("<a href=\"{0}\">{0}</a>", escaped)
+                } else {
+                    escaped
+                }
+            })
+            .collect();
+        let _ = writeln!(html, "<p class=\"contact\">{}</p>", items.join(" | "));
+    }
+    html.push_str("</header>\n");
+
+    if let Some(summary) = cv.summary.as_deref().filter(|s| !s.trim().is_empty()) {
+        let title = if template == CvTemplate::Ats { "Summary" } else { "Profile" };
+        let _ = writeln!(html, "<section>\n<h2>{}</h2>\n<p>{}</p>\n</section>", title, escape(summary));
+    }
+    if !cv.skills.is_empty() {
+        let skills: Vec<String> = cv.skills.iter().map(|s| escape(s)).collect();
+        let _ = writeln!(html, "<section>\n<h2>Skills</h2>\n<p>{}</p>\n</section>", skills.join(", "));
+    }
+
+    for (title, entries) in entry_sections(document, template) {
+        if entries.is_empty() {
+            continue;
+        }
+        let _ = writeln!(html, "<section>\n<h2>{}</h2>", title);
+        for entry in entries {
+            html.push_str("<div class=\"entry\">\n<div class=\"entry-head\">");
+            let _ = write!(html, "<h3>{}</h3>", escape(&entry.title));
+            if let Some(dates) = date_range(entry) {
+                let _ = write!(html, "<span class=\"dates\">{}</span>", escape(&dates));
+            }
+            html.push_str("</div>\n");
+            let place = place(entry);
+            if !place.is_empty() {
+                let _ = writeln!(html, "<p class=\"meta\">{}</p>", escape(&place));
+            }
+            match description_points(entry).as_slice() {
+                [] => {}
+                [single] => {
+                    let _ = writeln!(html, "<p>{}</p>", escape(single));
+                }
+                points => {
+                    html.push_str("<ul>\n");
+                    for point in points {
+                        let _ = writeln!(html, "<li>{}</li>", escape(point));
+                    }
+                    html.push_str("</ul>\n");
+                }
+            }
+            html.push_str("</div>\n");
+        }
+        html.push_str("</section>\n");
+    }
+
+    html.push_str("</main>\n</body>\n</html>\n");
+    html
+}
+
+/// Escapes characters Markdown would interpret as formatting.
+fn escape_markdown(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Renders a CV as Markdown.
+///
+/// # Example
+///
+/// ```
+/// use backend::cv::document::CvDocument;
+/// use backend::cv::{ContactInfo, ParsedCv};
+///
+/// let doc = CvDocument {
+///     cv: ParsedCv {
+///         contact: ContactInfo { full_name: Some("Ayesha Rahman".to_string()), ..ContactInfo::default() },
+///         skills: vec!["C#".to_string(), "Node_js".to_string()],
+///         ..ParsedCv::default()
+///     },
+///     ..CvDocument::default()
+/// };
+/// let md = backend::cv::html::render_markdown(&doc);
+/// assert!(md.starts_with("# Ayesha Rahman\n"));
+/// assert!(md.contains("C\\#, Node\\_js"));
+/// ```
+pub fn render_markdown(document: &CvDocument) -> String {
+    let cv = &document.cv;
+    let mut md = format!("# {}\n\n", escape_markdown(document.name()));
+
+    if let Some(headline) = &document.headline {
+        let _ = writeln!(md, "**{}**\n", escape_markdown(headline));
+    }
+    let contact = document.contact_items();
+    if !contact.is_empty() {
+        let items: Vec<String> = contact.iter().map(|c| escape_markdown(c)).collect();
+        let _ = writeln!(md, "{}\n", items.join(" · "));
+    }
+    if let Some(summary) = cv.summary.as_deref().filter(|s| !s.trim().is_empty()) {
+        let _ = writeln!(md, "## Summary\n\n{}\n", escape_markdown(summary.trim()));
+    }
+    if !cv.skills.is_empty() {
+        let skills: Vec<String> = cv.skills.iter().map(|s| escape_markdown(s)).collect();
+        let _ = writeln!(md, "## Skills\n\n{}\n", skills.join(", "));
+    }
+
+    for (title, entries) in entry_sections(document, CvTemplate::Classic) {
+        if entries.is_empty() {
+            continue;
+        }
+        let _ = writeln!(md, "## {}\n", title);
+        for entry in entries {
+            let _ = writeln!(md, "### {}\n", escape_markdown(&entry.title));
+            let details: Vec<String> = [Some(place(entry)), date_range(entry)]
+                .into_iter()
+                .flatten()
+                .filter(|s| !s.is_empty())
+                .map(|s| escape_markdown(&s))
+                .collect();
+            if !details.is_empty() {
+                let _ = writeln!(md, "*{}*\n", details.join(" | "));
+            }
+            let points = description_points(entry);
+            match points.as_slice() {
+                [] => {}
+                [single] => {
+                    let _ = writeln!(md, "{}\n", escape_markdown(single));
+                }
+                _ => {
+                    for point in &points {
+                        let _ = writeln!(md, "- {}", escape_markdown(point));
+                    }
+                    md.push('\n');
+                }
+            }
+        }
+    }
+
+    md.truncate(md.trim_end().len());
+    md.push('\n');
+    md
+}
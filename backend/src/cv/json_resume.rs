@@ -0,0 +1,554 @@
+//! [JSON Resume](https://jsonresume.org/schema) import and export.
+//!
+//! Only the sections CareerBridge models are mapped: basics, work,
+//! education, projects, certificates and skills. Other sections are ignored
+//! on import. Dates are written as `YYYY-MM`; on import `YYYY-MM-DD`,
+//! `YYYY-MM` and `YYYY` are accepted, and a work or project entry without
+//! an end date is treated as ongoing.
+
+use super::document::{CvDocument, description_points};
+use super::parser::{ContactInfo, CvEntry, ParsedCv};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Schema URL written to exported files.
+pub const SCHEMA_URL: &str =
+    "https://raw.githubusercontent.com/jsonresume/resume-schema/v1.0.0/schema.json";
+
+/// A JSON Resume document.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JsonResume {
+    /// Schema reference
+    #[serde(rename = "$schema", default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+    /// Name, contact details and summary
+    #[serde(default)]
+    pub basics: Basics,
+    /// Work experience
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub work: Vec<Work>,
+    /// Education
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub education: Vec<Education>,
+    /// Projects
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub projects: Vec<Project>,
+    /// Certificates
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub certificates: Vec<Certificate>,
+    /// Skills, optionally grouped with keywords
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skills: Vec<Skill>,
+}
+
+/// The `basics` section.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Basics {
+    /// Full name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Headline, e.g. "Backend Developer"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Email address
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    /// Phone number
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phone: Option<String>,
+    /// Personal website
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Summary paragraph
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    /// Address
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<Location>,
+    /// Social and portfolio profiles
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub profiles: Vec<Profile>,
+}
+
+/// The `basics.location` object.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Location {
+    /// Street address
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    /// City
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    /// State or division
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+    /// ISO 3166-1 alpha-2 country code
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country_code: Option<String>,
+}
+
+/// An entry of `basics.profiles`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Profile {
+    /// Network name, e.g. "GitHub"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network: Option<String>,
+    /// Username on the network
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    /// Profile URL
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// An entry of `work`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Work {
+    /// Employer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Job title
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<String>,
+    /// Location
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    /// First month
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<String>,
+    /// Last month; absent while ongoing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<String>,
+    /// Description
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    /// Achievements
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub highlights: Vec<String>,
+}
+
+/// An entry of `education`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Education {
+    /// Institution
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub institution: Option<String>,
+    /// Field of study, e.g. "Computer Science"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub area: Option<String>,
+    /// Degree, e.g. "Bachelor"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub study_type: Option<String>,
+    /// First month
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<String>,
+    /// Graduation month
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<String>,
+    /// Grade, e.g. "3.8/4.0"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<String>,
+    /// Notable courses
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub courses: Vec<String>,
+}
+
+/// An entry of `projects`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Project {
+    /// Project name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Organization the project was done for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entity: Option<String>,
+    /// Description
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Achievements
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub highlights: Vec<String>,
+    /// First month
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<String>,
+    /// Last month; absent while ongoing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<String>,
+    /// Project URL
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// An entry of `certificates`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Certificate {
+    /// Certificate name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Issuing organization
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issuer: Option<String>,
+    /// Issue date
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+}
+
+/// An entry of `skills`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Skill {
+    /// Skill or group name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Proficiency, e.g. "Advanced"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<String>,
+    /// Skills within the group
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+}
+
+impl JsonResume {
+    /// Whether the document contains anything CareerBridge can import.
+    pub fn is_empty(&self) -> bool {
+        let b = &self.basics;
+        b.name.is_none()
+            && b.label.is_none()
+            && b.summary.is_none()
+            && self.work.is_empty()
+            && self.education.is_empty()
+            && self.projects.is_empty()
+            && self.certificates.is_empty()
+            && self.skills.is_empty()
+    }
+}
+
+/// Converts a CV document to JSON Resume.
+///
+/// # Example
+///
+/// ```
+/// use backend::cv::document::CvDocument;
+/// use backend::cv::json_resume::{from_json_resume, to_json_resume};
+/// use backend::cv::{CvEntry, ParsedCv};
+/// use chrono::NaiveDate;
+///
+/// let doc = CvDocument {
+///     headline: Some("Backend Developer".to_string()),
+///     cv: ParsedCv {
+///         experience: vec![CvEntry {
+///             title: "Junior Developer".to_string(),
+///             organization: Some("Acme".to_string()),
+///             start_date: NaiveDate::from_ymd_opt(2022, 3, 1),
+///             is_current: true,
+///             ..CvEntry::default()
+///         }],
+///         skills: vec!["Rust".to_string()],
+///         ..ParsedCv::default()
+///     },
+/// };
+///
+/// let resume = to_json_resume(&doc);
+/// assert_eq!(resume.work[0].start_date.as_deref(), Some("2022-03"));
+/// assert_eq!(resume.work[0].end_date, None);
+/// assert_eq!(from_json_resume(&resume), doc);
+/// ```
+pub fn to_json_resume(document: &CvDocument) -> JsonResume {
+    let cv = &document.cv;
+    let contact = &cv.contact;
+
+    let (profiles, url) = contact.links.iter().fold((Vec::new(), None), |(mut profiles, url), link| {
+        match network_for(link) {
+            Some(network) => {
+                profiles.push(Profile {
+                    network: Some(network.to_string()),
+                    username: None,
+                    url: Some(link.clone()),
+                });
+                (profiles, url)
+            }
+            None if url.is_none() => (profiles, Some(link.clone())),
+            None => {
+                profiles.push(Profile { url: Some(link.clone()), ..Profile::default() });
+                (profiles, url)
+            }
+        }
+    });
+
+    JsonResume {
+        schema: Some(SCHEMA_URL.to_string()),
+        basics: Basics {
+            name: contact.full_name.clone(),
+            label: document.headline.clone(),
+            email: contact.email.clone(),
+            phone: contact.phone.clone(),
+            url,
+            summary: cv.summary.clone(),
+            location: contact.location.as_ref().map(|address| Location {
+                address: Some(address.clone()),
+                ..Location::default()
+            }),
+            profiles,
+        },
+        work: cv
+            .experience
+            .iter()
+            .map(|e| {
+                let (summary, highlights) = split_description(e);
+                Work {
+                    name: e.organization.clone(),
+                    position: Some(e.title.clone()),
+                    location: e.location.clone(),
+                    start_date: e.start_date.map(format_date),
+                    end_date: end_date(e),
+                    summary,
+                    highlights,
+                }
+            })
+            .collect(),
+        education: cv
+            .education
+            .iter()
+            .map(|e| Education {
+                institution: e.organization.clone(),
+                study_type: Some(e.title.clone()),
+                start_date: e.start_date.map(format_date),
+                end_date: end_date(e),
+                ..Education::default()
+            })
+            .collect(),
+        projects: cv
+            .projects
+            .iter()
+            .map(|e| {
+                let (description, highlights) = split_description(e);
+                Project {
+                    name: Some(e.title.clone()),
+                    entity: e.organization.clone(),
+                    description,
+                    highlights,
+                    start_date: e.start_date.map(format_date),
+                    end_date: end_date(e),
+                    url: None,
+                }
+            })
+            .collect(),
+        certificates: cv
+            .certifications
+            .iter()
+            .map(|e| Certificate {
+                name: Some(e.title.clone()),
+                issuer: e.organization.clone(),
+                date: e.end_date.or(e.start_date).map(format_date),
+            })
+            .collect(),
+        skills: cv
+            .skills
+            .iter()
+            .map(|s| Skill { name: Some(s.clone()), ..Skill::default() })
+            .collect(),
+    }
+}
+
+/// Converts a JSON Resume document to a CV document.
+///
+/// Entries without a title are dropped; grouped skills are flattened into
+/// their keywords.
+pub fn from_json_resume(resume: &JsonResume) -> CvDocument {
+    let basics = &resume.basics;
+
+    let location = basics.location.as_ref().and_then(|l| {
+        let parts: Vec<&str> = [&l.address, &l.city, &l.region, &l.country_code]
+            .into_iter()
+            .flatten()
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+            .collect();
+        (!parts.is_empty()).then(|| parts.join(", "))
+    });
+    let links = basics
+        .url
+        .iter()
+        .chain(basics.profiles.iter().filter_map(|p| p.url.as_ref()))
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    let experience = resume
+        .work
+        .iter()
+        .filter_map(|w| {
+            let title = non_empty(&w.position).or_else(|| non_empty(&w.name))?;
+            let start_date = w.start_date.as_deref().and_then(parse_date);
+            let end_date = w.end_date.as_deref().and_then(parse_date);
+            Some(CvEntry {
+                organization: non_empty(&w.name).filter(|n| *n != title),
+                title,
+                location: non_empty(&w.location),
+                is_current: start_date.is_some() && end_date.is_none(),
+                start_date,
+                end_date,
+                description: join_description(&w.summary, &w.highlights),
+            })
+        })
+        .collect();
+
+    let education = resume
+        .education
+        .iter()
+        .filter_map(|e| {
+            let title = match (non_empty(&e.study_type), non_empty(&e.area)) {
+                (Some(degree), Some(area)) => format!("{} in {}", degree, area),
+                (Some(only), None) | (None, Some(only)) => only,
+                (None, None) => non_empty(&e.institution)?,
+            };
+            let mut details = Vec::new();
+            if let Some(score) = non_empty(&e.score) {
+                details.push(format!("Score: {}", score));
+            }
+            if !e.courses.is_empty() {
+                details.push(format!("Courses: {}", e.courses.join(", ")));
+            }
+            Some(CvEntry {
+                organization: non_empty(&e.institution).filter(|i| *i != title),
+                title,
+                start_date: e.start_date.as_deref().and_then(parse_date),
+                end_date: e.end_date.as_deref().and_then(parse_date),
+                description: (!details.is_empty()).then(|| details.join("\n")),
+                ..CvEntry::default()
+            })
+        })
+        .collect();
+
+    let projects = resume
+        .projects
+        .iter()
+        .filter_map(|p| {
+            let title = non_empty(&p.name)?;
+            let start_date = p.start_date.as_deref().and_then(parse_date);
+            let end_date = p.end_date.as_deref().and_then(parse_date);
+            let mut description = join_description(&p.description, &p.highlights);
+            if let Some(url) = non_empty(&p.url) {
+                description = Some(match description {
+                    Some(d) => format!("{}\n{}", d, url),
+                    None => url,
+                });
+            }
+            Some(CvEntry {
+                title,
+                organization: non_empty(&p.entity),
+                is_current: start_date.is_some() && end_date.is_none(),
+                start_date,
+                end_date,
+                description,
+                ..CvEntry::default()
+            })
+        })
+        .collect();
+
+    let certifications = resume
+        .certificates
+        .iter()
+        .filter_map(|c| {
+            Some(CvEntry {
+                title: non_empty(&c.name)?,
+                organization: non_empty(&c.issuer),
+                end_date: c.date.as_deref().and_then(parse_date),
+                ..CvEntry::default()
+            })
+        })
+        .collect();
+
+    let mut skills: Vec<String> = Vec::new();
+    for skill in &resume.skills {
+        // A group with keywords ("Backend": ["Rust", "SQL"]) contributes its keywords
+        let names: Vec<&String> = if skill.keywords.is_empty() {
+            skill.name.iter().collect()
+        } else {
+            skill.keywords.iter().collect()
+        };
+        for name in names {
+            let name = name.trim();
+            if !name.is_empty() && !skills.iter().any(|s| s.eq_ignore_ascii_case(name)) {
+                skills.push(name.to_string());
+            }
+        }
+    }
+
+    CvDocument {
+        headline: non_empty(&basics.label),
+        cv: ParsedCv {
+            contact: ContactInfo {
+                full_name: non_empty(&basics.name),
+                email: non_empty(&basics.email),
+                phone: non_empty(&basics.phone),
+                location,
+                links,
+            },
+            summary: non_empty(&basics.summary),
+            education,
+            experience,
+            projects,
+            skills,
+            certifications,
+        },
+    }
+}
+
+fn non_empty(value: &Option<String>) -> Option<String> {
+    value.as_deref().map(str::trim).filter(|s| !s.is_empty()).map(str::to_string)
+}
+
+fn format_date(date: NaiveDate) -> String {
+    date.format("%Y-%m").to_string()
+}
+
+fn end_date(entry: &CvEntry) -> Option<String> {
+    if entry.is_current { None } else { entry.end_date.map(format_date) }
+}
+
+/// Parses `YYYY-MM-DD`, `YYYY-MM` or `YYYY`.
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    let value = value.trim();
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(&format!("{}-01", value), "%Y-%m-%d"))
+        .or_else(|_| NaiveDate::parse_from_str(&format!("{}-01-01", value), "%Y-%m-%d"))
+        .ok()
+}
+
+/// Single-line descriptions become the summary, multi-line ones highlights.
+fn split_description(entry: &CvEntry) -> (Option<String>, Vec<String>) {
+    let mut points = description_points(entry);
+    if points.len() == 1 { (points.pop(), Vec::new()) } else { (None, points) }
+}
+
+fn join_description(summary: &Option<String>, highlights: &[String]) -> Option<String> {
+    let lines: Vec<String> = non_empty(summary)
+        .into_iter()
+        .chain(highlights.iter().map(|h| h.trim()).filter(|h| !h.is_empty()).map(|h| format!("- {}", h)))
+        .collect();
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+/// Recognises links to well-known profile sites.
+fn network_for(link: &str) -> Option<&'static str> {
+    let link = link.to_lowercase();
+    let without_scheme = link.split_once("://").map_or(link.as_str(), |(_, rest)| rest);
+    let host = without_scheme.split(['/', '?', '#']).next().unwrap_or_default();
+    [
+        ("linkedin.com", "LinkedIn"),
+        ("github.com", "GitHub"),
+        ("gitlab.com", "GitLab"),
+        ("behance.net", "Behance"),
+        ("dribbble.com", "Dribbble"),
+        ("stackoverflow.com", "Stack Overflow"),
+        ("x.com", "X"),
+        ("twitter.com", "Twitter"),
+    ]
+    .into_iter()
+    .find(|(domain, _)| host == *domain || host.ends_with(&format!(".{}", domain)))
+    .map(|(_, network)| network)
+}
@@ -0,0 +1,20 @@
+//! CV/resume processing.
+//!
+//! Turns the raw text extracted from an uploaded CV into structured data
+//! without relying on an external AI provider, and exports CV documents as
+//! PDF, DOCX, HTML, Markdown and JSON Resume.
+
+pub mod document;
+pub mod docx;
+pub mod export;
+pub mod extract;
+pub mod fonts;
+pub mod html;
+pub mod json_resume;
+pub mod parser;
+pub mod pdf;
+pub mod sandbox;
+pub mod storage;
+pub mod tailor;
+
+pub use parser::{ContactInfo, CvEntry, ParsedCv, parse_cv};
@@ -0,0 +1,707 @@
+//! Deterministic, rule-based CV parser.
+//!
+//! The parser splits extracted CV text into sections by recognising common
+//! headings ("Education", "Work Experience", "Technical Skills", ...) and then
+//! breaks each section into entries. Dates such as `Jan 2021 - Present`,
+//! `03/2019 – 06/2020` or `2018 - 2022` are turned into [`NaiveDate`] ranges.
+//!
+//! The output is a best-effort first draft: it is stored for the user to
+//! review and correct, and can optionally be enriched by an AI provider.
+
+use chrono::NaiveDate;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
+
+/// Contact details found in the CV header.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ContactInfo {
+    /// Candidate's name, usually the first line of the CV
+    pub full_name: Option<String>,
+    /// Email address
+    pub email: Option<String>,
+    /// Phone number
+    pub phone: Option<String>,
+    /// Address or city
+    pub location: Option<String>,
+    /// Profile and portfolio links (LinkedIn, GitHub, personal sites)
+    #[serde(default)]
+    pub links: Vec<String>,
+}
+
+/// A single education, work experience, project or certification entry.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CvEntry {
+    /// Degree, job title, project name or certificate name
+    pub title: String,
+    /// Institution, employer or certificate issuer
+    pub organization: Option<String>,
+    /// Location, when listed on the entry
+    pub location: Option<String>,
+    /// First month of the entry
+    pub start_date: Option<NaiveDate>,
+    /// Last month of the entry (graduation or issue date for single dates)
+    pub end_date: Option<NaiveDate>,
+    /// Whether the entry is ongoing ("Present", "Current")
+    #[serde(default)]
+    pub is_current: bool,
+    /// Bullet points and remaining lines, newline separated
+    pub description: Option<String>,
+}
+
+/// Structured representation of a CV.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ParsedCv {
+    /// Contact details
+    #[serde(default)]
+    pub contact: ContactInfo,
+    /// Summary / objective paragraph
+    pub summary: Option<String>,
+    /// Education entries, most recent first as written in the CV
+    #[serde(default)]
+    pub education: Vec<CvEntry>,
+    /// Work experience and internships
+    #[serde(default)]
+    pub experience: Vec<CvEntry>,
+    /// Projects
+    #[serde(default)]
+    pub projects: Vec<CvEntry>,
+    /// Skills, deduplicated case-insensitively
+    #[serde(default)]
+    pub skills: Vec<String>,
+    /// Certifications, licenses and trainings
+    #[serde(default)]
+    pub certifications: Vec<CvEntry>,
+}
+
+/// CV sections recognised by the parser.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Header,
+    Contact,
+    Summary,
+    Education,
+    Experience,
+    Projects,
+    Skills,
+    Certifications,
+    /// Known heading we don't extract (references, hobbies, ...)
+    Ignored,
+}
+
+/// Heading text (lowercase, without punctuation) for each section.
+const HEADINGS: &[(Section, &[&str])] = &[
+    (
+        Section::Contact,
+        &["contact", "contact information", "contact details", "personal information", "personal details"],
+    ),
+    (
+        Section::Summary,
+        &[
+            "summary", "professional summary", "career summary", "profile", "professional profile",
+            "about", "about me", "objective", "career objective", "career objectives",
+        ],
+    ),
+    (
+        Section::Education,
+        &[
+            "education", "academic background", "academic qualification", "academic qualifications",
+            "educational qualification", "educational qualifications", "educational background",
+            "academics", "education and training",
+        ],
+    ),
+    (
+        Section::Experience,
+        &[
+            "experience", "work experience", "professional experience", "employment history",
+            "employment", "work history", "career history", "internship", "internships",
+            "internship experience", "relevant experience",
+        ],
+    ),
+    (
+        Section::Projects,
+        &["projects", "academic projects", "personal projects", "key projects", "selected projects", "project experience"],
+    ),
+    (
+        Section::Skills,
+        &[
+            "skills", "technical skills", "key skills", "core skills", "core competencies",
+            "technologies", "tools", "skills and tools", "tools and technologies", "technical expertise",
+            "computer skills", "soft skills",
+        ],
+    ),
+    (
+        Section::Certifications,
+        &[
+            "certifications", "certification", "certificates", "licenses and certifications",
+            "licenses certifications", "courses", "trainings", "training", "trainings and certifications",
+            "courses and certifications",
+        ],
+    ),
+    (
+        Section::Ignored,
+        &[
+            "languages", "references", "reference", "interests", "hobbies", "hobbies and interests",
+            "awards", "achievements", "awards and achievements", "honors and awards", "publications",
+            "volunteer experience", "volunteering", "extracurricular activities", "co curricular activities",
+            "declaration",
+        ],
+    ),
+];
+
+const MONTH: &str = r"(?:jan(?:uary)?|feb(?:ruary)?|mar(?:ch)?|apr(?:il)?|may|june?|july?|aug(?:ust)?|sep(?:t(?:ember)?)?|oct(?:ober)?|nov(?:ember)?|dec(?:ember)?)";
+
+static DATE_RANGE: LazyLock<Regex> = LazyLock::new(|| {
+    let date = format!(r"(?:{MONTH}\.?,?\s*\d{{4}}|\d{{1,2}}/\d{{4}}|\d{{4}})");
+    Regex::new(&format!(
+        r"(?i)(?P<start>{date})\s*(?:-|–|—|to|until)\s*(?P<end>{date}|present|current|now|ongoing|till\s+date|date)"
+    ))
+    .expect("invalid date range pattern")
+});
+
+static SINGLE_DATE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(r"(?i)\b(?:{MONTH}\.?,?\s*\d{{4}}|\d{{1,2}}/\d{{4}}|(?:19|20)\d{{2}})\b"))
+        .expect("invalid date pattern")
+});
+
+static EMAIL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b[a-z0-9._%+-]+@[a-z0-9.-]+\.[a-z]{2,}\b").expect("invalid email pattern")
+});
+
+static PHONE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\+?\d[\d\s().-]{7,}\d").expect("invalid phone pattern")
+});
+
+static LINK: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(?:https?://\S+|www\.\S+|(?:linkedin\.com|github\.com|gitlab\.com|behance\.net)/\S+|[a-z0-9-]+\.(?:github\.io|vercel\.app|netlify\.app)\S*)")
+        .expect("invalid link pattern")
+});
+
+static LOCATION_LABEL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(?:address|location)\b").expect("invalid location label pattern")
+});
+
+static DEGREE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(?:b\.?\s?sc|m\.?\s?sc|b\.?\s?a|m\.?\s?a|bba|mba|b\.?\s?tech|m\.?\s?tech|b\.?\s?eng|ph\.?\s?d|bachelor|master|diploma|doctor|associate|hsc|ssc|a[\s-]?levels?|o[\s-]?levels?|higher secondary|secondary school)\b")
+        .expect("invalid degree pattern")
+});
+
+/// Parses extracted CV text into structured sections.
+///
+/// # Example
+///
+/// ```
+/// use backend::cv::parse_cv;
+///
+/// let cv = parse_cv(
+///     "Ayesha Rahman\nayesha@example.com | 01712345678\n\n\
+///      EXPERIENCE\nJunior Developer at Acme Ltd\nJan 2022 - Present\n• Built REST APIs in Rust\n\n\
+///      EDUCATION\nBSc in Computer Science\nBRAC University\n2017 - 2021\n\n\
+///      SKILLS\nRust, PostgreSQL, Docker",
+/// );
+///
+/// assert_eq!(cv.contact.full_name.as_deref(), Some("Ayesha Rahman"));
+/// assert_eq!(cv.contact.email.as_deref(), Some("ayesha@example.com"));
+/// assert_eq!(cv.experience[0].title, "Junior Developer");
+/// assert_eq!(cv.experience[0].organization.as_deref(), Some("Acme Ltd"));
+/// assert!(cv.experience[0].is_current);
+/// assert_eq!(cv.education[0].organization.as_deref(), Some("BRAC University"));
+/// assert_eq!(cv.skills, vec!["Rust", "PostgreSQL", "Docker"]);
+///
+/// // Labels after characters that change length when lowercased
+/// let cv = parse_cv("Ayesha Rahman\n\u{212A} Address: Mirpur, Dhaka");
+/// assert_eq!(cv.contact.location.as_deref(), Some("Mirpur, Dhaka"));
+/// ```
+pub fn parse_cv(text: &str) -> ParsedCv {
+    let mut cv = ParsedCv::default();
+    let mut buffers: Vec<(Section, Vec<String>)> = vec![(Section::Header, Vec::new())];
+
+    for raw_line in text.lines() {
+        let line = raw_line.trim().replace('\u{a0}', " ");
+
+        if let Some((heading, rest)) = detect_heading(&line) {
+            buffers.push((heading, Vec::new()));
+            // "Skills: Rust, Go" keeps its content on the heading line
+            if let Some(rest) = rest {
+                buffers.last_mut().expect("just pushed").1.push(rest);
+            }
+            continue;
+        }
+
+        buffers.last_mut().expect("never empty").1.push(line);
+    }
+
+    for (section, lines) in buffers {
+        match section {
+            Section::Header | Section::Contact => parse_contact(&lines, &mut cv.contact),
+            Section::Summary => {
+                let summary = join_paragraph(&lines);
+                if !summary.is_empty() {
+                    cv.summary = Some(match cv.summary.take() {
+                        Some(existing) => format!("{existing}\n{summary}"),
+                        None => summary,
+                    });
+                }
+            }
+            Section::Education => cv.education.extend(
+                split_entries(&lines, |l| DEGREE.is_match(l)).iter().map(|b| build_education(b)),
+            ),
+            Section::Experience => cv.experience.extend(
+                split_entries(&lines, |_| false).iter().map(|b| build_experience(b)),
+            ),
+            Section::Projects => cv.projects.extend(
+                split_entries(&lines, is_project_header).iter().map(|b| build_project(b)),
+            ),
+            Section::Skills => {
+                for skill in parse_skills(&lines) {
+                    if !cv.skills.iter().any(|s| s.eq_ignore_ascii_case(&skill)) {
+                        cv.skills.push(skill);
+                    }
+                }
+            }
+            Section::Certifications => cv.certifications.extend(parse_certifications(&lines)),
+            Section::Ignored => {}
+        }
+    }
+
+    cv.education.retain(|e| !e.title.is_empty());
+    cv.experience.retain(|e| !e.title.is_empty());
+    cv.projects.retain(|e| !e.title.is_empty());
+    cv
+}
+
+/// Recognises a section heading, returning the section and any trailing content.
+fn detect_heading(line: &str) -> Option<(Section, Option<String>)> {
+    if line.is_empty() || line.chars().count() > 60 {
+        return None;
+    }
+
+    let (head, rest) = match line.split_once(':') {
+        Some((head, rest)) if !rest.trim().is_empty() => (head, Some(rest.trim().to_string())),
+        _ => (line, None),
+    };
+
+    let normalized = head
+        .to_lowercase()
+        .replace('&', " and ")
+        .chars()
+        .map(|c| if c.is_alphabetic() { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    if normalized.is_empty() || normalized.split(' ').count() > 5 {
+        return None;
+    }
+
+    HEADINGS
+        .iter()
+        .find(|(_, names)| names.contains(&normalized.as_str()))
+        .map(|(section, _)| (*section, rest))
+        // "Languages: Rust, Go" inside a skills section is content, not a heading
+        .filter(|(section, rest)| !(*section == Section::Ignored && rest.is_some()))
+}
+
+fn is_bullet(line: &str) -> bool {
+    line.starts_with(['•', '-', '*', '▪', '●', '◦', '–', '➢', '►', '○'])
+}
+
+fn strip_bullet(line: &str) -> &str {
+    line.trim_start_matches(['•', '-', '*', '▪', '●', '◦', '–', '➢', '►', '○']).trim()
+}
+
+fn join_paragraph(lines: &[String]) -> String {
+    lines
+        .iter()
+        .map(|l| strip_bullet(l))
+        .filter(|l| !l.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn parse_contact(lines: &[String], contact: &mut ContactInfo) {
+    for line in lines.iter().filter(|l| !l.is_empty()) {
+        let mut rest = line.clone();
+
+        for link in LINK.find_iter(line) {
+            let link = link.as_str().trim_end_matches(['.', ',', ';', ')']).to_string();
+            rest = rest.replace(&link, " ");
+            if !contact.links.contains(&link) {
+                contact.links.push(link);
+            }
+        }
+
+        if let Some(email) = EMAIL.find(&rest) {
+            let email = email.as_str().to_string();
+            rest = rest.replace(&email, " ");
+            contact.email.get_or_insert(email);
+        }
+
+        if let Some(phone) = PHONE.find(&rest) {
+            let phone = phone.as_str().trim().to_string();
+            // Ignore year ranges like "2019 - 2021"
+            if phone.chars().filter(|c| c.is_ascii_digit()).count() >= 10 {
+                rest = rest.replace(&phone, " ");
+                contact.phone.get_or_insert(phone);
+            }
+        }
+
+        if let Some(label) = LOCATION_LABEL.find(&rest) {
+            let value = rest[label.start()..]
+                .split_once([':', '-'])
+                .map(|(_, v)| v.trim().to_string())
+                .unwrap_or_default();
+            if !value.is_empty() {
+                contact.location.get_or_insert(value);
+            }
+            continue;
+        }
+
+        let remaining = rest
+            .split(['|', '•', '·'])
+            .map(str::trim)
+            .filter(|s| !s.is_empty() && !is_contact_label(s))
+            .collect::<Vec<_>>();
+
+        if let [candidate] = remaining.as_slice() {
+            if contact.full_name.is_none() && looks_like_name(candidate) {
+                contact.full_name = Some(candidate.to_string());
+            } else if contact.location.is_none()
+                && contact.full_name.is_some()
+                && candidate.contains(',')
+                && candidate.split(',').all(|part| looks_like_name(part.trim()))
+            {
+                // "Dhaka, Bangladesh" under the name
+                contact.location = Some(candidate.to_string());
+            }
+        }
+    }
+}
+
+fn is_contact_label(s: &str) -> bool {
+    let s = s.trim_end_matches(':').to_lowercase();
+    matches!(s.as_str(), "email" | "e-mail" | "phone" | "mobile" | "cell" | "linkedin" | "github" | "portfolio" | "website")
+}
+
+fn looks_like_name(s: &str) -> bool {
+    let words = s.split_whitespace().count();
+    (1..=5).contains(&words)
+        && s.chars().all(|c| c.is_alphabetic() || c == ' ' || c == '.' || c == '-' || c == '\'')
+        && s.chars().next().is_some_and(char::is_uppercase)
+}
+
+/// "CareerBridge | Rust, Axum" or "Weather App - React" style project header.
+fn is_project_header(line: &str) -> bool {
+    !is_bullet(line) && line.chars().count() <= 80 && (line.contains(" | ") || line.contains(" - "))
+}
+
+/// Splits section lines into entry blocks.
+///
+/// A new entry begins at a non-bullet line once the current entry already has
+/// bullet points, when a second date range shows up, or when `starts_entry`
+/// matches a line and already matched an earlier line of the current entry
+/// (a second degree, a second project header). Blank lines close an entry
+/// that already has a date or description.
+fn split_entries(lines: &[String], starts_entry: fn(&str) -> bool) -> Vec<Vec<String>> {
+    let mut blocks: Vec<Vec<String>> = Vec::new();
+    let mut current: Vec<String> = Vec::new();
+    let mut has_bullets = false;
+    let mut has_date = false;
+    let mut has_start = false;
+
+    for line in lines {
+        if line.is_empty() {
+            if has_bullets || has_date {
+                blocks.push(std::mem::take(&mut current));
+                (has_bullets, has_date, has_start) = (false, false, false);
+            }
+            continue;
+        }
+
+        let bullet = is_bullet(line);
+        let dated = DATE_RANGE.is_match(line);
+        let start = starts_entry(line);
+        if !current.is_empty() && !bullet && (has_bullets || (dated && has_date) || (start && has_start)) {
+            blocks.push(std::mem::take(&mut current));
+            (has_bullets, has_date, has_start) = (false, false, false);
+        }
+
+        has_bullets |= bullet;
+        has_start |= start;
+        has_date |= dated || SINGLE_DATE.is_match(line);
+        current.push(line.clone());
+    }
+
+    if !current.is_empty() {
+        blocks.push(current);
+    }
+    blocks
+}
+
+/// Parsed pieces of an entry block shared by every entry kind.
+struct Block {
+    headers: Vec<String>,
+    details: Vec<String>,
+    start_date: Option<NaiveDate>,
+    end_date: Option<NaiveDate>,
+    is_current: bool,
+}
+
+fn analyze_block(lines: &[String]) -> Block {
+    let mut block = Block {
+        headers: Vec::new(),
+        details: Vec::new(),
+        start_date: None,
+        end_date: None,
+        is_current: false,
+    };
+
+    for line in lines {
+        if is_bullet(line) {
+            block.details.push(strip_bullet(line).to_string());
+            continue;
+        }
+
+        let mut text = line.clone();
+        if block.start_date.is_none()
+            && block.end_date.is_none()
+            && let Some(caps) = DATE_RANGE.captures(line)
+        {
+            block.start_date = parse_date(&caps["start"]);
+            let end = caps["end"].to_lowercase();
+            if ["present", "current", "now", "ongoing", "date"].iter().any(|w| end.contains(w)) {
+                block.is_current = true;
+            } else {
+                block.end_date = parse_date(&end);
+            }
+            text = text.replace(&caps[0], " ");
+        } else if block.start_date.is_none()
+            && block.end_date.is_none()
+            && let Some(m) = SINGLE_DATE.find(line)
+        {
+            block.end_date = parse_date(m.as_str());
+            text = text.replace(m.as_str(), " ");
+        }
+
+        let text = clean_fragment(&text);
+        if text.is_empty() {
+            continue;
+        }
+        // Only the first couple of plain lines are headers; the rest is description
+        if block.headers.len() < 2 && block.details.is_empty() && text.chars().count() <= 100 {
+            block.headers.push(text);
+        } else {
+            block.details.push(text);
+        }
+    }
+
+    block
+}
+
+/// Trims separators left behind after removing dates from a line.
+fn clean_fragment(s: &str) -> String {
+    s.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_matches(|c: char| c == '|' || c == ',' || c == '-' || c == '–' || c == '(' || c == ')' || c.is_whitespace())
+        .replace("()", "")
+        .trim()
+        .to_string()
+}
+
+fn description(details: Vec<String>) -> Option<String> {
+    let text = details.join("\n");
+    (!text.trim().is_empty()).then_some(text)
+}
+
+/// Splits "Title at Company", "Title | Company", "Title, Company" or "Title - Company".
+fn split_title_org(line: &str) -> (String, Option<String>) {
+    for sep in [" at ", " | ", " @ ", " — ", " – ", " - ", ", "] {
+        if let Some((title, org)) = line.split_once(sep) {
+            let (title, org) = (title.trim(), org.trim());
+            if !title.is_empty() && !org.is_empty() {
+                return (title.to_string(), Some(org.to_string()));
+            }
+        }
+    }
+    (line.to_string(), None)
+}
+
+fn build_experience(lines: &[String]) -> CvEntry {
+    let block = analyze_block(lines);
+    let mut headers = block.headers.into_iter();
+    let (title, mut organization) = headers
+        .next()
+        .map(|h| split_title_org(&h))
+        .unwrap_or_default();
+    let mut location = None;
+
+    if let Some(second) = headers.next() {
+        if organization.is_none() {
+            let (org, loc) = split_title_org(&second);
+            organization = Some(org);
+            location = loc;
+        } else {
+            location = Some(second);
+        }
+    }
+
+    CvEntry {
+        title,
+        organization,
+        location,
+        start_date: block.start_date,
+        end_date: block.end_date,
+        is_current: block.is_current,
+        description: description(block.details),
+    }
+}
+
+fn build_education(lines: &[String]) -> CvEntry {
+    let block = analyze_block(lines);
+    let mut headers = block.headers;
+    let mut details = block.details;
+
+    // Put the line naming the degree first, whatever order the CV uses
+    if let Some(pos) = headers.iter().position(|h| DEGREE.is_match(h)) {
+        let degree = headers.remove(pos);
+        headers.insert(0, degree);
+    }
+
+    let mut headers = headers.into_iter();
+    let first = headers.next().unwrap_or_default();
+    let (title, mut organization) = if DEGREE.is_match(&first) {
+        match first.split_once(", ").or_else(|| first.split_once(" | ")) {
+            Some((degree, school)) if !DEGREE.is_match(school) => {
+                (degree.trim().to_string(), Some(school.trim().to_string()))
+            }
+            _ => (first, None),
+        }
+    } else {
+        (first, None)
+    };
+
+    for header in headers {
+        if organization.is_none() {
+            organization = Some(header);
+        } else {
+            details.insert(0, header);
+        }
+    }
+
+    CvEntry {
+        title,
+        organization,
+        location: None,
+        start_date: block.start_date,
+        end_date: block.end_date,
+        is_current: block.is_current,
+        description: description(details),
+    }
+}
+
+fn build_project(lines: &[String]) -> CvEntry {
+    let block = analyze_block(lines);
+    let mut headers = block.headers.into_iter();
+    let first = headers.next().unwrap_or_default();
+    let (title, organization) = match first.split_once(" | ").or_else(|| first.split_once(" - ")) {
+        Some((name, rest)) => (name.trim().to_string(), Some(rest.trim().to_string())),
+        None => (first, None),
+    };
+
+    let mut details: Vec<String> = headers.collect();
+    details.extend(block.details);
+
+    CvEntry {
+        title,
+        // For projects this holds the stack or context line, e.g. "React, Node.js"
+        organization,
+        location: None,
+        start_date: block.start_date,
+        end_date: block.end_date,
+        is_current: block.is_current,
+        description: description(details),
+    }
+}
+
+fn parse_skills(lines: &[String]) -> Vec<String> {
+    let mut skills = Vec::new();
+
+    for line in lines {
+        let line = strip_bullet(line);
+        // "Languages: Rust, Go" -> "Rust, Go"
+        let line = match line.split_once(':') {
+            Some((label, rest)) if label.split_whitespace().count() <= 4 => rest,
+            _ => line,
+        };
+
+        for skill in line.split([',', ';', '|', '•', '·']) {
+            let skill = skill.trim().trim_end_matches('.').trim();
+            if !skill.is_empty()
+                && skill.chars().count() <= 40
+                && !skills.iter().any(|s: &String| s.eq_ignore_ascii_case(skill))
+            {
+                skills.push(skill.to_string());
+            }
+        }
+    }
+
+    skills
+}
+
+fn parse_certifications(lines: &[String]) -> Vec<CvEntry> {
+    lines
+        .iter()
+        .map(|l| strip_bullet(l))
+        .filter(|l| !l.is_empty())
+        .map(|line| {
+            let mut text = line.to_string();
+            let end_date = SINGLE_DATE.find(line).and_then(|m| {
+                text = text.replace(m.as_str(), " ");
+                parse_date(m.as_str())
+            });
+            let text = clean_fragment(&text);
+            let (title, organization) = match text.split_once(" by ") {
+                Some((title, org)) => (title.trim().to_string(), Some(org.trim().to_string())),
+                None => split_title_org(&text),
+            };
+
+            CvEntry {
+                title,
+                organization,
+                end_date,
+                ..CvEntry::default()
+            }
+        })
+        .filter(|e| !e.title.is_empty())
+        .collect()
+}
+
+/// Parses `Jan 2021`, `January, 2021`, `03/2021` or `2021` into the first day of that month.
+fn parse_date(s: &str) -> Option<NaiveDate> {
+    let s = s.trim().to_lowercase();
+
+    if let Some((month, year)) = s.split_once('/') {
+        let month: u32 = month.trim().parse().ok()?;
+        let year: i32 = year.trim().parse().ok()?;
+        return NaiveDate::from_ymd_opt(year, month, 1);
+    }
+
+    let year: i32 = s
+        .split(|c: char| !c.is_ascii_digit())
+        .find(|p| p.len() == 4)?
+        .parse()
+        .ok()?;
+
+    let month_name = s.trim_start_matches(|c: char| !c.is_alphabetic());
+    let month = [
+        "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+    ]
+    .iter()
+    .position(|m| month_name.starts_with(m))
+    .map(|i| i as u32 + 1)
+    .unwrap_or(1);
+
+    NaiveDate::from_ymd_opt(year, month, 1)
+}
@@ -0,0 +1,828 @@
+//! PDF layout for generated CVs and cover letters.
+//!
+//! Text is shaped with rustybuzz so kerning, ligatures and Bengali conjuncts
+//! come out right, wrapped by measured width and laid out top to bottom;
+//! a new A4 page starts whenever the next line would cross the bottom margin.
+//! Glyphs the Latin typeface lacks fall back to the Bengali font, see
+//! [`super::fonts`].
+
+use super::document::{CvDocument, CvTemplate, date_range, description_points};
+use super::fonts::{Family, FontLibrary, Weight};
+use super::parser::CvEntry;
+use printpdf::path::PaintMode;
+use printpdf::{
+    Color, IndirectFontRef, Line, Mm, PdfDocument, PdfDocumentReference, PdfLayerReference,
+    Point, Rect, Rgb,
+};
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const PT_TO_MM: f32 = 25.4 / 72.0;
+
+type Rgb3 = (f32, f32, f32);
+
+const BLACK: Rgb3 = (0.0, 0.0, 0.0);
+const WHITE: Rgb3 = (1.0, 1.0, 1.0);
+
+/// Typography and spacing of a template.
+struct Theme {
+    family: Family,
+    body_size: f32,
+    heading_size: f32,
+    name_size: f32,
+    text: Rgb3,
+    muted: Rgb3,
+    accent: Rgb3,
+    margin_x: f32,
+    margin_top: f32,
+    margin_bottom: f32,
+    uppercase_headings: bool,
+    heading_rule: bool,
+    bullet: &'static str,
+    skill_separator: &'static str,
+    page_numbers: bool,
+}
+
+impl Theme {
+    fn for_template(template: CvTemplate) -> Self {
+        match template {
+            CvTemplate::Classic => Theme {
+                family: Family::Serif,
+                body_size: 10.5,
+                heading_size: 12.0,
+                name_size: 22.0,
+                text: (0.1, 0.1, 0.1),
+                muted: (0.35, 0.35, 0.35),
+                accent: (0.1, 0.1, 0.1),
+                margin_x: 20.0,
+                margin_top: 20.0,
+                margin_bottom: 20.0,
+                uppercase_headings: true,
+                heading_rule: true,
+                bullet: "•",
+                skill_separator: " • ",
+                page_numbers: true,
+            },
+            CvTemplate::Modern => Theme {
+                family: Family::Sans,
+                body_size: 10.0,
+                heading_size: 13.0,
+                name_size: 24.0,
+                text: (0.13, 0.15, 0.18),
+                muted: (0.4, 0.43, 0.48),
+                accent: (0.09, 0.36, 0.62),
+                margin_x: 18.0,
+                margin_top: 18.0,
+                margin_bottom: 18.0,
+                uppercase_headings: false,
+                heading_rule: true,
+                bullet: "▪",
+                skill_separator: "  ·  ",
+                page_numbers: true,
+            },
+            CvTemplate::Ats => Theme {
+                family: Family::Sans,
+                body_size: 10.5,
+                heading_size: 12.0,
+                name_size: 16.0,
+                text: BLACK,
+                muted: BLACK,
+                accent: BLACK,
+                margin_x: 20.0,
+                margin_top: 20.0,
+                margin_bottom: 20.0,
+                uppercase_headings: true,
+                heading_rule: false,
+                bullet: "-",
+                skill_separator: ", ",
+                page_numbers: false,
+            },
+        }
+    }
+}
+
+/// Font size, weight and color of a piece of text.
+#[derive(Clone, Copy)]
+struct Style {
+    size: f32,
+    weight: Weight,
+    color: Rgb3,
+}
+
+impl Style {
+    /// Height of one line of text in millimetres.
+    fn line_height(&self) -> f32 {
+        self.size * 1.35 * PT_TO_MM
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+/// Which of the two fonts of a weight a text run uses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Script {
+    Latin,
+    Bengali,
+}
+
+/// A font embedded in the PDF, paired with its shaping face.
+struct Face {
+    shaper: rustybuzz::Face<'static>,
+    font: IndirectFontRef,
+    /// Font units to thousandths of an em
+    scale: f32,
+}
+
+impl Face {
+    /// Glyph width as written to the PDF `W` array by printpdf.
+    fn pdf_width(&self, gid: u16) -> f32 {
+        self.shaper
+            .glyph_hor_advance(rustybuzz::ttf_parser::GlyphId(gid))
+            .map(|w| (w as f32 * self.scale).trunc())
+            .unwrap_or(1000.0)
+    }
+}
+
+/// A run of glyphs shaped with a single font.
+struct ShapedRun {
+    script: Script,
+    /// Glyph id, advance and horizontal offset in thousandths of an em
+    glyphs: Vec<(u16, f32, f32)>,
+    /// Total advance in thousandths of an em
+    width: f32,
+}
+
+/// Lays out text onto consecutive PDF pages.
+struct Writer {
+    doc: PdfDocumentReference,
+    pages: Vec<PdfLayerReference>,
+    /// Index of the page being written
+    current: usize,
+    library: &'static FontLibrary,
+    theme: Theme,
+    faces: HashMap<(Script, Weight), Face>,
+    widths: HashMap<(Weight, String), f32>,
+    /// Baseline cursor, millimetres from the bottom of the page
+    y: f32,
+}
+
+impl Writer {
+    fn new(title: &str, theme: Theme) -> Self {
+        let (doc, page, layer) =
+            PdfDocument::new(title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1");
+        let first = doc.get_page(page).get_layer(layer);
+        let y = PAGE_HEIGHT - theme.margin_top;
+        Writer {
+            doc,
+            pages: vec![first],
+            current: 0,
+            library: FontLibrary::global(),
+            theme,
+            faces: HashMap::new(),
+            widths: HashMap::new(),
+            y,
+        }
+    }
+
+    fn layer(&self) -> &PdfLayerReference {
+        &self.pages[self.current]
+    }
+
+    fn content_width(&self) -> f32 {
+        PAGE_WIDTH - 2.0 * self.theme.margin_x
+    }
+
+    /// Returns the face for a script and weight, embedding the font on first use.
+    fn face(&mut self, script: Script, weight: Weight) -> Result<&Face, printpdf::Error> {
+        if !self.faces.contains_key(&(script, weight)) {
+            let bytes = match script {
+                Script::Latin => self.library.latin(self.theme.family, weight),
+                Script::Bengali => self
+                    .library
+                    .bengali(weight)
+                    .expect("Bengali runs are only produced when the font is loaded"),
+            };
+            let shaper = rustybuzz::Face::from_slice(bytes, 0)
+                .ok_or_else(|| printpdf::Error::Io(std::io::Error::other("invalid font data")))?;
+            let font = self.doc.add_external_font(bytes)?;
+            let scale = 1000.0 / shaper.units_per_em() as f32;
+            self.faces.insert((script, weight), Face { shaper, font, scale });
+        }
+        Ok(&self.faces[&(script, weight)])
+    }
+
+    /// Splits text into runs by the font that has its glyphs.
+    ///
+    /// Whitespace, joiners and combining marks stay with the preceding run.
+    fn script_runs(&self, text: &str, weight: Weight) -> Vec<(Script, std::ops::Range<usize>)> {
+        let latin = rustybuzz::ttf_parser::Face::parse(self.library.latin(self.theme.family, weight), 0).ok();
+        let bengali = self
+            .library
+            .bengali(weight)
+            .and_then(|bytes| rustybuzz::ttf_parser::Face::parse(bytes, 0).ok());
+
+        let mut runs: Vec<(Script, std::ops::Range<usize>)> = Vec::new();
+        for (i, c) in text.char_indices() {
+            let inherits = c.is_whitespace() || is_combining(c);
+            let script = match (&runs.last(), inherits) {
+                (Some((script, _)), true) => *script,
+                _ => {
+                    let in_latin = latin.as_ref().is_some_and(|f| f.glyph_index(c).is_some());
+                    let in_bengali = bengali.as_ref().is_some_and(|f| f.glyph_index(c).is_some());
+                    if !in_latin && in_bengali { Script::Bengali } else { Script::Latin }
+                }
+            };
+            match runs.last_mut() {
+                Some((last, range)) if *last == script => range.end = i + c.len_utf8(),
+                _ => runs.push((script, i..i + c.len_utf8())),
+            }
+        }
+        runs
+    }
+
+    fn shape(&mut self, text: &str, weight: Weight) -> Result<Vec<ShapedRun>, printpdf::Error> {
+        let mut shaped = Vec::new();
+        for (script, range) in self.script_runs(text, weight) {
+            let face = self.face(script, weight)?;
+            let mut buffer = rustybuzz::UnicodeBuffer::new();
+            buffer.push_str(&text[range]);
+            buffer.guess_segment_properties();
+            // Ligature glyphs have no ToUnicode entry, so "fi" would not be
+            // searchable or parseable by applicant tracking systems
+            let features: &[rustybuzz::Feature] = match script {
+                Script::Latin => &*NO_LIGATURES,
+                Script::Bengali => &[],
+            };
+            let output = rustybuzz::shape(&face.shaper, features, buffer);
+
+            let glyphs: Vec<(u16, f32, f32)> = output
+                .glyph_infos()
+                .iter()
+                .zip(output.glyph_positions())
+                .map(|(info, pos)| {
+                    (
+                        info.glyph_id as u16,
+                        pos.x_advance as f32 * face.scale,
+                        pos.x_offset as f32 * face.scale,
+                    )
+                })
+                .collect();
+            let width = glyphs.iter().map(|g| g.1).sum();
+            shaped.push(ShapedRun { script, glyphs, width });
+        }
+        Ok(shaped)
+    }
+
+    /// Width of `text` in millimetres.
+    fn measure(&mut self, text: &str, style: Style) -> Result<f32, printpdf::Error> {
+        let key = (style.weight, text.to_string());
+        let units = match self.widths.get(&key) {
+            Some(units) => *units,
+            None => {
+                let units = self.shape(text, style.weight)?.iter().map(|r| r.width).sum();
+                self.widths.insert(key, units);
+                units
+            }
+        };
+        Ok(units / 1000.0 * style.size * PT_TO_MM)
+    }
+
+    /// Draws a single line of text with its baseline at `y`.
+    fn draw_text(&mut self, text: &str, style: Style, x: f32, y: f32, align: Align) -> Result<(), printpdf::Error> {
+        let x = match align {
+            Align::Left => x,
+            Align::Center => x - self.measure(text, style)? / 2.0,
+            Align::Right => x - self.measure(text, style)?,
+        };
+        let runs = self.shape(text, style.weight)?;
+        let layer = self.layer().clone();
+        layer.set_fill_color(rgb(style.color));
+
+        let mut pen = x;
+        for run in runs {
+            let face = &self.faces[&(run.script, style.weight)];
+            layer.begin_text_section();
+            layer.set_font(&face.font, style.size);
+            layer.set_text_cursor(Mm(pen), Mm(y));
+
+            // The PDF viewer advances by the font's own widths; the TJ
+            // adjustments move each glyph to where the shaper placed it.
+            let mut carry = 0.0;
+            let positioned: Vec<(i64, u16)> = run
+                .glyphs
+                .iter()
+                .map(|&(gid, advance, offset)| {
+                    let adjust = (carry - offset).round() as i64;
+                    carry = face.pdf_width(gid) + offset - advance;
+                    (adjust, gid)
+                })
+                .collect();
+            layer.write_positioned_codepoints(positioned);
+            layer.end_text_section();
+
+            pen += run.width / 1000.0 * style.size * PT_TO_MM;
+        }
+        Ok(())
+    }
+
+    /// Breaks text into lines no wider than `max_width` millimetres.
+    fn wrap(&mut self, text: &str, style: Style, max_width: f32) -> Result<Vec<String>, printpdf::Error> {
+        let space = self.measure(" ", style)?;
+        let mut lines = Vec::new();
+
+        for paragraph in text.lines() {
+            let mut line = String::new();
+            let mut line_width = 0.0;
+
+            for word in paragraph.split_whitespace() {
+                let width = self.measure(word, style)?;
+                let needed = if line.is_empty() { width } else { line_width + space + width };
+                if needed <= max_width {
+                    if !line.is_empty() {
+                        line.push(' ');
+                    }
+                    line.push_str(word);
+                    line_width = needed;
+                    continue;
+                }
+
+                if !line.is_empty() {
+                    lines.push(std::mem::take(&mut line));
+                }
+                if width <= max_width {
+                    line = word.to_string();
+                    line_width = width;
+                } else {
+                    // Break words longer than a line (URLs, long identifiers)
+                    let mut pieces = self.split_word(word, style, max_width)?;
+                    let last = pieces.pop().unwrap_or_default();
+                    lines.extend(pieces);
+                    line_width = self.measure(&last, style)?;
+                    line = last;
+                }
+            }
+            if !line.is_empty() {
+                lines.push(line);
+            }
+        }
+        Ok(lines)
+    }
+
+    /// Splits a single word into pieces that fit, never inside a character cluster.
+    fn split_word(&mut self, word: &str, style: Style, max_width: f32) -> Result<Vec<String>, printpdf::Error> {
+        let mut pieces = Vec::new();
+        let mut current = String::new();
+        let mut chars = word.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            let mut cluster = c.to_string();
+            while let Some(&next) = chars.peek() {
+                if is_combining(next) || cluster.ends_with(BENGALI_VIRAMA) {
+                    cluster.push(next);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            let candidate = format!("{}{}", current, cluster);
+            if !current.is_empty() && self.measure(&candidate, style)? > max_width {
+                pieces.push(std::mem::replace(&mut current, cluster));
+            } else {
+                current = candidate;
+            }
+        }
+        if !current.is_empty() {
+            pieces.push(current);
+        }
+        Ok(pieces)
+    }
+
+    /// Starts a new page when fewer than `height` millimetres are left.
+    fn ensure_space(&mut self, height: f32) {
+        if self.y - height < self.theme.margin_bottom {
+            let (page, layer) = self
+                .doc
+                .add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1");
+            self.pages.push(self.doc.get_page(page).get_layer(layer));
+            self.current = self.pages.len() - 1;
+            self.y = PAGE_HEIGHT - self.theme.margin_top;
+        }
+    }
+
+    /// Writes wrapped text, starting a new page as needed.
+    fn paragraph(&mut self, text: &str, style: Style, indent: f32, align: Align) -> Result<(), printpdf::Error> {
+        let width = self.content_width() - indent;
+        let x = match align {
+            Align::Left => self.theme.margin_x + indent,
+            Align::Center => PAGE_WIDTH / 2.0,
+            Align::Right => PAGE_WIDTH - self.theme.margin_x,
+        };
+        for line in self.wrap(text, style, width)? {
+            self.ensure_space(style.line_height());
+            self.y -= style.line_height();
+            self.draw_text(&line, style, x, self.y + style.line_height() * 0.25, align)?;
+        }
+        Ok(())
+    }
+
+    /// Writes a bullet point with a hanging indent.
+    fn bullet(&mut self, text: &str, style: Style, indent: f32) -> Result<(), printpdf::Error> {
+        let hang = 4.5;
+        let x = self.theme.margin_x + indent;
+        let lines = self.wrap(text, style, self.content_width() - indent - hang)?;
+        for (i, line) in lines.iter().enumerate() {
+            self.ensure_space(style.line_height());
+            self.y -= style.line_height();
+            let baseline = self.y + style.line_height() * 0.25;
+            if i == 0 {
+                let bullet = self.theme.bullet;
+                self.draw_text(bullet, style, x, baseline, Align::Left)?;
+            }
+            self.draw_text(line, style, x + hang, baseline, Align::Left)?;
+        }
+        Ok(())
+    }
+
+    /// Draws a horizontal rule across the content width at the cursor.
+    fn rule(&mut self, color: Rgb3, thickness: f32) {
+        let layer = self.layer().clone();
+        layer.set_outline_color(rgb(color));
+        layer.set_outline_thickness(thickness);
+        layer.add_line(Line {
+            points: vec![
+                (Point::new(Mm(self.theme.margin_x), Mm(self.y)), false),
+                (Point::new(Mm(PAGE_WIDTH - self.theme.margin_x), Mm(self.y)), false),
+            ],
+            is_closed: false,
+        });
+    }
+
+    fn gap(&mut self, height: f32) {
+        self.y -= height;
+    }
+
+    fn body(&self) -> Style {
+        Style { size: self.theme.body_size, weight: Weight::Regular, color: self.theme.text }
+    }
+
+    fn muted(&self) -> Style {
+        Style { color: self.theme.muted, ..self.body() }
+    }
+
+    fn strong(&self) -> Style {
+        Style { weight: Weight::Bold, ..self.body() }
+    }
+
+    /// Writes a section heading, keeping it on the same page as the next line.
+    fn heading(&mut self, title: &str) -> Result<(), printpdf::Error> {
+        let style = Style { size: self.theme.heading_size, weight: Weight::Bold, color: self.theme.accent };
+        let title = if self.theme.uppercase_headings { title.to_uppercase() } else { title.to_string() };
+
+        self.ensure_space(4.0 + style.line_height() + 2.0 + self.body().line_height() * 2.0);
+        self.gap(4.0);
+        self.paragraph(&title, style, 0.0, Align::Left)?;
+        if self.theme.heading_rule {
+            self.gap(0.8);
+            self.rule(self.theme.accent, 0.6);
+        }
+        self.gap(1.5);
+        Ok(())
+    }
+
+    /// Writes an experience, education, project or certification entry.
+    fn entry(&mut self, entry: &CvEntry, plain: bool) -> Result<(), printpdf::Error> {
+        let strong = self.strong();
+        let muted = self.muted();
+        let dates = date_range(entry);
+        let place = [entry.organization.as_deref(), entry.location.as_deref()]
+            .into_iter()
+            .flatten()
+            .filter(|s| !s.trim().is_empty())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        self.ensure_space(strong.line_height() * 2.0 + muted.line_height());
+        self.gap(1.2);
+
+        if plain {
+            // ATS: everything left-aligned, in reading order
+            self.paragraph(&entry.title, strong, 0.0, Align::Left)?;
+            let details = [Some(place), dates]
+                .into_iter()
+                .flatten()
+                .filter(|s| !s.is_empty())
+                .collect::<Vec<_>>()
+                .join(" | ");
+            if !details.is_empty() {
+                self.paragraph(&details, muted, 0.0, Align::Left)?;
+            }
+        } else {
+            // Title on the left, dates right-aligned on the first line
+            let dates_width = match &dates {
+                Some(d) => self.measure(d, muted)? + 4.0,
+                None => 0.0,
+            };
+            let title_lines = self.wrap(&entry.title, strong, self.content_width() - dates_width)?;
+            for (i, line) in title_lines.iter().enumerate() {
+                self.ensure_space(strong.line_height());
+                self.y -= strong.line_height();
+                let baseline = self.y + strong.line_height() * 0.25;
+                self.draw_text(line, strong, self.theme.margin_x, baseline, Align::Left)?;
+                if let (0, Some(dates)) = (i, &dates) {
+                    self.draw_text(dates, muted, PAGE_WIDTH - self.theme.margin_x, baseline, Align::Right)?;
+                }
+            }
+            if !place.is_empty() {
+                self.paragraph(&place, muted, 0.0, Align::Left)?;
+            }
+        }
+
+        let points = description_points(entry);
+        let body = self.body();
+        match points.as_slice() {
+            [] => {}
+            [single] => self.paragraph(single, body, 0.0, Align::Left)?,
+            _ => {
+                for point in &points {
+                    self.bullet(point, body, 1.0)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes "Page n of m" at the bottom of every page.
+    fn page_numbers(&mut self) -> Result<(), printpdf::Error> {
+        let total = self.pages.len();
+        let style = Style { size: 8.0, ..self.muted() };
+        let y = self.theme.margin_bottom / 2.0;
+        for index in 0..total {
+            self.current = index;
+            let label = format!("Page {} of {}", index + 1, total);
+            self.draw_text(&label, style, PAGE_WIDTH / 2.0, y, Align::Center)?;
+        }
+        Ok(())
+    }
+}
+
+/// Whether a character attaches to the previous one (marks, joiners, variation selectors).
+fn is_combining(c: char) -> bool {
+    matches!(c,
+        '\u{0300}'..='\u{036F}'
+        | '\u{0981}'..='\u{0983}'
+        | '\u{09BC}'
+        | '\u{09BE}'..='\u{09CD}'
+        | '\u{09D7}'
+        | '\u{09E2}'..='\u{09E3}'
+        | '\u{09FE}'
+        | '\u{200C}'..='\u{200D}'
+        | '\u{FE00}'..='\u{FE0F}')
+}
+
+const BENGALI_VIRAMA: char = '\u{09CD}';
+
+static NO_LIGATURES: LazyLock<[rustybuzz::Feature; 1]> =
+    LazyLock::new(|| [rustybuzz::Feature::new(rustybuzz::ttf_parser::Tag::from_bytes(b"liga"), 0, ..)]);
+
+/// Matches ToUnicode targets outside the Basic Multilingual Plane.
+static WIDE_CODEPOINT: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<([0-9a-fA-F]{5,6})>").unwrap());
+
+/// Rewrites ToUnicode CMaps so code points above U+FFFF are UTF-16 surrogate pairs.
+///
+/// printpdf writes them as bare code points (`<1f600>`), which is invalid and
+/// makes text extractors, including the one used for CV uploads, fail.
+fn fix_to_unicode(pdf: Vec<u8>) -> Result<Vec<u8>, printpdf::Error> {
+    use printpdf::lopdf::{Document, Object};
+
+    let lopdf_error = |e: printpdf::lopdf::Error| printpdf::Error::Io(std::io::Error::other(e.to_string()));
+    let mut doc = Document::load_mem(&pdf).map_err(lopdf_error)?;
+
+    for object in doc.objects.values_mut() {
+        let Object::Stream(stream) = object else { continue };
+        let content = stream
+            .decompressed_content()
+            .unwrap_or_else(|_| stream.content.clone());
+        let text = String::from_utf8_lossy(&content);
+        if !text.contains("begincmap") || !WIDE_CODEPOINT.is_match(&text) {
+            continue;
+        }
+
+        let fixed = WIDE_CODEPOINT.replace_all(&text, |caps: &regex::Captures| {
+            let mut units = [0u16; 2];
+            let encoded = u32::from_str_radix(&caps[1], 16)
+                .ok()
+                .and_then(char::from_u32)
+                .map(|c| c.encode_utf16(&mut units).iter().map(|u| format!("{:04x}", u)).collect::<String>())
+                .unwrap_or_else(|| "fffd".to_string());
+            format!("<{}>", encoded)
+        });
+        stream.set_plain_content(fixed.into_owned().into_bytes());
+        stream.compress().map_err(lopdf_error)?;
+    }
+
+    let mut out = Vec::with_capacity(pdf.len());
+    doc.save_to(&mut out)?;
+    Ok(out)
+}
+
+fn rgb((r, g, b): Rgb3) -> Color {
+    Color::Rgb(Rgb::new(r, g, b, None))
+}
+
+/// Renders a CV document as an A4 PDF using the given template.
+///
+/// Content flows onto as many pages as needed.
+///
+/// # Example
+///
+/// ```
+/// use backend::cv::document::{CvDocument, CvTemplate};
+/// use backend::cv::{ContactInfo, CvEntry, ParsedCv};
+///
+/// let doc = CvDocument {
+///     headline: Some("Backend Developer".to_string()),
+///     cv: ParsedCv {
+///         contact: ContactInfo {
+///             full_name: Some("Rahim Uddin".to_string()),
+///             ..ContactInfo::default()
+///         },
+///         experience: vec![CvEntry { title: "Intern".to_string(), ..CvEntry::default() }; 80],
+///         ..ParsedCv::default()
+///     },
+/// };
+///
+/// let pdf = backend::cv::pdf::render_pdf(&doc, CvTemplate::Ats).unwrap();
+/// assert!(pdf.starts_with(b"%PDF"));
+/// ```
+pub fn render_pdf(document: &CvDocument, template: CvTemplate) -> Result<Vec<u8>, printpdf::Error> {
+    let theme = Theme::for_template(template);
+    let page_numbers = theme.page_numbers;
+    let mut w = Writer::new(&format!("{} - CV", document.name()), theme);
+
+    header(&mut w, document, template)?;
+
+    let plain = template == CvTemplate::Ats;
+    let cv = &document.cv;
+
+    if let Some(summary) = cv.summary.as_deref().filter(|s| !s.trim().is_empty()) {
+        w.heading(if plain { "Summary" } else { "Profile" })?;
+        let body = w.body();
+        w.paragraph(summary, body, 0.0, Align::Left)?;
+    }
+
+    if !cv.skills.is_empty() {
+        w.heading("Skills")?;
+        let skills = cv.skills.join(w.theme.skill_separator);
+        let body = w.body();
+        w.paragraph(&skills, body, 0.0, Align::Left)?;
+    }
+
+    let sections: [(&str, &Vec<CvEntry>); 4] = [
+        (if plain { "Work Experience" } else { "Experience" }, &cv.experience),
+        ("Projects", &cv.projects),
+        ("Education", &cv.education),
+        ("Certifications", &cv.certifications),
+    ];
+    for (title, entries) in sections {
+        if entries.is_empty() {
+            continue;
+        }
+        w.heading(title)?;
+        for entry in entries {
+            w.entry(entry, plain)?;
+        }
+    }
+
+    if page_numbers {
+        w.page_numbers()?;
+    }
+    fix_to_unicode(w.doc.save_to_bytes()?)
+}
+
+/// Renders a letter as an A4 PDF with the sender's CV header.
+///
+/// `document` supplies the name, headline and contact details; `body` is the
+/// letter text, with paragraphs separated by blank lines. `date` is printed
+/// above the body.
+///
+/// # Example
+///
+/// ```
+/// use backend::cv::document::{CvDocument, CvTemplate};
+///
+/// let body = "Dear Hiring Manager,\n\nI am applying for the role.\n\nSincerely,\nRahim Uddin";
+/// let pdf = backend::cv::pdf::render_letter_pdf(&CvDocument::default(), "18 October 2026", body, CvTemplate::Modern)
+///     .unwrap();
+/// assert!(pdf.starts_with(b"%PDF"));
+/// ```
+pub fn render_letter_pdf(
+    document: &CvDocument,
+    date: &str,
+    body: &str,
+    template: CvTemplate,
+) -> Result<Vec<u8>, printpdf::Error> {
+    let theme = Theme::for_template(template);
+    let page_numbers = theme.page_numbers;
+    let mut w = Writer::new(&format!("{} - Cover Letter", document.name()), theme);
+
+    header(&mut w, document, template)?;
+    w.gap(8.0);
+    let muted = w.muted();
+    w.paragraph(date, muted, 0.0, Align::Left)?;
+    w.gap(4.0);
+
+    let style = Style { size: w.theme.body_size + 0.5, ..w.body() };
+    let normalized = body.replace("\r\n", "\n");
+    for paragraph in normalized.split("\n\n").map(str::trim).filter(|p| !p.is_empty()) {
+        // Single line breaks (e.g. in the sign-off) are kept
+        for line in paragraph.lines() {
+            w.paragraph(line.trim(), style, 0.0, Align::Left)?;
+        }
+        w.gap(style.line_height() * 0.6);
+    }
+
+    if page_numbers && w.pages.len() > 1 {
+        w.page_numbers()?;
+    }
+    fix_to_unicode(w.doc.save_to_bytes()?)
+}
+
+/// Writes the name, headline and contact details in the template's style.
+fn header(w: &mut Writer, document: &CvDocument, template: CvTemplate) -> Result<(), printpdf::Error> {
+    let name = Style { size: w.theme.name_size, weight: Weight::Bold, color: w.theme.text };
+    let headline = Style { size: w.theme.body_size + 1.5, ..w.muted() };
+    let contact = document.contact_items().join("  |  ");
+
+    match template {
+        CvTemplate::Classic => {
+            w.paragraph(document.name(), name, 0.0, Align::Center)?;
+            if let Some(text) = &document.headline {
+                w.paragraph(text, headline, 0.0, Align::Center)?;
+            }
+            if !contact.is_empty() {
+                w.gap(1.0);
+                let muted = w.muted();
+                w.paragraph(&contact, muted, 0.0, Align::Center)?;
+            }
+            w.gap(2.5);
+            w.rule(w.theme.text, 1.2);
+        }
+        CvTemplate::Modern => {
+            // Measure first so the band fits a wrapped name or contact line
+            let width = w.content_width();
+            let name = Style { color: WHITE, ..name };
+            let headline = Style { color: (0.85, 0.91, 0.97), ..headline };
+            let contact_style = Style { color: WHITE, ..w.body() };
+            let name_lines = w.wrap(document.name(), name, width)?;
+            let headline_lines = match &document.headline {
+                Some(text) => w.wrap(text, headline, width)?,
+                None => Vec::new(),
+            };
+            let contact_lines = w.wrap(&contact, contact_style, width)?;
+            let band = w.theme.margin_top
+                + name_lines.len() as f32 * name.line_height()
+                + headline_lines.len() as f32 * headline.line_height()
+                + contact_lines.len() as f32 * contact_style.line_height()
+                + 10.0;
+
+            let layer = w.layer().clone();
+            layer.set_fill_color(rgb(w.theme.accent));
+            layer.add_rect(
+                Rect::new(Mm(0.0), Mm(PAGE_HEIGHT - band), Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT))
+                    .with_mode(PaintMode::Fill),
+            );
+
+            for (lines, style) in [(name_lines, name), (headline_lines, headline)] {
+                for line in lines {
+                    w.paragraph(&line, style, 0.0, Align::Left)?;
+                }
+            }
+            w.gap(2.0);
+            for line in contact_lines {
+                w.paragraph(&line, contact_style, 0.0, Align::Left)?;
+            }
+            w.y = PAGE_HEIGHT - band - 2.0;
+        }
+        CvTemplate::Ats => {
+            w.paragraph(document.name(), name, 0.0, Align::Left)?;
+            if let Some(text) = &document.headline {
+                let body = w.body();
+                w.paragraph(text, body, 0.0, Align::Left)?;
+            }
+            // One item per line so parsers pick up each contact field
+            let body = w.body();
+            for item in document.contact_items() {
+                w.paragraph(item, body, 0.0, Align::Left)?;
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,443 @@
+//! Resource-limited CV text extraction.
+//!
+//! Uploaded files are untrusted: a crafted PDF or zip can make a parser loop
+//! forever or allocate without bound. [`CvExtractor`] checks the size and the
+//! magic bytes up front and then extracts the text either
+//!
+//! - in a **subprocess** (default on Unix): the server binary re-executes
+//!   itself with [`WORKER_ARG`], under an address-space limit (`RLIMIT_AS`), a
+//!   CPU-time limit and a wall-clock timeout after which it is killed, or
+//! - on a **thread** from the blocking pool with a timeout. The memory ceiling
+//!   is not enforced in this mode, and a timed-out extraction keeps running in
+//!   the background while still holding its concurrency slot.
+//!
+//! Limits are configured through environment variables:
+//!
+//! - `CV_MAX_UPLOAD_MB` - largest accepted file (default 10)
+//! - `CV_EXTRACT_TIMEOUT_SECS` - wall-clock limit per extraction (default 30)
+//! - `CV_EXTRACT_MEMORY_MB` - address-space limit of the subprocess (default 1024)
+//! - `CV_EXTRACT_CONCURRENCY` - extractions running at once (default 2)
+//! - `CV_EXTRACT_ISOLATION` - `subprocess` or `thread`
+
+use super::extract::{
+    CvFormat, ExtractError, ExtractedText, OcrEngine, TesseractOcr, detect_format, extract_text,
+};
+use std::io::Cursor;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::sync::Semaphore;
+
+/// Argument that makes the server binary run as an extraction worker.
+pub const WORKER_ARG: &str = "__extract-cv-worker";
+
+/// Most entries accepted in a DOCX/ODT container.
+const MAX_ZIP_ENTRIES: usize = 1000;
+
+/// Reasons an uploaded CV is rejected, each with a stable error code.
+#[derive(Debug, Error)]
+pub enum UploadRejection {
+    /// The request has no `cv_file` field
+    #[error("No cv_file field found in request")]
+    MissingFile,
+    /// The file has no content
+    #[error("The uploaded file is empty")]
+    EmptyFile,
+    /// The file is larger than the configured limit
+    #[error("File size exceeds the {limit_mb}MB limit")]
+    FileTooLarge { limit_mb: usize },
+    /// The content doesn't match any supported format
+    #[error(
+        "Unsupported file type. Upload a PDF, DOCX, ODT, TXT or Markdown file, or a photo of your CV."
+    )]
+    UnsupportedFileType,
+    /// The format was recognised but the file could not be read
+    #[error("The file appears to be corrupted and could not be read.")]
+    CorruptFile,
+    /// The file was read but contains no text
+    #[error("The file contains no extractable text.")]
+    NoExtractableText,
+    /// An image was uploaded but no OCR engine is configured
+    #[error("Image CV uploads are not available on this server.")]
+    OcrUnavailable,
+    /// The OCR engine failed on the image
+    #[error("Could not read text from the image. Try a clearer photo or another format.")]
+    OcrFailed,
+    /// Extraction took longer than the configured timeout
+    #[error("Processing the file took too long. Try a smaller or simpler file.")]
+    ExtractionTimeout,
+    /// Extraction hit the memory or CPU ceiling
+    #[error("Processing the file needed too many resources. Try a smaller or simpler file.")]
+    ExtractionResourceLimit,
+    /// All extraction slots are in use
+    #[error("The server is busy processing other files. Please try again shortly.")]
+    ExtractionBusy,
+    /// The extraction worker could not be run
+    #[error("The file could not be processed. Please try again later.")]
+    ExtractionFailed,
+}
+
+impl UploadRejection {
+    /// Machine-readable code returned as `code` in the error body.
+    pub fn code(&self) -> &'static str {
+        match self {
+            UploadRejection::MissingFile => "missing_file",
+            UploadRejection::EmptyFile => "empty_file",
+            UploadRejection::FileTooLarge { .. } => "file_too_large",
+            UploadRejection::UnsupportedFileType => "unsupported_file_type",
+            UploadRejection::CorruptFile => "corrupt_file",
+            UploadRejection::NoExtractableText => "no_extractable_text",
+            UploadRejection::OcrUnavailable => "ocr_unavailable",
+            UploadRejection::OcrFailed => "ocr_failed",
+            UploadRejection::ExtractionTimeout => "extraction_timeout",
+            UploadRejection::ExtractionResourceLimit => "extraction_resource_limit",
+            UploadRejection::ExtractionBusy => "extraction_busy",
+            UploadRejection::ExtractionFailed => "extraction_failed",
+        }
+    }
+}
+
+impl From<ExtractError> for UploadRejection {
+    fn from(err: ExtractError) -> Self {
+        match err {
+            ExtractError::UnsupportedFormat => UploadRejection::UnsupportedFileType,
+            ExtractError::Corrupt(_) => UploadRejection::CorruptFile,
+            ExtractError::Empty => UploadRejection::NoExtractableText,
+            ExtractError::OcrUnavailable => UploadRejection::OcrUnavailable,
+            ExtractError::Ocr(_) => UploadRejection::OcrFailed,
+        }
+    }
+}
+
+/// How extraction is isolated from the server process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Isolation {
+    /// Re-executed server binary with resource limits
+    Subprocess,
+    /// Blocking thread with a timeout
+    Thread,
+}
+
+/// Limits applied to CV uploads and extraction.
+#[derive(Debug, Clone)]
+pub struct ExtractionLimits {
+    /// Largest accepted file in bytes
+    pub max_file_bytes: usize,
+    /// Wall-clock limit per extraction
+    pub timeout: Duration,
+    /// Address-space limit of the worker subprocess in bytes
+    pub memory_limit_bytes: u64,
+    /// Extractions running at once
+    pub concurrency: usize,
+    /// Isolation mode
+    pub isolation: Isolation,
+}
+
+impl Default for ExtractionLimits {
+    fn default() -> Self {
+        Self {
+            max_file_bytes: 10 * 1024 * 1024,
+            timeout: Duration::from_secs(30),
+            memory_limit_bytes: 1024 * 1024 * 1024,
+            concurrency: 2,
+            isolation: if cfg!(unix) {
+                Isolation::Subprocess
+            } else {
+                Isolation::Thread
+            },
+        }
+    }
+}
+
+impl ExtractionLimits {
+    /// Reads the limits from the environment, falling back to the defaults.
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let number = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .filter(|v| *v > 0)
+        };
+
+        Self {
+            max_file_bytes: number("CV_MAX_UPLOAD_MB")
+                .map_or(defaults.max_file_bytes, |mb| mb as usize * 1024 * 1024),
+            timeout: number("CV_EXTRACT_TIMEOUT_SECS").map_or(defaults.timeout, Duration::from_secs),
+            memory_limit_bytes: number("CV_EXTRACT_MEMORY_MB")
+                .map_or(defaults.memory_limit_bytes, |mb| mb * 1024 * 1024),
+            concurrency: number("CV_EXTRACT_CONCURRENCY").map_or(defaults.concurrency, |n| n as usize),
+            isolation: match std::env::var("CV_EXTRACT_ISOLATION").as_deref() {
+                Ok("thread") => Isolation::Thread,
+                Ok("subprocess") if cfg!(unix) => Isolation::Subprocess,
+                _ => defaults.isolation,
+            },
+        }
+    }
+
+    /// Checks the size and the magic bytes of an upload before extraction.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use backend::cv::extract::CvFormat;
+    /// use backend::cv::sandbox::{ExtractionLimits, UploadRejection};
+    ///
+    /// let limits = ExtractionLimits { max_file_bytes: 16, ..ExtractionLimits::default() };
+    /// assert_eq!(limits.validate(b"%PDF-1.7\n%%EOF").unwrap(), CvFormat::Pdf);
+    /// assert!(matches!(limits.validate(b""), Err(UploadRejection::EmptyFile)));
+    /// assert!(matches!(limits.validate(b"MZ\x90\x00\x03\x00"), Err(UploadRejection::UnsupportedFileType)));
+    /// assert!(matches!(limits.validate(&[b'a'; 17]), Err(UploadRejection::FileTooLarge { .. })));
+    /// assert!(matches!(limits.validate(b"%PDFx"), Err(UploadRejection::CorruptFile)));
+    /// ```
+    pub fn validate(&self, bytes: &[u8]) -> Result<CvFormat, UploadRejection> {
+        if bytes.is_empty() {
+            return Err(UploadRejection::EmptyFile);
+        }
+        if bytes.len() > self.max_file_bytes {
+            return Err(UploadRejection::FileTooLarge {
+                limit_mb: self.max_file_bytes.div_ceil(1024 * 1024),
+            });
+        }
+
+        let format = detect_format(bytes).ok_or(UploadRejection::UnsupportedFileType)?;
+        match format {
+            // "%PDF-" followed by the version, e.g. "%PDF-1.7"
+            CvFormat::Pdf if !(bytes.len() > 6 && bytes[4] == b'-' && bytes[5].is_ascii_digit()) => {
+                Err(UploadRejection::CorruptFile)
+            }
+            CvFormat::Docx | CvFormat::Odt => {
+                let archive = zip::ZipArchive::new(Cursor::new(bytes))
+                    .map_err(|_| UploadRejection::CorruptFile)?;
+                if archive.len() > MAX_ZIP_ENTRIES {
+                    return Err(UploadRejection::CorruptFile);
+                }
+                Ok(format)
+            }
+            _ => Ok(format),
+        }
+    }
+}
+
+/// Extracts CV text within the configured limits.
+pub struct CvExtractor {
+    limits: ExtractionLimits,
+    ocr: Option<Arc<dyn OcrEngine>>,
+    permits: Arc<Semaphore>,
+}
+
+impl CvExtractor {
+    /// Creates an extractor.
+    ///
+    /// In subprocess mode the worker builds its own [`TesseractOcr`] from the
+    /// environment when `ocr` is set; other engines only run in thread mode.
+    pub fn new(limits: ExtractionLimits, ocr: Option<Arc<dyn OcrEngine>>) -> Self {
+        let permits = Arc::new(Semaphore::new(limits.concurrency.max(1)));
+        Self {
+            limits,
+            ocr,
+            permits,
+        }
+    }
+
+    /// Limits in effect.
+    pub fn limits(&self) -> &ExtractionLimits {
+        &self.limits
+    }
+
+    /// Validates an upload and extracts its text.
+    pub async fn extract(&self, bytes: &[u8]) -> Result<ExtractedText, UploadRejection> {
+        self.limits.validate(bytes)?;
+
+        let permit = tokio::time::timeout(self.limits.timeout, self.permits.clone().acquire_owned())
+            .await
+            .map_err(|_| UploadRejection::ExtractionBusy)?
+            .expect("extraction semaphore is never closed");
+
+        match self.limits.isolation {
+            Isolation::Subprocess => {
+                let result = self.extract_in_subprocess(bytes).await;
+                drop(permit);
+                result
+            }
+            Isolation::Thread => {
+                let ocr = self.ocr.clone();
+                let bytes = bytes.to_vec();
+                let task = tokio::task::spawn_blocking(move || {
+                    // Held until the extraction really ends, even after a timeout
+                    let _permit = permit;
+                    extract_text(&bytes, ocr.as_deref())
+                });
+                match tokio::time::timeout(self.limits.timeout, task).await {
+                    Err(_) => Err(UploadRejection::ExtractionTimeout),
+                    // The parser panicked on malformed input
+                    Ok(Err(_)) => Err(UploadRejection::CorruptFile),
+                    Ok(Ok(result)) => result.map_err(UploadRejection::from),
+                }
+            }
+        }
+    }
+
+    #[cfg(unix)]
+    async fn extract_in_subprocess(&self, bytes: &[u8]) -> Result<ExtractedText, UploadRejection> {
+        use std::os::unix::process::ExitStatusExt;
+        use std::process::Stdio;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let exe = std::env::current_exe().map_err(|e| {
+            tracing::error!("Cannot locate server binary for CV extraction: {}", e);
+            UploadRejection::ExtractionFailed
+        })?;
+
+        let memory_limit = self.limits.memory_limit_bytes;
+        let cpu_seconds = self.limits.timeout.as_secs().max(1) + 1;
+
+        let mut command = tokio::process::Command::new(exe);
+        command
+            .arg(WORKER_ARG)
+            .env("CV_EXTRACT_OCR", if self.ocr.is_some() { "1" } else { "0" })
+            // Fewer malloc arenas keep the reserved address space small
+            .env("MALLOC_ARENA_MAX", "2")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true);
+        // SAFETY: only async-signal-safe calls (setpgid, setrlimit) run between fork and exec
+        unsafe {
+            command.pre_exec(move || {
+                // Own process group, so OCR subprocesses are killed along with the worker
+                if libc::setpgid(0, 0) != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                set_rlimit(libc::RLIMIT_AS, memory_limit)?;
+                set_rlimit(libc::RLIMIT_CPU, cpu_seconds)?;
+                set_rlimit(libc::RLIMIT_CORE, 0)
+            });
+        }
+
+        let mut child = command.spawn().map_err(|e| {
+            tracing::error!("Failed to start CV extraction worker: {}", e);
+            UploadRejection::ExtractionFailed
+        })?;
+
+        let pid = child.id();
+        let mut stdin = child.stdin.take().expect("worker stdin is piped");
+        let mut stdout = child.stdout.take().expect("worker stdout is piped");
+
+        let run = async {
+            // Feed the file while reading the result so neither pipe fills up
+            let write = async {
+                // The worker may exit early; a broken pipe shows up in its status
+                let _ = stdin.write_all(bytes).await;
+                drop(stdin);
+            };
+            let mut output = Vec::new();
+            let read = stdout.read_to_end(&mut output);
+            let (_, read) = tokio::join!(write, read);
+            let status = child.wait().await;
+            (read.map(|_| output), status)
+        };
+
+        let (output, status) = match tokio::time::timeout(self.limits.timeout, run).await {
+            Ok(done) => done,
+            Err(_) => {
+                if let Some(pid) = pid {
+                    // SAFETY: signals the worker's own process group
+                    unsafe { libc::kill(-(pid as libc::pid_t), libc::SIGKILL) };
+                }
+                tracing::warn!("CV extraction worker timed out, killed");
+                return Err(UploadRejection::ExtractionTimeout);
+            }
+        };
+
+        let status = status.map_err(|_| UploadRejection::ExtractionFailed)?;
+        if let Some(signal) = status.signal() {
+            tracing::warn!("CV extraction worker killed by signal {}", signal);
+            return Err(match signal {
+                libc::SIGXCPU | libc::SIGKILL => UploadRejection::ExtractionTimeout,
+                _ => UploadRejection::ExtractionResourceLimit,
+            });
+        }
+
+        let output = output.map_err(|_| UploadRejection::ExtractionResourceLimit)?;
+        match serde_json::from_slice::<Result<ExtractedText, ExtractError>>(&output) {
+            Ok(result) => result.map_err(UploadRejection::from),
+            Err(_) => {
+                // A panic on malformed input exits with 101 before writing a result;
+                // an allocation failure under RLIMIT_AS aborts
+                tracing::warn!("CV extraction worker failed with {}", status);
+                Err(if status.code() == Some(101) {
+                    UploadRejection::CorruptFile
+                } else {
+                    UploadRejection::ExtractionResourceLimit
+                })
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    async fn extract_in_subprocess(&self, _bytes: &[u8]) -> Result<ExtractedText, UploadRejection> {
+        unreachable!("subprocess isolation is only selected on Unix")
+    }
+}
+
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+type RlimitResource = libc::__rlimit_resource_t;
+#[cfg(all(unix, not(all(target_os = "linux", target_env = "gnu"))))]
+type RlimitResource = libc::c_int;
+
+#[cfg(unix)]
+fn set_rlimit(resource: RlimitResource, value: u64) -> std::io::Result<()> {
+    let limit = libc::rlimit {
+        rlim_cur: value as libc::rlim_t,
+        rlim_max: value as libc::rlim_t,
+    };
+    // SAFETY: plain syscall on a stack value
+    if unsafe { libc::setrlimit(resource, &limit) } == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
+}
+
+/// Entry point of the extraction worker subprocess.
+///
+/// Reads the file from stdin and writes the JSON-encoded
+/// `Result<ExtractedText, ExtractError>` to stdout. Output printed by the
+/// parsers themselves is discarded. Returns the process exit code.
+#[cfg(unix)]
+pub fn run_worker() -> i32 {
+    use std::io::{Read, Write};
+    use std::os::fd::FromRawFd;
+
+    // Keep the real stdout for the result and point fd 1 at /dev/null, since
+    // pdf-extract prints diagnostics with println!
+    // SAFETY: fds are duplicated before any Rust code writes to stdout
+    let mut result_out = unsafe {
+        let fd = libc::dup(1);
+        let null = libc::open(c"/dev/null".as_ptr(), libc::O_WRONLY);
+        if fd < 0 || null < 0 || libc::dup2(null, 1) < 0 {
+            return 2;
+        }
+        libc::close(null);
+        std::fs::File::from_raw_fd(fd)
+    };
+
+    let mut bytes = Vec::new();
+    if std::io::stdin().read_to_end(&mut bytes).is_err() {
+        return 2;
+    }
+
+    let ocr = (std::env::var("CV_EXTRACT_OCR").as_deref() == Ok("1"))
+        .then(TesseractOcr::from_env)
+        .flatten();
+    let result = extract_text(&bytes, ocr.as_ref().map(|o| o as &dyn OcrEngine));
+
+    let written = serde_json::to_writer(&mut result_out, &result).is_ok() && result_out.flush().is_ok();
+    if written { 0 } else { 2 }
+}
+
+#[cfg(not(unix))]
+pub fn run_worker() -> i32 {
+    2
+}
@@ -0,0 +1,425 @@
+//! Storage backends for uploaded CV files.
+//!
+//! Original files are kept so users can download and compare the versions
+//! they uploaded. The backend is chosen per deployment:
+//!
+//! - `CV_STORAGE=local` (default): files under `CV_STORAGE_DIR` (default `./data/cv`)
+//! - `CV_STORAGE=s3`: any S3-compatible service (AWS S3, MinIO, Cloudflare R2, ...)
+//!   configured through `S3_BUCKET`, `S3_REGION`, `S3_ACCESS_KEY_ID`,
+//!   `S3_SECRET_ACCESS_KEY` and optionally `S3_ENDPOINT`
+
+use async_trait::async_trait;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use thiserror::Error;
+
+/// Errors raised by a storage backend.
+#[derive(Debug, Error)]
+pub enum StorageError {
+    /// No object is stored under the key
+    #[error("Object not found")]
+    NotFound,
+    /// The key contains path traversal or is otherwise unusable
+    #[error("Invalid storage key: {0}")]
+    InvalidKey(String),
+    /// Local filesystem failure
+    #[error("Storage I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    /// Remote (S3) failure
+    #[error("Remote storage error: {0}")]
+    Remote(String),
+    /// Missing or invalid configuration
+    #[error("Storage configuration error: {0}")]
+    Config(String),
+}
+
+/// Blob storage for CV documents.
+#[async_trait]
+pub trait DocumentStorage: Send + Sync {
+    /// Backend name stored with each version ("local", "s3")
+    fn backend(&self) -> &'static str;
+
+    /// Stores `bytes` under `key`, replacing any existing object.
+    async fn put(&self, key: &str, bytes: &[u8], content_type: &str) -> Result<(), StorageError>;
+
+    /// Returns the object stored under `key`.
+    async fn get(&self, key: &str) -> Result<Vec<u8>, StorageError>;
+
+    /// Removes the object stored under `key`. Missing objects are not an error.
+    async fn delete(&self, key: &str) -> Result<(), StorageError>;
+}
+
+/// Builds the storage backend configured through environment variables.
+pub fn storage_from_env() -> Result<Arc<dyn DocumentStorage>, StorageError> {
+    match std::env::var("CV_STORAGE").unwrap_or_else(|_| "local".to_string()).as_str() {
+        "local" => {
+            let root = std::env::var("CV_STORAGE_DIR").unwrap_or_else(|_| "./data/cv".to_string());
+            Ok(Arc::new(LocalFsStorage::new(root)))
+        }
+        "s3" => Ok(Arc::new(S3Storage::from_env()?)),
+        other => Err(StorageError::Config(format!(
+            "unknown CV_STORAGE '{}', expected 'local' or 's3'",
+            other
+        ))),
+    }
+}
+
+/// Validates a storage key: relative, `/`-separated, no `.` or `..` segments.
+fn validate_key(key: &str) -> Result<(), StorageError> {
+    let valid = !key.is_empty()
+        && !key.starts_with('/')
+        && !key.contains('\\')
+        && Path::new(key)
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)));
+    if valid {
+        Ok(())
+    } else {
+        Err(StorageError::InvalidKey(key.to_string()))
+    }
+}
+
+/// Stores documents as files under a root directory.
+#[derive(Debug, Clone)]
+pub struct LocalFsStorage {
+    root: PathBuf,
+}
+
+impl LocalFsStorage {
+    /// Creates a storage rooted at `root`; the directory is created on first write.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path_for(&self, key: &str) -> Result<PathBuf, StorageError> {
+        validate_key(key)?;
+        Ok(self.root.join(key))
+    }
+}
+
+#[async_trait]
+impl DocumentStorage for LocalFsStorage {
+    fn backend(&self) -> &'static str {
+        "local"
+    }
+
+    async fn put(&self, key: &str, bytes: &[u8], _content_type: &str) -> Result<(), StorageError> {
+        let path = self.path_for(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        // Write to a sibling file first so readers never see a partial object
+        let tmp = path.with_extension("partial");
+        tokio::fs::write(&tmp, bytes).await?;
+        tokio::fs::rename(&tmp, &path).await?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, StorageError> {
+        match tokio::fs::read(self.path_for(key)?).await {
+            Ok(bytes) => Ok(bytes),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Err(StorageError::NotFound),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), StorageError> {
+        match tokio::fs::remove_file(self.path_for(key)?).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Stores documents in an S3-compatible bucket, signing requests with AWS Signature V4.
+pub struct S3Storage {
+    client: reqwest::Client,
+    /// Scheme and host, e.g. `https://s3.eu-central-1.amazonaws.com`
+    endpoint: String,
+    bucket: String,
+    region: String,
+    access_key_id: String,
+    secret_access_key: String,
+    /// `https://endpoint/bucket/key` instead of `https://bucket.endpoint/key`
+    path_style: bool,
+}
+
+impl S3Storage {
+    /// Reads the S3 configuration from the environment.
+    ///
+    /// Without `S3_ENDPOINT` the AWS endpoint for `S3_REGION` is used with
+    /// virtual-hosted addressing; custom endpoints use path-style addressing
+    /// unless `S3_PATH_STYLE=false`.
+    pub fn from_env() -> Result<Self, StorageError> {
+        let var = |name: &str| {
+            std::env::var(name).map_err(|_| StorageError::Config(format!("{} must be set", name)))
+        };
+
+        let region = std::env::var("S3_REGION").unwrap_or_else(|_| "us-east-1".to_string());
+        let custom_endpoint = std::env::var("S3_ENDPOINT").ok();
+        let path_style = match std::env::var("S3_PATH_STYLE") {
+            Ok(v) => v != "false",
+            Err(_) => custom_endpoint.is_some(),
+        };
+        let endpoint = custom_endpoint
+            .unwrap_or_else(|| format!("https://s3.{}.amazonaws.com", region))
+            .trim_end_matches('/')
+            .to_string();
+
+        Ok(Self {
+            client: reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(30))
+                .build()
+                .map_err(|e| StorageError::Config(e.to_string()))?,
+            endpoint,
+            bucket: var("S3_BUCKET")?,
+            region,
+            access_key_id: var("S3_ACCESS_KEY_ID")?,
+            secret_access_key: var("S3_SECRET_ACCESS_KEY")?,
+            path_style,
+        })
+    }
+
+    /// Returns the request URL, host header and canonical URI for an object key.
+    fn object_location(&self, key: &str) -> Result<(String, String, String), StorageError> {
+        validate_key(key)?;
+        let (scheme, host) = self
+            .endpoint
+            .split_once("://")
+            .ok_or_else(|| StorageError::Config(format!("invalid S3 endpoint '{}'", self.endpoint)))?;
+
+        let encoded_key = key
+            .split('/')
+            .map(|segment| urlencoding::encode(segment).into_owned())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        let (host, uri) = if self.path_style {
+            (host.to_string(), format!("/{}/{}", self.bucket, encoded_key))
+        } else {
+            (format!("{}.{}", self.bucket, host), format!("/{}", encoded_key))
+        };
+
+        Ok((format!("{}://{}{}", scheme, host, uri), host, uri))
+    }
+
+    /// Sends a signed request and returns the response.
+    async fn send(
+        &self,
+        method: reqwest::Method,
+        key: &str,
+        body: Vec<u8>,
+        content_type: Option<&str>,
+    ) -> Result<reqwest::Response, StorageError> {
+        let (url, host, uri) = self.object_location(key)?;
+        let amz_date = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+        let payload_hash = hex::encode(Sha256::digest(&body));
+
+        let mut headers = vec![
+            ("host", host),
+            ("x-amz-content-sha256", payload_hash.clone()),
+            ("x-amz-date", amz_date.clone()),
+        ];
+        if let Some(content_type) = content_type {
+            headers.push(("content-type", content_type.to_string()));
+        }
+
+        let credentials = SigV4Credentials {
+            access_key_id: &self.access_key_id,
+            secret_access_key: &self.secret_access_key,
+            region: &self.region,
+            service: "s3",
+        };
+        let signed = sign_v4(&credentials, method.as_str(), &uri, &headers, &payload_hash, &amz_date);
+
+        let mut request = self.client.request(method, &url).header("Authorization", signed.authorization);
+        for (name, value) in headers.into_iter().filter(|(k, _)| *k != "host") {
+            request = request.header(name, value);
+        }
+
+        request
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| StorageError::Remote(e.to_string()))
+    }
+}
+
+/// Credentials and scope of an AWS Signature V4.
+#[derive(Debug, Clone, Copy)]
+pub struct SigV4Credentials<'a> {
+    /// Access key ID
+    pub access_key_id: &'a str,
+    /// Secret access key
+    pub secret_access_key: &'a str,
+    /// Region, e.g. `us-east-1`
+    pub region: &'a str,
+    /// Service, e.g. `s3`
+    pub service: &'a str,
+}
+
+/// An AWS Signature V4 and the intermediate values it was computed from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SigV4Signature {
+    /// Canonical request that was hashed
+    pub canonical_request: String,
+    /// String that was signed
+    pub string_to_sign: String,
+    /// Hex-encoded signature
+    pub signature: String,
+    /// Value of the `Authorization` header
+    pub authorization: String,
+}
+
+/// Signs a request without a query string with AWS Signature V4.
+///
+/// `headers` are the signed headers with lowercase names, `host` and
+/// `x-amz-date` (`amz_date`, e.g. `20150830T123600Z`) included.
+///
+/// # Example
+///
+/// AWS's `get-vanilla` test vector:
+///
+/// ```
+/// use backend::cv::storage::{SigV4Credentials, sign_v4};
+///
+/// let credentials = SigV4Credentials {
+///     access_key_id: "AKIDEXAMPLE",
+///     secret_access_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+///     region: "us-east-1",
+///     service: "service",
+/// };
+/// let headers = [
+///     ("host", "example.amazonaws.com".to_string()),
+///     ("x-amz-date", "20150830T123600Z".to_string()),
+/// ];
+/// let empty_payload = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+/// let signed = sign_v4(&credentials, "GET", "/", &headers, empty_payload, "20150830T123600Z");
+///
+/// assert_eq!(
+///     signed.canonical_request,
+///     "GET\n/\n\nhost:example.amazonaws.com\nx-amz-date:20150830T123600Z\n\nhost;x-amz-date\n\
+///      e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+/// );
+/// assert_eq!(
+///     signed.string_to_sign,
+///     "AWS4-HMAC-SHA256\n20150830T123600Z\n20150830/us-east-1/service/aws4_request\n\
+///      bb579772317eb040ac9ed261061d46c1f17a8133879d6129b6e1c25292927e63"
+/// );
+/// assert_eq!(signed.signature, "5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31");
+/// assert_eq!(
+///     signed.authorization,
+///     "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, \
+///      SignedHeaders=host;x-amz-date, Signature=5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31"
+/// );
+/// ```
+pub fn sign_v4(
+    credentials: &SigV4Credentials<'_>,
+    method: &str,
+    uri: &str,
+    headers: &[(&str, String)],
+    payload_hash: &str,
+    amz_date: &str,
+) -> SigV4Signature {
+    let mut headers = headers.to_vec();
+    headers.sort_by(|a, b| a.0.cmp(b.0));
+
+    let signed_headers = headers.iter().map(|(k, _)| *k).collect::<Vec<_>>().join(";");
+    let canonical_headers: String = headers
+        .iter()
+        .map(|(k, v)| format!("{}:{}\n", k, v.trim()))
+        .collect();
+    let canonical_request = format!(
+        "{}\n{}\n\n{}\n{}\n{}",
+        method,
+        uri,
+        canonical_headers,
+        signed_headers,
+        payload_hash
+    );
+
+    let date = amz_date.get(..8).unwrap_or(amz_date);
+    let scope = format!("{}/{}/{}/aws4_request", date, credentials.region, credentials.service);
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        amz_date,
+        scope,
+        hex::encode(Sha256::digest(canonical_request.as_bytes()))
+    );
+
+    let signing_key = [date, credentials.region, credentials.service, "aws4_request"]
+        .iter()
+        .fold(format!("AWS4{}", credentials.secret_access_key).into_bytes(), |key, part| {
+            hmac_sha256(&key, part.as_bytes())
+        });
+    let signature = hex::encode(hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+
+    let authorization = format!(
+        "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+        credentials.access_key_id, scope, signed_headers, signature
+    );
+
+    SigV4Signature {
+        canonical_request,
+        string_to_sign,
+        signature,
+        authorization,
+    }
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Turns a non-success S3 response into an error.
+async fn s3_error(response: reqwest::Response) -> StorageError {
+    let status = response.status();
+    if status == reqwest::StatusCode::NOT_FOUND {
+        return StorageError::NotFound;
+    }
+    let body = response.text().await.unwrap_or_default();
+    StorageError::Remote(format!("S3 returned {}: {}", status, body.chars().take(300).collect::<String>()))
+}
+
+#[async_trait]
+impl DocumentStorage for S3Storage {
+    fn backend(&self) -> &'static str {
+        "s3"
+    }
+
+    async fn put(&self, key: &str, bytes: &[u8], content_type: &str) -> Result<(), StorageError> {
+        let response = self
+            .send(reqwest::Method::PUT, key, bytes.to_vec(), Some(content_type))
+            .await?;
+        if !response.status().is_success() {
+            return Err(s3_error(response).await);
+        }
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, StorageError> {
+        let response = self.send(reqwest::Method::GET, key, Vec::new(), None).await?;
+        if !response.status().is_success() {
+            return Err(s3_error(response).await);
+        }
+        response
+            .bytes()
+            .await
+            .map(|b| b.to_vec())
+            .map_err(|e| StorageError::Remote(e.to_string()))
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), StorageError> {
+        let response = self.send(reqwest::Method::DELETE, key, Vec::new(), None).await?;
+        // S3 answers 204 for deletes, including missing keys
+        if !response.status().is_success() && response.status() != reqwest::StatusCode::NOT_FOUND {
+            return Err(s3_error(response).await);
+        }
+        Ok(())
+    }
+}
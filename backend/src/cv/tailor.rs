@@ -0,0 +1,128 @@
+//! Tailoring a CV to a job posting.
+//!
+//! Skills, experience and projects that match the job's required skills move
+//! to the top of their sections, and within each entry the bullet points that
+//! mention a required skill come first. Nothing is removed, so the tailored
+//! CV stays truthful to what the user entered.
+
+use super::document::{CvDocument, description_points};
+use super::parser::CvEntry;
+use crate::ai_matching::{matched_skills, skills_mentioned};
+use serde::{Deserialize, Serialize};
+
+/// What tailoring changed, stored alongside the tailored document.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TailoringSummary {
+    /// Required skills listed in the CV's skills section
+    pub matched_skills: Vec<String>,
+    /// Required skills the CV does not list
+    pub missing_skills: Vec<String>,
+    /// Titles of experience and project entries that mention a required skill
+    pub highlighted_entries: Vec<String>,
+}
+
+/// Reorders a CV for a job and sets the headline to the job title.
+///
+/// # Example
+///
+/// ```
+/// use backend::cv::document::CvDocument;
+/// use backend::cv::tailor::tailor_document;
+/// use backend::cv::{CvEntry, ParsedCv};
+///
+/// let mut doc = CvDocument {
+///     cv: ParsedCv {
+///         skills: vec!["Excel".to_string(), "SQL".to_string()],
+///         projects: vec![
+///             CvEntry { title: "Budget sheet".to_string(), ..CvEntry::default() },
+///             CvEntry {
+///                 title: "Sales dashboard".to_string(),
+///                 description: Some("- Designed charts\n- Wrote SQL views".to_string()),
+///                 ..CvEntry::default()
+///             },
+///         ],
+///         ..ParsedCv::default()
+///     },
+///     ..CvDocument::default()
+/// };
+/// let required = vec!["SQL".to_string(), "Python".to_string()];
+/// let summary = tailor_document(&mut doc, "Data Analyst", &required);
+///
+/// assert_eq!(doc.headline.as_deref(), Some("Data Analyst"));
+/// assert_eq!(doc.cv.skills, vec!["SQL", "Excel"]);
+/// assert_eq!(doc.cv.projects[0].title, "Sales dashboard");
+/// assert_eq!(doc.cv.projects[0].description.as_deref(), Some("Wrote SQL views\nDesigned charts"));
+/// assert_eq!(summary.missing_skills, vec!["Python"]);
+/// ```
+pub fn tailor_document(
+    document: &mut CvDocument,
+    job_title: &str,
+    required_skills: &[String],
+) -> TailoringSummary {
+    let cv = &mut document.cv;
+    let matched = matched_skills(&cv.skills, required_skills);
+
+    // Matched skills in the job's order, then the rest as entered
+    let mut skills = matched.clone();
+    skills.extend(
+        cv.skills
+            .iter()
+            .filter(|s| !matched.iter().any(|m| m.eq_ignore_ascii_case(s)))
+            .cloned(),
+    );
+    cv.skills = skills;
+
+    let mut highlighted_entries = Vec::new();
+    for entries in [&mut cv.experience, &mut cv.projects] {
+        highlighted_entries.extend(reorder_entries(entries, required_skills));
+    }
+
+    let missing_skills = required_skills
+        .iter()
+        .filter(|s| !matched.iter().any(|m| m.eq_ignore_ascii_case(s)))
+        .cloned()
+        .collect();
+
+    if !job_title.trim().is_empty() {
+        document.headline = Some(job_title.trim().to_string());
+    }
+
+    TailoringSummary {
+        matched_skills: matched,
+        missing_skills,
+        highlighted_entries,
+    }
+}
+
+/// Sorts entries by how many required skills they mention (stable, so ties
+/// keep the user's order) and moves matching bullet points first.
+///
+/// Returns the titles of entries that mention at least one required skill.
+fn reorder_entries(entries: &mut [CvEntry], required_skills: &[String]) -> Vec<String> {
+    let mut scored: Vec<(usize, CvEntry)> = entries
+        .iter()
+        .map(|entry| {
+            let text = format!("{}\n{}", entry.title, entry.description.as_deref().unwrap_or_default());
+            (skills_mentioned(&text, required_skills).len(), entry.clone())
+        })
+        .collect();
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+
+    let mut highlighted = Vec::new();
+    for (slot, (score, mut entry)) in entries.iter_mut().zip(scored) {
+        if score > 0 {
+            highlighted.push(entry.title.clone());
+            let points = description_points(&entry);
+            if points.len() > 1 {
+                let (relevant, other): (Vec<String>, Vec<String>) = points
+                    .into_iter()
+                    .partition(|p| !skills_mentioned(p, required_skills).is_empty());
+                if !relevant.is_empty() && !other.is_empty() {
+                    entry.description = Some([relevant, other].concat().join("\n"));
+                }
+            }
+        }
+        *slot = entry;
+    }
+    highlighted
+}
@@ -35,10 +35,26 @@ pub enum AppError {
     #[error("Unauthorized")]
     Unauthorized,
     
+    /// Authenticated user is not allowed to perform the action
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+    
     /// Requested resource not found
     #[error("Not found")]
     NotFound,
     
+    /// The resource changed since the client read it (`If-Match` mismatch)
+    #[error("Precondition failed: {0}")]
+    PreconditionFailed(String),
+    
+    /// The request conflicts with the resource's current state
+    #[error("Conflict: {0}")]
+    Conflict(String),
+    
+    /// The user must wait before repeating the action
+    #[error("Too many requests: {0}")]
+    TooManyRequests(String),
+    
     /// Bad request with custom message
     #[error("{0}")]
     BadRequest(String),
@@ -50,6 +66,10 @@ pub enum AppError {
     /// External service error (e.g., AI API)
     #[error("External service error: {0}")]
     ExternalServiceError(String),
+    
+    /// Uploaded file was rejected; the response carries a stable error code
+    #[error("Upload rejected: {0}")]
+    UploadRejected(#[from] crate::cv::sandbox::UploadRejection),
 }
 
 impl IntoResponse for AppError {
@@ -61,10 +81,15 @@ impl IntoResponse for AppError {
             AppError::ValidationError(msg) => debug!("Validation error: {}", msg),
             AppError::ValidatorErrors(_) => debug!("Validation errors: {:?}", self),
             AppError::Unauthorized => debug!("Unauthorized access attempt"),
+            AppError::Forbidden(msg) => debug!("Forbidden: {}", msg),
             AppError::NotFound => debug!("Resource not found"),
+            AppError::PreconditionFailed(msg) => debug!("Precondition failed: {}", msg),
+            AppError::Conflict(msg) => debug!("Conflict: {}", msg),
+            AppError::TooManyRequests(msg) => debug!("Too many requests: {}", msg),
             AppError::BadRequest(msg) => warn!("Bad request: {}", msg),
             AppError::ConfigurationError(msg) => error!("Configuration error: {}", msg),
             AppError::ExternalServiceError(msg) => error!("External service error: {}", msg),
+            AppError::UploadRejected(reason) => warn!("Upload rejected ({}): {}", reason.code(), reason),
             AppError::DatabaseError(err) => {
                 // Check if it's a user error (like duplicate key) vs system error
                 if let Some(db_err) = err.as_database_error() {
@@ -97,21 +122,23 @@ impl IntoResponse for AppError {
             ),
             
             AppError::DatabaseError(err) => {
-                if let Some(db_err) = err.as_database_error() {
-                    if db_err.is_unique_violation() {
-                        // Check which constraint was violated for better error messages
-                        let constraint = db_err.constraint().unwrap_or("");
-                        let message = if constraint.contains("email") {
-                            "An account with this email already exists. Please login or use a different email."
-                        } else {
-                            "A record with this information already exists."
-                        };
-                        
-                        return (
-                            StatusCode::CONFLICT,
-                            Json(json!({"error": message}))
-                        ).into_response();
-                    }
+                if let Some(db_err) = err.as_database_error()
+                    && db_err.is_unique_violation()
+                {
+                    // Check which constraint was violated for better error messages
+                    let constraint = db_err.constraint().unwrap_or("");
+                    let message = if constraint.contains("email") {
+                        "An account with this email already exists. Please login or use a different email."
+                    } else if constraint.contains("slug") {
+                        "This profile URL is already taken. Please choose a different slug."
+                    } else {
+                        "A record with this information already exists."
+                    };
+
+                    return (
+                        StatusCode::CONFLICT,
+                        Json(json!({"error": message}))
+                    ).into_response();
                 }
                 
                 (
@@ -125,11 +152,31 @@ impl IntoResponse for AppError {
                 json!({"error": "Unauthorized"})
             ),
             
+            AppError::Forbidden(msg) => (
+                StatusCode::FORBIDDEN,
+                json!({"error": msg})
+            ),
+            
             AppError::NotFound => (
                 StatusCode::NOT_FOUND,
                 json!({"error": "Not found"})
             ),
             
+            AppError::PreconditionFailed(msg) => (
+                StatusCode::PRECONDITION_FAILED,
+                json!({"error": msg})
+            ),
+            
+            AppError::Conflict(msg) => (
+                StatusCode::CONFLICT,
+                json!({"error": msg})
+            ),
+            
+            AppError::TooManyRequests(msg) => (
+                StatusCode::TOO_MANY_REQUESTS,
+                json!({"error": msg})
+            ),
+            
             AppError::BadRequest(msg) => (
                 StatusCode::BAD_REQUEST,
                 json!({"error": msg})
@@ -143,12 +190,53 @@ impl IntoResponse for AppError {
             AppError::ExternalServiceError(msg) => (
                 StatusCode::BAD_GATEWAY,
                 json!({"error": msg})
-            )
+            ),
+            
+            AppError::UploadRejected(reason) => {
+                use crate::cv::sandbox::UploadRejection;
+
+                let status = match reason {
+                    UploadRejection::MissingFile | UploadRejection::EmptyFile => StatusCode::BAD_REQUEST,
+                    UploadRejection::FileTooLarge { .. } => StatusCode::PAYLOAD_TOO_LARGE,
+                    UploadRejection::UnsupportedFileType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                    UploadRejection::CorruptFile
+                    | UploadRejection::NoExtractableText
+                    | UploadRejection::OcrFailed
+                    | UploadRejection::ExtractionTimeout
+                    | UploadRejection::ExtractionResourceLimit => StatusCode::UNPROCESSABLE_ENTITY,
+                    UploadRejection::OcrUnavailable | UploadRejection::ExtractionBusy => {
+                        StatusCode::SERVICE_UNAVAILABLE
+                    }
+                    UploadRejection::ExtractionFailed => StatusCode::INTERNAL_SERVER_ERROR,
+                };
+                (status, json!({"error": reason.to_string(), "code": reason.code()}))
+            }
         };
 
         (status, Json(error_message)).into_response()
     }
 }
 
+impl From<crate::cv::extract::ExtractError> for AppError {
+    fn from(err: crate::cv::extract::ExtractError) -> Self {
+        AppError::UploadRejected(err.into())
+    }
+}
+
+impl From<crate::cv::storage::StorageError> for AppError {
+    fn from(err: crate::cv::storage::StorageError) -> Self {
+        use crate::cv::storage::StorageError;
+
+        match err {
+            StorageError::NotFound => AppError::NotFound,
+            StorageError::Config(msg) => AppError::ConfigurationError(msg),
+            other => {
+                tracing::error!("CV storage error: {}", other);
+                AppError::InternalServerError
+            }
+        }
+    }
+}
+
 /// Result type alias for application operations.
 pub type AppResult<T> = Result<T, AppError>;
\ No newline at end of file
@@ -0,0 +1,286 @@
+//! Real-time events.
+//!
+//! Events for a user (a new notification, an application update, a finished
+//! AI job) are published with Postgres `NOTIFY` on [`EVENTS_CHANNEL`]. Every
+//! backend instance listens on the channel and hands events to its connected
+//! clients through an [`EventBus`], so a client receives events no matter
+//! which instance behind the load balancer produced them.
+//!
+//! The bus numbers each user's events, so a client that falls behind and
+//! has events dropped learns whether any of its own were among them.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::PgPool;
+use sqlx::postgres::PgListener;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::broadcast::{
+    self,
+    error::{RecvError, TryRecvError},
+};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// Postgres channel events are published on.
+pub const EVENTS_CHANNEL: &str = "careerbridge_events";
+
+/// Largest `NOTIFY` payload sent; Postgres rejects payloads from 8000 bytes.
+const MAX_PAYLOAD_BYTES: usize = 7900;
+
+/// Events buffered per instance for slow clients before they miss some.
+const BUS_CAPACITY: usize = 1024;
+
+/// Wait before listening again after the connection to Postgres is lost.
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Kind of event pushed to clients.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    /// A notification was created; data is the notification
+    Notification,
+    /// An application was created or its status changed
+    ApplicationUpdated,
+    /// A long-running AI job (roadmap, cover letter, CV enrichment) finished
+    AiJobCompleted,
+    /// A long-running AI job ran out of attempts or failed permanently
+    AiJobFailed,
+}
+
+impl EventKind {
+    /// Event name sent to clients.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EventKind::Notification => "notification",
+            EventKind::ApplicationUpdated => "application_updated",
+            EventKind::AiJobCompleted => "ai_job_completed",
+            EventKind::AiJobFailed => "ai_job_failed",
+        }
+    }
+}
+
+/// An event for one user.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Event {
+    /// User the event is delivered to
+    pub user_id: Uuid,
+    /// Kind of event
+    pub kind: EventKind,
+    /// Event data; `null` when it was too large to publish, in which case
+    /// clients fetch the resource instead
+    pub data: Value,
+}
+
+impl Event {
+    /// `NOTIFY` payload for the event, dropping the data if it is too large.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use backend::events::{Event, EventKind};
+    /// use serde_json::json;
+    /// use uuid::Uuid;
+    ///
+    /// let mut event = Event { user_id: Uuid::nil(), kind: EventKind::Notification, data: json!({"id": 1}) };
+    /// assert!(event.payload().contains(r#""data":{"id":1}"#));
+    ///
+    /// event.data = json!({"message": "x".repeat(10_000)});
+    /// assert!(event.payload().contains(r#""data":null"#));
+    /// ```
+    pub fn payload(&self) -> String {
+        let payload = serde_json::to_string(self).unwrap_or_default();
+        if payload.len() <= MAX_PAYLOAD_BYTES {
+            return payload;
+        }
+        serde_json::to_string(&Event { data: Value::Null, ..self.clone() }).unwrap_or_default()
+    }
+}
+
+/// Publishes an event to every backend instance.
+///
+/// # Errors
+///
+/// Returns an error if a database operation fails.
+pub async fn publish(pool: &PgPool, user_id: Uuid, kind: EventKind, data: Value) -> Result<(), sqlx::Error> {
+    let payload = Event { user_id, kind, data }.payload();
+    sqlx::query!("SELECT pg_notify($1, $2)", EVENTS_CHANNEL, payload)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Publishes an event, logging instead of failing: events are a convenience
+/// on top of the stored data, so a lost one must not fail the request.
+pub async fn publish_or_warn(pool: &PgPool, user_id: Uuid, kind: EventKind, data: Value) {
+    if let Err(e) = publish(pool, user_id, kind, data).await {
+        warn!("Failed to publish {} event for user {}: {}", kind.as_str(), user_id, e);
+    }
+}
+
+/// Publishes [`EventKind::AiJobCompleted`] for background job `job_id`, an
+/// AI job of kind `job` (`roadmap`, `cover_letter`, `cv_enrichment`) whose
+/// result is at `link`.
+pub async fn publish_ai_job_completed(pool: &PgPool, user_id: Uuid, job_id: i64, job: &str, link: &str) {
+    let data = serde_json::json!({ "job_id": job_id, "job": job, "link": link });
+    publish_or_warn(pool, user_id, EventKind::AiJobCompleted, data).await;
+}
+
+/// Publishes [`EventKind::AiJobFailed`] for background job `job_id`, an AI
+/// job of kind `job` that died with `error`.
+pub async fn publish_ai_job_failed(pool: &PgPool, user_id: Uuid, job_id: i64, job: &str, error: &str) {
+    let data = serde_json::json!({ "job_id": job_id, "job": job, "error": error });
+    publish_or_warn(pool, user_id, EventKind::AiJobFailed, data).await;
+}
+
+/// An event handed to this instance's clients.
+#[derive(Debug, Clone)]
+struct Delivery {
+    /// Events for the user handed out by this instance, this one included
+    seq: u64,
+    event: Event,
+}
+
+/// Hands events received from Postgres to this instance's clients.
+#[derive(Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<Delivery>,
+    /// Events handed out per user, the last [`Delivery::seq`]
+    delivered: Arc<Mutex<HashMap<Uuid, u64>>>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventBus {
+    /// Creates a bus with no listener; see [`EventBus::listen`].
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(BUS_CAPACITY);
+        EventBus { sender, delivered: Arc::default() }
+    }
+
+    /// Receives the user's events from now on.
+    pub fn subscribe(&self, user_id: Uuid) -> Subscription {
+        // Under the lock, so no event for the user is sent in between
+        let delivered = self.delivered.lock().unwrap_or_else(|e| e.into_inner());
+        Subscription {
+            receiver: self.sender.subscribe(),
+            delivered: Arc::clone(&self.delivered),
+            user_id,
+            seen: delivered.get(&user_id).copied().unwrap_or(0),
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Listens on [`EVENTS_CHANNEL`] in the background and forwards events
+    /// to subscribers, reconnecting when the connection is lost.
+    pub fn listen(&self, pool: PgPool) {
+        let bus = self.clone();
+        tokio::spawn(async move {
+            loop {
+                if let Err(e) = forward_events(&pool, &bus).await {
+                    error!("Event listener failed, retrying in {}s: {}", RECONNECT_DELAY.as_secs(), e);
+                }
+                tokio::time::sleep(RECONNECT_DELAY).await;
+            }
+        });
+    }
+}
+
+async fn forward_events(pool: &PgPool, bus: &EventBus) -> Result<(), sqlx::Error> {
+    let mut listener = PgListener::connect_with(pool).await?;
+    listener.listen(EVENTS_CHANNEL).await?;
+    info!("Listening for events on channel {}", EVENTS_CHANNEL);
+
+    loop {
+        let notification = listener.recv().await?;
+        match serde_json::from_str::<Event>(notification.payload()) {
+            Ok(event) => {
+                let mut delivered = bus.delivered.lock().unwrap_or_else(|e| e.into_inner());
+                let seq = delivered.entry(event.user_id).or_default();
+                *seq += 1;
+                // No subscribers is not an error: nobody is connected to this instance
+                let _ = bus.sender.send(Delivery { seq: *seq, event });
+            }
+            Err(e) => warn!("Ignoring malformed event: {}", e),
+        }
+    }
+}
+
+/// What a [`Subscription`] received.
+#[derive(Debug)]
+pub enum Received {
+    /// An event for the user
+    Event(Event),
+    /// The client fell behind and this many of the user's events were dropped
+    Lagged(u64),
+}
+
+/// A user's events from an [`EventBus`].
+pub struct Subscription {
+    receiver: broadcast::Receiver<Delivery>,
+    delivered: Arc<Mutex<HashMap<Uuid, u64>>>,
+    user_id: Uuid,
+    /// Last [`Delivery::seq`] received or counted as dropped
+    seen: u64,
+    /// The user's events still buffered when the client fell behind
+    pending: VecDeque<Event>,
+}
+
+impl Subscription {
+    /// Next event for the user, or `None` once the bus is gone.
+    ///
+    /// When the client fell behind, events for other users that were
+    /// dropped are skipped silently; [`Received::Lagged`] is returned only
+    /// if some of the user's own were dropped.
+    pub async fn recv(&mut self) -> Option<Received> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Some(Received::Event(event));
+            }
+            match self.receiver.recv().await {
+                Ok(delivery) if delivery.event.user_id == self.user_id => {
+                    self.seen = delivery.seq;
+                    return Some(Received::Event(delivery.event));
+                }
+                Ok(_) => continue,
+                Err(RecvError::Lagged(_)) => {
+                    let missed = self.catch_up();
+                    if missed > 0 {
+                        return Some(Received::Lagged(missed));
+                    }
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+
+    /// Buffers the user's events still in the channel after falling behind
+    /// and returns how many were dropped: those handed out before now that
+    /// are neither received nor buffered.
+    fn catch_up(&mut self) -> u64 {
+        let delivered = {
+            let delivered = self.delivered.lock().unwrap_or_else(|e| e.into_inner());
+            delivered.get(&self.user_id).copied().unwrap_or(0)
+        };
+        let mut missed = 0;
+        loop {
+            match self.receiver.try_recv() {
+                Ok(delivery) if delivery.event.user_id == self.user_id => {
+                    missed += delivery.seq.saturating_sub(self.seen + 1);
+                    self.seen = delivery.seq;
+                    self.pending.push_back(delivery.event);
+                }
+                Ok(_) | Err(TryRecvError::Lagged(_)) => continue,
+                Err(TryRecvError::Empty | TryRecvError::Closed) => break,
+            }
+        }
+        missed += delivered.saturating_sub(self.seen);
+        self.seen = self.seen.max(delivered);
+        missed
+    }
+}
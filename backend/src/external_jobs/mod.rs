@@ -0,0 +1,801 @@
+//! External job listings.
+//!
+//! Listings from the external sources configured for the deployment (see
+//! [`sources`]) are fetched by a periodic import
+//! ([`crate::queue::Task::ImportExternalJobs`]) and stored in `external_jobs`,
+//! so requests read them from the database instead of calling the sources.
+//!
+//! - A listing is identified by its source and source ID; importing it again
+//!   updates it.
+//! - A listing from another source (or with a new ID) whose [`fuzzy_key`]
+//!   matches a stored listing is a duplicate and only keeps that one alive.
+//! - A listing expires when no import has seen it for `EXTERNAL_JOB_TTL_DAYS`
+//!   (default 7) or `EXTERNAL_JOB_MAX_AGE_DAYS` (default 60) after it was
+//!   posted, and is then deleted.
+//! - Job type, experience level and salary are stored as published and
+//!   normalized (see [`normalize`]), with the known skills the description
+//!   mentions.
+//!
+//! Every import records in `external_job_sources` when each source was last
+//! fetched and last fetched successfully, so listings can say whether a
+//! source is live, served from earlier imports or unavailable
+//! ([`SourceHealth::state`]).
+
+pub mod normalize;
+pub mod sources;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use crate::models::{ExperienceLevel, JobType};
+use normalize::NormalizedFields;
+use serde::{Deserialize, Serialize};
+use sources::JobSource;
+use sqlx::{FromRow, PgPool};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, error, info};
+
+/// Time allowed for one request to a source.
+const FETCH_TIMEOUT_SECS: u64 = 30;
+
+/// Default interval of the import.
+const DEFAULT_IMPORT_INTERVAL_SECS: u64 = 3600;
+
+/// Default days a listing stays after the last import that saw it.
+const DEFAULT_TTL_DAYS: i64 = 7;
+
+/// Default days a listing stays after its posting date.
+const DEFAULT_MAX_AGE_DAYS: i64 = 60;
+
+/// Most listings a search considers before scoring and paging.
+const MAX_SEARCH_CANDIDATES: i64 = 1000;
+
+/// Words left out of company names in [`fuzzy_key`]: legal suffixes and the
+/// country, which sources add or omit ("UNICEF Bangladesh").
+const COMPANY_NOISE_WORDS: [&str; 9] = ["the", "ltd", "limited", "inc", "plc", "llc", "co", "company", "bangladesh"];
+
+/// Columns of [`StoredExternalJob`].
+///
+/// Queries using them are built at runtime: `query_as!` cannot fill the
+/// flattened listing and normalized fields.
+const EXTERNAL_JOB_COLUMNS: &str = "id, source, source_id, category, title, company, location, description, url, \
+    job_type, experience_level, skills, salary, posted_date, first_seen_at, last_seen_at, expires_at, \
+    normalized_job_type, normalized_experience_level, salary_min, salary_max, salary_currency, salary_period, \
+    extracted_skills";
+
+/// A job listing from an external source.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ExternalJob {
+    /// ID at the source, e.g. `reliefweb_4125371`
+    #[sqlx(rename = "source_id")]
+    pub id: String,
+    pub title: String,
+    pub company: String,
+    pub location: String,
+    pub description: String,
+    /// Where to read and apply
+    pub url: String,
+    /// Posting date as given by the source
+    pub posted_date: String,
+    /// Source name, e.g. `ReliefWeb`
+    pub source: String,
+    pub job_type: Option<String>,
+    pub experience_level: Option<String>,
+    pub skills: Vec<String>,
+    pub salary: Option<String>,
+}
+
+/// Kind of external source, used to list NGO, government and local job
+/// board listings separately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SourceCategory {
+    /// UN agencies and NGOs (ReliefWeb)
+    Ngo,
+    /// Government job portals
+    Government,
+    /// Local job boards
+    Local,
+}
+
+impl SourceCategory {
+    /// Value stored in `external_jobs.category`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SourceCategory::Ngo => "ngo",
+            SourceCategory::Government => "government",
+            SourceCategory::Local => "local",
+        }
+    }
+}
+
+/// Listings fetched from one source.
+#[derive(Debug, Clone)]
+pub struct FetchedBatch {
+    /// Name of the source
+    pub source: String,
+    /// Category of the source
+    pub category: SourceCategory,
+    /// Fetched listings
+    pub jobs: Vec<ExternalJob>,
+}
+
+/// A source whose fetch failed.
+#[derive(Debug, Clone)]
+pub struct FailedFetch {
+    /// Name of the source
+    pub source: String,
+    /// Why the fetch failed
+    pub error: String,
+}
+
+/// Outcome of fetching every source.
+#[derive(Debug, Clone, Default)]
+pub struct FetchReport {
+    /// Listings of the sources fetched successfully
+    pub batches: Vec<FetchedBatch>,
+    /// Sources that failed
+    pub failures: Vec<FailedFetch>,
+}
+
+/// Listings from all `sources`, fetched concurrently; a failing source is
+/// reported instead.
+pub async fn fetch_all_external_jobs(sources: &[Arc<dyn JobSource>]) -> FetchReport {
+    let client = match reqwest::Client::builder()
+        .user_agent("CareerBridge/1.0")
+        .timeout(Duration::from_secs(FETCH_TIMEOUT_SECS))
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => {
+            error!("Failed to build HTTP client for external jobs: {}", e);
+            return FetchReport {
+                batches: Vec::new(),
+                failures: sources
+                    .iter()
+                    .map(|source| FailedFetch { source: source.name().to_string(), error: e.to_string() })
+                    .collect(),
+            };
+        }
+    };
+
+    let results = futures_util::future::join_all(sources.iter().map(|source| source.fetch(&client))).await;
+    let mut report = FetchReport::default();
+    for (source, result) in sources.iter().zip(results) {
+        match result {
+            Ok(jobs) => {
+                debug!("Fetched {} listings from {}", jobs.len(), source.name());
+                report.batches.push(FetchedBatch {
+                    source: source.name().to_string(),
+                    category: source.category(),
+                    jobs,
+                });
+            }
+            Err(e) => {
+                error!("Failed to fetch external jobs from {}: {}", source.name(), e);
+                report.failures.push(FailedFetch { source: source.name().to_string(), error: e.to_string() });
+            }
+        }
+    }
+    report
+}
+
+/// Records the configured sources in `external_job_sources`; sources no
+/// longer configured are kept for their history but marked as such, and the
+/// listings of sample sources no longer configured are deleted.
+///
+/// # Errors
+///
+/// Returns an error if a database operation fails.
+pub async fn register_sources(pool: &PgPool, sources: &[Arc<dyn JobSource>]) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    for source in sources {
+        sqlx::query!(
+            r#"
+            INSERT INTO external_job_sources (name, kind, category, configured)
+            VALUES ($1, $2, $3, TRUE)
+            ON CONFLICT (name) DO UPDATE
+            SET kind = EXCLUDED.kind, category = EXCLUDED.category, configured = TRUE,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+            source.name(),
+            source.kind(),
+            source.category().as_str()
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    let names: Vec<String> = sources.iter().map(|source| source.name().to_string()).collect();
+    sqlx::query!(
+        r#"
+        UPDATE external_job_sources
+        SET configured = FALSE, updated_at = CURRENT_TIMESTAMP
+        WHERE configured AND NOT (name = ANY($1))
+        "#,
+        &names
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let removed = sqlx::query!(
+        r#"
+        DELETE FROM external_jobs
+        WHERE source IN (SELECT name FROM external_job_sources WHERE kind = 'sample' AND NOT configured)
+        "#
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+    if removed > 0 {
+        info!("Deleted {} sample external listing(s) of sources no longer imported", removed);
+    }
+    tx.commit().await
+}
+
+/// A stored external listing.
+#[derive(Debug, Clone, FromRow)]
+pub struct StoredExternalJob {
+    /// Row ID
+    pub id: i32,
+    /// The listing
+    #[sqlx(flatten)]
+    pub listing: ExternalJob,
+    /// "ngo", "government" or "local"
+    pub category: String,
+    /// When an import first stored the listing
+    pub first_seen_at: DateTime<Utc>,
+    /// When an import last saw the listing
+    pub last_seen_at: DateTime<Utc>,
+    /// When the listing expires unless seen again
+    pub expires_at: DateTime<Utc>,
+    /// Published fields mapped to the internal types
+    #[sqlx(flatten)]
+    pub normalized: NormalizedFields,
+}
+
+impl StoredExternalJob {
+    /// Skills given by the source, then those found in its description.
+    pub fn skills(&self) -> Vec<String> {
+        let mut skills = self.listing.skills.clone();
+        skills.extend(self.normalized.extracted_skills.iter().cloned());
+        skills
+    }
+}
+
+/// Key identifying the same posting across sources: the title's words in
+/// any order, the company without legal suffixes and the city.
+///
+/// # Example
+///
+/// ```
+/// use backend::external_jobs::fuzzy_key;
+///
+/// assert_eq!(
+///     fuzzy_key("WASH Engineer", "Action Against Hunger Ltd.", "Khulna, Bangladesh"),
+///     fuzzy_key("Engineer - WASH", "Action Against Hunger", "Khulna"),
+/// );
+/// assert_ne!(
+///     fuzzy_key("WASH Engineer", "Action Against Hunger", "Khulna"),
+///     fuzzy_key("WASH Engineer", "Action Against Hunger", "Sylhet"),
+/// );
+/// ```
+pub fn fuzzy_key(title: &str, company: &str, location: &str) -> String {
+    let words = |text: &str| -> Vec<String> {
+        text.to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(str::to_string)
+            .collect()
+    };
+    let mut title_words = words(title);
+    title_words.sort();
+    title_words.dedup();
+    let company_words: Vec<String> = words(company)
+        .into_iter()
+        .filter(|word| !COMPANY_NOISE_WORDS.contains(&word.as_str()))
+        .collect();
+    let city = words(location.split(',').next().unwrap_or_default());
+
+    format!("{}|{}|{}", title_words.join(" "), company_words.join(" "), city.join(" "))
+}
+
+/// Posting date of a listing: an RFC 3339 or RFC 2822 (RSS) timestamp, or a
+/// date (midnight UTC) such as `2026-10-12`, `12-10-2026`, `12/10/2026`,
+/// `12 Oct 2026` or `Oct 12, 2026`.
+///
+/// # Example
+///
+/// ```
+/// use backend::external_jobs::parse_posted_date;
+///
+/// let date = parse_posted_date("2026-10-12").unwrap();
+/// assert_eq!(parse_posted_date("12-10-2026"), Some(date));
+/// assert_eq!(parse_posted_date("12 October 2026"), Some(date));
+/// assert_eq!(parse_posted_date("Oct 12, 2026"), Some(date));
+/// assert!(parse_posted_date("Mon, 12 Oct 2026 09:30:00 +0600").is_some());
+/// assert_eq!(parse_posted_date("soon"), None);
+/// ```
+pub fn parse_posted_date(posted_date: &str) -> Option<DateTime<Utc>> {
+    const DATE_FORMATS: [&str; 6] = ["%d-%m-%Y", "%d/%m/%Y", "%d %b %Y", "%d %B %Y", "%b %d, %Y", "%B %d, %Y"];
+
+    let posted_date = posted_date.trim();
+    if let Ok(date) = DateTime::parse_from_rfc3339(posted_date).or_else(|_| DateTime::parse_from_rfc2822(posted_date)) {
+        return Some(date.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(posted_date.get(..10).unwrap_or(""), "%Y-%m-%d")
+        .ok()
+        .or_else(|| {
+            DATE_FORMATS
+                .iter()
+                .find_map(|format| NaiveDate::parse_from_str(posted_date, format).ok())
+        })
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(|d| d.and_utc())
+}
+
+/// Import interval from `EXTERNAL_JOBS_IMPORT_INTERVAL_SECS` (default one hour).
+pub fn import_interval() -> Duration {
+    let secs = std::env::var("EXTERNAL_JOBS_IMPORT_INTERVAL_SECS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|secs| *secs > 0)
+        .unwrap_or(DEFAULT_IMPORT_INTERVAL_SECS);
+    Duration::from_secs(secs)
+}
+
+fn days_from_env(name: &str, default: i64) -> chrono::Duration {
+    let days = std::env::var(name)
+        .ok()
+        .and_then(|v| v.parse::<i64>().ok())
+        .filter(|days| *days > 0)
+        .unwrap_or(default);
+    chrono::Duration::days(days)
+}
+
+/// Outcome of an import.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ImportSummary {
+    /// New listings stored
+    pub inserted: usize,
+    /// Stored listings seen again under the same source ID
+    pub updated: usize,
+    /// Listings matching a stored listing from another source or ID
+    pub duplicates: usize,
+    /// Listings posted too long ago to store
+    pub stale: usize,
+    /// Stored listings that expired and were deleted
+    pub expired: usize,
+    /// Sources whose fetch failed; their stored listings are kept
+    pub failed: usize,
+}
+
+/// Stores fetched listings, deduplicating them, deletes expired ones and
+/// records the outcome of each source's fetch.
+///
+/// # Errors
+///
+/// Returns an error if a database operation fails; nothing is stored then.
+pub async fn import_external_jobs(pool: &PgPool, report: &FetchReport) -> Result<ImportSummary, sqlx::Error> {
+    let ttl = days_from_env("EXTERNAL_JOB_TTL_DAYS", DEFAULT_TTL_DAYS);
+    let max_age = days_from_env("EXTERNAL_JOB_MAX_AGE_DAYS", DEFAULT_MAX_AGE_DAYS);
+    let now: DateTime<Utc> = sqlx::query_scalar!(r#"SELECT CURRENT_TIMESTAMP AS "now!""#).fetch_one(pool).await?;
+    let vocabulary = normalize::load_skill_vocabulary(pool).await?;
+    let mut summary = ImportSummary { failed: report.failures.len(), ..ImportSummary::default() };
+
+    let mut tx = pool.begin().await?;
+    for batch in &report.batches {
+        sqlx::query!(
+            r#"
+            UPDATE external_job_sources
+            SET last_attempt_at = $2, last_success_at = $2, last_error = NULL, last_fetched = $3,
+                updated_at = CURRENT_TIMESTAMP
+            WHERE name = $1
+            "#,
+            &batch.source,
+            now,
+            batch.jobs.len() as i32
+        )
+        .execute(&mut *tx)
+        .await?;
+        for job in &batch.jobs {
+            let posted_at = parse_posted_date(&job.posted_date);
+            let expires_at = match posted_at {
+                Some(posted_at) => (now + ttl).min(posted_at + max_age),
+                None => now + ttl,
+            };
+            if expires_at <= now {
+                summary.stale += 1;
+                continue;
+            }
+            let key = fuzzy_key(&job.title, &job.company, &job.location);
+            let normalized = normalize::normalize(job, &vocabulary);
+
+            let updated: Option<i32> = sqlx::query_scalar!(
+                r#"
+                UPDATE external_jobs
+                SET category = $3, dedup_key = $4, title = $5, company = $6, location = $7,
+                    description = $8, url = $9, job_type = $10, experience_level = $11, skills = $12,
+                    salary = $13, posted_date = $14, posted_at = $15, last_seen_at = $16,
+                    expires_at = $17, normalized_job_type = $18, normalized_experience_level = $19,
+                    salary_min = $20, salary_max = $21, salary_currency = $22, salary_period = $23,
+                    extracted_skills = $24, updated_at = CURRENT_TIMESTAMP
+                WHERE source = $1 AND source_id = $2
+                RETURNING id
+                "#,
+                &job.source,
+                &job.id,
+                batch.category.as_str(),
+                &key,
+                &job.title,
+                &job.company,
+                &job.location,
+                &job.description,
+                &job.url,
+                job.job_type.as_deref(),
+                job.experience_level.as_deref(),
+                &job.skills,
+                job.salary.as_deref(),
+                &job.posted_date,
+                posted_at,
+                now,
+                expires_at,
+                normalized.job_type as _,
+                normalized.experience_level as _,
+                normalized.salary.min,
+                normalized.salary.max,
+                normalized.salary.currency.as_deref(),
+                normalized.salary.period as _,
+                &normalized.extracted_skills
+            )
+            .fetch_optional(&mut *tx)
+            .await?;
+            if updated.is_some() {
+                summary.updated += 1;
+                continue;
+            }
+
+            let duplicate_of: Option<i32> = sqlx::query_scalar!(
+                r#"
+                UPDATE external_jobs
+                SET last_seen_at = $2, expires_at = GREATEST(expires_at, $3), updated_at = CURRENT_TIMESTAMP
+                WHERE id = (
+                    SELECT id FROM external_jobs
+                    WHERE dedup_key = $1 AND expires_at > $2
+                    ORDER BY first_seen_at, id
+                    LIMIT 1
+                )
+                RETURNING id
+                "#,
+                &key,
+                now,
+                expires_at
+            )
+            .fetch_optional(&mut *tx)
+            .await?;
+            if let Some(original) = duplicate_of {
+                debug!("{} listing {} duplicates external job {}", job.source, job.id, original);
+                summary.duplicates += 1;
+                continue;
+            }
+
+            sqlx::query!(
+                r#"
+                INSERT INTO external_jobs
+                    (source, source_id, category, dedup_key, title, company, location, description, url,
+                     job_type, experience_level, skills, salary, posted_date, posted_at,
+                     first_seen_at, last_seen_at, expires_at, normalized_job_type, normalized_experience_level,
+                     salary_min, salary_max, salary_currency, salary_period, extracted_skills)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $16, $17,
+                        $18, $19, $20, $21, $22, $23, $24)
+                "#,
+                &job.source,
+                &job.id,
+                batch.category.as_str(),
+                &key,
+                &job.title,
+                &job.company,
+                &job.location,
+                &job.description,
+                &job.url,
+                job.job_type.as_deref(),
+                job.experience_level.as_deref(),
+                &job.skills,
+                job.salary.as_deref(),
+                &job.posted_date,
+                posted_at,
+                now,
+                expires_at,
+                normalized.job_type as _,
+                normalized.experience_level as _,
+                normalized.salary.min,
+                normalized.salary.max,
+                normalized.salary.currency.as_deref(),
+                normalized.salary.period as _,
+                &normalized.extracted_skills
+            )
+            .execute(&mut *tx)
+            .await?;
+            summary.inserted += 1;
+        }
+    }
+
+    for failure in &report.failures {
+        sqlx::query!(
+            r#"
+            UPDATE external_job_sources
+            SET last_attempt_at = $2, last_error = $3, updated_at = CURRENT_TIMESTAMP
+            WHERE name = $1
+            "#,
+            &failure.source,
+            now,
+            &failure.error
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    summary.expired = sqlx::query!("DELETE FROM external_jobs WHERE expires_at <= $1", now)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected() as usize;
+    tx.commit().await?;
+    Ok(summary)
+}
+
+/// Filters of an external job search; unset filters match every listing.
+#[derive(Debug, Default)]
+pub struct ExternalJobFilters<'a> {
+    /// Words that must all appear in the title, company, description or skills
+    pub keywords: Option<&'a str>,
+    /// Source category
+    pub category: Option<SourceCategory>,
+    /// Source name (case-insensitive)
+    pub source: Option<&'a str>,
+    /// Text the location must contain
+    pub location: Option<&'a str>,
+    /// Normalized job type
+    pub job_type: Option<JobType>,
+    /// Normalized experience level
+    pub experience_level: Option<ExperienceLevel>,
+    /// The listing must need at least one of these skills, given by the
+    /// source or found in the description
+    pub skills: &'a [String],
+    /// Lowercase source names, any of which matches; empty for all
+    pub sources: &'a [String],
+    /// Most recently posted listings kept per source; all when unset
+    pub per_source_limit: Option<i64>,
+}
+
+/// Current listings passing `filters`, most recently posted first.
+///
+/// # Errors
+///
+/// Returns an error if a database operation fails.
+pub async fn search_external_jobs(
+    pool: &PgPool,
+    filters: &ExternalJobFilters<'_>,
+) -> Result<Vec<StoredExternalJob>, sqlx::Error> {
+    let lower = |value: Option<&str>| {
+        value
+            .map(|v| v.trim().to_lowercase())
+            .filter(|v| !v.is_empty())
+    };
+    let keywords: Vec<String> = filters
+        .keywords
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect();
+    let skills: Vec<String> = filters
+        .skills
+        .iter()
+        .map(|skill| skill.trim().to_lowercase())
+        .filter(|skill| !skill.is_empty())
+        .collect();
+
+    sqlx::query_as::<_, StoredExternalJob>(&format!(
+        r#"
+        SELECT {}
+        FROM (
+            SELECT *, ROW_NUMBER() OVER (
+                       PARTITION BY source ORDER BY COALESCE(posted_at, first_seen_at) DESC, id DESC
+                   ) AS source_rank
+            FROM external_jobs
+            WHERE expires_at > CURRENT_TIMESTAMP
+              AND ($1::TEXT IS NULL OR category = $1)
+              AND ($2::TEXT IS NULL OR LOWER(source) = $2)
+              AND ($3::TEXT IS NULL OR STRPOS(LOWER(location), $3) > 0)
+              AND ($4::job_type IS NULL OR normalized_job_type = $4)
+              AND ($5::experience_level IS NULL OR normalized_experience_level = $5)
+              AND (CARDINALITY($6::TEXT[]) = 0 OR EXISTS (
+                    SELECT 1 FROM UNNEST(skills || extracted_skills) AS skill WHERE LOWER(skill) = ANY($6)))
+              AND NOT EXISTS (
+                    SELECT 1 FROM UNNEST($7::TEXT[]) AS keyword
+                    WHERE STRPOS(LOWER(title || ' ' || company || ' ' || description || ' '
+                                       || ARRAY_TO_STRING(skills, ' ')), keyword) = 0)
+              AND (CARDINALITY($9::TEXT[]) = 0 OR LOWER(source) = ANY($9))
+        ) ranked
+        WHERE ($10::BIGINT IS NULL OR source_rank <= $10)
+        ORDER BY COALESCE(posted_at, first_seen_at) DESC, id DESC
+        LIMIT $8
+        "#,
+        EXTERNAL_JOB_COLUMNS
+    ))
+    .bind(filters.category.map(SourceCategory::as_str))
+    .bind(lower(filters.source))
+    .bind(lower(filters.location))
+    .bind(filters.job_type)
+    .bind(filters.experience_level)
+    .bind(&skills)
+    .bind(&keywords)
+    .bind(MAX_SEARCH_CANDIDATES)
+    .bind(filters.sources)
+    .bind(filters.per_source_limit)
+    .fetch_all(pool)
+    .await
+}
+
+/// Current listings first stored in `(since, until]`.
+///
+/// # Errors
+///
+/// Returns an error if a database operation fails.
+pub async fn external_jobs_first_seen_between(
+    pool: &PgPool,
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> Result<Vec<StoredExternalJob>, sqlx::Error> {
+    sqlx::query_as::<_, StoredExternalJob>(&format!(
+        r#"
+        SELECT {}
+        FROM external_jobs
+        WHERE first_seen_at > $1 AND first_seen_at <= $2 AND expires_at > CURRENT_TIMESTAMP
+        "#,
+        EXTERNAL_JOB_COLUMNS
+    ))
+    .bind(since)
+    .bind(until)
+    .fetch_all(pool)
+    .await
+}
+
+/// Import intervals after which a source's last successful fetch no longer
+/// counts as live; allows for one import delayed or retried.
+const LIVE_IMPORT_INTERVALS: u32 = 2;
+
+/// Whether the listings of a source are current.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SourceState {
+    /// The last fetch succeeded, recently
+    Live,
+    /// The listings are from an earlier import: the last fetch failed or
+    /// imports stopped
+    Cached,
+    /// No listings: the source was never fetched successfully or its
+    /// listings expired
+    Unavailable,
+}
+
+/// Import history of a source, from `external_job_sources`.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct SourceHealth {
+    /// Source name
+    pub name: String,
+    /// Connector kind, e.g. `reliefweb`; `sample` sources only run in demo mode
+    pub kind: String,
+    /// "ngo", "government" or "local"
+    pub category: String,
+    /// Whether the source is in the current sources file
+    pub configured: bool,
+    /// When an import last fetched the source
+    pub last_attempt_at: Option<DateTime<Utc>>,
+    /// When a fetch last succeeded
+    pub last_success_at: Option<DateTime<Utc>>,
+    /// Error of the last fetch, if it failed
+    pub last_error: Option<String>,
+    /// Listings returned by the last successful fetch
+    pub last_fetched: Option<i32>,
+    /// Stored listings of the source that have not expired
+    pub current_listings: i64,
+    /// When an import last saw one of those listings
+    pub last_seen_at: Option<DateTime<Utc>>,
+}
+
+impl SourceHealth {
+    /// State of the source at `now`: live when it is configured and the last
+    /// fetch succeeded within two import intervals, otherwise cached while
+    /// stored listings remain and unavailable after.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use backend::external_jobs::{SourceHealth, SourceState};
+    /// use chrono::{Duration, Utc};
+    ///
+    /// let now = Utc::now();
+    /// let mut health = SourceHealth {
+    ///     name: "ReliefWeb".to_string(),
+    ///     kind: "reliefweb".to_string(),
+    ///     category: "ngo".to_string(),
+    ///     configured: true,
+    ///     last_attempt_at: Some(now - Duration::minutes(5)),
+    ///     last_success_at: Some(now - Duration::minutes(5)),
+    ///     last_error: None,
+    ///     last_fetched: Some(20),
+    ///     current_listings: 18,
+    ///     last_seen_at: Some(now - Duration::minutes(5)),
+    /// };
+    /// assert_eq!(health.state(now), SourceState::Live);
+    ///
+    /// // The next fetch failed: the stored listings are served
+    /// health.last_attempt_at = Some(now);
+    /// health.last_error = Some("Source returned status 503".to_string());
+    /// assert_eq!(health.state(now), SourceState::Cached);
+    /// assert_eq!(health.synced_at(), Some(now - Duration::minutes(5)));
+    ///
+    /// // ...until they expire
+    /// health.current_listings = 0;
+    /// assert_eq!(health.state(now), SourceState::Unavailable);
+    /// ```
+    pub fn state(&self, now: DateTime<Utc>) -> SourceState {
+        let live_for = chrono::Duration::from_std(import_interval() * LIVE_IMPORT_INTERVALS)
+            .unwrap_or_else(|_| chrono::Duration::hours(2));
+        let live = self.configured
+            && self.last_error.is_none()
+            && self.last_success_at.is_some_and(|at| now - at <= live_for);
+        if live {
+            SourceState::Live
+        } else if self.current_listings > 0 {
+            SourceState::Cached
+        } else {
+            SourceState::Unavailable
+        }
+    }
+
+    /// When the source's listings were last refreshed: the last successful
+    /// fetch, or for sources imported before fetches were recorded, the last
+    /// time one of its listings was seen.
+    pub fn synced_at(&self) -> Option<DateTime<Utc>> {
+        self.last_success_at.or(self.last_seen_at)
+    }
+
+    /// Whether the source serves built-in sample listings.
+    pub fn is_sample(&self) -> bool {
+        self.kind == "sample"
+    }
+}
+
+/// Import history of the sources, optionally of one category or source
+/// name (case-insensitive), configured sources first.
+///
+/// # Errors
+///
+/// Returns an error if a database operation fails.
+pub async fn source_health(
+    pool: &PgPool,
+    category: Option<SourceCategory>,
+    source: Option<&str>,
+    configured_only: bool,
+) -> Result<Vec<SourceHealth>, sqlx::Error> {
+    sqlx::query_as!(
+        SourceHealth,
+        r#"
+        SELECT s.name, s.kind, s.category, s.configured, s.last_attempt_at, s.last_success_at,
+               s.last_error, s.last_fetched, COUNT(j.id) AS "current_listings!",
+               MAX(j.last_seen_at) AS last_seen_at
+        FROM external_job_sources s
+        LEFT JOIN external_jobs j ON j.source = s.name AND j.expires_at > CURRENT_TIMESTAMP
+        WHERE ($1::TEXT IS NULL OR s.category = $1)
+          AND ($2::TEXT IS NULL OR LOWER(s.name) = $2)
+          AND (s.configured OR NOT $3)
+        GROUP BY s.name
+        ORDER BY s.configured DESC, s.name
+        "#,
+        category.map(SourceCategory::as_str),
+        source.map(|name| name.trim().to_lowercase()).filter(|name| !name.is_empty()),
+        configured_only
+    )
+    .fetch_all(pool)
+    .await
+}
@@ -0,0 +1,445 @@
+//! Normalization of external listings.
+//!
+//! Sources publish job types, experience levels and salaries as free text
+//! ("Contract", "Mid-Senior Level", "BDT 100,000 - 150,000"). The import maps
+//! them to the internal [`JobType`] and [`ExperienceLevel`], parses salaries
+//! into a [`SalaryRange`] and finds the skills a description mentions, so
+//! listings filter and score like internal jobs. The published values are
+//! kept alongside.
+
+use super::ExternalJob;
+use crate::ai_matching::{contains_word, skills_mentioned};
+use crate::models::{ExperienceLevel, JobType};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use std::sync::LazyLock;
+
+/// Skill names up to this long ("Go", "R", "C") are common words or letters,
+/// so they are only found written exactly as the skill.
+const SHORT_SKILL_LEN: usize = 3;
+
+/// Longest skill name considered when extracting skills.
+const MAX_SKILL_LEN: usize = 40;
+
+/// Amounts with an optional multiplier: `40,000`, `1.5 lakh`, `55k`.
+static AMOUNT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)(\d[\d,]*(?:\.\d+)?)\s*(k|lakhs?|lacs?|crores?|m|mn|million)?\b").expect("invalid amount pattern")
+});
+
+/// Currencies by the symbols and words sources use, in ISO 4217.
+const CURRENCIES: [(&str, &str); 14] = [
+    ("৳", "BDT"),
+    ("bdt", "BDT"),
+    ("tk", "BDT"),
+    ("taka", "BDT"),
+    ("us$", "USD"),
+    ("usd", "USD"),
+    ("$", "USD"),
+    ("eur", "EUR"),
+    ("€", "EUR"),
+    ("gbp", "GBP"),
+    ("£", "GBP"),
+    ("inr", "INR"),
+    ("₹", "INR"),
+    ("chf", "CHF"),
+];
+
+/// Words that mark a number next to them as a salary.
+const SALARY_WORDS: [&str; 7] = ["salary", "pay", "wage", "wages", "stipend", "remuneration", "ctc"];
+
+/// Words after an amount giving how often it is paid, marking it as a salary.
+const PERIOD_WORDS: [&str; 9] = ["per", "pm", "pa", "hourly", "daily", "weekly", "monthly", "yearly", "annually"];
+
+/// How often a salary is paid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "salary_period")]
+#[sqlx(rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum SalaryPeriod {
+    /// Per hour
+    Hour,
+    /// Per day
+    Day,
+    /// Per week
+    Week,
+    /// Per month
+    Month,
+    /// Per year
+    Year,
+}
+
+/// A salary parsed from its published text; amounts are whole units of
+/// `currency`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, FromRow)]
+pub struct SalaryRange {
+    /// Lowest amount offered
+    #[sqlx(rename = "salary_min")]
+    pub min: Option<i32>,
+    /// Highest amount offered
+    #[sqlx(rename = "salary_max")]
+    pub max: Option<i32>,
+    /// ISO 4217 code, e.g. `BDT`
+    #[sqlx(rename = "salary_currency")]
+    pub currency: Option<String>,
+    /// How often the amount is paid
+    #[sqlx(rename = "salary_period")]
+    pub period: Option<SalaryPeriod>,
+}
+
+/// Fields of a listing mapped to the internal types.
+#[derive(Debug, Clone, Default, Serialize, FromRow)]
+pub struct NormalizedFields {
+    /// Job type, from the published job type
+    #[sqlx(rename = "normalized_job_type")]
+    pub job_type: Option<JobType>,
+    /// Experience level, from the published level or years of experience
+    #[sqlx(rename = "normalized_experience_level")]
+    pub experience_level: Option<ExperienceLevel>,
+    /// Salary range, from the published salary
+    #[sqlx(flatten)]
+    pub salary: SalaryRange,
+    /// Known skills mentioned in the title or description but not given as
+    /// skills by the source
+    pub extracted_skills: Vec<String>,
+}
+
+/// Normalizes a listing; `vocabulary` lists the skill names to look for
+/// (see [`load_skill_vocabulary`]).
+pub fn normalize(job: &ExternalJob, vocabulary: &[String]) -> NormalizedFields {
+    let text = format!("{}\n{}", job.title, job.description);
+    let extracted_skills = extract_skills(&text, vocabulary)
+        .into_iter()
+        .filter(|skill| !job.skills.iter().any(|given| given.eq_ignore_ascii_case(skill)))
+        .collect();
+
+    NormalizedFields {
+        job_type: job.job_type.as_deref().and_then(parse_job_type),
+        experience_level: job.experience_level.as_deref().and_then(parse_experience_level),
+        salary: job.salary.as_deref().and_then(parse_salary).unwrap_or_default(),
+        extracted_skills,
+    }
+}
+
+/// Words of `text`, lowercase.
+fn words(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Maps a published job type to a [`JobType`]; contracts and consultancies
+/// count as freelance work.
+///
+/// # Example
+///
+/// ```
+/// use backend::external_jobs::normalize::parse_job_type;
+/// use backend::models::JobType;
+///
+/// assert_eq!(parse_job_type("Full-time"), Some(JobType::FullTime));
+/// assert_eq!(parse_job_type("PART_TIME"), Some(JobType::PartTime));
+/// assert_eq!(parse_job_type("Contract"), Some(JobType::Freelance));
+/// assert_eq!(parse_job_type("Consultancy"), Some(JobType::Freelance));
+/// assert_eq!(parse_job_type("Paid Internship"), Some(JobType::Internship));
+/// assert_eq!(parse_job_type("Volunteer Opportunity"), None);
+/// ```
+pub fn parse_job_type(raw: &str) -> Option<JobType> {
+    if let Ok(job_type) = raw.trim().parse::<JobType>() {
+        return Some(job_type);
+    }
+    let words = words(raw);
+    let has = |word: &str| words.iter().any(|w| w == word);
+    let has_pair = |first: &str, second: &str| words.windows(2).any(|pair| pair[0] == first && pair[1] == second);
+
+    if has("intern") || has("internship") || has("apprenticeship") {
+        Some(JobType::Internship)
+    } else if has_pair("part", "time") || has("parttime") {
+        Some(JobType::PartTime)
+    } else if ["contract", "contractual", "consultancy", "consultant", "freelance", "freelancer", "temporary", "temp"]
+        .iter()
+        .any(|word| has(word))
+        || has_pair("fixed", "term")
+        || has_pair("project", "based")
+    {
+        Some(JobType::Freelance)
+    } else if has_pair("full", "time") || has("fulltime") || has("permanent") || has("regular") {
+        Some(JobType::FullTime)
+    } else {
+        None
+    }
+}
+
+/// Maps a published experience level to an [`ExperienceLevel`], from its
+/// wording ("Entry level", "Mid-Senior Level") or else the years of
+/// experience it asks for ("3-4 years", see [`ExperienceLevel::from_years`]).
+/// A level naming two ("Junior-Mid") maps to the higher one. "Executive"
+/// alone is left unmapped: local sources use it for anything from junior
+/// officers to management.
+///
+/// # Example
+///
+/// ```
+/// use backend::external_jobs::normalize::parse_experience_level;
+/// use backend::models::ExperienceLevel;
+///
+/// assert_eq!(parse_experience_level("Entry level"), Some(ExperienceLevel::Fresher));
+/// assert_eq!(parse_experience_level("Associate"), Some(ExperienceLevel::Junior));
+/// assert_eq!(parse_experience_level("Junior-Mid Level"), Some(ExperienceLevel::Mid));
+/// assert_eq!(parse_experience_level("Mid-Senior Level"), Some(ExperienceLevel::Senior));
+/// assert_eq!(parse_experience_level("Team Lead"), Some(ExperienceLevel::Lead));
+/// assert_eq!(parse_experience_level("Director"), Some(ExperienceLevel::Manager));
+/// assert_eq!(parse_experience_level("Senior Executive"), Some(ExperienceLevel::Senior));
+/// assert_eq!(parse_experience_level("Executive"), None);
+/// assert_eq!(parse_experience_level("0-2 years"), Some(ExperienceLevel::Fresher));
+/// assert_eq!(parse_experience_level("At least 2 years"), Some(ExperienceLevel::Junior));
+/// assert_eq!(parse_experience_level("3-4 years"), Some(ExperienceLevel::Mid));
+/// assert_eq!(parse_experience_level("5-9 years"), Some(ExperienceLevel::Senior));
+/// assert_eq!(parse_experience_level("10+ years"), Some(ExperienceLevel::Lead));
+/// assert_eq!(parse_experience_level("Not applicable"), None);
+/// ```
+pub fn parse_experience_level(raw: &str) -> Option<ExperienceLevel> {
+    if let Ok(level) = raw.trim().parse::<ExperienceLevel>() {
+        return Some(level);
+    }
+    let words = words(raw);
+    let has = |options: &[&str]| words.iter().any(|w| options.contains(&w.as_str()));
+
+    // Highest level first
+    if has(&["manager", "management", "head", "director"]) {
+        return Some(ExperienceLevel::Manager);
+    }
+    if has(&["lead", "principal", "staff"]) {
+        return Some(ExperienceLevel::Lead);
+    }
+    if has(&["senior", "sr", "expert"]) {
+        return Some(ExperienceLevel::Senior);
+    }
+    if has(&["mid", "intermediate", "experienced"]) {
+        return Some(ExperienceLevel::Mid);
+    }
+    if has(&["junior", "jr", "associate"]) {
+        return Some(ExperienceLevel::Junior);
+    }
+    if has(&["entry", "fresher", "freshers", "graduate", "trainee", "intern", "internship", "beginner"]) {
+        return Some(ExperienceLevel::Fresher);
+    }
+
+    // Least years asked for, e.g. "3-4 years" or "5+ years"
+    let mentions_years = has(&["year", "years", "yr", "yrs"]);
+    let years = words.iter().find_map(|w| w.parse::<i32>().ok())?;
+    if !mentions_years {
+        return None;
+    }
+    Some(ExperienceLevel::from_years(years))
+}
+
+/// Parses a published salary: the amounts (with `k`, `lakh`, `crore` or
+/// `million` multipliers), the currency and how often it is paid. A single
+/// amount is the minimum after "from" or "min", the maximum after "up to" or
+/// "max", and both otherwise. Returns `None` when no amount is given, as in
+/// "Negotiable".
+///
+/// Unless the text is nothing but numbers, an amount counts only with a
+/// currency, a multiplier or a salary or period word next to it, or when a
+/// range joins it to one that does, so "5 years experience" or "2 positions"
+/// is no salary.
+///
+/// # Example
+///
+/// ```
+/// use backend::external_jobs::normalize::{SalaryPeriod, SalaryRange, parse_salary};
+///
+/// assert_eq!(
+///     parse_salary("BDT 100,000 - 150,000"),
+///     Some(SalaryRange { min: Some(100_000), max: Some(150_000), currency: Some("BDT".to_string()), period: None }),
+/// );
+/// let salary = parse_salary("Tk. 40-55k per month").unwrap();
+/// assert_eq!((salary.min, salary.max), (Some(40_000), Some(55_000)));
+/// assert_eq!(salary.currency.as_deref(), Some("BDT"));
+/// assert_eq!(salary.period, Some(SalaryPeriod::Month));
+///
+/// let salary = parse_salary("Up to $4,500/month").unwrap();
+/// assert_eq!((salary.min, salary.max), (None, Some(4_500)));
+/// assert_eq!(salary.currency.as_deref(), Some("USD"));
+///
+/// assert_eq!(parse_salary("1.2 lakh yearly").unwrap().min, Some(120_000));
+/// assert_eq!(parse_salary("120000").unwrap().max, Some(120_000));
+/// assert_eq!(parse_salary("Competitive (UN Scale)"), None);
+///
+/// assert_eq!(parse_salary("5 years experience"), None);
+/// assert_eq!(parse_salary("2 positions"), None);
+/// let salary = parse_salary("Tk 30,000 for 2 positions").unwrap();
+/// assert_eq!((salary.min, salary.max), (Some(30_000), Some(30_000)));
+/// ```
+pub fn parse_salary(raw: &str) -> Option<SalaryRange> {
+    let lower = raw.to_lowercase();
+    let matches: Vec<_> = AMOUNT.captures_iter(&lower).collect();
+    let span = |i: usize| matches[i].get(0).map_or(0..0, |m| m.range());
+    let bare = !lower.chars().any(char::is_alphabetic);
+    let mut marked: Vec<bool> = (0..matches.len())
+        .map(|i| {
+            bare || matches[i].get(2).is_some()
+                || ends_with_salary_marker(&lower[..span(i).start])
+                || starts_with_salary_marker(&lower[span(i).end..])
+        })
+        .collect();
+    // "BDT 100,000 - 150,000": the currency marks both ends of the range
+    for i in 1..matches.len() {
+        let between = lower[span(i - 1).end..span(i).start].trim();
+        if matches!(between, "-" | "–" | "—" | "to") && (marked[i - 1] || marked[i]) {
+            marked[i - 1] = true;
+            marked[i] = true;
+        }
+    }
+
+    let mut amounts: Vec<(f64, Option<f64>)> = matches
+        .iter()
+        .zip(marked)
+        .filter(|(_, marked)| *marked)
+        .filter_map(|(captures, _)| {
+            let value: f64 = captures[1].replace(',', "").parse().ok()?;
+            let multiplier = captures.get(2).map(|unit| match unit.as_str() {
+                "k" => 1e3,
+                "m" | "mn" | "million" => 1e6,
+                unit if unit.starts_with("crore") => 1e7,
+                _ => 1e5,
+            });
+            Some((value, multiplier))
+        })
+        .take(2)
+        .collect();
+    if amounts.is_empty() {
+        return None;
+    }
+    // "40-55k": the second amount's multiplier applies to the first
+    if let [(_, first @ None), (_, Some(second))] = amounts.as_mut_slice() {
+        *first = Some(*second);
+    }
+    let amounts: Vec<i32> = amounts
+        .into_iter()
+        .map(|(value, multiplier)| (value * multiplier.unwrap_or(1.0)).round().min(i32::MAX as f64) as i32)
+        .collect();
+
+    let words = words(raw);
+    let has = |options: &[&str]| words.iter().any(|w| options.contains(&w.as_str()));
+    let (min, max) = match amounts.as_slice() {
+        [a, b] => (Some(*a.min(b)), Some(*a.max(b))),
+        [amount] if lower.contains("up to") || lower.contains("upto") || has(&["max", "maximum"]) => {
+            (None, Some(*amount))
+        }
+        [amount] if has(&["from", "min", "minimum", "starting"]) || lower.contains('+') => (Some(*amount), None),
+        [amount] => (Some(*amount), Some(*amount)),
+        _ => return None,
+    };
+
+    let currency = CURRENCIES
+        .iter()
+        .find(|(symbol, _)| {
+            if symbol.chars().all(char::is_alphabetic) {
+                words.iter().any(|w| w == symbol)
+            } else {
+                lower.contains(symbol)
+            }
+        })
+        .map(|(_, code)| code.to_string());
+
+    let period = if has(&["hour", "hourly", "hr", "hrs"]) {
+        Some(SalaryPeriod::Hour)
+    } else if has(&["day", "daily", "diem"]) {
+        Some(SalaryPeriod::Day)
+    } else if has(&["week", "weekly", "wk"]) {
+        Some(SalaryPeriod::Week)
+    } else if has(&["month", "monthly", "mo", "mon", "pm"]) {
+        Some(SalaryPeriod::Month)
+    } else if has(&["year", "yearly", "annual", "annually", "annum", "yr", "pa"]) || lower.contains("p.a.") {
+        Some(SalaryPeriod::Year)
+    } else {
+        None
+    };
+
+    Some(SalaryRange { min, max, currency, period })
+}
+
+/// Whether `text` ends with a currency or salary word, as in "Salary: Tk.".
+fn ends_with_salary_marker(text: &str) -> bool {
+    let text = text.trim_end().trim_end_matches(['.', ':']).trim_end();
+    let last_word = text.rsplit(|c: char| !c.is_alphanumeric()).next().unwrap_or_default();
+    CURRENCIES.iter().any(|(symbol, _)| {
+        if symbol.chars().all(char::is_alphabetic) { last_word == *symbol } else { text.ends_with(symbol) }
+    }) || SALARY_WORDS.contains(&last_word)
+}
+
+/// Whether `text` starts with a currency, salary or period word, as in
+/// "Taka per month" or "/month".
+fn starts_with_salary_marker(text: &str) -> bool {
+    let text = text.trim_start();
+    let first_word = text.split(|c: char| !c.is_alphanumeric()).next().unwrap_or_default();
+    text.starts_with('/')
+        || CURRENCIES.iter().any(|(symbol, _)| {
+            if symbol.chars().all(char::is_alphabetic) { first_word == *symbol } else { text.starts_with(symbol) }
+        })
+        || SALARY_WORDS.contains(&first_word)
+        || PERIOD_WORDS.contains(&first_word)
+}
+
+/// Skills of `vocabulary` mentioned in `text` as whole words, in vocabulary
+/// order. Names up to three characters must match exactly, so "Go" is not
+/// found in "good to go".
+///
+/// # Example
+///
+/// ```
+/// use backend::external_jobs::normalize::extract_skills;
+///
+/// let vocabulary: Vec<String> = ["Excel", "Go", "Monitoring and Evaluation", "R", "SQL"]
+///     .iter()
+///     .map(|s| s.to_string())
+///     .collect();
+/// let text = "Good to go: you will run monitoring and evaluation using Excel and R. SQL a plus.";
+/// assert_eq!(extract_skills(text, &vocabulary), ["Excel", "Monitoring and Evaluation", "R", "SQL"]);
+/// ```
+pub fn extract_skills(text: &str, vocabulary: &[String]) -> Vec<String> {
+    let mentioned = skills_mentioned(text, vocabulary);
+    vocabulary
+        .iter()
+        .filter(|skill| {
+            let skill = skill.trim();
+            if skill.chars().count() <= SHORT_SKILL_LEN {
+                contains_word(text, skill)
+            } else {
+                mentioned.iter().any(|found| found.trim() == skill)
+            }
+        })
+        .map(|skill| skill.trim().to_string())
+        .collect()
+}
+
+/// Skill names known to the platform: those of internal jobs, learning
+/// resources and approved quizzes, one spelling per name.
+///
+/// Only curated sources are used; what users type into their profiles is
+/// not, so it never leaks into listings seen by everyone.
+///
+/// # Errors
+///
+/// Returns an error if a database operation fails.
+pub async fn load_skill_vocabulary(pool: &PgPool) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        SELECT DISTINCT ON (LOWER(skill)) skill AS "skill!"
+        FROM (
+            SELECT TRIM(UNNEST(required_skills)) AS skill FROM jobs
+            UNION ALL SELECT TRIM(UNNEST(related_skills)) FROM learning_resources
+            UNION ALL SELECT TRIM(skill_name) FROM quiz_questions WHERE status = 'approved'
+        ) names
+        WHERE LENGTH(skill) BETWEEN 1 AND $1
+        ORDER BY LOWER(skill), skill
+        "#,
+        MAX_SKILL_LEN as i32
+    )
+    .fetch_all(pool)
+    .await
+}
@@ -0,0 +1,277 @@
+//! RSS 2.0 and Atom feed connector.
+
+use super::html::html_text;
+use super::{JobSource, RawListing, SourceError, SourceOptions, response_text};
+use crate::external_jobs::{ExternalJob, SourceCategory};
+use async_trait::async_trait;
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+use scraper::Html;
+use serde::Deserialize;
+
+/// Settings of a feed source.
+#[derive(Debug, Clone, Deserialize)]
+pub struct FeedSettings {
+    /// Feed URL
+    pub url: String,
+    /// Item element holding the company, for feeds with job extensions
+    /// (e.g. `job:company`)
+    #[serde(default)]
+    pub company_element: Option<String>,
+    /// Item element holding the location
+    #[serde(default)]
+    pub location_element: Option<String>,
+    /// Item element holding the job type
+    #[serde(default)]
+    pub job_type_element: Option<String>,
+    /// Item element holding the salary
+    #[serde(default)]
+    pub salary_element: Option<String>,
+}
+
+/// Listings from an RSS 2.0 or Atom feed: one per `<item>` or `<entry>`,
+/// with its categories as skills and its HTML description as text.
+#[derive(Debug, Clone)]
+pub struct FeedSource {
+    options: SourceOptions,
+    settings: FeedSettings,
+}
+
+/// Item element whose text is being read.
+enum Field {
+    Title,
+    Link,
+    Description,
+    Published,
+    Updated,
+    Id,
+    Category,
+    Company,
+    Location,
+    JobType,
+    Salary,
+}
+
+impl FeedSource {
+    /// Creates the source.
+    pub fn new(options: SourceOptions, settings: FeedSettings) -> Self {
+        Self { options, settings }
+    }
+
+    fn field(&self, name: &str) -> Option<Field> {
+        let configured = |element: &Option<String>| element.as_deref().is_some_and(|e| e.eq_ignore_ascii_case(name));
+        if configured(&self.settings.company_element) {
+            return Some(Field::Company);
+        }
+        if configured(&self.settings.location_element) {
+            return Some(Field::Location);
+        }
+        if configured(&self.settings.job_type_element) {
+            return Some(Field::JobType);
+        }
+        if configured(&self.settings.salary_element) {
+            return Some(Field::Salary);
+        }
+        Some(match name {
+            "title" => Field::Title,
+            "link" => Field::Link,
+            "description" | "summary" | "content" | "content:encoded" => Field::Description,
+            "pubdate" | "published" | "dc:date" => Field::Published,
+            "updated" => Field::Updated,
+            "guid" | "id" => Field::Id,
+            "category" => Field::Category,
+            _ => return None,
+        })
+    }
+
+    /// Reads an RSS 2.0 or Atom feed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use backend::external_jobs::SourceCategory;
+    /// use backend::external_jobs::sources::{FeedSettings, FeedSource, SourceOptions};
+    ///
+    /// let settings = FeedSettings {
+    ///     url: "https://jobs.example.com/feed".to_string(),
+    ///     company_element: Some("job:company".to_string()),
+    ///     location_element: Some("job:location".to_string()),
+    ///     job_type_element: None,
+    ///     salary_element: Some("job:salary".to_string()),
+    /// };
+    /// let rss = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/external_jobs/feed.rss"));
+    /// let jobs = FeedSource::new(SourceOptions::new("BDJobs", SourceCategory::Local), settings.clone())
+    ///     .parse(rss)
+    ///     .unwrap();
+    /// assert_eq!(jobs.len(), 3);
+    /// assert_eq!(jobs[0].title, "Junior Data Analyst");
+    /// assert_eq!(jobs[0].company, "bKash Limited");
+    /// assert_eq!(jobs[0].location, "Dhaka");
+    /// assert_eq!(jobs[0].salary.as_deref(), Some("BDT 40,000 - 55,000 per month"));
+    /// assert_eq!(jobs[0].skills, ["SQL", "Excel"]);
+    /// assert_eq!(jobs[0].description, "Analyse payment data & build dashboards. Requirements: SQL, Excel.");
+    /// assert_eq!(jobs[0].posted_date, "Mon, 12 Oct 2026 09:30:00 +0600");
+    ///
+    /// // Keeping only some categories, and a feed without company elements
+    /// let mut options = SourceOptions::new("Chakri", SourceCategory::Local);
+    /// options.categories = vec!["accounting".to_string()];
+    /// options.default_company = Some("Various employers".to_string());
+    /// let atom = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/external_jobs/feed.atom"));
+    /// let jobs = FeedSource::new(options, settings).parse(atom).unwrap();
+    /// assert_eq!(jobs.len(), 1);
+    /// assert_eq!(jobs[0].id, "urn:chakri:job:981");
+    /// assert_eq!(jobs[0].title, "Junior Accountant – Head Office");
+    /// assert_eq!(jobs[0].url, "https://chakri.example.com/jobs/981");
+    /// assert_eq!(jobs[0].company, "Various employers");
+    /// assert_eq!(jobs[0].posted_date, "2026-10-14T08:00:00Z");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the feed is not well-formed XML.
+    pub fn parse(&self, xml: &str) -> Result<Vec<ExternalJob>, SourceError> {
+        let mut reader = Reader::from_str(xml);
+        reader.config_mut().trim_text(true);
+
+        let mut listings = Vec::new();
+        let mut item: Option<RawListing> = None;
+        // Depth inside the current item, and the direct child being read
+        let mut depth = 0usize;
+        let mut field: Option<Field> = None;
+        let mut text = String::new();
+        let mut updated = String::new();
+
+        loop {
+            let event = reader.read_event().map_err(|e| SourceError::Parse(e.to_string()))?;
+            match event {
+                Event::Start(e) => {
+                    let name = element_name(&e);
+                    match item.as_mut() {
+                        None if name == "item" || name == "entry" => {
+                            item = Some(RawListing::default());
+                            depth = 0;
+                            updated.clear();
+                        }
+                        None => {}
+                        Some(current) => {
+                            depth += 1;
+                            if depth == 1 {
+                                field = self.field(&name);
+                                text.clear();
+                                read_attributes(&e, &field, current);
+                            }
+                        }
+                    }
+                }
+                Event::Empty(e) => {
+                    if let Some(current) = item.as_mut()
+                        && depth == 0
+                    {
+                        let name = element_name(&e);
+                        read_attributes(&e, &self.field(&name), current);
+                    }
+                }
+                Event::Text(e) if field.is_some() => {
+                    // HTML entities such as &nbsp; are not XML; read the text as HTML then
+                    let raw = String::from_utf8_lossy(&e).into_owned();
+                    text.push_str(&e.unescape().map(|t| t.into_owned()).unwrap_or_else(|_| {
+                        Html::parse_fragment(&raw).root_element().text().collect()
+                    }));
+                }
+                Event::CData(e) if field.is_some() => text.push_str(&String::from_utf8_lossy(&e)),
+                Event::End(e) => {
+                    let Some(current) = item.as_mut() else {
+                        continue;
+                    };
+                    if depth == 0 {
+                        let name = String::from_utf8_lossy(e.name().as_ref()).to_lowercase();
+                        if name == "item" || name == "entry" {
+                            let mut done = item.take().unwrap_or_default();
+                            if done.posted_date.is_empty() {
+                                done.posted_date = std::mem::take(&mut updated);
+                            }
+                            listings.push(done);
+                        }
+                        continue;
+                    }
+                    depth -= 1;
+                    if depth == 0 {
+                        let value = std::mem::take(&mut text).trim().to_string();
+                        match field.take() {
+                            Some(Field::Title) => current.title = value,
+                            Some(Field::Link) if current.url.is_empty() => current.url = value,
+                            Some(Field::Description) => {
+                                let value = html_text(&value);
+                                if value.len() > current.description.len() {
+                                    current.description = value;
+                                }
+                            }
+                            Some(Field::Published) => current.posted_date = value,
+                            Some(Field::Updated) => updated = value,
+                            Some(Field::Id) => current.id = value,
+                            Some(Field::Category) if !value.is_empty() => current.categories.push(value),
+                            Some(Field::Company) => current.company = Some(value),
+                            Some(Field::Location) => current.location = Some(value),
+                            Some(Field::JobType) => current.job_type = Some(value),
+                            Some(Field::Salary) => current.salary = Some(value),
+                            _ => {}
+                        }
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        Ok(self.options.finish(listings))
+    }
+}
+
+/// Lowercase qualified name of an element.
+fn element_name(e: &BytesStart<'_>) -> String {
+    String::from_utf8_lossy(e.name().as_ref()).to_lowercase()
+}
+
+/// Values given as attributes: an Atom `<link href>` (the alternate link)
+/// and `<category term>`.
+fn read_attributes(e: &BytesStart<'_>, field: &Option<Field>, item: &mut RawListing) {
+    let attribute = |name: &str| {
+        e.try_get_attribute(name)
+            .ok()
+            .flatten()
+            .and_then(|a| a.unescape_value().ok().map(|v| v.trim().to_string()))
+            .filter(|v| !v.is_empty())
+    };
+    match field {
+        Some(Field::Link) => {
+            let alternate = attribute("rel").is_none_or(|rel| rel == "alternate");
+            if alternate && item.url.is_empty()
+                && let Some(href) = attribute("href")
+            {
+                item.url = href;
+            }
+        }
+        Some(Field::Category) => item.categories.extend(attribute("term")),
+        _ => {}
+    }
+}
+
+#[async_trait]
+impl JobSource for FeedSource {
+    fn name(&self) -> &str {
+        &self.options.name
+    }
+
+    fn category(&self) -> SourceCategory {
+        self.options.category
+    }
+
+    fn kind(&self) -> &'static str {
+        "feed"
+    }
+
+    async fn fetch(&self, client: &reqwest::Client) -> Result<Vec<ExternalJob>, SourceError> {
+        let response = client.get(&self.settings.url).send().await?;
+        self.parse(&response_text(response).await?)
+    }
+}
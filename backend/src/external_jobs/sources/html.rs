@@ -0,0 +1,271 @@
+//! HTML listing page connector.
+
+use super::{JobSource, RawListing, SourceError, SourceOptions, response_text};
+use crate::external_jobs::{ExternalJob, SourceCategory};
+use async_trait::async_trait;
+use scraper::{ElementRef, Html, Selector};
+use serde::Deserialize;
+use url::Url;
+
+/// Elements whose content is not text; it is left out.
+const NON_TEXT_ELEMENTS: [&str; 4] = ["script", "style", "noscript", "template"];
+
+/// Elements separating the text around them, so their text is not run
+/// together with their neighbours'.
+const BLOCK_ELEMENTS: [&str; 30] = [
+    "address", "article", "aside", "blockquote", "br", "dd", "div", "dl", "dt", "fieldset", "figure", "footer",
+    "form", "h1", "h2", "h3", "h4", "h5", "h6", "header", "hr", "li", "main", "nav", "ol", "p", "section", "table",
+    "td", "tr",
+];
+
+/// Settings of an HTML source. Field selectors are relative to the
+/// listing's element; a field's value is the text of its first match.
+#[derive(Debug, Clone, Deserialize)]
+pub struct HtmlSettings {
+    /// Listing page URL; relative links are resolved against it
+    pub url: String,
+    /// Element of each listing, e.g. `div.job-card`
+    pub item: String,
+    /// Title
+    pub title: String,
+    /// Link whose `href` is the listing's URL (default: the title's link, or
+    /// the listing's first link)
+    #[serde(default)]
+    pub link: Option<String>,
+    /// Company
+    #[serde(default)]
+    pub company: Option<String>,
+    /// Location
+    #[serde(default)]
+    pub location: Option<String>,
+    /// Short description
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Posting date
+    #[serde(default)]
+    pub posted_date: Option<String>,
+    /// Job type
+    #[serde(default)]
+    pub job_type: Option<String>,
+    /// Experience level
+    #[serde(default)]
+    pub experience_level: Option<String>,
+    /// Salary
+    #[serde(default)]
+    pub salary: Option<String>,
+    /// Tags or categories, stored as skills; every match is one
+    #[serde(default)]
+    pub tags: Option<String>,
+}
+
+/// Compiled selectors of the optional fields.
+#[derive(Debug, Clone, Default)]
+struct FieldSelectors {
+    link: Option<Selector>,
+    company: Option<Selector>,
+    location: Option<Selector>,
+    description: Option<Selector>,
+    posted_date: Option<Selector>,
+    job_type: Option<Selector>,
+    experience_level: Option<Selector>,
+    salary: Option<Selector>,
+    tags: Option<Selector>,
+}
+
+/// Listings read from an HTML page with CSS selectors.
+#[derive(Debug, Clone)]
+pub struct HtmlSource {
+    options: SourceOptions,
+    page_url: Url,
+    item: Selector,
+    title: Selector,
+    fields: FieldSelectors,
+}
+
+impl HtmlSource {
+    /// Creates the source, compiling its selectors.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the URL or a selector is invalid.
+    pub fn new(options: SourceOptions, settings: HtmlSettings) -> Result<Self, SourceError> {
+        let compile = |selector: &str| {
+            Selector::parse(selector)
+                .map_err(|e| SourceError::Config(format!("invalid selector '{}': {}", selector, e)))
+        };
+        let optional = |selector: &Option<String>| selector.as_deref().map(compile).transpose();
+
+        Ok(Self {
+            page_url: Url::parse(&settings.url)
+                .map_err(|e| SourceError::Config(format!("invalid url '{}': {}", settings.url, e)))?,
+            item: compile(&settings.item)?,
+            title: compile(&settings.title)?,
+            fields: FieldSelectors {
+                link: optional(&settings.link)?,
+                company: optional(&settings.company)?,
+                location: optional(&settings.location)?,
+                description: optional(&settings.description)?,
+                posted_date: optional(&settings.posted_date)?,
+                job_type: optional(&settings.job_type)?,
+                experience_level: optional(&settings.experience_level)?,
+                salary: optional(&settings.salary)?,
+                tags: optional(&settings.tags)?,
+            },
+            options,
+        })
+    }
+
+    /// Reads a listing page. The listing URL is also its ID.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use backend::external_jobs::SourceCategory;
+    /// use backend::external_jobs::sources::{HtmlSettings, HtmlSource, SourceOptions};
+    ///
+    /// let settings: HtmlSettings = serde_json::from_str(r#"{
+    ///     "url": "https://jobs.example.gov.bd/circulars?page=1",
+    ///     "item": "table.circulars tr.circular",
+    ///     "title": "td.post a",
+    ///     "company": "td.office .name",
+    ///     "location": "td.office span.district",
+    ///     "posted_date": "td.published",
+    ///     "tags": "td.post .tag"
+    /// }"#).unwrap();
+    /// let mut options = SourceOptions::new("BPSC", SourceCategory::Government);
+    /// options.default_location = Some("Bangladesh".to_string());
+    /// let source = HtmlSource::new(options, settings).unwrap();
+    ///
+    /// let page = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/external_jobs/listing.html"));
+    /// let jobs = source.parse(page);
+    /// assert_eq!(jobs.len(), 3);
+    /// assert_eq!(jobs[0].title, "Assistant Engineer (Civil)");
+    /// assert_eq!(jobs[0].url, "https://jobs.example.gov.bd/circulars/2026/118");
+    /// assert_eq!(jobs[0].id, jobs[0].url);
+    /// assert_eq!(jobs[0].company, "Roads and Highways Department");
+    /// assert_eq!(jobs[0].location, "Dhaka");
+    /// assert_eq!(jobs[0].skills, ["Engineering"]);
+    /// assert_eq!(jobs[1].url, "https://portal.example.org/apply?id=77&src=bd");
+    /// assert_eq!(jobs[2].location, "Bangladesh");
+    ///
+    /// // Malformed markup is read the way browsers read it
+    /// let settings: HtmlSettings = serde_json::from_str(
+    ///     r#"{ "url": "https://jobs.example.com/", "item": "ul.jobs > li.job", "title": "a[href^='/j/']" }"#,
+    /// ).unwrap();
+    /// let source = HtmlSource::new(SourceOptions::new("Example", SourceCategory::Local), settings).unwrap();
+    /// let jobs = source.parse(
+    ///     r#"<ul class="jobs"><li class="job"><a href="/j/1">Data Analyst</a> <span>Dhaka</span>
+    ///        <li class="job"><a href="/j/2">Field Officer &amp; Trainer</a></ul>"#,
+    /// );
+    /// assert_eq!(jobs[1].title, "Field Officer & Trainer");
+    /// assert_eq!(jobs[1].url, "https://jobs.example.com/j/2");
+    /// ```
+    pub fn parse(&self, html: &str) -> Vec<ExternalJob> {
+        let doc = Html::parse_document(html);
+        let first_text = |item: &ElementRef<'_>, selector: &Option<Selector>| {
+            selector
+                .as_ref()
+                .and_then(|selector| item.select(selector).next())
+                .map(element_text)
+        };
+
+        let listings = doc
+            .select(&self.item)
+            .map(|item| {
+                let title = item.select(&self.title).next();
+                let href = match &self.fields.link {
+                    Some(link) => item.select(link).next().and_then(|a| a.attr("href")),
+                    None => title
+                        .and_then(|t| if t.value().name() == "a" { t.attr("href") } else { first_link(&t) })
+                        .or_else(|| first_link(&item)),
+                };
+                let url = href
+                    .and_then(|href| self.page_url.join(href.trim()).ok())
+                    .map(String::from)
+                    .unwrap_or_default();
+                let categories = self
+                    .fields
+                    .tags
+                    .as_ref()
+                    .map(|selector| item.select(selector).map(element_text).collect())
+                    .unwrap_or_default();
+
+                RawListing {
+                    id: url.clone(),
+                    title: title.map(element_text).unwrap_or_default(),
+                    company: first_text(&item, &self.fields.company),
+                    location: first_text(&item, &self.fields.location),
+                    description: first_text(&item, &self.fields.description).unwrap_or_default(),
+                    url,
+                    posted_date: first_text(&item, &self.fields.posted_date).unwrap_or_default(),
+                    job_type: first_text(&item, &self.fields.job_type),
+                    experience_level: first_text(&item, &self.fields.experience_level),
+                    salary: first_text(&item, &self.fields.salary),
+                    categories,
+                }
+            })
+            .collect();
+
+        self.options.finish(listings)
+    }
+}
+
+fn first_link<'a>(element: &ElementRef<'a>) -> Option<&'a str> {
+    let links = Selector::parse("a[href]").ok()?;
+    element.select(&links).next().and_then(|a| a.attr("href"))
+}
+
+/// Text of an HTML fragment with whitespace collapsed, e.g. a job
+/// description given as HTML.
+pub(super) fn html_text(html: &str) -> String {
+    element_text(Html::parse_fragment(html).root_element())
+}
+
+/// An element's text with whitespace collapsed; block elements are
+/// separated by a space.
+fn element_text(element: ElementRef<'_>) -> String {
+    let mut out = String::new();
+    collect_text(element, &mut out);
+    out.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn collect_text(element: ElementRef<'_>, out: &mut String) {
+    for child in element.children() {
+        if let Some(text) = child.value().as_text() {
+            out.push_str(text);
+        } else if let Some(child) = ElementRef::wrap(child) {
+            let name = child.value().name();
+            if NON_TEXT_ELEMENTS.contains(&name) {
+                continue;
+            }
+            let block = BLOCK_ELEMENTS.contains(&name);
+            if block {
+                out.push(' ');
+            }
+            collect_text(child, out);
+            if block {
+                out.push(' ');
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl JobSource for HtmlSource {
+    fn name(&self) -> &str {
+        &self.options.name
+    }
+
+    fn category(&self) -> SourceCategory {
+        self.options.category
+    }
+
+    fn kind(&self) -> &'static str {
+        "html"
+    }
+
+    async fn fetch(&self, client: &reqwest::Client) -> Result<Vec<ExternalJob>, SourceError> {
+        let response = client.get(self.page_url.clone()).send().await?;
+        Ok(self.parse(&response_text(response).await?))
+    }
+}
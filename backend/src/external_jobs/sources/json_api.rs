@@ -0,0 +1,248 @@
+//! Generic JSON API connector.
+
+use super::html::html_text;
+use super::{JobSource, RawListing, SourceError, SourceOptions, expand_env, response_text};
+use crate::external_jobs::{ExternalJob, SourceCategory};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Where each listing field is in an API response.
+///
+/// A path is a dot-separated list of object keys and array indexes, e.g.
+/// `employer.name` or `locations.0.city`. A key applied to an array is
+/// applied to each element, so `tags.name` gives every tag's name.
+#[derive(Debug, Clone, Deserialize)]
+pub struct FieldMapping {
+    /// Path of the listings array; empty when the response is the array
+    #[serde(default)]
+    pub items: String,
+    /// Listing ID
+    pub id: String,
+    /// Title
+    pub title: String,
+    /// Listing URL
+    pub url: String,
+    /// Prefix of relative URLs, e.g. `https://jobs.example.com`
+    #[serde(default)]
+    pub url_prefix: Option<String>,
+    /// Company
+    #[serde(default)]
+    pub company: Option<String>,
+    /// Location
+    #[serde(default)]
+    pub location: Option<String>,
+    /// Description; HTML is reduced to text
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Posting date
+    #[serde(default)]
+    pub posted_date: Option<String>,
+    /// Job type
+    #[serde(default)]
+    pub job_type: Option<String>,
+    /// Experience level
+    #[serde(default)]
+    pub experience_level: Option<String>,
+    /// Salary
+    #[serde(default)]
+    pub salary: Option<String>,
+    /// Tags or categories, stored as skills
+    #[serde(default)]
+    pub tags: Option<String>,
+}
+
+/// A field mapping given inline or as the path of a mapping file (relative
+/// to the sources file).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum MappingSource {
+    /// Path of a JSON file holding the mapping
+    File(PathBuf),
+    /// The mapping itself
+    Inline(Box<FieldMapping>),
+}
+
+/// Settings of a JSON API source.
+#[derive(Debug, Clone, Deserialize)]
+pub struct JsonApiSettings {
+    /// Endpoint returning the listings
+    pub url: String,
+    /// Request headers; `${NAME}` is replaced with environment variable `NAME`
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// Field mapping
+    pub mapping: MappingSource,
+}
+
+/// Listings from any JSON API, read through a [`FieldMapping`].
+#[derive(Debug, Clone)]
+pub struct JsonApiSource {
+    options: SourceOptions,
+    url: String,
+    headers: HashMap<String, String>,
+    mapping: FieldMapping,
+}
+
+impl JsonApiSource {
+    /// Creates the source, reading its mapping file if it has one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the mapping file cannot be read or parsed.
+    pub fn new(options: SourceOptions, settings: JsonApiSettings, base_dir: &Path) -> Result<Self, SourceError> {
+        let mapping = match settings.mapping {
+            MappingSource::Inline(mapping) => *mapping,
+            MappingSource::File(path) => {
+                let path = base_dir.join(path);
+                let json = std::fs::read_to_string(&path)
+                    .map_err(|e| SourceError::Config(format!("cannot read {}: {}", path.display(), e)))?;
+                serde_json::from_str(&json)
+                    .map_err(|e| SourceError::Config(format!("invalid mapping {}: {}", path.display(), e)))?
+            }
+        };
+        Ok(Self { options, url: settings.url, headers: settings.headers, mapping })
+    }
+
+    /// Reads an API response.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use backend::external_jobs::SourceCategory;
+    /// use backend::external_jobs::sources::{JsonApiSettings, JsonApiSource, SourceOptions};
+    /// use std::path::Path;
+    ///
+    /// let fixtures = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/external_jobs"));
+    /// let settings: JsonApiSettings = serde_json::from_str(
+    ///     r#"{ "url": "https://api.example.com/v2/jobs", "mapping": "json_api_mapping.json" }"#,
+    /// ).unwrap();
+    /// let source = JsonApiSource::new(SourceOptions::new("Skill Jobs", SourceCategory::Local), settings, fixtures).unwrap();
+    ///
+    /// let response = std::fs::read_to_string(fixtures.join("json_api.json")).unwrap();
+    /// let jobs = source.parse(&response).unwrap();
+    /// assert_eq!(jobs.len(), 2);
+    /// assert_eq!(jobs[0].id, "88121");
+    /// assert_eq!(jobs[0].title, "Backend Developer (Rust)");
+    /// assert_eq!(jobs[0].company, "Pathao");
+    /// assert_eq!(jobs[0].location, "Dhaka");
+    /// assert_eq!(jobs[0].url, "https://jobs.example.com/jobs/88121");
+    /// assert_eq!(jobs[0].description, "Build payment services. Rust and PostgreSQL required.");
+    /// assert_eq!(jobs[0].salary.as_deref(), Some("120000"));
+    /// assert_eq!(jobs[0].skills, ["Rust", "PostgreSQL"]);
+    /// assert_eq!(jobs[1].company, "Skill Jobs");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the response is not JSON or has no listings array
+    /// at the mapped path.
+    pub fn parse(&self, response: &str) -> Result<Vec<ExternalJob>, SourceError> {
+        let root: Value = serde_json::from_str(response).map_err(|e| SourceError::Parse(e.to_string()))?;
+        let items = resolve(&root, &self.mapping.items);
+        let items = match items.as_slice() {
+            [Value::Array(items)] => items.iter().collect::<Vec<_>>(),
+            [] | [_] => {
+                return Err(SourceError::Parse(format!("no listings array at '{}'", self.mapping.items)));
+            }
+            // A key applied across an array already yields the listings
+            many => many.to_vec(),
+        };
+
+        let mapping = &self.mapping;
+        let listings = items
+            .into_iter()
+            .map(|item| {
+                let text = |path: &Option<String>| path.as_deref().and_then(|path| first_text(item, path));
+                let url = first_text(item, &mapping.url).unwrap_or_default();
+                let url = match &mapping.url_prefix {
+                    Some(prefix) if !url.is_empty() && !url.starts_with("http") => {
+                        format!("{}/{}", prefix.trim_end_matches('/'), url.trim_start_matches('/'))
+                    }
+                    _ => url,
+                };
+                let categories = mapping
+                    .tags
+                    .as_deref()
+                    .map(|path| resolve(item, path).into_iter().filter_map(value_text).collect())
+                    .unwrap_or_default();
+
+                RawListing {
+                    id: first_text(item, &mapping.id).unwrap_or_default(),
+                    title: first_text(item, &mapping.title).unwrap_or_default(),
+                    company: text(&mapping.company),
+                    location: text(&mapping.location),
+                    description: text(&mapping.description)
+                        .map(|html| html_text(&html))
+                        .unwrap_or_default(),
+                    url,
+                    posted_date: text(&mapping.posted_date).unwrap_or_default(),
+                    job_type: text(&mapping.job_type),
+                    experience_level: text(&mapping.experience_level),
+                    salary: text(&mapping.salary),
+                    categories,
+                }
+            })
+            .collect();
+
+        Ok(self.options.finish(listings))
+    }
+}
+
+/// Values at `path` under `value`; keys applied to arrays are applied to each
+/// element.
+fn resolve<'a>(value: &'a Value, path: &str) -> Vec<&'a Value> {
+    let mut current = vec![value];
+    for segment in path.split('.').map(str::trim).filter(|s| !s.is_empty()) {
+        current = current
+            .into_iter()
+            .flat_map(|value| match (value, segment.parse::<usize>()) {
+                (Value::Array(items), Ok(index)) => items.get(index).into_iter().collect::<Vec<_>>(),
+                (Value::Array(items), Err(_)) => items.iter().filter_map(|item| item.get(segment)).collect(),
+                (Value::Object(map), _) => map.get(segment).into_iter().collect(),
+                _ => Vec::new(),
+            })
+            .collect();
+    }
+    current
+}
+
+/// Text of the first non-empty value at `path`.
+fn first_text(value: &Value, path: &str) -> Option<String> {
+    resolve(value, path).into_iter().find_map(value_text)
+}
+
+fn value_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.trim().to_string()).filter(|t| !t.is_empty()),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Bool(flag) => Some(flag.to_string()),
+        Value::Array(items) => items.iter().find_map(value_text),
+        _ => None,
+    }
+}
+
+#[async_trait]
+impl JobSource for JsonApiSource {
+    fn name(&self) -> &str {
+        &self.options.name
+    }
+
+    fn category(&self) -> SourceCategory {
+        self.options.category
+    }
+
+    fn kind(&self) -> &'static str {
+        "json_api"
+    }
+
+    async fn fetch(&self, client: &reqwest::Client) -> Result<Vec<ExternalJob>, SourceError> {
+        let mut request = client.get(&self.url).header("Accept", "application/json");
+        for (name, value) in &self.headers {
+            request = request.header(name, expand_env(value));
+        }
+        self.parse(&response_text(request.send().await?).await?)
+    }
+}
@@ -0,0 +1,404 @@
+//! Connectors to external job sources.
+//!
+//! Each deployment lists its sources in a JSON file
+//! (`EXTERNAL_JOB_SOURCES_FILE`, default `assets/job_sources.json`):
+//!
+//! ```json
+//! {
+//!   "sources": [
+//!     { "name": "ReliefWeb", "kind": "reliefweb", "category": "ngo", "countries": ["BGD"], "limit": 20 },
+//!     { "name": "BDJobs", "kind": "feed", "category": "local", "url": "https://example.com/jobs.rss", "enabled": false }
+//!   ]
+//! }
+//! ```
+//!
+//! Every source has a unique `name` (stored as the listings' source), a
+//! `category` (`ngo`, `government`, `local`) and optionally `enabled`
+//! (default `true`), `limit` (most listings per import, default 50),
+//! `categories` (only listings in one of them), and `default_company` and
+//! `default_location` for listings that give none. The `kind` selects the
+//! connector:
+//!
+//! - `reliefweb`: [`ReliefWebSource`], the ReliefWeb jobs API
+//! - `feed`: [`FeedSource`], an RSS 2.0 or Atom feed
+//! - `json_api`: [`JsonApiSource`], any JSON API read through a field mapping
+//! - `html`: [`HtmlSource`], a listing page read with CSS selectors
+//! - `sample`: [`SampleSource`], built-in sample listings, only imported in
+//!   demo mode (`EXTERNAL_JOBS_DEMO_MODE=true`)
+//!
+//! A source that fails is reported as failing; its stored listings are not
+//! replaced with sample data.
+//!
+//! Connectors parse a response separately from fetching it, so each is
+//! tested against recorded responses in `fixtures/external_jobs`.
+
+mod feed;
+mod html;
+mod json_api;
+mod reliefweb;
+mod sample;
+
+pub use feed::{FeedSettings, FeedSource};
+pub use html::{HtmlSettings, HtmlSource};
+pub use json_api::{FieldMapping, JsonApiSettings, JsonApiSource, MappingSource};
+pub use reliefweb::{ReliefWebSettings, ReliefWebSource};
+pub use sample::SampleSource;
+
+use super::{ExternalJob, SourceCategory};
+use async_trait::async_trait;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::info;
+
+/// Default sources file.
+const DEFAULT_SOURCES_FILE: &str = "assets/job_sources.json";
+
+/// Default most listings taken from a source per import.
+const DEFAULT_LIMIT: usize = 50;
+
+/// Widths of the `external_jobs` columns; longer values are cut.
+const MAX_ID_LEN: usize = 255;
+const MAX_TITLE_LEN: usize = 500;
+const MAX_NAME_LEN: usize = 255;
+const MAX_SHORT_LEN: usize = 100;
+
+/// Errors raised by job sources.
+#[derive(Debug, Error)]
+pub enum SourceError {
+    /// The request failed
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+    /// The source answered with an error status
+    #[error("Source returned status {0}")]
+    Status(u16),
+    /// The response could not be read
+    #[error("Unreadable response: {0}")]
+    Parse(String),
+    /// Missing or invalid configuration
+    #[error("Job source configuration error: {0}")]
+    Config(String),
+}
+
+/// A source of external job listings.
+#[async_trait]
+pub trait JobSource: Send + Sync {
+    /// Configured name, stored as the listings' source
+    fn name(&self) -> &str;
+
+    /// Category the listings are filed under
+    fn category(&self) -> SourceCategory;
+
+    /// Connector kind as named in the sources file, e.g. `reliefweb`
+    fn kind(&self) -> &'static str;
+
+    /// Fetches the current listings.
+    async fn fetch(&self, client: &reqwest::Client) -> Result<Vec<ExternalJob>, SourceError>;
+}
+
+/// Settings shared by every source.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SourceOptions {
+    /// Unique name, stored as the listings' source
+    pub name: String,
+    /// Category the listings are filed under
+    pub category: SourceCategory,
+    /// Whether the source is imported
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+    /// Most listings taken per import
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+    /// Only listings in one of these categories (case-insensitive); empty keeps all
+    #[serde(default)]
+    pub categories: Vec<String>,
+    /// Company of listings that give none (default: the source name)
+    #[serde(default)]
+    pub default_company: Option<String>,
+    /// Location of listings that give none
+    #[serde(default)]
+    pub default_location: Option<String>,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+fn default_limit() -> usize {
+    DEFAULT_LIMIT
+}
+
+impl SourceOptions {
+    /// Options with the defaults for a source called `name`.
+    pub fn new(name: impl Into<String>, category: SourceCategory) -> Self {
+        Self {
+            name: name.into(),
+            category,
+            enabled: true,
+            limit: DEFAULT_LIMIT,
+            categories: Vec::new(),
+            default_company: None,
+            default_location: None,
+        }
+    }
+
+    /// Applies the shared settings to the listings a connector read: drops
+    /// those without a title or URL or outside `categories`, fills in the
+    /// defaults, cuts values to the column widths and keeps `limit` listings.
+    fn finish(&self, listings: Vec<RawListing>) -> Vec<ExternalJob> {
+        let wanted: HashSet<String> = self.categories.iter().map(|c| c.trim().to_lowercase()).collect();
+        listings
+            .into_iter()
+            .filter(|listing| !listing.title.trim().is_empty() && !listing.url.trim().is_empty())
+            .filter(|listing| {
+                wanted.is_empty() || listing.categories.iter().any(|c| wanted.contains(&c.trim().to_lowercase()))
+            })
+            .take(self.limit)
+            .map(|listing| {
+                let id = if listing.id.trim().is_empty() { listing.url.trim() } else { listing.id.trim() };
+                let id = if id.chars().count() > MAX_ID_LEN {
+                    hex::encode(Sha256::digest(id.as_bytes()))
+                } else {
+                    id.to_string()
+                };
+                let company = non_empty(listing.company)
+                    .or_else(|| self.default_company.clone())
+                    .unwrap_or_else(|| self.name.clone());
+                let location = non_empty(listing.location).or_else(|| self.default_location.clone()).unwrap_or_default();
+                let mut skills: Vec<String> = Vec::new();
+                for category in listing.categories {
+                    let category = category.trim().to_string();
+                    if !category.is_empty() && !skills.contains(&category) {
+                        skills.push(category);
+                    }
+                }
+
+                ExternalJob {
+                    id,
+                    title: truncate(listing.title.trim(), MAX_TITLE_LEN),
+                    company: truncate(&company, MAX_NAME_LEN),
+                    location: truncate(&location, MAX_NAME_LEN),
+                    description: listing.description.trim().to_string(),
+                    url: listing.url.trim().to_string(),
+                    posted_date: truncate(listing.posted_date.trim(), MAX_SHORT_LEN),
+                    source: self.name.clone(),
+                    job_type: non_empty(listing.job_type).map(|v| truncate(&v, MAX_SHORT_LEN)),
+                    experience_level: non_empty(listing.experience_level).map(|v| truncate(&v, MAX_SHORT_LEN)),
+                    skills,
+                    salary: non_empty(listing.salary).map(|v| truncate(&v, MAX_NAME_LEN)),
+                }
+            })
+            .collect()
+    }
+}
+
+/// A listing as read by a connector, before the shared settings apply.
+#[derive(Debug, Default)]
+struct RawListing {
+    id: String,
+    title: String,
+    company: Option<String>,
+    location: Option<String>,
+    description: String,
+    url: String,
+    posted_date: String,
+    job_type: Option<String>,
+    experience_level: Option<String>,
+    salary: Option<String>,
+    /// Categories or tags; stored as the listing's skills
+    categories: Vec<String>,
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+fn truncate(value: &str, max_chars: usize) -> String {
+    value.chars().take(max_chars).collect()
+}
+
+/// One entry of the sources file.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SourceConfig {
+    /// Settings shared by every source
+    #[serde(flatten)]
+    pub options: SourceOptions,
+    /// Connector and its settings
+    #[serde(flatten)]
+    pub kind: SourceKind,
+}
+
+/// Connector of a source and its settings.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SourceKind {
+    /// ReliefWeb jobs API
+    Reliefweb(ReliefWebSettings),
+    /// RSS 2.0 or Atom feed
+    Feed(FeedSettings),
+    /// JSON API with a field mapping
+    JsonApi(JsonApiSettings),
+    /// HTML listing page with CSS selectors
+    Html(HtmlSettings),
+    /// Built-in sample listings
+    Sample,
+}
+
+#[derive(Debug, Deserialize)]
+struct SourcesFile {
+    sources: Vec<SourceConfig>,
+}
+
+/// Sources file from `EXTERNAL_JOB_SOURCES_FILE` (default `assets/job_sources.json`).
+pub fn sources_file() -> PathBuf {
+    std::env::var("EXTERNAL_JOB_SOURCES_FILE")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from(DEFAULT_SOURCES_FILE))
+}
+
+/// Whether sample sources are imported, from `EXTERNAL_JOBS_DEMO_MODE`
+/// (`true` or `1`; default off).
+pub fn demo_mode() -> bool {
+    std::env::var("EXTERNAL_JOBS_DEMO_MODE")
+        .is_ok_and(|value| matches!(value.trim().to_lowercase().as_str(), "true" | "1"))
+}
+
+/// Builds the enabled sources listed in `EXTERNAL_JOB_SOURCES_FILE`, with
+/// sample sources only in [`demo_mode`].
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or an enabled source is
+/// misconfigured; no source is built then.
+pub fn sources_from_env() -> Result<Vec<Arc<dyn JobSource>>, SourceError> {
+    let path = sources_file();
+    let json = std::fs::read_to_string(&path)
+        .map_err(|e| SourceError::Config(format!("cannot read {}: {}", path.display(), e)))?;
+    let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+    build_sources(&parse_sources(&json)?, base_dir, demo_mode())
+}
+
+/// Parses a sources file.
+///
+/// # Example
+///
+/// ```
+/// use backend::external_jobs::sources::{SourceKind, build_sources, parse_sources};
+/// use std::path::Path;
+///
+/// let fixtures = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/external_jobs"));
+/// let json = std::fs::read_to_string(fixtures.join("sources.json")).unwrap();
+/// let sources = parse_sources(&json).unwrap();
+/// assert_eq!(sources.len(), 6);
+/// assert!(matches!(sources[0].kind, SourceKind::Reliefweb(_)));
+/// assert_eq!(sources[0].options.limit, 20);
+/// assert_eq!(sources[1].options.default_location.as_deref(), Some("Bangladesh"));
+/// assert!(!sources[4].options.enabled);
+///
+/// // Disabled sources are left out, and sample sources outside demo mode
+/// let names = |demo_mode| -> Vec<String> {
+///     build_sources(&sources, fixtures, demo_mode)
+///         .unwrap()
+///         .iter()
+///         .map(|source| source.name().to_string())
+///         .collect()
+/// };
+/// assert_eq!(names(false), ["ReliefWeb", "BDJobs", "Skill Jobs", "Government circulars"]);
+/// assert_eq!(names(true), ["ReliefWeb", "BDJobs", "Skill Jobs", "Government circulars", "Demo listings"]);
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the JSON does not describe a list of sources.
+pub fn parse_sources(json: &str) -> Result<Vec<SourceConfig>, SourceError> {
+    serde_json::from_str::<SourcesFile>(json)
+        .map(|file| file.sources)
+        .map_err(|e| SourceError::Config(e.to_string()))
+}
+
+/// Builds the enabled sources of `configs`; relative mapping file paths are
+/// resolved against `base_dir`. Sample sources are left out unless
+/// `demo_mode` is set.
+///
+/// # Errors
+///
+/// Returns an error if two sources share a name or an enabled source is
+/// misconfigured.
+pub fn build_sources(
+    configs: &[SourceConfig],
+    base_dir: &Path,
+    demo_mode: bool,
+) -> Result<Vec<Arc<dyn JobSource>>, SourceError> {
+    let mut names = HashSet::new();
+    let mut sources: Vec<Arc<dyn JobSource>> = Vec::new();
+
+    for config in configs {
+        let options = config.options.clone();
+        if options.name.trim().is_empty() || options.name.len() > MAX_SHORT_LEN {
+            return Err(SourceError::Config("source names must be 1-100 characters".to_string()));
+        }
+        if !names.insert(options.name.to_lowercase()) {
+            return Err(SourceError::Config(format!("duplicate source name '{}'", options.name)));
+        }
+        if !options.enabled {
+            continue;
+        }
+        if matches!(config.kind, SourceKind::Sample) && !demo_mode {
+            info!("Skipping sample source {} (set EXTERNAL_JOBS_DEMO_MODE=true to import it)", options.name);
+            continue;
+        }
+
+        let context = |e: SourceError| match e {
+            SourceError::Config(message) => SourceError::Config(format!("{}: {}", options.name, message)),
+            other => other,
+        };
+        let source: Arc<dyn JobSource> = match &config.kind {
+            SourceKind::Reliefweb(settings) => Arc::new(ReliefWebSource::new(options.clone(), settings.clone())),
+            SourceKind::Feed(settings) => Arc::new(FeedSource::new(options.clone(), settings.clone())),
+            SourceKind::JsonApi(settings) => {
+                Arc::new(JsonApiSource::new(options.clone(), settings.clone(), base_dir).map_err(context)?)
+            }
+            SourceKind::Html(settings) => {
+                Arc::new(HtmlSource::new(options.clone(), settings.clone()).map_err(context)?)
+            }
+            SourceKind::Sample => Arc::new(SampleSource::new(options.clone())),
+        };
+        sources.push(source);
+    }
+
+    Ok(sources)
+}
+
+/// Replaces `${NAME}` in `value` with environment variable `NAME`, so API
+/// keys stay out of the sources file. Unset variables become empty.
+fn expand_env(value: &str) -> String {
+    let mut out = String::new();
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        out.push_str(&rest[..start]);
+        match rest[start + 2..].find('}') {
+            Some(end) => {
+                out.push_str(&std::env::var(&rest[start + 2..start + 2 + end]).unwrap_or_default());
+                rest = &rest[start + 3 + end..];
+            }
+            None => {
+                out.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Body of a successful response.
+async fn response_text(response: reqwest::Response) -> Result<String, SourceError> {
+    let status = response.status();
+    if !status.is_success() {
+        return Err(SourceError::Status(status.as_u16()));
+    }
+    Ok(response.text().await?)
+}
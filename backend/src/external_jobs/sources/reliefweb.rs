@@ -0,0 +1,295 @@
+//! ReliefWeb jobs API connector.
+
+use super::html::html_text;
+use super::{JobSource, RawListing, SourceError, SourceOptions, response_text};
+use crate::external_jobs::{ExternalJob, SourceCategory};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::json;
+
+/// Fields requested for each job.
+const FIELDS: [&str; 11] = [
+    "title",
+    "body",
+    "body-html",
+    "url_alias",
+    "source",
+    "country",
+    "city",
+    "date",
+    "experience",
+    "career_categories",
+    "type",
+];
+
+/// Settings of a ReliefWeb source.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReliefWebSettings {
+    /// Countries as ISO 3166-1 alpha-3 codes, e.g. `BGD`; empty for all
+    #[serde(default)]
+    pub countries: Vec<String>,
+    /// Jobs API endpoint
+    #[serde(default = "default_api_url")]
+    pub api_url: String,
+    /// `appname` sent with each request, as registered with ReliefWeb
+    #[serde(default = "default_appname")]
+    pub appname: String,
+}
+
+fn default_api_url() -> String {
+    "https://api.reliefweb.int/v1/jobs".to_string()
+}
+
+fn default_appname() -> String {
+    "careerbridge".to_string()
+}
+
+impl Default for ReliefWebSettings {
+    fn default() -> Self {
+        Self {
+            countries: Vec::new(),
+            api_url: default_api_url(),
+            appname: default_appname(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ReliefWebResponse {
+    data: Vec<ReliefWebJob>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReliefWebJob {
+    id: String,
+    fields: ReliefWebFields,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReliefWebFields {
+    title: String,
+    #[serde(default)]
+    body: Option<String>,
+    /// The body rendered as HTML; its text reads better than the Markdown
+    #[serde(default, rename = "body-html")]
+    body_html: Option<String>,
+    /// Path of the public job posting on reliefweb.int
+    #[serde(default)]
+    url_alias: String,
+    #[serde(default)]
+    source: Vec<ReliefWebOrganization>,
+    #[serde(default)]
+    country: Vec<ReliefWebCountry>,
+    #[serde(default)]
+    city: Vec<ReliefWebCity>,
+    #[serde(default)]
+    date: ReliefWebDate,
+    #[serde(default)]
+    experience: Vec<ReliefWebExperience>,
+    #[serde(default)]
+    career_categories: Vec<ReliefWebCareer>,
+    #[serde(default, rename = "type")]
+    job_type: Vec<ReliefWebType>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReliefWebOrganization {
+    #[serde(default)]
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReliefWebCountry {
+    #[serde(default)]
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReliefWebCity {
+    #[serde(default)]
+    name: String,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct ReliefWebDate {
+    #[serde(default)]
+    created: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReliefWebExperience {
+    #[serde(default)]
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReliefWebCareer {
+    #[serde(default)]
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReliefWebType {
+    #[serde(default)]
+    name: String,
+}
+
+/// UN and NGO jobs from the ReliefWeb API, filtered by country and by the
+/// source's `categories` (ReliefWeb career categories).
+#[derive(Debug, Clone)]
+pub struct ReliefWebSource {
+    options: SourceOptions,
+    settings: ReliefWebSettings,
+}
+
+impl ReliefWebSource {
+    /// Creates the source.
+    pub fn new(options: SourceOptions, settings: ReliefWebSettings) -> Self {
+        Self { options, settings }
+    }
+
+    /// Search request sent to the API: the latest jobs matching the
+    /// countries and career categories.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use backend::external_jobs::SourceCategory;
+    /// use backend::external_jobs::sources::{ReliefWebSettings, ReliefWebSource, SourceOptions};
+    ///
+    /// let mut options = SourceOptions::new("ReliefWeb", SourceCategory::Ngo);
+    /// options.categories = vec!["Program/Project Management".to_string()];
+    /// let settings = ReliefWebSettings { countries: vec!["BGD".to_string()], ..Default::default() };
+    /// let request = ReliefWebSource::new(options, settings).request_body();
+    ///
+    /// let conditions = &request["filter"]["conditions"];
+    /// assert_eq!(conditions[0]["field"], "country.iso3");
+    /// assert_eq!(conditions[0]["value"][0], "bgd");
+    /// assert_eq!(conditions[1]["field"], "career_categories.name");
+    /// assert_eq!(request["limit"], 50);
+    /// ```
+    pub fn request_body(&self) -> serde_json::Value {
+        let mut conditions = Vec::new();
+        if !self.settings.countries.is_empty() {
+            let countries: Vec<String> = self.settings.countries.iter().map(|c| c.trim().to_lowercase()).collect();
+            conditions.push(json!({ "field": "country.iso3", "value": countries, "operator": "OR" }));
+        }
+        if !self.options.categories.is_empty() {
+            conditions.push(json!({ "field": "career_categories.name", "value": self.options.categories, "operator": "OR" }));
+        }
+
+        let mut request = json!({
+            "preset": "latest",
+            "profile": "list",
+            "limit": self.options.limit.clamp(1, 1000),
+            "fields": { "include": FIELDS },
+        });
+        if !conditions.is_empty() {
+            request["filter"] = json!({ "operator": "AND", "conditions": conditions });
+        }
+        request
+    }
+
+    /// Reads a search response.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use backend::external_jobs::SourceCategory;
+    /// use backend::external_jobs::sources::{ReliefWebSettings, ReliefWebSource, SourceOptions};
+    ///
+    /// let response = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/external_jobs/reliefweb.json"));
+    /// let source = ReliefWebSource::new(SourceOptions::new("ReliefWeb", SourceCategory::Ngo), ReliefWebSettings::default());
+    /// let jobs = source.parse(response).unwrap();
+    ///
+    /// assert_eq!(jobs.len(), 3);
+    /// assert_eq!(jobs[0].id, "reliefweb_4125371");
+    /// assert_eq!(jobs[0].company, "UNICEF");
+    /// assert_eq!(jobs[0].location, "Cox's Bazar, Bangladesh");
+    /// assert_eq!(jobs[0].url, "https://reliefweb.int/job/4125371/education-officer");
+    /// assert!(jobs[0].description.starts_with("UNICEF is seeking an Education Officer to support"));
+    /// assert!(jobs[0].description.ends_with("Bachelor's degree in Education Experience with program monitoring and Excel"));
+    /// assert_eq!(jobs[1].description, "Design the endline evaluation of a livelihoods project.");
+    /// assert_eq!(jobs[0].job_type.as_deref(), Some("Full-time"));
+    /// assert_eq!(jobs[0].skills, ["Program/Project Management"]);
+    /// assert_eq!(jobs[1].job_type.as_deref(), Some("Consultancy"));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the response is not a ReliefWeb search result.
+    pub fn parse(&self, response: &str) -> Result<Vec<ExternalJob>, SourceError> {
+        let data: ReliefWebResponse =
+            serde_json::from_str(response).map_err(|e| SourceError::Parse(e.to_string()))?;
+
+        let listings = data
+            .data
+            .into_iter()
+            .map(|job| {
+                let fields = job.fields;
+                let country = fields.country.first().map(|c| c.name.clone());
+                let city = fields.city.first().map(|c| c.name.clone());
+                let location = match (city, country) {
+                    (Some(city), Some(country)) => Some(format!("{}, {}", city, country)),
+                    (city, country) => city.or(country),
+                };
+                let url = if fields.url_alias.is_empty() {
+                    format!("https://reliefweb.int/job/{}", job.id)
+                } else {
+                    format!("https://reliefweb.int{}", fields.url_alias)
+                };
+                // "Job" is a regular position; other types (Consultancy,
+                // Internship, Volunteer Opportunity) are kept as named
+                let job_type = fields.job_type.first().map(|t| match t.name.as_str() {
+                    "Job" => "Full-time".to_string(),
+                    other => other.to_string(),
+                });
+
+                RawListing {
+                    id: format!("reliefweb_{}", job.id),
+                    title: fields.title,
+                    company: fields.source.first().map(|s| s.name.clone()),
+                    location,
+                    description: fields
+                        .body_html
+                        .map(|html| html_text(&html))
+                        .or(fields.body)
+                        .unwrap_or_default(),
+                    url,
+                    posted_date: fields.date.created,
+                    job_type: job_type.or_else(|| Some("Full-time".to_string())),
+                    experience_level: fields.experience.first().map(|e| e.name.clone()),
+                    salary: None,
+                    categories: fields.career_categories.into_iter().map(|c| c.name).collect(),
+                }
+            })
+            .collect();
+
+        Ok(self.options.finish(listings))
+    }
+}
+
+#[async_trait]
+impl JobSource for ReliefWebSource {
+    fn name(&self) -> &str {
+        &self.options.name
+    }
+
+    fn category(&self) -> SourceCategory {
+        self.options.category
+    }
+
+    fn kind(&self) -> &'static str {
+        "reliefweb"
+    }
+
+    async fn fetch(&self, client: &reqwest::Client) -> Result<Vec<ExternalJob>, SourceError> {
+        let response = client
+            .post(&self.settings.api_url)
+            .query(&[("appname", self.settings.appname.as_str())])
+            .json(&self.request_body())
+            .send()
+            .await?;
+        self.parse(&response_text(response).await?)
+    }
+}
@@ -0,0 +1,234 @@
+//! Built-in sample listings, for development and demonstrations.
+
+use super::{JobSource, SourceError, SourceOptions};
+use crate::external_jobs::{ExternalJob, SourceCategory};
+use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
+
+/// Date the sample listings were written; their posting dates are moved so
+/// that this is today, keeping them fresh enough to import.
+const SAMPLES_WRITTEN_ON: NaiveDate = NaiveDate::from_ymd_opt(2025, 11, 13).unwrap();
+
+/// The built-in sample listings of the source's category, filed under the
+/// source's name. Only built in demo mode (see [`super::demo_mode`]).
+#[derive(Debug, Clone)]
+pub struct SampleSource {
+    options: SourceOptions,
+}
+
+impl SampleSource {
+    /// Creates the source.
+    pub fn new(options: SourceOptions) -> Self {
+        Self { options }
+    }
+}
+
+#[async_trait]
+impl JobSource for SampleSource {
+    fn name(&self) -> &str {
+        &self.options.name
+    }
+
+    fn category(&self) -> SourceCategory {
+        self.options.category
+    }
+
+    fn kind(&self) -> &'static str {
+        "sample"
+    }
+
+    async fn fetch(&self, _client: &reqwest::Client) -> Result<Vec<ExternalJob>, SourceError> {
+        Ok(sample_jobs(self.options.category)
+            .into_iter()
+            .take(self.options.limit)
+            .map(|job| {
+                let shift = Utc::now().date_naive() - SAMPLES_WRITTEN_ON;
+                let posted_date = NaiveDate::parse_from_str(&job.posted_date, "%Y-%m-%d")
+                    .map(|date| (date + shift).format("%Y-%m-%d").to_string())
+                    .unwrap_or(job.posted_date);
+                ExternalJob { source: self.options.name.clone(), posted_date, ..job }
+            })
+            .collect())
+    }
+}
+
+/// Sample listings of `category`.
+fn sample_jobs(category: SourceCategory) -> Vec<ExternalJob> {
+    match category {
+        SourceCategory::Ngo => sample_ngo_jobs(),
+        SourceCategory::Government => sample_govt_jobs(),
+        SourceCategory::Local => sample_local_jobs(),
+    }
+}
+
+/// Sample NGO/UN jobs for Bangladesh (ReliefWeb-style data)
+fn sample_ngo_jobs() -> Vec<ExternalJob> {
+    vec![
+        ExternalJob {
+            id: "reliefweb_001".to_string(),
+            title: "Program Manager - Education".to_string(),
+            company: "UNICEF Bangladesh".to_string(),
+            location: "Dhaka, Bangladesh".to_string(),
+            description: "UNICEF is seeking a Program Manager to lead education initiatives in Bangladesh. Responsible for managing education programs, coordinating with government stakeholders, and ensuring quality education access for vulnerable children.".to_string(),
+            url: "https://www.unicef.org/bangladesh/en/jobs".to_string(),
+            posted_date: "2025-11-10".to_string(),
+            source: "ReliefWeb".to_string(),
+            job_type: Some("Full-time".to_string()),
+            experience_level: Some("Mid-Senior Level".to_string()),
+            skills: vec!["Program Management".to_string(), "Education".to_string(), "Stakeholder Engagement".to_string(), "M&E".to_string()],
+            salary: Some("Competitive (UN Scale)".to_string()),
+        },
+        ExternalJob {
+            id: "reliefweb_002".to_string(),
+            title: "Field Coordinator - Humanitarian Response".to_string(),
+            company: "BRAC".to_string(),
+            location: "Cox's Bazar, Bangladesh".to_string(),
+            description: "BRAC is recruiting a Field Coordinator for humanitarian operations in Cox's Bazar. Lead field teams, coordinate relief activities, and ensure effective service delivery to displaced populations.".to_string(),
+            url: "https://www.brac.net/opportunities/job-opportunities".to_string(),
+            posted_date: "2025-11-12".to_string(),
+            source: "ReliefWeb".to_string(),
+            job_type: Some("Full-time".to_string()),
+            experience_level: Some("Mid Level".to_string()),
+            skills: vec!["Humanitarian Response".to_string(), "Field Coordination".to_string(), "Emergency Management".to_string()],
+            salary: Some("BDT 100,000 - 150,000".to_string()),
+        },
+        ExternalJob {
+            id: "reliefweb_003".to_string(),
+            title: "Health Officer - Primary Healthcare".to_string(),
+            company: "WHO Bangladesh".to_string(),
+            location: "Sylhet, Bangladesh".to_string(),
+            description: "World Health Organization seeks a Health Officer to support primary healthcare programs. Work with local health authorities to strengthen healthcare systems and improve service delivery.".to_string(),
+            url: "https://www.who.int/careers".to_string(),
+            posted_date: "2025-11-08".to_string(),
+            source: "ReliefWeb".to_string(),
+            job_type: Some("Contract".to_string()),
+            experience_level: Some("Junior-Mid Level".to_string()),
+            skills: vec!["Public Health".to_string(), "Healthcare Management".to_string(), "Community Health".to_string()],
+            salary: Some("Competitive".to_string()),
+        },
+        ExternalJob {
+            id: "reliefweb_004".to_string(),
+            title: "Monitoring & Evaluation Officer".to_string(),
+            company: "Save the Children Bangladesh".to_string(),
+            location: "Dhaka, Bangladesh".to_string(),
+            description: "Join Save the Children as M&E Officer to design and implement monitoring systems for child protection programs. Conduct assessments, analyze data, and prepare reports for donors.".to_string(),
+            url: "https://www.savethechildren.net/careers".to_string(),
+            posted_date: "2025-11-11".to_string(),
+            source: "ReliefWeb".to_string(),
+            job_type: Some("Full-time".to_string()),
+            experience_level: Some("Mid Level".to_string()),
+            skills: vec!["M&E".to_string(), "Data Analysis".to_string(), "Reporting".to_string(), "Child Protection".to_string()],
+            salary: Some("BDT 80,000 - 120,000".to_string()),
+        },
+        ExternalJob {
+            id: "reliefweb_005".to_string(),
+            title: "Livelihood Specialist".to_string(),
+            company: "Oxfam Bangladesh".to_string(),
+            location: "Rangpur, Bangladesh".to_string(),
+            description: "Oxfam is hiring a Livelihood Specialist to support income generation programs for vulnerable communities. Design and implement livelihood interventions, provide technical support, and build capacity.".to_string(),
+            url: "https://www.oxfam.org.uk/jobs/".to_string(),
+            posted_date: "2025-11-09".to_string(),
+            source: "ReliefWeb".to_string(),
+            job_type: Some("Full-time".to_string()),
+            experience_level: Some("Mid Level".to_string()),
+            skills: vec!["Livelihood Development".to_string(), "Rural Development".to_string(), "Capacity Building".to_string()],
+            salary: Some("BDT 90,000 - 130,000".to_string()),
+        },
+        ExternalJob {
+            id: "reliefweb_006".to_string(),
+            title: "WASH Engineer".to_string(),
+            company: "Action Against Hunger".to_string(),
+            location: "Khulna, Bangladesh".to_string(),
+            description: "Seeking WASH Engineer to design and implement water, sanitation, and hygiene projects. Conduct assessments, develop technical designs, and supervise construction activities.".to_string(),
+            url: "https://www.actionagainsthunger.org/careers".to_string(),
+            posted_date: "2025-11-07".to_string(),
+            source: "ReliefWeb".to_string(),
+            job_type: Some("Contract".to_string()),
+            experience_level: Some("Mid-Senior Level".to_string()),
+            skills: vec!["WASH".to_string(), "Civil Engineering".to_string(), "Project Management".to_string(), "Technical Design".to_string()],
+            salary: Some("Competitive".to_string()),
+        },
+    ]
+}
+
+/// Sample data for Bangladesh government job portals
+fn sample_govt_jobs() -> Vec<ExternalJob> {
+    vec![
+        ExternalJob {
+            id: "bpsc_001".to_string(),
+            title: "Assistant Engineer - BPSC".to_string(),
+            company: "Bangladesh Public Service Commission".to_string(),
+            location: "Dhaka, Bangladesh".to_string(),
+            description: "Hiring for Assistant Engineer position in various government departments. Bachelor's degree in Engineering required.".to_string(),
+            url: "http://bpsc.gov.bd".to_string(),
+            posted_date: "2025-11-01".to_string(),
+            source: "BPSC".to_string(),
+            job_type: Some("Government".to_string()),
+            experience_level: Some("Entry Level".to_string()),
+            skills: vec!["Engineering".to_string(), "Problem Solving".to_string()],
+            salary: Some("BDT 35,000 - 45,000".to_string()),
+        },
+        ExternalJob {
+            id: "ngoab_001".to_string(),
+            title: "Program Officer - Development".to_string(),
+            company: "NGO Affairs Bureau Registered Organization".to_string(),
+            location: "Chittagong, Bangladesh".to_string(),
+            description: "Seeking Program Officer for community development projects. Experience in rural development preferred.".to_string(),
+            url: "http://ngoab.gov.bd".to_string(),
+            posted_date: "2025-11-10".to_string(),
+            source: "NGOAB".to_string(),
+            job_type: Some("Full-time".to_string()),
+            experience_level: Some("Mid Level".to_string()),
+            skills: vec!["Project Management".to_string(), "Community Development".to_string(), "Report Writing".to_string()],
+            salary: Some("BDT 40,000 - 60,000".to_string()),
+        },
+    ]
+}
+
+/// Sample data for local Bangladesh job boards
+fn sample_local_jobs() -> Vec<ExternalJob> {
+    vec![
+        ExternalJob {
+            id: "bdjobs_001".to_string(),
+            title: "Software Developer - FinTech".to_string(),
+            company: "bKash Limited".to_string(),
+            location: "Dhaka, Bangladesh".to_string(),
+            description: "Looking for experienced software developers to work on mobile financial services platform. Strong Java/Kotlin skills required.".to_string(),
+            url: "https://bdjobs.com".to_string(),
+            posted_date: "2025-11-12".to_string(),
+            source: "BDJobs".to_string(),
+            job_type: Some("Full-time".to_string()),
+            experience_level: Some("Mid Level".to_string()),
+            skills: vec!["Java".to_string(), "Kotlin".to_string(), "Android".to_string(), "APIs".to_string()],
+            salary: Some("BDT 80,000 - 120,000".to_string()),
+        },
+        ExternalJob {
+            id: "prothomalo_001".to_string(),
+            title: "Digital Marketing Specialist".to_string(),
+            company: "Grameen Phone".to_string(),
+            location: "Dhaka, Bangladesh".to_string(),
+            description: "Join our marketing team to drive digital campaigns for Bangladesh's leading telecom operator.".to_string(),
+            url: "https://jobs.prothomalo.com".to_string(),
+            posted_date: "2025-11-13".to_string(),
+            source: "Prothom Alo Jobs".to_string(),
+            job_type: Some("Full-time".to_string()),
+            experience_level: Some("Junior".to_string()),
+            skills: vec!["Digital Marketing".to_string(), "SEO".to_string(), "Social Media".to_string(), "Analytics".to_string()],
+            salary: Some("BDT 50,000 - 70,000".to_string()),
+        },
+        ExternalJob {
+            id: "chakri_001".to_string(),
+            title: "Training Coordinator".to_string(),
+            company: "SEIP - Skills for Employment Investment Program".to_string(),
+            location: "Sylhet, Bangladesh".to_string(),
+            description: "Coordinate vocational training programs for youth employment. Government-funded skill development initiative.".to_string(),
+            url: "https://seip.gov.bd".to_string(),
+            posted_date: "2025-11-08".to_string(),
+            source: "Chakri.com".to_string(),
+            job_type: Some("Contract".to_string()),
+            experience_level: Some("Junior".to_string()),
+            skills: vec!["Training".to_string(), "Coordination".to_string(), "Communication".to_string()],
+            salary: Some("BDT 35,000 - 50,000".to_string()),
+        },
+    ]
+}
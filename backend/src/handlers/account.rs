@@ -0,0 +1,340 @@
+//! Account data export and deletion handlers.
+//!
+//! Users can download everything stored about them as a zip archive, and
+//! delete their account. Deletion is scheduled after a grace period (see
+//! [`crate::account`]) and can be cancelled until then.
+
+use super::profile::attachment;
+use super::types::{AccountDeletionStatus, DeleteAccountPayload};
+use crate::AppState;
+use crate::account::deletion_grace_period;
+use crate::auth::AuthUser;
+use crate::errors::{AppError, AppResult};
+use crate::security::verify_password;
+use axum::{
+    Json,
+    extract::State,
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use chrono::Utc;
+use serde_json::{Value, json};
+use std::io::{Cursor, Write};
+use tracing::{error, info, warn};
+use zip::write::SimpleFileOptions;
+
+/// Tables with a `user_id` column, exported one JSON file each.
+const EXPORT_TABLES: [&str; 19] = [
+    "application_tracking",
+    "user_progress",
+    "career_roadmaps",
+    "notifications",
+    "notification_preferences",
+    "skill_assessments",
+    "quiz_attempts",
+    "cv_versions",
+    "cv_parses",
+    "cv_parse_entries",
+    "tailored_cvs",
+    "cover_letters",
+    "education_entries",
+    "work_experiences",
+    "projects",
+    "public_profiles",
+    "profile_history",
+    "saved_searches",
+    "saved_search_matches",
+];
+
+/// How recently an account without a password must have signed in to
+/// delete it.
+const REAUTH_WINDOW_SECS: i64 = 10 * 60;
+
+/// Downloads all of the authenticated user's data as a zip archive.
+///
+/// The archive holds `users.json` (the account, without the password hash),
+/// one JSON file per table with the user's rows, the background jobs queued
+/// with the user's data in `background_jobs.json`, the original CV files
+/// under `cv_files/`, and a `manifest.json` listing what was exported.
+///
+/// # Endpoint
+/// `GET /api/me/export`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - User not found in database
+/// - Database operation fails
+pub async fn export_my_data(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+) -> AppResult<Response> {
+    let pool = &app_state.db_pool;
+    info!("Exporting data for user {}", auth_user.user_id);
+
+    let account: Value = sqlx::query_scalar!(
+        r#"SELECT to_jsonb(u) - 'password_hash' AS "account!" FROM users u WHERE id = $1"#,
+        auth_user.user_id
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or(AppError::NotFound)?;
+
+    let mut entries = vec![("users.json".to_string(), to_pretty_json(&json!([account])))];
+    let mut table_counts = serde_json::Map::new();
+    table_counts.insert("users".to_string(), json!(1));
+
+    for table in EXPORT_TABLES {
+        // Table names come from the constant above, never from input
+        let rows: Value = sqlx::query_scalar(&format!(
+            "SELECT COALESCE(json_agg(t), '[]'::json) FROM (SELECT * FROM {} WHERE user_id = $1 ORDER BY 1) t",
+            table
+        ))
+        .bind(auth_user.user_id)
+        .fetch_one(pool)
+        .await?;
+        table_counts.insert(table.to_string(), json!(rows.as_array().map_or(0, Vec::len)));
+        entries.push((format!("{}.json", table), to_pretty_json(&rows)));
+    }
+
+    // Jobs have no user_id column; AI jobs carry it in their payload
+    let jobs: Value = sqlx::query_scalar!(
+        r#"
+        SELECT COALESCE(json_agg(t), '[]'::json) AS "jobs!" FROM (
+            SELECT id, kind, payload, status, attempts, last_error, created_at, finished_at
+            FROM background_jobs
+            WHERE payload->'payload'->>'user_id' = $1::UUID::TEXT
+            ORDER BY id
+        ) t
+        "#,
+        auth_user.user_id
+    )
+    .fetch_one(pool)
+    .await?;
+    table_counts.insert("background_jobs".to_string(), json!(jobs.as_array().map_or(0, Vec::len)));
+    entries.push(("background_jobs.json".to_string(), to_pretty_json(&jobs)));
+
+    let versions = sqlx::query!(
+        "SELECT version_number, file_name, storage_key FROM cv_versions WHERE user_id = $1 ORDER BY version_number",
+        auth_user.user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut files = Vec::new();
+    let mut missing_files = Vec::new();
+    for version in versions {
+        let path = format!("cv_files/v{}_{}", version.version_number, safe_file_name(&version.file_name));
+        let storage_key = version.storage_key;
+        match app_state.cv_storage.get(&storage_key).await {
+            Ok(bytes) => files.push((path, bytes)),
+            Err(e) => {
+                warn!("CV file {} missing from export for user {}: {}", storage_key, auth_user.user_id, e);
+                missing_files.push(path);
+            }
+        }
+    }
+
+    let manifest = json!({
+        "exported_at": Utc::now(),
+        "user_id": auth_user.user_id,
+        "tables": table_counts,
+        "cv_files": files.iter().map(|(path, _)| path).collect::<Vec<_>>(),
+        "missing_cv_files": missing_files,
+    });
+    entries.insert(0, ("manifest.json".to_string(), to_pretty_json(&manifest)));
+
+    let archive = tokio::task::spawn_blocking(move || build_zip(entries, files))
+        .await
+        .map_err(|e| {
+            error!("Export archive task failed: {}", e);
+            AppError::InternalServerError
+        })?
+        .map_err(|e| {
+            error!("Failed to build export archive: {}", e);
+            AppError::InternalServerError
+        })?;
+
+    let file_name = format!("careerbridge-export-{}.zip", Utc::now().format("%Y-%m-%d"));
+    info!("Exported {} bytes for user {}", archive.len(), auth_user.user_id);
+
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "application/zip".to_string()),
+            (header::CONTENT_DISPOSITION, attachment(&file_name)),
+        ],
+        archive,
+    )
+        .into_response())
+}
+
+/// Schedules the authenticated user's account for deletion.
+///
+/// Accounts with a password must confirm it; accounts that sign in only
+/// through Google or GitHub must have signed in within the last 10 minutes.
+/// The account stays usable and can be restored until the grace period
+/// (`ACCOUNT_DELETION_GRACE_DAYS`, default 30) ends; its public profile is
+/// hidden in the meantime. A background task then permanently deletes the
+/// account, its CV files and AI-generated content.
+///
+/// Requesting deletion again keeps the original schedule.
+///
+/// # Endpoint
+/// `DELETE /api/me`
+///
+/// # Request Body
+/// ```json
+/// { "password": "current password" }
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - The password is missing (400) or wrong (403)
+/// - A password-less account has not signed in recently (403)
+/// - Database operation fails
+pub async fn delete_account(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    payload: Option<Json<DeleteAccountPayload>>,
+) -> AppResult<(StatusCode, Json<AccountDeletionStatus>)> {
+    let pool = &app_state.db_pool;
+    let payload = payload.map(|Json(p)| p).unwrap_or_default();
+
+    let password_hash: String = sqlx::query_scalar!("SELECT password_hash FROM users WHERE id = $1", auth_user.user_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    if password_hash.is_empty() {
+        let signed_in_secs_ago = Utc::now().timestamp() - auth_user.issued_at;
+        if signed_in_secs_ago > REAUTH_WINDOW_SECS {
+            warn!("Account deletion refused for user {}: sign-in too old", auth_user.user_id);
+            return Err(AppError::Forbidden(
+                "Please sign in again to confirm deleting your account".to_string(),
+            ));
+        }
+    } else {
+        let password = payload.password.filter(|p| !p.is_empty()).ok_or_else(|| {
+            AppError::ValidationError("Password is required to delete your account".to_string())
+        })?;
+        if !verify_password(password_hash, password).await? {
+            warn!("Account deletion refused for user {}: wrong password", auth_user.user_id);
+            return Err(AppError::Forbidden("Password is incorrect".to_string()));
+        }
+    }
+
+    let scheduled_for = Utc::now() + deletion_grace_period();
+    let status = sqlx::query_as!(
+        AccountDeletionStatus,
+        r#"
+        UPDATE users
+        SET deletion_requested_at = COALESCE(deletion_requested_at, CURRENT_TIMESTAMP),
+            deletion_scheduled_for = COALESCE(deletion_scheduled_for, $1),
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $2
+        RETURNING deletion_requested_at, deletion_scheduled_for
+        "#,
+        scheduled_for,
+        auth_user.user_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    info!(
+        "Account deletion scheduled for user {} at {:?}",
+        auth_user.user_id, status.deletion_scheduled_for
+    );
+
+    Ok((StatusCode::ACCEPTED, Json(status)))
+}
+
+/// Retrieves whether the authenticated user's account is scheduled for deletion.
+///
+/// # Endpoint
+/// `GET /api/me/deletion`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - User not found in database
+/// - Database operation fails
+pub async fn get_deletion_status(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+) -> AppResult<Json<AccountDeletionStatus>> {
+    sqlx::query_as!(
+        AccountDeletionStatus,
+        "SELECT deletion_requested_at, deletion_scheduled_for FROM users WHERE id = $1",
+        auth_user.user_id
+    )
+    .fetch_optional(&app_state.db_pool)
+    .await?
+    .map(Json)
+    .ok_or(AppError::NotFound)
+}
+
+/// Cancels a scheduled account deletion.
+///
+/// # Endpoint
+/// `POST /api/me/deletion/cancel`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - User not found in database (including accounts already purged)
+/// - Database operation fails
+pub async fn cancel_account_deletion(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+) -> AppResult<Json<AccountDeletionStatus>> {
+    let status = sqlx::query_as!(
+        AccountDeletionStatus,
+        r#"
+        UPDATE users
+        SET deletion_requested_at = NULL, deletion_scheduled_for = NULL, updated_at = CURRENT_TIMESTAMP
+        WHERE id = $1
+        RETURNING deletion_requested_at, deletion_scheduled_for
+        "#,
+        auth_user.user_id
+    )
+    .fetch_optional(&app_state.db_pool)
+    .await?
+    .ok_or(AppError::NotFound)?;
+
+    info!("Account deletion cancelled for user {}", auth_user.user_id);
+    Ok(Json(status))
+}
+
+fn to_pretty_json(value: &Value) -> Vec<u8> {
+    serde_json::to_vec_pretty(value).unwrap_or_default()
+}
+
+/// Keeps only characters that are safe in a zip entry name.
+fn safe_file_name(name: &str) -> String {
+    let name = name.rsplit(['/', '\\']).next().unwrap_or(name);
+    let safe: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || "._- ".contains(c) { c } else { '_' })
+        .collect();
+    if safe.trim_matches(['.', ' ']).is_empty() {
+        "cv".to_string()
+    } else {
+        safe
+    }
+}
+
+fn build_zip(entries: Vec<(String, Vec<u8>)>, files: Vec<(String, Vec<u8>)>) -> zip::result::ZipResult<Vec<u8>> {
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    for (name, content) in entries.into_iter().chain(files) {
+        zip.start_file(name, options)?;
+        zip.write_all(&content)?;
+    }
+    Ok(zip.finish()?.into_inner())
+}
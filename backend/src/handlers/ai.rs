@@ -3,16 +3,50 @@
 //! Provides endpoints for AI-powered features like skill extraction,
 //! roadmap generation, and more.
 
-use axum::{Json, extract::State};
+use axum::{Json, extract::State, http::StatusCode};
 use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
 
 use crate::{
     AppState,
-    ai::types::{AIActionRequest, AIActionResponse},
+    ai::{
+        AIService,
+        redaction::Redactor,
+        types::{AIActionRequest, AIActionResponse},
+    },
     auth::AuthUser,
     errors::AppError,
+    events::publish_ai_job_completed,
+    notifications::{NewNotification, NotificationType, crossed_milestone, notify},
+    queue::{self, Task},
 };
 
+use super::types::{AiJobAccepted, AiJobStatus, RoadmapJob};
+
+/// Loads a user's AI privacy settings.
+///
+/// Returns a redactor that also masks the user's own name and email, and
+/// whether they allow CV-derived data to be sent to third-party AI providers.
+pub(super) async fn load_ai_privacy(pool: &PgPool, user_id: Uuid) -> Result<(Redactor, bool), AppError> {
+    let user = sqlx::query!(
+        "SELECT full_name, email, share_cv_with_ai FROM users WHERE id = $1",
+        user_id
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or(AppError::NotFound)?;
+
+    Ok((Redactor::for_user(&user.full_name, &user.email), user.share_cv_with_ai))
+}
+
+/// Error returned when a user has opted out of sharing CV data with AI providers.
+pub(super) fn cv_sharing_disabled() -> AppError {
+    AppError::Forbidden(
+        "You have opted out of sharing CV data with AI providers. Enable it in your privacy settings to use this feature.".to_string(),
+    )
+}
+
 /// Process an AI action
 ///
 /// # Endpoint
@@ -39,8 +73,12 @@ use crate::{
 /// # Providers
 /// - `gemini`: Google Gemini API (default)
 /// - `groq`: Groq API
+///
+/// Users who opted out of sharing CV data with AI providers get `403` for
+/// requests carrying CV or profile data (see
+/// [`AIActionRequest::includes_cv_data`]).
 pub async fn process_ai_action(
-    _auth_user: AuthUser,
+    auth_user: AuthUser,
     State(state): State<AppState>,
     Json(request): Json<AIActionRequest>,
 ) -> Result<Json<AIActionResponse>, AppError> {
@@ -50,12 +88,17 @@ pub async fn process_ai_action(
         request.provider
     );
 
+    let (redactor, share_cv_with_ai) = load_ai_privacy(&state.db_pool, auth_user.user_id).await?;
+    if !share_cv_with_ai && request.includes_cv_data() {
+        return Err(cv_sharing_disabled());
+    }
+
     let ai_service = state
         .ai_service
         .as_ref()
         .ok_or_else(|| AppError::ConfigurationError("AI service not configured".to_string()))?;
 
-    let response = ai_service.process_action(request).await?;
+    let response = ai_service.process_action_with(request, &redactor).await?;
 
     Ok(Json(response))
 }
@@ -83,6 +126,11 @@ pub async fn extract_and_save_skills(
         .and_then(|v| v.as_str())
         .ok_or_else(|| AppError::ValidationError("cv_text is required".to_string()))?;
 
+    let (redactor, share_cv_with_ai) = load_ai_privacy(&state.db_pool, auth_user.user_id).await?;
+    if !share_cv_with_ai {
+        return Err(cv_sharing_disabled());
+    }
+
     let provider_str = payload
         .get("provider")
         .and_then(|v| v.as_str())
@@ -111,7 +159,7 @@ pub async fn extract_and_save_skills(
         .ok_or_else(|| AppError::ConfigurationError("AI service not configured".to_string()))?;
 
     tracing::info!("Calling AI service to extract skills, update_profile={}", update_profile);
-    let response = ai_service.process_action(ai_request).await?;
+    let response = ai_service.process_action_with(ai_request, &redactor).await?;
 
     tracing::info!("AI response received, success={}", response.success);
     
@@ -144,10 +192,8 @@ pub async fn extract_and_save_skills(
                             Some(name.to_string())
                         }
                         // Fallback: try as plain string
-                        else if let Some(name) = skill.as_str() {
-                            Some(name.to_string())
-                        } else {
-                            None
+                        else {
+                            skill.as_str().map(|name| name.to_string())
                         }
                     })
                     .collect()
@@ -174,7 +220,7 @@ pub async fn extract_and_save_skills(
         let user_id = auth_user.user_id;
         let existing_user =
             sqlx::query_as::<_, crate::models::User>("SELECT * FROM users WHERE id = $1")
-                .bind(&user_id)
+                .bind(user_id)
                 .fetch_one(&state.db_pool)
                 .await?;
 
@@ -210,7 +256,7 @@ pub async fn extract_and_save_skills(
         .bind(&combined_skills)
         .bind(&combined_roles)
         .bind(cv_text)
-        .bind(&user_id)
+        .bind(user_id)
         .execute(&state.db_pool)
         .await?;
 
@@ -231,6 +277,9 @@ pub async fn extract_and_save_skills(
 
 /// Generate a personalized learning roadmap
 ///
+/// The roadmap is generated by a background job; the response carries its
+/// ID, and an `ai_job_completed` event links to the saved roadmap.
+///
 /// # Endpoint
 /// `POST /api/ai/roadmap`
 ///
@@ -244,11 +293,14 @@ pub async fn extract_and_save_skills(
 ///   "include_current_skills": true
 /// }
 /// ```
+///
+/// # Response
+/// `202 Accepted` with `{"job_id": 42, "job": "roadmap"}`
 pub async fn generate_roadmap(
     auth_user: AuthUser,
     State(state): State<AppState>,
     Json(payload): Json<serde_json::Value>,
-) -> Result<Json<serde_json::Value>, AppError> {
+) -> Result<(StatusCode, Json<AiJobAccepted>), AppError> {
     let target_role = payload
         .get("target_role")
         .or_else(|| payload.get("tech_stack"))
@@ -277,11 +329,96 @@ pub async fn generate_roadmap(
         .and_then(|v| v.as_bool())
         .unwrap_or(true);
 
-    // Get user's current skills if requested
-    let (current_skills, user_skills_json) = if include_current_skills {
+    if state.ai_service.is_none() {
+        return Err(AppError::ConfigurationError("AI service not configured".to_string()));
+    }
+
+    let task = Task::GenerateRoadmap(RoadmapJob {
+        user_id: auth_user.user_id,
+        target_role: target_role.to_string(),
+        timeframe_months,
+        learning_hours_per_week,
+        provider: if provider_str == "groq" {
+            crate::ai::types::AIProvider::Groq
+        } else {
+            crate::ai::types::AIProvider::Gemini
+        },
+        include_current_skills,
+    });
+    let job_id = queue::enqueue(&state.db_pool, &task).await?;
+    tracing::info!("Queued roadmap job {} for user {}", job_id, auth_user.user_id);
+
+    Ok((StatusCode::ACCEPTED, Json(AiJobAccepted { job_id, job: "roadmap" })))
+}
+
+/// Get the status of one of the user's AI jobs
+///
+/// Lets clients that missed the `ai_job_completed` or `ai_job_failed`
+/// event find out how a job queued by the roadmap, cover letter or CV
+/// enrichment endpoints ended.
+///
+/// # Endpoint
+/// `GET /api/ai/jobs/{id}`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Job doesn't exist, isn't an AI job or belongs to another user (404)
+/// - Database operation fails
+pub async fn get_ai_job(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    axum::extract::Path(job_id): axum::extract::Path<i64>,
+) -> Result<Json<AiJobStatus>, AppError> {
+    let row = sqlx::query!(
+        r#"
+        SELECT id, payload, status, attempts, max_attempts, last_error, created_at, finished_at
+        FROM background_jobs
+        WHERE id = $1 AND kind = ANY($2) AND payload->'payload'->>'user_id' = $3::UUID::TEXT
+        "#,
+        job_id,
+        &queue::AI_TASK_KINDS.map(String::from),
+        auth_user.user_id
+    )
+    .fetch_optional(&state.db_pool)
+    .await?
+    .ok_or(AppError::NotFound)?;
+
+    let (owner, job) = serde_json::from_value::<Task>(row.payload)
+        .ok()
+        .and_then(|task| task.ai_job())
+        .ok_or(AppError::NotFound)?;
+    if owner != auth_user.user_id {
+        return Err(AppError::NotFound);
+    }
+
+    Ok(Json(AiJobStatus {
+        job_id: row.id,
+        job,
+        status: row.status,
+        attempts: row.attempts,
+        max_attempts: row.max_attempts,
+        error: row.last_error,
+        created_at: row.created_at,
+        finished_at: row.finished_at,
+    }))
+}
+
+/// Generates and saves the roadmap of background job `job_id`.
+pub(crate) async fn run_roadmap_job(
+    pool: &PgPool,
+    ai_service: &AIService,
+    job_id: i64,
+    job: &RoadmapJob,
+) -> Result<(), AppError> {
+    // Get user's current skills if requested; they come from the CV, so not
+    // for users who opted out of sharing it
+    let (redactor, share_cv_with_ai) = load_ai_privacy(pool, job.user_id).await?;
+    let (current_skills, user_skills_json) = if job.include_current_skills && share_cv_with_ai {
         let user = sqlx::query_as::<_, crate::models::User>("SELECT * FROM users WHERE id = $1")
-            .bind(auth_user.user_id)
-            .fetch_one(&state.db_pool)
+            .bind(job.user_id)
+            .fetch_one(pool)
             .await?;
 
         let skills_str = user.skills.join(", ");
@@ -296,26 +433,17 @@ pub async fn generate_roadmap(
     if let Some(ref skills) = current_skills {
         parameters.insert("current_skills".to_string(), json!(skills));
     }
-    parameters.insert("timeframe_months".to_string(), json!(timeframe_months));
-    parameters.insert("learning_hours_per_week".to_string(), json!(learning_hours_per_week));
+    parameters.insert("timeframe_months".to_string(), json!(job.timeframe_months));
+    parameters.insert("learning_hours_per_week".to_string(), json!(job.learning_hours_per_week));
 
     let ai_request = AIActionRequest {
         action: crate::ai::types::ActionType::GenerateRoadmap,
-        provider: if provider_str == "groq" {
-            crate::ai::types::AIProvider::Groq
-        } else {
-            crate::ai::types::AIProvider::Gemini
-        },
-        input: target_role.to_string(),
+        provider: job.provider.clone(),
+        input: job.target_role.clone(),
         parameters: Some(serde_json::Value::Object(parameters)),
     };
 
-    let ai_service = state
-        .ai_service
-        .as_ref()
-        .ok_or_else(|| AppError::ConfigurationError("AI service not configured".to_string()))?;
-
-    let response = ai_service.process_action(ai_request).await?;
+    let response = ai_service.process_action_with(ai_request, &redactor).await?;
 
     if !response.success {
         return Err(AppError::ExternalServiceError(
@@ -346,31 +474,25 @@ pub async fn generate_roadmap(
         ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) 
         RETURNING id",
     )
-    .bind(auth_user.user_id)
-    .bind(format!("Roadmap to {}", target_role))
-    .bind(target_role)
+    .bind(job.user_id)
+    .bind(format!("Roadmap to {}", job.target_role))
+    .bind(&job.target_role)
     .bind(&response.data)
     .bind(provider_string)
-    .bind(timeframe_months as i32)
-    .bind(learning_hours_per_week as i32)
+    .bind(job.timeframe_months as i32)
+    .bind(job.learning_hours_per_week as i32)
     .bind(&user_skills_json)
     .bind(&project_suggestions)
     .bind(job_application_timing)
-    .fetch_one(&state.db_pool)
+    .fetch_one(pool)
     .await?;
 
-    Ok(Json(json!({
-        "success": true,
-        "roadmap": response.data,
-        "roadmap_id": roadmap_id,
-        "provider": response.provider,
-        "message": "Roadmap generated and saved successfully",
-        "metadata": {
-            "timeframe_months": timeframe_months,
-            "learning_hours_per_week": learning_hours_per_week,
-            "job_application_timing": job_application_timing
-        }
-    })))
+    tracing::info!("Roadmap {} saved for user {} (job {})", roadmap_id, job.user_id, job_id);
+
+    let link = format!("/api/ai/roadmaps/{}", roadmap_id);
+    publish_ai_job_completed(pool, job.user_id, job_id, "roadmap", &link).await;
+
+    Ok(())
 }
 
 /// Generate professional summary for CV/profile
@@ -399,21 +521,38 @@ pub async fn generate_professional_summary(
         .and_then(|v| v.as_str())
         .unwrap_or("gemini");
 
+    // Projects and skills are derived from the CV, so respect the opt-out
+    let (redactor, share_cv_with_ai) = load_ai_privacy(&state.db_pool, auth_user.user_id).await?;
+    if !share_cv_with_ai {
+        return Err(cv_sharing_disabled());
+    }
+
     // Get user profile
     let user = sqlx::query_as::<_, crate::models::User>("SELECT * FROM users WHERE id = $1")
         .bind(auth_user.user_id)
         .fetch_one(&state.db_pool)
         .await?;
 
-    // Build context from user profile
-    let context = format!(
-        "User Profile:\nSkills: {}\nProjects: {}\nTarget Roles: {}\nEducation: {}\nExperience Level: {:?}",
-        user.skills.join(", "),
-        user.projects.join(", "),
-        user.target_roles.join(", "),
-        user.education_level.as_deref().unwrap_or("Not specified"),
-        user.experience_level
-    );
+    let entries = super::profile_entries::load_profile_entries(&state.db_pool, auth_user.user_id).await?;
+
+    // Build context from user profile; structured entries replace the free-text fields
+    let mut context_lines = vec![
+        "User Profile:".to_string(),
+        format!("Skills: {}", user.skills.join(", ")),
+        format!("Target Roles: {}", user.target_roles.join(", ")),
+        format!("Experience Level: {:?}", user.experience_level),
+    ];
+    if entries.education.is_empty() {
+        context_lines.push(format!(
+            "Education: {}",
+            user.education_level.as_deref().unwrap_or("Not specified")
+        ));
+    }
+    if entries.projects.is_empty() {
+        context_lines.push(format!("Projects: {}", user.projects.join(", ")));
+    }
+    context_lines.extend(entries.context_lines());
+    let context = context_lines.join("\n");
 
     let prompt = format!(
         "Generate a professional summary for a CV/LinkedIn profile based on the following information:\n\n{}\n\nCreate a compelling 2-3 sentence professional summary that highlights key strengths, experience, and career goals. Make it engaging and professional.",
@@ -440,7 +579,7 @@ pub async fn generate_professional_summary(
         .as_ref()
         .ok_or_else(|| AppError::ConfigurationError("AI service not configured".to_string()))?;
 
-    let response = ai_service.process_action(ai_request).await?;
+    let response = ai_service.process_action_with(ai_request, &redactor).await?;
 
     Ok(Json(json!({
         "success": response.success,
@@ -476,6 +615,11 @@ pub async fn improve_project_descriptions(
         .and_then(|v| v.as_str())
         .unwrap_or("gemini");
 
+    let (redactor, share_cv_with_ai) = load_ai_privacy(&state.db_pool, auth_user.user_id).await?;
+    if !share_cv_with_ai {
+        return Err(cv_sharing_disabled());
+    }
+
     // Get user skills for context
     let user = sqlx::query_as::<_, crate::models::User>("SELECT * FROM users WHERE id = $1")
         .bind(auth_user.user_id)
@@ -513,7 +657,7 @@ pub async fn improve_project_descriptions(
         .as_ref()
         .ok_or_else(|| AppError::ConfigurationError("AI service not configured".to_string()))?;
 
-    let response = ai_service.process_action(ai_request).await?;
+    let response = ai_service.process_action_with(ai_request, &redactor).await?;
 
     Ok(Json(json!({
         "success": response.success,
@@ -549,6 +693,12 @@ pub async fn get_profile_suggestions(
         .and_then(|v| v.as_str())
         .unwrap_or("gemini");
 
+    // The suggestions are built from the whole profile, so respect the opt-out
+    let (redactor, share_cv_with_ai) = load_ai_privacy(&state.db_pool, auth_user.user_id).await?;
+    if !share_cv_with_ai {
+        return Err(cv_sharing_disabled());
+    }
+
     // Get user profile
     let user = sqlx::query_as::<_, crate::models::User>("SELECT * FROM users WHERE id = $1")
         .bind(auth_user.user_id)
@@ -583,7 +733,7 @@ pub async fn get_profile_suggestions(
         .as_ref()
         .ok_or_else(|| AppError::ConfigurationError("AI service not configured".to_string()))?;
 
-    let response = ai_service.process_action(ai_request).await?;
+    let response = ai_service.process_action_with(ai_request, &redactor).await?;
 
     Ok(Json(json!({
         "success": response.success,
@@ -626,12 +776,22 @@ pub async fn ask_career_mentor(
         .fetch_one(&state.db_pool)
         .await?;
 
-    let context = format!(
-        "User's current skills: {}\nTarget roles: {}\nExperience level: {:?}",
-        user.skills.join(", "),
-        user.target_roles.join(", "),
-        user.experience_level
-    );
+    // The profile comes from the CV; opted-out users get answers without it
+    let (redactor, share_cv_with_ai) = load_ai_privacy(&state.db_pool, auth_user.user_id).await?;
+    let mut context = String::new();
+    if share_cv_with_ai {
+        context = format!(
+            "User's current skills: {}\nTarget roles: {}\nExperience level: {:?}",
+            user.skills.join(", "),
+            user.target_roles.join(", "),
+            user.experience_level
+        );
+        let entries = super::profile_entries::load_profile_entries(&state.db_pool, auth_user.user_id).await?;
+        for line in entries.context_lines() {
+            context.push('\n');
+            context.push_str(&line);
+        }
+    }
 
     let ai_request = AIActionRequest {
         action: crate::ai::types::ActionType::AskQuestion,
@@ -649,7 +809,7 @@ pub async fn ask_career_mentor(
         .as_ref()
         .ok_or_else(|| AppError::ConfigurationError("AI service not configured".to_string()))?;
 
-    let response = ai_service.process_action(ai_request).await?;
+    let response = ai_service.process_action_with(ai_request, &redactor).await?;
 
     Ok(Json(json!({
         "success": response.success,
@@ -742,13 +902,26 @@ pub async fn enhanced_career_mentor(
         .fetch_one(&state.db_pool)
         .await?;
 
-    let mut context_parts = vec![
-        format!("User Profile:\n- Name: {}", user.full_name),
-        format!("- Current Skills: {}", user.skills.join(", ")),
-        format!("- Target Roles: {}", user.target_roles.join(", ")),
-        format!("- Experience Level: {:?}", user.experience_level),
-        format!("- Projects: {}", user.projects.join(", ")),
-    ];
+    let (redactor, share_cv_with_ai) = load_ai_privacy(&state.db_pool, auth_user.user_id).await?;
+
+    // The profile and the skill gap built from it come from the CV; leave
+    // them out for opted-out users
+    let include_skill_gap = include_skill_gap && share_cv_with_ai;
+    let mut context_parts = Vec::new();
+    if share_cv_with_ai {
+        context_parts.extend([
+            "User Profile:".to_string(),
+            format!("- Name: {}", user.full_name),
+            format!("- Current Skills: {}", user.skills.join(", ")),
+            format!("- Target Roles: {}", user.target_roles.join(", ")),
+            format!("- Experience Level: {:?}", user.experience_level),
+        ]);
+        let entries = super::profile_entries::load_profile_entries(&state.db_pool, auth_user.user_id).await?;
+        if entries.projects.is_empty() {
+            context_parts.push(format!("- Projects: {}", user.projects.join(", ")));
+        }
+        context_parts.extend(entries.context_lines().into_iter().map(|line| format!("- {}", line)));
+    }
 
     // Add skill gap analysis if requested
     if include_skill_gap {
@@ -767,7 +940,7 @@ pub async fn enhanced_career_mentor(
                 let role_lower = role.to_lowercase();
                 role_keywords.iter()
                     .find(|&&keyword| role_lower.contains(keyword))
-                    .map(|&s| s)
+                    .copied()
                     .or_else(|| user.target_roles.first().map(|s| s.as_str()))
                     .unwrap_or("Software Developer")
             } else {
@@ -785,7 +958,7 @@ pub async fn enhanced_career_mentor(
             let question_lower = question.to_lowercase();
             role_keywords.iter()
                 .find(|&&keyword| question_lower.contains(keyword))
-                .map(|&s| s)
+                .copied()
                 .or_else(|| user.target_roles.first().map(|s| s.as_str()))
                 .unwrap_or("Software Developer")
         };
@@ -1046,7 +1219,7 @@ pub async fn enhanced_career_mentor(
     }
 
     // Add CV data if requested and available
-    if include_cv_data && user.raw_cv_text.is_some() {
+    if include_cv_data && share_cv_with_ai && user.raw_cv_text.is_some() {
         let cv_length = user.raw_cv_text.as_ref().map(|cv| cv.len()).unwrap_or(0);
         context_parts.push(format!(
             "\nCV Data: User has uploaded a CV ({} characters). Can extract skills using /api/ai/extract-skills endpoint.",
@@ -1123,7 +1296,7 @@ pub async fn enhanced_career_mentor(
         .as_ref()
         .ok_or_else(|| AppError::ConfigurationError("AI service not configured".to_string()))?;
 
-    let response = ai_service.process_action(ai_request).await?;
+    let response = ai_service.process_action_with(ai_request, &redactor).await?;
 
     // Extract the answer string from the response data
     let answer_text = if let Some(answer_str) = response.data.get("answer").and_then(|a| a.as_str()) {
@@ -1320,7 +1493,7 @@ pub async fn update_roadmap_progress(
     let mut param_index = 3;
     
     if let Some(progress) = progress_percentage {
-        if progress < 0 || progress > 100 {
+        if !(0..=100).contains(&progress) {
             return Err(AppError::ValidationError("Progress percentage must be between 0 and 100".to_string()));
         }
         // We inject the integer directly since it's safe and simplifies binding indices
@@ -1357,8 +1530,36 @@ pub async fn update_roadmap_progress(
         query_builder = query_builder.bind(note_text);
     }
     
+    // Progress before the update, to notice a milestone being reached
+    let previous = match progress_percentage {
+        Some(_) => sqlx::query!(
+            "SELECT progress_percentage, title FROM career_roadmaps WHERE id = $1 AND user_id = $2",
+            roadmap_id,
+            auth_user.user_id
+        )
+        .fetch_optional(&state.db_pool)
+        .await?
+        .map(|roadmap| (roadmap.progress_percentage, roadmap.title)),
+        None => None,
+    };
+
     let result = query_builder.fetch_optional(&state.db_pool).await?;
 
+    if let (Some(_), Some(new), Some((old, title))) = (result, progress_percentage, previous)
+        && let Some(milestone) = crossed_milestone(old.unwrap_or(0), new)
+    {
+        let notification = NewNotification {
+            kind: NotificationType::RoadmapMilestone,
+            title: format!("Roadmap {}% complete", milestone),
+            message: format!("You have reached {}% of \"{}\". Keep going!", milestone, title),
+            link: Some(format!("/api/ai/roadmaps/{}", roadmap_id)),
+            dedup_key: Some(format!("roadmap:{}:{}", roadmap_id, milestone)),
+        };
+        if let Err(e) = notify(&state.db_pool, auth_user.user_id, notification).await {
+            tracing::warn!("Roadmap milestone notification failed for roadmap {}: {}", roadmap_id, e);
+        }
+    }
+
     match result {
         Some(_) => Ok(Json(json!({
             "success": true,
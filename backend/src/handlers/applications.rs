@@ -1,8 +1,10 @@
 //! Job application tracking handlers.
 
 use axum::{extract::{State, Path}, Json};
-use tracing::{info, debug};
+use tracing::{info, debug, warn};
 use crate::models::ApplicationTracking;
+use crate::events::{EventKind, publish_or_warn};
+use crate::notifications::{NewNotification, NotificationType, notify};
 use crate::errors::AppResult;
 use crate::auth::AuthUser;
 use crate::AppState;
@@ -10,7 +12,8 @@ use super::types::{CreateApplicationPayload, UpdateApplicationPayload};
 
 /// Creates a new job application record.
 /// 
-/// Tracks when a user applies to a job with optional notes.
+/// Tracks when a user applies to a job with optional notes. The latest
+/// tailored CV generated for the job, if any, is linked to the application.
 /// 
 /// # Errors
 /// 
@@ -40,8 +43,23 @@ pub async fn create_application(
     .fetch_one(&app_state.db_pool)
     .await?;
 
+    if let Some(application_id) = application.id
+        && let Some(tailored_cv_id) = super::tailored_cv::link_latest_tailored_cv(
+            &app_state.db_pool,
+            auth_user.user_id,
+            payload.job_id,
+            application_id,
+        )
+        .await?
+    {
+        debug!("Linked tailored CV {} to application {}", tailored_cv_id, application_id);
+    }
+
     info!("Application created successfully: application_id={}, user_id={}, job_id={}",
           application.id.unwrap_or(0), auth_user.user_id, payload.job_id);
+
+    let data = serde_json::to_value(&application).unwrap_or_default();
+    publish_or_warn(&app_state.db_pool, auth_user.user_id, EventKind::ApplicationUpdated, data).await;
     
     Ok(Json(application))
 }
@@ -103,6 +121,24 @@ pub async fn update_application(
     info!("Updating application: application_id={}, user_id={}, new_status={}",
           application_id, auth_user.user_id, payload.status);
     
+    let mut tx = app_state.db_pool.begin().await?;
+
+    // Status before the update, to notify the user of a change
+    let previous = sqlx::query!(
+        r#"
+        SELECT a.status, j.job_title, j.company
+        FROM application_tracking a
+        JOIN jobs j ON j.id = a.job_id
+        WHERE a.id = $1 AND a.user_id = $2
+        FOR UPDATE OF a
+        "#,
+        application_id,
+        auth_user.user_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .map(|row| (row.status, row.job_title, row.company));
+
     sqlx::query!(
         r#"
         UPDATE application_tracking
@@ -114,10 +150,37 @@ pub async fn update_application(
         application_id,
         auth_user.user_id
     )
-    .execute(&app_state.db_pool)
+    .execute(&mut *tx)
     .await?;
 
+    tx.commit().await?;
+
     info!("Application updated successfully: application_id={}", application_id);
+
+    if let Some((old_status, job_title, company)) = previous {
+        let data = serde_json::json!({
+            "id": application_id,
+            "status": payload.status,
+            "previous_status": old_status,
+        });
+        publish_or_warn(&app_state.db_pool, auth_user.user_id, EventKind::ApplicationUpdated, data).await;
+
+        if old_status != payload.status {
+            let notification = NewNotification {
+                kind: NotificationType::ApplicationStatus,
+                title: format!("Application status: {}", payload.status),
+                message: format!(
+                    "Your application for {} at {} moved from {} to {}.",
+                    job_title, company, old_status, payload.status
+                ),
+                link: Some("/api/applications".to_string()),
+                dedup_key: None,
+            };
+            if let Err(e) = notify(&app_state.db_pool, auth_user.user_id, notification).await {
+                warn!("Application status notification failed for application {}: {}", application_id, e);
+            }
+        }
+    }
     
     Ok(Json(serde_json::json!({
         "message": "Application updated successfully"
@@ -51,11 +51,11 @@ pub async fn register(
     .await
     .map_err(|e| {
         // Check for unique constraint violation (duplicate email)
-        if let Some(db_err) = e.as_database_error() {
-            if db_err.is_unique_violation() {
-                warn!("Registration failed: Email already exists - {}", payload.email);
-                return AppError::DatabaseError(e);
-            }
+        if let Some(db_err) = e.as_database_error()
+            && db_err.is_unique_violation()
+        {
+            warn!("Registration failed: Email already exists - {}", payload.email);
+            return AppError::DatabaseError(e);
         }
         error!("Database error during registration for {}: {}", payload.email, e);
         AppError::DatabaseError(e)
@@ -108,6 +108,7 @@ pub async fn login(
         SELECT 
             id, full_name, email, education_level,
             experience_level as "experience_level: ExperienceLevel",
+            experience_years_min, experience_years_max,
             preferred_track as "preferred_track: CareerTrack",
             profile_completed as "profile_completed!",
             skills, projects, target_roles, raw_cv_text, password_hash
@@ -141,6 +142,11 @@ pub async fn login(
     
     info!("Login successful for user: user_id={}, email={}", user.id, user.email);
 
+    let entries = super::profile_entries::load_profile_entries(&app_state.db_pool, user.id).await?;
+    let profile_version = sqlx::query_scalar!("SELECT profile_version FROM users WHERE id = $1", user.id)
+        .fetch_one(&app_state.db_pool)
+        .await?;
+
     Ok(Json(LoginResponse {
         token,
         user: UserProfile {
@@ -150,10 +156,16 @@ pub async fn login(
             profile_completed: user.profile_completed,
             education_level: user.education_level,
             experience_level: user.experience_level,
+            experience_years_min: user.experience_years_min,
+            experience_years_max: user.experience_years_max,
             preferred_track: user.preferred_track,
             skills: user.skills,
             projects: user.projects,
             target_roles: user.target_roles,
+            education: entries.education,
+            work_experience: entries.work_experience,
+            project_details: entries.projects,
+            profile_version,
         },
     }))
 }
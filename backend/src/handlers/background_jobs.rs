@@ -0,0 +1,116 @@
+//! Background job administration handlers.
+//!
+//! Lets administrators inspect the queue (see [`crate::queue`]), in
+//! particular dead jobs, and retry them once the cause is fixed.
+
+use super::types::{BackgroundJobList, BackgroundJobQuery};
+use crate::AppState;
+use crate::auth::AdminUser;
+use crate::errors::{AppError, AppResult};
+use crate::models::BackgroundJob;
+use crate::queue::JobStatus;
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use tracing::info;
+
+/// Default and largest number of jobs listed.
+const DEFAULT_JOB_LIMIT: i64 = 50;
+const MAX_JOB_LIMIT: i64 = 200;
+
+/// Lists background jobs with the number of jobs in each status.
+///
+/// # Endpoint
+/// `GET /api/admin/background-jobs?status=dead&kind=check_saved_searches&limit=50`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated (401) or not an administrator (403)
+/// - Database operation fails
+pub async fn list_background_jobs(
+    _admin: AdminUser,
+    State(app_state): State<AppState>,
+    Query(query): Query<BackgroundJobQuery>,
+) -> AppResult<Json<BackgroundJobList>> {
+    let pool = &app_state.db_pool;
+    let counts = sqlx::query!(r#"SELECT status, COUNT(*) AS "count!" FROM background_jobs GROUP BY status"#)
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|row| (row.status, row.count))
+        .collect();
+
+    let status = query.status.unwrap_or(JobStatus::Dead);
+    let jobs = sqlx::query_as!(
+        BackgroundJob,
+        r#"
+        SELECT id, kind, payload, status, attempts, max_attempts, run_at, locked_at,
+               locked_by, last_error, dedup_key, created_at, updated_at, finished_at
+        FROM background_jobs
+        WHERE status = $1 AND ($2::TEXT IS NULL OR kind = $2)
+        ORDER BY updated_at DESC, id DESC
+        LIMIT $3
+        "#,
+        status.as_str(),
+        query.kind.as_deref().map(str::trim),
+        query.limit.unwrap_or(DEFAULT_JOB_LIMIT).clamp(1, MAX_JOB_LIMIT)
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(Json(BackgroundJobList { counts, jobs }))
+}
+
+/// Retries a dead job: it becomes pending with its attempts reset.
+///
+/// # Endpoint
+/// `POST /api/admin/background-jobs/{id}/retry`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated (401) or not an administrator (403)
+/// - Job not found (404)
+/// - Job is not dead (409)
+/// - A pending or running job already has the same dedup key (409)
+/// - Database operation fails
+pub async fn retry_background_job(
+    AdminUser(admin): AdminUser,
+    State(app_state): State<AppState>,
+    Path(job_id): Path<i64>,
+) -> AppResult<Json<BackgroundJob>> {
+    let pool = &app_state.db_pool;
+    let retried = sqlx::query_as!(
+        BackgroundJob,
+        r#"
+        UPDATE background_jobs
+        SET status = 'pending', attempts = 0, run_at = CURRENT_TIMESTAMP, finished_at = NULL,
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $1 AND status = 'dead'
+        RETURNING id, kind, payload, status, attempts, max_attempts, run_at, locked_at,
+                  locked_by, last_error, dedup_key, created_at, updated_at, finished_at
+        "#,
+        job_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    match retried {
+        Some(job) => {
+            info!("Admin {} retried background job {} ({})", admin.user_id, job.id, job.kind);
+            Ok(Json(job))
+        }
+        None => {
+            let exists: bool = sqlx::query_scalar!(r#"SELECT EXISTS(SELECT 1 FROM background_jobs WHERE id = $1) AS "exists!""#, job_id)
+                .fetch_one(pool)
+                .await?;
+            if exists {
+                Err(AppError::Conflict("Only dead jobs can be retried".to_string()))
+            } else {
+                Err(AppError::NotFound)
+            }
+        }
+    }
+}
@@ -0,0 +1,451 @@
+//! Cover letter handlers.
+//!
+//! Cover letters are generated by AI for a specific application from the
+//! job posting and the user's matching skills and projects, stored with the
+//! application so the user can edit them, and exported through the PDF
+//! generator.
+
+use super::ai::{cv_sharing_disabled, load_ai_privacy};
+use super::profile::attachment;
+use super::types::{
+    AiJobAccepted, CoverLetterExportQuery, CoverLetterJob, GenerateCoverLetterPayload, UpdateCoverLetterPayload,
+};
+use crate::AppState;
+use crate::ai::AIService;
+use crate::ai::types::{AIActionRequest, AIProvider, ActionType};
+use crate::auth::AuthUser;
+use crate::cv::document::{CvDocument, description_points};
+use crate::cv::pdf;
+use crate::cv::tailor::tailor_document;
+use crate::errors::{AppError, AppResult};
+use crate::events::publish_ai_job_completed;
+use crate::models::CoverLetter;
+use crate::queue::{self, Task};
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use serde_json::json;
+use sqlx::PgPool;
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+use validator::Validate;
+
+/// Number of projects and of work experiences quoted in the prompt.
+const MAX_PROMPT_ENTRIES: usize = 3;
+
+/// Job details a cover letter is written for.
+#[derive(sqlx::FromRow)]
+struct ApplicationJob {
+    job_title: String,
+    company: String,
+    job_description: String,
+    required_skills: Vec<String>,
+    responsibilities: Vec<String>,
+    requirements: Vec<String>,
+}
+
+/// Loads the job of one of the user's applications.
+async fn load_application_job(pool: &PgPool, application_id: i32, user_id: Uuid) -> AppResult<ApplicationJob> {
+    sqlx::query_as!(
+        ApplicationJob,
+        r#"
+        SELECT j.job_title, j.company, j.job_description, j.required_skills,
+               j.responsibilities, j.requirements
+        FROM application_tracking a
+        JOIN jobs j ON j.id = a.job_id
+        WHERE a.id = $1 AND a.user_id = $2
+        "#,
+        application_id,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or(AppError::NotFound)
+}
+
+/// Loads the cover letter of one of the user's applications.
+async fn load_cover_letter(pool: &PgPool, application_id: i32, user_id: Uuid) -> AppResult<CoverLetter> {
+    sqlx::query_as!(
+        CoverLetter,
+        r#"
+        SELECT id, application_id, user_id, content, ai_provider, edited_at, created_at, updated_at
+        FROM cover_letters
+        WHERE application_id = $1 AND user_id = $2
+        "#,
+        application_id,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or(AppError::NotFound)
+}
+
+/// Builds the cover letter prompt from the job and the CV tailored to it.
+fn cover_letter_prompt(
+    job: &ApplicationJob,
+    document: &CvDocument,
+    request: &CoverLetterJob,
+    matched_skills: &[String],
+) -> String {
+    let list = |items: &[String]| {
+        if items.is_empty() {
+            "Not specified".to_string()
+        } else {
+            format!("- {}", items.join("\n- "))
+        }
+    };
+    // Entries are already ordered by relevance to the job
+    let entries = |entries: &[crate::cv::CvEntry]| {
+        let quoted: Vec<String> = entries
+            .iter()
+            .take(MAX_PROMPT_ENTRIES)
+            .map(|entry| {
+                let points = description_points(entry);
+                let details: Vec<String> = entry
+                    .organization
+                    .iter()
+                    .cloned()
+                    .chain(points.into_iter().take(3))
+                    .filter(|s| !s.trim().is_empty())
+                    .collect();
+                if details.is_empty() {
+                    entry.title.clone()
+                } else {
+                    format!("{} ({})", entry.title, details.join("; "))
+                }
+            })
+            .collect();
+        list(&quoted)
+    };
+    let cv = &document.cv;
+
+    format!(
+        "Write a cover letter for the job application below.\n\n\
+         Job: {} at {}\n\
+         Description: {}\n\
+         Responsibilities:\n{}\n\
+         Requirements:\n{}\n\n\
+         Candidate: {}\n\
+         Matching skills: {}\n\
+         Other skills: {}\n\
+         Relevant projects:\n{}\n\
+         Work experience:\n{}\n\
+         Summary: {}\n\n\
+         Tone: {}\n\
+         Additional points to mention: {}\n\n\
+         Write 3-4 short paragraphs separated by blank lines, starting with \"Dear Hiring Manager,\" and ending with a sign-off and the candidate's name. \
+         Connect the candidate's matching skills and projects to the responsibilities and requirements. \
+         Only use facts stated above; do not invent employers, numbers or qualifications. \
+         Return the letter text only as the content.",
+        job.job_title,
+        job.company,
+        job.job_description,
+        list(&job.responsibilities),
+        list(&job.requirements),
+        document.name(),
+        if matched_skills.is_empty() { "None listed".to_string() } else { matched_skills.join(", ") },
+        cv.skills.iter().filter(|s| !matched_skills.contains(s)).cloned().collect::<Vec<_>>().join(", "),
+        entries(&cv.projects),
+        entries(&cv.experience),
+        cv.summary.as_deref().unwrap_or("Not specified"),
+        request.tone.as_deref().filter(|t| !t.trim().is_empty()).unwrap_or("professional"),
+        request.instructions.as_deref().filter(|t| !t.trim().is_empty()).unwrap_or("None"),
+    )
+}
+
+/// Generates a cover letter for an application with AI.
+///
+/// The letter is generated by a background job; the response carries its
+/// ID, and an `ai_job_completed` event links to the saved letter. The
+/// prompt combines the job's title, company, description, responsibilities
+/// and requirements with the user's skills that match the job and their
+/// most relevant projects and work experience. The letter is stored on the
+/// application; generating again replaces it.
+///
+/// # Endpoint
+/// `POST /api/applications/{id}/cover-letter`
+///
+/// # Request Body
+/// ```json
+/// {
+///   "provider": "gemini",
+///   "tone": "enthusiastic",
+///   "instructions": "Mention that I can relocate to Dhaka"
+/// }
+/// ```
+/// All fields are optional.
+///
+/// # Response
+/// `202 Accepted` with `{"job_id": 42, "job": "cover_letter"}`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Application doesn't exist or doesn't belong to user (404)
+/// - Tone or instructions are too long (400)
+/// - User opted out of sharing CV data with AI providers (403)
+/// - AI service is not configured
+/// - Database operation fails
+pub async fn generate_cover_letter(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Path(application_id): Path<i32>,
+    payload: Option<Json<GenerateCoverLetterPayload>>,
+) -> AppResult<(StatusCode, Json<AiJobAccepted>)> {
+    let payload = payload.map(|Json(p)| p).unwrap_or_default();
+    info!(
+        "Generating cover letter: application_id={}, user_id={}",
+        application_id, auth_user.user_id
+    );
+
+    payload.validate().map_err(|e| {
+        warn!("Cover letter validation failed for user {}: {}", auth_user.user_id, e);
+        e
+    })?;
+
+    load_application_job(&app_state.db_pool, application_id, auth_user.user_id).await?;
+
+    // Skills and projects come from the CV, so respect the opt-out
+    let (_, share_cv_with_ai) = load_ai_privacy(&app_state.db_pool, auth_user.user_id).await?;
+    if !share_cv_with_ai {
+        return Err(cv_sharing_disabled());
+    }
+    if app_state.ai_service.is_none() {
+        return Err(AppError::ConfigurationError("AI service not configured".to_string()));
+    }
+
+    let task = Task::GenerateCoverLetter(CoverLetterJob {
+        user_id: auth_user.user_id,
+        application_id,
+        provider: payload.provider.unwrap_or(AIProvider::Gemini),
+        tone: payload.tone,
+        instructions: payload.instructions,
+    });
+    let job_id = queue::enqueue(&app_state.db_pool, &task).await?;
+    info!("Queued cover letter job {} for application {}", job_id, application_id);
+
+    Ok((StatusCode::ACCEPTED, Json(AiJobAccepted { job_id, job: "cover_letter" })))
+}
+
+/// Generates and saves the cover letter of background job `job_id`.
+pub(crate) async fn run_cover_letter_job(
+    pool: &PgPool,
+    ai_service: &AIService,
+    job_id: i64,
+    request: &CoverLetterJob,
+) -> AppResult<()> {
+    let job = load_application_job(pool, request.application_id, request.user_id).await?;
+
+    // The user may have opted out since the job was queued
+    let (redactor, share_cv_with_ai) = load_ai_privacy(pool, request.user_id).await?;
+    if !share_cv_with_ai {
+        return Err(cv_sharing_disabled());
+    }
+
+    let mut document = super::cv::load_cv_document(pool, request.user_id).await?;
+    let tailoring = tailor_document(&mut document, &job.job_title, &job.required_skills);
+    debug!("Cover letter uses {} matched skills", tailoring.matched_skills.len());
+
+    let ai_request = AIActionRequest {
+        action: ActionType::GenerateContent,
+        provider: request.provider.clone(),
+        input: cover_letter_prompt(&job, &document, request, &tailoring.matched_skills),
+        parameters: Some(json!({
+            "content_type": "cover_letter",
+            "tone": request.tone.as_deref().unwrap_or("professional"),
+            "length": "medium"
+        })),
+    };
+    let response = ai_service.process_action_with(ai_request, &redactor).await?;
+
+    let content = response
+        .data
+        .get("content")
+        .and_then(|c| c.as_str())
+        .map(str::trim)
+        .filter(|c| response.success && !c.is_empty())
+        .ok_or_else(|| {
+            error!("AI provider returned no cover letter: {:?}", response.message);
+            AppError::ExternalServiceError(
+                response.message.clone().unwrap_or_else(|| "AI provider returned no cover letter".to_string()),
+            )
+        })?;
+
+    let provider_string = match response.provider {
+        AIProvider::Gemini => "gemini",
+        AIProvider::Groq => "groq",
+    };
+
+    let letter = sqlx::query_as!(
+        CoverLetter,
+        r#"
+        INSERT INTO cover_letters (application_id, user_id, content, ai_provider)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (application_id) DO UPDATE
+        SET content = EXCLUDED.content,
+            ai_provider = EXCLUDED.ai_provider,
+            edited_at = NULL,
+            updated_at = CURRENT_TIMESTAMP
+        RETURNING id, application_id, user_id, content, ai_provider, edited_at, created_at, updated_at
+        "#,
+        request.application_id,
+        request.user_id,
+        content,
+        provider_string
+    )
+    .fetch_one(pool)
+    .await?;
+
+    info!(
+        "Cover letter saved: cover_letter_id={}, application_id={}, job_id={}",
+        letter.id, request.application_id, job_id
+    );
+
+    let link = format!("/api/applications/{}/cover-letter", request.application_id);
+    publish_ai_job_completed(pool, request.user_id, job_id, "cover_letter", &link).await;
+
+    Ok(())
+}
+
+/// Retrieves the cover letter of an application.
+///
+/// # Endpoint
+/// `GET /api/applications/{id}/cover-letter`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Application doesn't exist, doesn't belong to user or has no cover letter (404)
+/// - Database operation fails
+pub async fn get_cover_letter(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Path(application_id): Path<i32>,
+) -> AppResult<Json<CoverLetter>> {
+    load_cover_letter(&app_state.db_pool, application_id, auth_user.user_id)
+        .await
+        .map(Json)
+}
+
+/// Saves the user's edits to a cover letter.
+///
+/// # Endpoint
+/// `PUT /api/applications/{id}/cover-letter`
+///
+/// # Request Body
+/// ```json
+/// { "content": "Dear Hiring Manager,\n\n..." }
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Content is empty or longer than 20000 characters (400)
+/// - Application doesn't exist, doesn't belong to user or has no cover letter (404)
+/// - Database operation fails
+pub async fn update_cover_letter(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Path(application_id): Path<i32>,
+    Json(payload): Json<UpdateCoverLetterPayload>,
+) -> AppResult<Json<CoverLetter>> {
+    info!(
+        "Updating cover letter: application_id={}, user_id={}",
+        application_id, auth_user.user_id
+    );
+
+    payload.validate().map_err(|e| {
+        warn!("Cover letter validation failed for user {}: {}", auth_user.user_id, e);
+        e
+    })?;
+
+    let letter = sqlx::query_as!(
+        CoverLetter,
+        r#"
+        UPDATE cover_letters
+        SET content = $1, edited_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP
+        WHERE application_id = $2 AND user_id = $3
+        RETURNING id, application_id, user_id, content, ai_provider, edited_at, created_at, updated_at
+        "#,
+        payload.content.trim(),
+        application_id,
+        auth_user.user_id
+    )
+    .fetch_optional(&app_state.db_pool)
+    .await?
+    .ok_or(AppError::NotFound)?;
+
+    Ok(Json(letter))
+}
+
+/// Exports the cover letter of an application as a PDF.
+///
+/// The letter is printed under the same name and contact header as the
+/// generated CV, in the chosen template, dated when it was last changed.
+///
+/// # Endpoint
+/// `GET /api/applications/{id}/cover-letter/pdf?template=classic|modern|ats`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Application doesn't exist, doesn't belong to user or has no cover letter (404)
+/// - The template is unknown (400)
+/// - Rendering fails
+pub async fn export_cover_letter_pdf(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Path(application_id): Path<i32>,
+    Query(query): Query<CoverLetterExportQuery>,
+) -> AppResult<Response> {
+    info!(
+        "Exporting cover letter: application_id={}, user_id={}",
+        application_id, auth_user.user_id
+    );
+
+    let letter = load_cover_letter(&app_state.db_pool, application_id, auth_user.user_id).await?;
+    let job = load_application_job(&app_state.db_pool, application_id, auth_user.user_id).await?;
+    let mut document = super::cv::load_cv_document(&app_state.db_pool, auth_user.user_id).await?;
+    document.headline = None;
+
+    let date = letter
+        .updated_at
+        .unwrap_or_else(chrono::Utc::now)
+        .format("%-d %B %Y")
+        .to_string();
+    let file_name = format!(
+        "{}_{}_Cover_Letter.pdf",
+        document.name().split_whitespace().collect::<Vec<_>>().join("_"),
+        job.company.split_whitespace().collect::<Vec<_>>().join("_")
+    );
+    let template = query.template;
+    let content = letter.content;
+
+    let bytes = tokio::task::spawn_blocking(move || pdf::render_letter_pdf(&document, &date, &content, template))
+        .await
+        .map_err(|e| {
+            error!("Cover letter rendering task failed: {}", e);
+            AppError::InternalServerError
+        })?
+        .map_err(|e| {
+            error!("Failed to render cover letter: {}", e);
+            AppError::InternalServerError
+        })?;
+
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "application/pdf".to_string()),
+            (header::CONTENT_DISPOSITION, attachment(&file_name)),
+        ],
+        bytes,
+    )
+        .into_response())
+}
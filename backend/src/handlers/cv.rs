@@ -0,0 +1,651 @@
+//! Structured CV review handlers.
+//!
+//! Uploaded CVs are parsed deterministically into contact info, summary,
+//! education, experience, projects, skills and certifications. Users can
+//! review and correct the result; AI extraction is an optional enrichment.
+
+use super::ai::{cv_sharing_disabled, load_ai_privacy};
+use super::types::{AiJobAccepted, CvEnrichmentJob, EnrichParsedCvPayload, ParsedCvResponse, ReviewParsedCvPayload};
+use crate::AppState;
+use crate::ai::AIService;
+use crate::ai::types::{AIActionRequest, AIProvider, ActionType};
+use crate::auth::AuthUser;
+use crate::cv::document::CvDocument;
+use crate::cv::json_resume::{JsonResume, from_json_resume};
+use crate::cv::{ContactInfo, CvEntry, ParsedCv};
+use crate::errors::{AppError, AppResult};
+use crate::events::publish_ai_job_completed;
+use crate::models::{CareerTrack, CvParse, CvParseEntry};
+use crate::queue::{self, Task};
+use axum::{Json, extract::State, http::StatusCode};
+use sqlx::{PgConnection, PgPool};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Section names used in `cv_parse_entries.section`.
+const SECTIONS: [&str; 4] = ["education", "experience", "project", "certification"];
+
+/// Replaces the stored parse for a user.
+///
+/// `reviewed` marks the data as confirmed by the user; a fresh parse from an
+/// upload clears the review and enrichment timestamps.
+pub(super) async fn store_parsed_cv(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    cv: &ParsedCv,
+    reviewed: bool,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO cv_parses (user_id, full_name, email, phone, location, links, summary, skills, reviewed_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, CASE WHEN $9 THEN CURRENT_TIMESTAMP END)
+        ON CONFLICT (user_id) DO UPDATE SET
+            full_name = EXCLUDED.full_name,
+            email = EXCLUDED.email,
+            phone = EXCLUDED.phone,
+            location = EXCLUDED.location,
+            links = EXCLUDED.links,
+            summary = EXCLUDED.summary,
+            skills = EXCLUDED.skills,
+            reviewed_at = EXCLUDED.reviewed_at,
+            enriched_at = CASE WHEN $9 THEN cv_parses.enriched_at END,
+            updated_at = CURRENT_TIMESTAMP
+        "#,
+        user_id,
+        cv.contact.full_name.as_deref(),
+        cv.contact.email.as_deref(),
+        cv.contact.phone.as_deref(),
+        cv.contact.location.as_deref(),
+        &cv.contact.links,
+        cv.summary.as_deref(),
+        &cv.skills,
+        reviewed
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query!("DELETE FROM cv_parse_entries WHERE user_id = $1", user_id)
+        .execute(&mut *conn)
+        .await?;
+
+    let sections = [&cv.education, &cv.experience, &cv.projects, &cv.certifications];
+    for (section, entries) in SECTIONS.iter().zip(sections) {
+        for (position, entry) in entries.iter().enumerate() {
+            sqlx::query!(
+                r#"
+                INSERT INTO cv_parse_entries
+                    (user_id, section, position, title, organization, location, start_date, end_date, is_current, description)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                "#,
+                user_id,
+                section,
+                position as i32,
+                &entry.title,
+                entry.organization.as_deref(),
+                entry.location.as_deref(),
+                entry.start_date,
+                entry.end_date,
+                entry.is_current,
+                entry.description.as_deref()
+            )
+            .execute(&mut *conn)
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Copies the parsed skills onto the CV version the parse came from.
+///
+/// Keeps the per-version skill list in step with the user's review and
+/// AI enrichment, so switching versions restores the reviewed skills.
+pub(super) async fn sync_version_skills(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    skills: &[String],
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        UPDATE cv_versions SET skills = $1
+        WHERE id = (SELECT cv_version_id FROM cv_parses WHERE user_id = $2)
+        "#,
+        skills,
+        user_id
+    )
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+/// Loads the stored parse for a user, if the user has uploaded a CV.
+pub(super) async fn load_parsed_cv(pool: &PgPool, user_id: Uuid) -> AppResult<Option<ParsedCvResponse>> {
+    let Some(parse) = sqlx::query_as!(CvParse, "SELECT * FROM cv_parses WHERE user_id = $1", user_id)
+        .fetch_optional(pool)
+        .await?
+    else {
+        return Ok(None);
+    };
+
+    let entries = sqlx::query_as!(
+        CvParseEntry,
+        "SELECT * FROM cv_parse_entries WHERE user_id = $1 ORDER BY section, position",
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut cv = ParsedCv {
+        contact: ContactInfo {
+            full_name: parse.full_name,
+            email: parse.email,
+            phone: parse.phone,
+            location: parse.location,
+            links: parse.links,
+        },
+        summary: parse.summary,
+        skills: parse.skills,
+        ..ParsedCv::default()
+    };
+
+    for entry in entries {
+        let target = match entry.section.as_str() {
+            "education" => &mut cv.education,
+            "experience" => &mut cv.experience,
+            "project" => &mut cv.projects,
+            _ => &mut cv.certifications,
+        };
+        target.push(CvEntry {
+            title: entry.title,
+            organization: entry.organization,
+            location: entry.location,
+            start_date: entry.start_date,
+            end_date: entry.end_date,
+            is_current: entry.is_current,
+            description: entry.description,
+        });
+    }
+
+    Ok(Some(ParsedCvResponse {
+        cv,
+        reviewed: parse.reviewed_at.is_some(),
+        reviewed_at: parse.reviewed_at,
+        enriched_at: parse.enriched_at,
+        cv_version_id: parse.cv_version_id,
+        updated_at: parse.updated_at,
+    }))
+}
+
+/// Builds the content of a generated CV.
+///
+/// Name, email and skills come from the profile; phone, location, links,
+/// summary and the entry sections come from the parsed CV. Education and
+/// work experience entered on the profile replace the CV's sections, profile
+/// projects come before the CV's, and the education level fills in when there
+/// is no education at all.
+pub(super) async fn load_cv_document(pool: &PgPool, user_id: Uuid) -> AppResult<CvDocument> {
+    let user = sqlx::query!(
+        r#"
+        SELECT full_name, email, education_level,
+               preferred_track as "preferred_track: CareerTrack",
+               skills, projects, target_roles
+        FROM users
+        WHERE id = $1
+        "#,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or(AppError::NotFound)?;
+    let (full_name, email, education_level, preferred_track, skills, projects, target_roles) = (
+        user.full_name,
+        user.email,
+        user.education_level,
+        user.preferred_track,
+        user.skills,
+        user.projects,
+        user.target_roles,
+    );
+
+    let mut cv = load_parsed_cv(pool, user_id)
+        .await?
+        .map(|parsed| parsed.cv)
+        .unwrap_or_default();
+
+    cv.contact.full_name = Some(full_name);
+    cv.contact.email = Some(email);
+    if !skills.is_empty() {
+        cv.skills = skills;
+    }
+
+    let entries = super::profile_entries::load_profile_entries(pool, user_id).await?;
+    if !entries.education.is_empty() {
+        cv.education = entries.education_cv_entries();
+    }
+    if !entries.work_experience.is_empty() {
+        cv.experience = entries.experience_cv_entries();
+    }
+    let mut merged = entries.project_cv_entries();
+    let legacy = projects.into_iter().map(|title| CvEntry { title, ..CvEntry::default() });
+    for project in cv.projects.drain(..).chain(legacy) {
+        if !merged.iter().any(|p| p.title.eq_ignore_ascii_case(&project.title)) {
+            merged.push(project);
+        }
+    }
+    cv.projects = merged;
+
+    if cv.education.is_empty()
+        && let Some(level) = education_level.filter(|l| !l.trim().is_empty())
+    {
+        cv.education.push(CvEntry { title: level, ..CvEntry::default() });
+    }
+
+    let headline = profile_headline(&target_roles, preferred_track);
+
+    Ok(CvDocument { headline, cv })
+}
+
+/// Line shown under the user's name: the target roles, or the career track
+/// when none are set.
+pub(super) fn profile_headline(target_roles: &[String], track: Option<CareerTrack>) -> Option<String> {
+    if !target_roles.is_empty() {
+        return Some(target_roles.join(" | "));
+    }
+    track.map(|track| {
+        match track {
+            CareerTrack::WebDevelopment => "Web Development",
+            CareerTrack::Data => "Data Science & Analytics",
+            CareerTrack::Design => "UI/UX Design",
+            CareerTrack::Marketing => "Digital Marketing",
+        }
+        .to_string()
+    })
+}
+
+/// Retrieves the structured data parsed from the user's CV.
+///
+/// # Endpoint
+/// `GET /api/profile/cv/parsed`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - No CV has been uploaded yet (404)
+/// - Database operation fails
+pub async fn get_parsed_cv(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+) -> AppResult<Json<ParsedCvResponse>> {
+    load_parsed_cv(&app_state.db_pool, auth_user.user_id)
+        .await?
+        .map(Json)
+        .ok_or(AppError::NotFound)
+}
+
+/// Saves the user's corrections to the parsed CV.
+///
+/// The payload replaces the stored parse entirely and marks it as reviewed.
+/// With `apply_to_profile`, the reviewed skills are merged into the profile.
+///
+/// # Endpoint
+/// `PUT /api/profile/cv/parsed`
+///
+/// # Request Body
+/// ```json
+/// {
+///   "contact": { "full_name": "Ayesha Rahman", "email": "ayesha@example.com", "links": [] },
+///   "summary": "Backend developer...",
+///   "education": [{ "title": "BSc in CSE", "organization": "BRAC University", "end_date": "2021-01-01" }],
+///   "experience": [{ "title": "Junior Developer", "organization": "Acme", "start_date": "2022-01-01", "is_current": true }],
+///   "projects": [],
+///   "skills": ["Rust", "PostgreSQL"],
+///   "certifications": [],
+///   "apply_to_profile": true
+/// }
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - An entry has an empty title or an end date before its start date
+/// - Database operation fails
+pub async fn update_parsed_cv(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Json(payload): Json<ReviewParsedCvPayload>,
+) -> AppResult<Json<ParsedCvResponse>> {
+    let mut cv = payload.cv;
+    validate_parsed_cv(&mut cv)?;
+
+    let mut tx = app_state.db_pool.begin().await?;
+    store_parsed_cv(&mut tx, auth_user.user_id, &cv, true).await?;
+    sync_version_skills(&mut tx, auth_user.user_id, &cv.skills).await?;
+
+    if payload.apply_to_profile {
+        // Append reviewed skills the profile doesn't have yet, keeping existing order
+        sqlx::query!(
+            r#"
+            UPDATE users SET
+                skills = skills || ARRAY(
+                    SELECT s FROM unnest($1::TEXT[]) AS s
+                    WHERE lower(s) NOT IN (SELECT lower(e) FROM unnest(skills) AS e)
+                ),
+                updated_at = CURRENT_TIMESTAMP
+            WHERE id = $2
+            "#,
+            &cv.skills,
+            auth_user.user_id
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+    info!(
+        "Parsed CV reviewed by user {} (apply_to_profile={})",
+        auth_user.user_id, payload.apply_to_profile
+    );
+
+    load_parsed_cv(&app_state.db_pool, auth_user.user_id)
+        .await?
+        .map(Json)
+        .ok_or(AppError::InternalServerError)
+}
+
+/// Imports a JSON Resume file.
+///
+/// The resume replaces the parsed CV, marked as reviewed and no longer tied to
+/// an uploaded version, and fills in the profile: skills and project names
+/// are merged, and the resume's label and first education entry become the
+/// target role and education level when those are still empty. Name and email
+/// are left unchanged.
+///
+/// # Endpoint
+/// `POST /api/profile/cv/import`
+///
+/// # Request Body
+/// A [JSON Resume](https://jsonresume.org/schema) document, e.g.
+/// `curl --data-binary @resume.json -H 'Content-Type: application/json'`.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - The body is not a JSON Resume document or has nothing to import
+/// - An entry has an end date before its start date
+/// - Database operation fails
+pub async fn import_json_resume(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Json(resume): Json<JsonResume>,
+) -> AppResult<Json<ParsedCvResponse>> {
+    if resume.is_empty() {
+        return Err(AppError::ValidationError(
+            "The JSON Resume has no basics, work, education, projects, certificates or skills to import"
+                .to_string(),
+        ));
+    }
+
+    let document = from_json_resume(&resume);
+    let mut cv = document.cv;
+    validate_parsed_cv(&mut cv)?;
+
+    let project_titles: Vec<String> = cv.projects.iter().map(|p| p.title.clone()).collect();
+    let education_level = cv.education.first().map(|e| e.title.clone());
+
+    let mut tx = app_state.db_pool.begin().await?;
+    store_parsed_cv(&mut tx, auth_user.user_id, &cv, true).await?;
+    sqlx::query!(
+        "UPDATE cv_parses SET cv_version_id = NULL WHERE user_id = $1",
+        auth_user.user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        UPDATE users SET
+            skills = skills || ARRAY(
+                SELECT s FROM unnest($1::TEXT[]) AS s
+                WHERE lower(s) NOT IN (SELECT lower(e) FROM unnest(skills) AS e)
+            ),
+            projects = projects || ARRAY(
+                SELECT p FROM unnest($2::TEXT[]) AS p
+                WHERE lower(p) NOT IN (SELECT lower(e) FROM unnest(projects) AS e)
+            ),
+            target_roles = CASE
+                WHEN cardinality(target_roles) = 0 AND $3::TEXT IS NOT NULL THEN ARRAY[$3::TEXT]
+                ELSE target_roles
+            END,
+            education_level = COALESCE(NULLIF(education_level, ''), $4),
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $5
+        "#,
+        &cv.skills,
+        &project_titles,
+        document.headline.as_deref(),
+        education_level.as_deref(),
+        auth_user.user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    info!(
+        "JSON Resume imported for user {} ({} skills, {} experience entries)",
+        auth_user.user_id,
+        cv.skills.len(),
+        cv.experience.len()
+    );
+
+    load_parsed_cv(&app_state.db_pool, auth_user.user_id)
+        .await?
+        .map(Json)
+        .ok_or(AppError::InternalServerError)
+}
+
+/// Trims user input and rejects entries that can't be stored meaningfully.
+fn validate_parsed_cv(cv: &mut ParsedCv) -> AppResult<()> {
+    cv.skills = cv
+        .skills
+        .iter()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .fold(Vec::new(), |mut acc: Vec<String>, s| {
+            if !acc.iter().any(|a| a.eq_ignore_ascii_case(&s)) {
+                acc.push(s);
+            }
+            acc
+        });
+
+    let sections = [
+        ("education", &mut cv.education),
+        ("experience", &mut cv.experience),
+        ("projects", &mut cv.projects),
+        ("certifications", &mut cv.certifications),
+    ];
+    for (name, entries) in sections {
+        for (i, entry) in entries.iter_mut().enumerate() {
+            entry.title = entry.title.trim().to_string();
+            if entry.title.is_empty() {
+                return Err(AppError::ValidationError(format!(
+                    "{}[{}]: title is required",
+                    name, i
+                )));
+            }
+            if let (Some(start), Some(end)) = (entry.start_date, entry.end_date)
+                && end < start
+            {
+                return Err(AppError::ValidationError(format!(
+                    "{}[{}]: end_date is before start_date",
+                    name, i
+                )));
+            }
+            if entry.is_current {
+                entry.end_date = None;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Enriches the parsed CV with skills and certifications extracted by AI.
+///
+/// Optional step on top of the deterministic parse: the stored CV text is
+/// redacted and sent to the selected provider, and anything the parser missed
+/// is appended. Existing entries are never modified. The enrichment runs as a
+/// background job; the response carries its ID, and an `ai_job_completed`
+/// event is published when the parsed CV has been updated.
+///
+/// # Endpoint
+/// `POST /api/profile/cv/parsed/enrich`
+///
+/// # Request Body
+/// ```json
+/// {
+///   "provider": "gemini"
+/// }
+/// ```
+///
+/// # Response
+/// `202 Accepted` with `{"job_id": 42, "job": "cv_enrichment"}`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - User has opted out of sharing CV data with AI providers (403)
+/// - No CV has been uploaded yet
+/// - AI service is not configured
+/// - Database operation fails
+pub async fn enrich_parsed_cv(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Json(payload): Json<EnrichParsedCvPayload>,
+) -> AppResult<(StatusCode, Json<AiJobAccepted>)> {
+    let (_, share_cv_with_ai) = load_ai_privacy(&app_state.db_pool, auth_user.user_id).await?;
+    if !share_cv_with_ai {
+        return Err(cv_sharing_disabled());
+    }
+    if app_state.ai_service.is_none() {
+        return Err(AppError::ConfigurationError("AI service not configured".to_string()));
+    }
+    load_cv_text(&app_state.db_pool, auth_user.user_id).await?;
+
+    let task = Task::EnrichParsedCv(CvEnrichmentJob {
+        user_id: auth_user.user_id,
+        provider: payload.provider.unwrap_or(AIProvider::Gemini),
+    });
+    let job_id = queue::enqueue(&app_state.db_pool, &task).await?;
+    info!("Queued CV enrichment job {} for user {}", job_id, auth_user.user_id);
+
+    Ok((StatusCode::ACCEPTED, Json(AiJobAccepted { job_id, job: "cv_enrichment" })))
+}
+
+/// Loads the user's stored CV text, failing when no CV has been uploaded.
+async fn load_cv_text(pool: &PgPool, user_id: Uuid) -> AppResult<String> {
+    let cv_text: Option<String> =
+        sqlx::query_scalar!("SELECT raw_cv_text FROM users WHERE id = $1", user_id)
+            .fetch_one(pool)
+            .await?;
+    cv_text
+        .filter(|t| !t.trim().is_empty())
+        .ok_or_else(|| AppError::BadRequest("Upload a CV before enriching it".to_string()))
+}
+
+/// Runs the CV enrichment of background job `job_id`.
+pub(crate) async fn run_cv_enrichment_job(
+    pool: &PgPool,
+    ai_service: &AIService,
+    job_id: i64,
+    job: &CvEnrichmentJob,
+) -> AppResult<()> {
+    // The user may have opted out since the job was queued
+    let (redactor, share_cv_with_ai) = load_ai_privacy(pool, job.user_id).await?;
+    if !share_cv_with_ai {
+        return Err(cv_sharing_disabled());
+    }
+
+    let cv_text = load_cv_text(pool, job.user_id).await?;
+    let mut parsed = load_parsed_cv(pool, job.user_id)
+        .await?
+        .map(|p| p.cv)
+        .unwrap_or_else(|| crate::cv::parse_cv(&cv_text));
+
+    let response = ai_service
+        .process_action_with(
+            AIActionRequest {
+                action: ActionType::ExtractSkills,
+                provider: job.provider.clone(),
+                input: cv_text,
+                parameters: None,
+            },
+            &redactor,
+        )
+        .await?;
+
+    if !response.success {
+        warn!("CV enrichment failed for user {}: {:?}", job.user_id, response.message);
+        return Err(AppError::ExternalServiceError(
+            response.message.unwrap_or_else(|| "AI enrichment failed".to_string()),
+        ));
+    }
+
+    let names = |key: &str| -> Vec<String> {
+        response
+            .data
+            .get(key)
+            .and_then(|v| v.as_array())
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|v| v.get("name").and_then(|n| n.as_str()).or_else(|| v.as_str()))
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    let mut added_skills = 0;
+    for skill in names("technical_skills").into_iter().chain(names("tools")) {
+        if !parsed.skills.iter().any(|s| s.eq_ignore_ascii_case(&skill)) {
+            parsed.skills.push(skill);
+            added_skills += 1;
+        }
+    }
+
+    let mut added_certifications = 0;
+    for title in names("certifications") {
+        if !parsed.certifications.iter().any(|c| c.title.eq_ignore_ascii_case(&title)) {
+            parsed.certifications.push(CvEntry {
+                title,
+                ..CvEntry::default()
+            });
+            added_certifications += 1;
+        }
+    }
+
+    let mut tx = pool.begin().await?;
+    // Enrichment is a suggestion: it doesn't count as a user review
+    store_parsed_cv(&mut tx, job.user_id, &parsed, false).await?;
+    sync_version_skills(&mut tx, job.user_id, &parsed.skills).await?;
+    sqlx::query!(
+        "UPDATE cv_parses SET enriched_at = CURRENT_TIMESTAMP WHERE user_id = $1",
+        job.user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    info!(
+        "Enriched parsed CV for user {} (job {}): {} skills, {} certifications added",
+        job.user_id, job_id, added_skills, added_certifications
+    );
+
+    publish_ai_job_completed(pool, job.user_id, job_id, "cv_enrichment", "/api/profile/cv/parsed").await;
+
+    Ok(())
+}
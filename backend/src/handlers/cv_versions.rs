@@ -0,0 +1,417 @@
+//! CV version handlers.
+//!
+//! Every upload keeps the original file in the configured storage backend
+//! together with its hash, size, extracted text and skills. One version per
+//! user is active; it feeds `raw_cv_text` and the structured parse.
+
+use super::cv::store_parsed_cv;
+use super::types::CvVersionDetail;
+use crate::AppState;
+use crate::auth::AuthUser;
+use crate::cv::extract::ExtractedText;
+use crate::cv::{ParsedCv, parse_cv};
+use crate::errors::{AppError, AppResult};
+use crate::models::CvVersion;
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use sha2::{Digest, Sha256};
+use sqlx::PgConnection;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// Result of storing an uploaded CV.
+pub(super) struct SavedUpload {
+    /// The version now active
+    pub version: CvVersion,
+    /// Structured data parsed from the version
+    pub parsed: ParsedCv,
+    /// Whether the same file had been uploaded before
+    pub duplicate: bool,
+}
+
+/// Stores an uploaded CV as a new version and makes it active.
+///
+/// Re-uploading a file with the same SHA-256 as an existing version
+/// re-activates that version instead of storing a copy. The stored object is
+/// removed again if the database transaction fails.
+pub(super) async fn save_upload(
+    app_state: &AppState,
+    user_id: Uuid,
+    file_name: &str,
+    bytes: &[u8],
+    extracted: &ExtractedText,
+) -> AppResult<SavedUpload> {
+    let sha256 = hex::encode(Sha256::digest(bytes));
+
+    let existing = sqlx::query_as!(
+        CvVersion,
+        "SELECT * FROM cv_versions WHERE user_id = $1 AND sha256 = $2 ORDER BY version_number DESC LIMIT 1",
+        user_id,
+        &sha256
+    )
+    .fetch_optional(&app_state.db_pool)
+    .await?;
+
+    if let Some(version) = existing {
+        info!(
+            "CV upload for user {} matches version {}, re-activating it",
+            user_id, version.version_number
+        );
+        let mut tx = app_state.db_pool.begin().await?;
+        let (version, parsed) = activate_version(&mut tx, user_id, version).await?;
+        tx.commit().await?;
+        return Ok(SavedUpload {
+            version,
+            parsed,
+            duplicate: true,
+        });
+    }
+
+    let storage = &app_state.cv_storage;
+    let storage_key = format!(
+        "cv/{}/{}.{}",
+        user_id,
+        Uuid::new_v4(),
+        extracted.format.extension()
+    );
+    storage
+        .put(&storage_key, bytes, extracted.format.mime_type())
+        .await
+        .map_err(|e| {
+            error!("Failed to store CV file for user {}: {}", user_id, e);
+            AppError::from(e)
+        })?;
+
+    let result = async {
+        let mut tx = app_state.db_pool.begin().await?;
+
+        // Serialize concurrent uploads by the same user so version numbers stay sequential
+        sqlx::query_scalar!("SELECT id FROM users WHERE id = $1 FOR UPDATE", user_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        let parsed = parse_cv(&extracted.text);
+        let version = sqlx::query_as!(
+            CvVersion,
+            r#"
+            INSERT INTO cv_versions
+                (user_id, version_number, file_name, content_type, format, size_bytes, sha256,
+                 storage_backend, storage_key, extracted_text, skills)
+            VALUES (
+                $1,
+                (SELECT COALESCE(MAX(version_number), 0) + 1 FROM cv_versions WHERE user_id = $1),
+                $2, $3, $4, $5, $6, $7, $8, $9, $10
+            )
+            RETURNING *
+            "#,
+            user_id,
+            file_name.chars().take(255).collect::<String>(),
+            extracted.format.mime_type(),
+            extracted.format.as_str(),
+            bytes.len() as i64,
+            &sha256,
+            storage.backend(),
+            &storage_key,
+            &extracted.text,
+            &parsed.skills
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let (version, parsed) = activate_version(&mut tx, user_id, version).await?;
+        tx.commit().await?;
+        Ok::<_, AppError>((version, parsed))
+    }
+    .await;
+
+    match result {
+        Ok((version, parsed)) => Ok(SavedUpload {
+            version,
+            parsed,
+            duplicate: false,
+        }),
+        Err(e) => {
+            if let Err(cleanup) = storage.delete(&storage_key).await {
+                warn!("Failed to remove orphaned CV file {}: {}", storage_key, cleanup);
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Makes a version the active one and rebuilds the profile data from it.
+///
+/// Sets `raw_cv_text`, re-parses the version's text (keeping the skills saved
+/// on the version) and links the parse to the version.
+async fn activate_version(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    version: CvVersion,
+) -> AppResult<(CvVersion, ParsedCv)> {
+    // Two statements: the one-active-per-user index is checked row by row
+    sqlx::query!(
+        "UPDATE cv_versions SET is_active = FALSE WHERE user_id = $1 AND is_active AND id <> $2",
+        user_id,
+        version.id
+    )
+    .execute(&mut *conn)
+    .await?;
+    let version = sqlx::query_as!(
+        CvVersion,
+        "UPDATE cv_versions SET is_active = TRUE WHERE id = $1 RETURNING *",
+        version.id
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    sqlx::query!(
+        "UPDATE users SET raw_cv_text = $1, updated_at = CURRENT_TIMESTAMP WHERE id = $2",
+        &version.extracted_text,
+        user_id
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    let mut parsed = parse_cv(&version.extracted_text);
+    parsed.skills = version.skills.clone();
+    store_parsed_cv(conn, user_id, &parsed, false).await?;
+    sqlx::query!(
+        "UPDATE cv_parses SET cv_version_id = $1 WHERE user_id = $2",
+        version.id,
+        user_id
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok((version, parsed))
+}
+
+/// Loads a version owned by the user.
+async fn find_version(conn: &mut PgConnection, user_id: Uuid, id: i32) -> AppResult<CvVersion> {
+    sqlx::query_as!(
+        CvVersion,
+        "SELECT * FROM cv_versions WHERE id = $1 AND user_id = $2",
+        id,
+        user_id
+    )
+    .fetch_optional(&mut *conn)
+    .await?
+    .ok_or(AppError::NotFound)
+}
+
+/// Lists the user's CV versions, newest first.
+///
+/// # Endpoint
+/// `GET /api/profile/cv/versions`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Database operation fails
+pub async fn list_cv_versions(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+) -> AppResult<Json<Vec<CvVersion>>> {
+    let versions = sqlx::query_as!(
+        CvVersion,
+        "SELECT * FROM cv_versions WHERE user_id = $1 ORDER BY version_number DESC",
+        auth_user.user_id
+    )
+    .fetch_all(&app_state.db_pool)
+    .await?;
+
+    Ok(Json(versions))
+}
+
+/// Retrieves a CV version with its extracted text and skills.
+///
+/// # Endpoint
+/// `GET /api/profile/cv/versions/{id}`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Version doesn't exist or belongs to another user (404)
+/// - Database operation fails
+pub async fn get_cv_version(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<CvVersionDetail>> {
+    let mut conn = app_state.db_pool.acquire().await?;
+    let version = find_version(&mut conn, auth_user.user_id, id).await?;
+
+    Ok(Json(CvVersionDetail {
+        extracted_text: version.extracted_text.clone(),
+        version,
+    }))
+}
+
+/// Downloads the original file of a CV version.
+///
+/// The file is checked against the SHA-256 recorded at upload before it is
+/// returned.
+///
+/// # Endpoint
+/// `GET /api/profile/cv/versions/{id}/download`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Version doesn't exist, belongs to another user or its file is missing (404)
+/// - Stored file doesn't match the recorded hash
+/// - Storage or database operation fails
+pub async fn download_cv_version(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Path(id): Path<i32>,
+) -> AppResult<Response> {
+    let mut conn = app_state.db_pool.acquire().await?;
+    let version = find_version(&mut conn, auth_user.user_id, id).await?;
+
+    if version.storage_backend != app_state.cv_storage.backend() {
+        warn!(
+            "CV version {} is stored in '{}' but the server uses '{}'",
+            version.id,
+            version.storage_backend,
+            app_state.cv_storage.backend()
+        );
+    }
+
+    let bytes = app_state.cv_storage.get(&version.storage_key).await?;
+    if hex::encode(Sha256::digest(&bytes)) != version.sha256 {
+        error!(
+            "Integrity check failed for CV version {} ({})",
+            version.id, version.storage_key
+        );
+        return Err(AppError::InternalServerError);
+    }
+
+    // Keep the header value ASCII and quote-safe
+    let file_name: String = version
+        .file_name
+        .chars()
+        .map(|c| if c.is_ascii_graphic() && c != '"' && c != '\\' || c == ' ' { c } else { '_' })
+        .collect();
+
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, version.content_type.clone()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", file_name),
+            ),
+        ],
+        bytes,
+    )
+        .into_response())
+}
+
+/// Makes a CV version the active one.
+///
+/// The profile's CV text and the structured parse are rebuilt from the
+/// version; previous review edits other than skills are replaced.
+///
+/// # Endpoint
+/// `POST /api/profile/cv/versions/{id}/activate`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Version doesn't exist or belongs to another user (404)
+/// - Database operation fails
+pub async fn activate_cv_version(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<CvVersion>> {
+    let mut tx = app_state.db_pool.begin().await?;
+    let version = find_version(&mut tx, auth_user.user_id, id).await?;
+    let (version, _) = activate_version(&mut tx, auth_user.user_id, version).await?;
+    tx.commit().await?;
+
+    info!(
+        "User {} activated CV version {}",
+        auth_user.user_id, version.version_number
+    );
+    Ok(Json(version))
+}
+
+/// Deletes a CV version and its stored file.
+///
+/// Deleting the active version activates the most recent remaining one; when
+/// none is left the profile's CV text is cleared.
+///
+/// # Endpoint
+/// `DELETE /api/profile/cv/versions/{id}`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Version doesn't exist or belongs to another user (404)
+/// - Database operation fails
+pub async fn delete_cv_version(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<serde_json::Value>> {
+    let user_id = auth_user.user_id;
+    let mut tx = app_state.db_pool.begin().await?;
+    let version = find_version(&mut tx, user_id, id).await?;
+
+    sqlx::query!("DELETE FROM cv_versions WHERE id = $1", version.id)
+        .execute(&mut *tx)
+        .await?;
+
+    let mut active_version = None;
+    if version.is_active {
+        let next = sqlx::query_as!(
+            CvVersion,
+            "SELECT * FROM cv_versions WHERE user_id = $1 ORDER BY version_number DESC LIMIT 1",
+            user_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        match next {
+            Some(next) => {
+                let (next, _) = activate_version(&mut tx, user_id, next).await?;
+                active_version = Some(next.id);
+            }
+            None => {
+                sqlx::query!(
+                    "UPDATE users SET raw_cv_text = NULL, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+                    user_id
+                )
+                .execute(&mut *tx)
+                .await?;
+                sqlx::query!("DELETE FROM cv_parses WHERE user_id = $1", user_id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+    }
+    tx.commit().await?;
+
+    // The row is gone; a leftover file is only logged
+    if let Err(e) = app_state.cv_storage.delete(&version.storage_key).await {
+        warn!("Failed to delete CV file {}: {}", version.storage_key, e);
+    }
+
+    info!("User {} deleted CV version {}", user_id, version.version_number);
+    Ok(Json(serde_json::json!({
+        "message": "CV version deleted",
+        "deleted_version": version.version_number,
+        "activated_version_id": active_version
+    })))
+}
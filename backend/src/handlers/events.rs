@@ -0,0 +1,182 @@
+//! Real-time event handlers.
+//!
+//! Pushes the authenticated user's events (see [`crate::events`]) over
+//! Server-Sent Events or a WebSocket, so clients need not poll for new
+//! notifications, application updates and finished AI jobs. Both streams
+//! start with a `ready` event; clients should refetch what they show then,
+//! since events published while they were disconnected are not replayed.
+//!
+//! A stream is closed when its token expires. The user is checked again on
+//! every connect, so a reconnect needs a valid token for an account that is
+//! not scheduled for deletion.
+
+use super::types::EventStreamQuery;
+use crate::AppState;
+use crate::auth::AuthUser;
+use crate::errors::{AppError, AppResult};
+use crate::events::{Received, Subscription};
+use axum::{
+    extract::{
+        Query, State,
+        ws::{CloseFrame, Message, WebSocket, WebSocketUpgrade, close_code},
+    },
+    http::{HeaderMap, header},
+    response::{
+        Response,
+        sse::{Event as SseEvent, KeepAlive, Sse},
+    },
+};
+use chrono::Utc;
+use futures_util::{Stream, StreamExt, stream};
+use serde_json::{Value, json};
+use sqlx::PgPool;
+use std::convert::Infallible;
+use std::time::Duration;
+use tracing::{debug, info};
+
+/// Interval of WebSocket pings, keeping idle connections open through
+/// proxies and load balancers.
+const PING_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Streams the authenticated user's events as Server-Sent Events.
+///
+/// Each event has a name (`notification`, `application_updated`,
+/// `ai_job_completed`, `ai_job_failed`, or `lagged` when some of the user's
+/// events were dropped for a slow client) and JSON data.
+///
+/// # Endpoint
+/// `GET /api/events` (or `GET /api/events?access_token=<jwt>` for `EventSource`)
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated (including an account that no longer exists)
+/// - Account is scheduled for deletion (403)
+/// - Database operation fails
+pub async fn stream_events(
+    State(app_state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<EventStreamQuery>,
+) -> AppResult<Sse<impl Stream<Item = Result<SseEvent, Infallible>>>> {
+    let auth_user = authenticate(&app_state.db_pool, &headers, &query).await?;
+    let user_id = auth_user.user_id;
+    let events = app_state.events.subscribe(user_id);
+    info!("User {} connected to the event stream (SSE)", user_id);
+
+    let ready = stream::once(async { Ok(SseEvent::default().event("ready").data("{}")) });
+    let events = stream::unfold(events, |mut events| async move {
+        let (name, data) = next_event(&mut events).await?;
+        Some((Ok(SseEvent::default().event(name).data(data.to_string())), events))
+    });
+    let events = ready.chain(events).take_until(tokio::time::sleep(until_expiry(&auth_user)));
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+/// Streams the authenticated user's events over a WebSocket.
+///
+/// Each message is a JSON object `{"event": "<name>", "data": {...}}` with
+/// the same events as [`stream_events`]. Messages from the client are
+/// ignored.
+///
+/// # Endpoint
+/// `GET /api/events/ws` (or `GET /api/events/ws?access_token=<jwt>` from a browser)
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated (including an account that no longer exists)
+/// - Account is scheduled for deletion (403)
+/// - Database operation fails
+pub async fn events_websocket(
+    State(app_state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<EventStreamQuery>,
+    upgrade: WebSocketUpgrade,
+) -> AppResult<Response> {
+    let auth_user = authenticate(&app_state.db_pool, &headers, &query).await?;
+    let events = app_state.events.subscribe(auth_user.user_id);
+    info!("User {} connected to the event stream (WebSocket)", auth_user.user_id);
+
+    Ok(upgrade.on_upgrade(move |socket| push_events(socket, events, auth_user)))
+}
+
+/// The user from the `Authorization` header, or else the `access_token`
+/// query parameter, if their account is still active.
+async fn authenticate(pool: &PgPool, headers: &HeaderMap, query: &EventStreamQuery) -> AppResult<AuthUser> {
+    let header_token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    let auth_user = header_token
+        .or(query.access_token.as_deref())
+        .ok_or(AppError::Unauthorized)
+        .and_then(AuthUser::from_token)?;
+    // Token validation allows some clock skew; a stream would close right away
+    if auth_user.expires_at <= Utc::now().timestamp() {
+        return Err(AppError::Unauthorized);
+    }
+
+    let user = sqlx::query!("SELECT deletion_scheduled_for FROM users WHERE id = $1", auth_user.user_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or(AppError::Unauthorized)?;
+    if user.deletion_scheduled_for.is_some() {
+        return Err(AppError::Forbidden("Your account is scheduled for deletion".to_string()));
+    }
+    Ok(auth_user)
+}
+
+/// Time left until the user's token expires.
+fn until_expiry(auth_user: &AuthUser) -> Duration {
+    let secs = auth_user.expires_at - Utc::now().timestamp();
+    Duration::from_secs(secs.max(0) as u64)
+}
+
+/// Next event for the user as a name and data, or `None` once the bus is
+/// gone.
+async fn next_event(events: &mut Subscription) -> Option<(&'static str, Value)> {
+    match events.recv().await? {
+        Received::Event(event) => Some((event.kind.as_str(), event.data)),
+        Received::Lagged(missed) => Some(("lagged", json!({ "missed": missed }))),
+    }
+}
+
+async fn push_events(mut socket: WebSocket, mut events: Subscription, auth_user: AuthUser) {
+    let user_id = auth_user.user_id;
+    let ready = json!({ "event": "ready", "data": {} }).to_string();
+    if socket.send(Message::Text(ready.into())).await.is_err() {
+        return;
+    }
+
+    let expiry = tokio::time::sleep(until_expiry(&auth_user));
+    tokio::pin!(expiry);
+    let mut ping = tokio::time::interval(PING_INTERVAL);
+    loop {
+        tokio::select! {
+            _ = &mut expiry => {
+                let close = CloseFrame { code: close_code::POLICY, reason: "Token expired".into() };
+                let _ = socket.send(Message::Close(Some(close))).await;
+                break;
+            }
+            event = next_event(&mut events) => {
+                let Some((name, data)) = event else { break };
+                let message = json!({ "event": name, "data": data }).to_string();
+                if socket.send(Message::Text(message.into())).await.is_err() {
+                    break;
+                }
+            }
+            message = socket.recv() => match message {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+            _ = ping.tick() => {
+                if socket.send(Message::Ping(Default::default())).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+    debug!("User {} disconnected from the event stream (WebSocket)", user_id);
+}
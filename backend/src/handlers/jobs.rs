@@ -1,17 +1,26 @@
 //! Job recommendation handlers.
 
-use super::types::{JobQueryParams, JobRecommendation, PlatformLinks};
+use super::external_jobs::source_status;
+use super::types::{FeedRecommendation, JobFeedQuery, JobFeedResponse, JobQueryParams, JobRecommendation, PlatformLinks};
 use crate::AppState;
-use crate::ai_matching::{calculate_enhanced_match, generate_ai_explanation};
+use crate::ai_matching::{calculate_enhanced_match, generate_ai_explanation, matched_skills, missing_skills};
 use crate::auth::AuthUser;
-use crate::errors::AppResult;
+use crate::errors::{AppError, AppResult};
+use crate::external_jobs::source_health;
+use crate::job_feed::{FeedFilters, load_feed};
 use crate::models::{CareerTrack, ExperienceLevel, Job, JobType, User};
 use axum::{
     Json,
     extract::{Query, State},
 };
+use chrono::Utc;
+use std::cmp::Ordering;
 use tracing::{debug, info};
 
+/// Default and largest number of feed entries returned.
+const DEFAULT_FEED_LIMIT: i64 = 50;
+const MAX_FEED_LIMIT: i64 = 200;
+
 /// Gets job recommendations for the authenticated user.
 ///
 /// Retrieves jobs matching user's experience level (or specified level) and
@@ -58,6 +67,7 @@ pub async fn get_job_recommendations(
         SELECT 
             id, full_name, email, education_level,
             experience_level as "experience_level: ExperienceLevel",
+            experience_years_min, experience_years_max,
             preferred_track as "preferred_track: CareerTrack",
             profile_completed as "profile_completed!",
             skills, projects, target_roles, raw_cv_text, password_hash
@@ -69,6 +79,9 @@ pub async fn get_job_recommendations(
     .fetch_one(&app_state.db_pool)
     .await?;
 
+    let skill_levels = super::skills::load_skill_levels(&app_state.db_pool, auth_user.user_id).await?;
+    let (_, share_cv_with_ai) = super::ai::load_ai_privacy(&app_state.db_pool, auth_user.user_id).await?;
+
     // Fetch jobs matching criteria
     let limit = params.limit.unwrap_or(50);
 
@@ -82,6 +95,7 @@ pub async fn get_job_recommendations(
             SELECT 
                 id, job_title, company, location, job_description, required_skills,
                 experience_level as "experience_level: ExperienceLevel",
+                experience_years_min, experience_years_max,
                 job_type as "job_type: JobType",
                 salary_min, salary_max, responsibilities, requirements, benefits
             FROM jobs 
@@ -102,6 +116,7 @@ pub async fn get_job_recommendations(
             SELECT 
                 id, job_title, company, location, job_description, required_skills,
                 experience_level as "experience_level: ExperienceLevel",
+                experience_years_min, experience_years_max,
                 job_type as "job_type: JobType",
                 salary_min, salary_max, responsibilities, requirements, benefits
             FROM jobs 
@@ -133,17 +148,10 @@ pub async fn get_job_recommendations(
         // Calculate enhanced match using heuristic
         let enhanced = calculate_enhanced_match(
             &user.skills,
+            &skill_levels,
             &job.required_skills,
-            user.experience_level.as_ref().map(|e| match e {
-                ExperienceLevel::Fresher => "fresher",
-                ExperienceLevel::Junior => "junior",
-                ExperienceLevel::Mid => "mid",
-            }),
-            match job.experience_level {
-                ExperienceLevel::Fresher => "fresher",
-                ExperienceLevel::Junior => "junior",
-                ExperienceLevel::Mid => "mid",
-            },
+            user.experience_level.map(ExperienceLevel::as_str),
+            job.experience_level.as_str(),
             user.preferred_track.as_ref().map(|t| match t {
                 CareerTrack::WebDevelopment => "web_development",
                 CareerTrack::Data => "data",
@@ -153,34 +161,31 @@ pub async fn get_job_recommendations(
             &job.job_title,
         );
 
-        // Try to enhance explanation with AI (falls back to heuristic if AI fails)
-        let ai_enhanced_explanation = generate_ai_explanation(
-            &user.skills,
-            &job.required_skills,
-            user.experience_level.as_ref().map(|e| match e {
-                ExperienceLevel::Fresher => "fresher",
-                ExperienceLevel::Junior => "junior",
-                ExperienceLevel::Mid => "mid",
-            }),
-            match job.experience_level {
-                ExperienceLevel::Fresher => "fresher",
-                ExperienceLevel::Junior => "junior",
-                ExperienceLevel::Mid => "mid",
-            },
-            user.preferred_track.as_ref().map(|t| match t {
-                CareerTrack::WebDevelopment => "web_development",
-                CareerTrack::Data => "data",
-                CareerTrack::Design => "design",
-                CareerTrack::Marketing => "marketing",
-            }),
-            &job.job_title,
-            &job.job_description,
-            enhanced.match_score,
-            enhanced.skill_overlap,
-            enhanced.experience_alignment,
-            enhanced.track_alignment,
-        )
-        .await;
+        // Try to enhance explanation with AI (falls back to heuristic if AI fails);
+        // the prompt carries the user's skills, so not for users who opted out
+        let ai_enhanced_explanation = if share_cv_with_ai {
+            generate_ai_explanation(
+                &user.skills,
+                &job.required_skills,
+                user.experience_level.map(ExperienceLevel::as_str),
+                job.experience_level.as_str(),
+                user.preferred_track.as_ref().map(|t| match t {
+                    CareerTrack::WebDevelopment => "web_development",
+                    CareerTrack::Data => "data",
+                    CareerTrack::Design => "design",
+                    CareerTrack::Marketing => "marketing",
+                }),
+                &job.job_title,
+                &job.job_description,
+                enhanced.match_score,
+                enhanced.skill_overlap,
+                enhanced.experience_alignment,
+                enhanced.track_alignment,
+            )
+            .await
+        } else {
+            enhanced.match_explanation
+        };
 
         // Generate platform links
         let encoded_title = urlencoding::encode(&job.job_title);
@@ -238,3 +243,101 @@ pub async fn get_job_recommendations(
 
     Ok(Json(recommendations))
 }
+
+/// Gets internal jobs and imported external listings in one feed, scored
+/// for the authenticated user with the same algorithm and best match first.
+///
+/// A posting listed by several sources appears once, under the internal job
+/// if there is one, with the other sources in `also_listed_on`. External
+/// listings are scored by their normalized experience level and all their
+/// skills, including those found in the description. Only the most recent
+/// jobs of each source that pass the filters are scored (see
+/// [`load_feed`]).
+///
+/// # Endpoint
+/// `GET /api/jobs/feed?source=internal,ReliefWeb&experience_level=junior&job_type=full_time&location=dhaka&min_score=40&limit=50&offset=0`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Database operation fails
+pub async fn get_job_feed(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Query(query): Query<JobFeedQuery>,
+) -> AppResult<Json<JobFeedResponse>> {
+    info!("Fetching job feed for user: {}", auth_user.user_id);
+    let pool = &app_state.db_pool;
+    let sources: Vec<String> = query
+        .source
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .map(|source| source.trim().to_lowercase())
+        .filter(|source| !source.is_empty())
+        .collect();
+    let filters = FeedFilters {
+        sources: &sources,
+        experience_level: query.experience_level,
+        job_type: query.job_type,
+        location: query.location.as_deref(),
+    };
+    let feed = load_feed(pool, &filters).await?;
+
+    let user = sqlx::query!(
+        "SELECT skills, experience_level::TEXT, preferred_track::TEXT FROM users WHERE id = $1",
+        auth_user.user_id
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or(AppError::NotFound)?;
+    let (user_skills, experience_level, preferred_track) = (user.skills, user.experience_level, user.preferred_track);
+    let skill_levels = super::skills::load_skill_levels(pool, auth_user.user_id).await?;
+
+    let min_score = query.min_score.unwrap_or(0.0);
+    let mut recommendations: Vec<FeedRecommendation> = feed
+        .into_iter()
+        .map(|job| {
+            let analysis = calculate_enhanced_match(
+                &user_skills,
+                &skill_levels,
+                &job.skills,
+                experience_level.as_deref(),
+                job.experience_level.map_or("", ExperienceLevel::as_str),
+                preferred_track.as_deref(),
+                &job.title,
+            );
+            FeedRecommendation {
+                matched_skills: matched_skills(&user_skills, &job.skills),
+                missing_skills: missing_skills(&user_skills, &job.skills),
+                job,
+                match_score: analysis.match_score,
+                match_explanation: analysis.match_explanation,
+                strengths: analysis.strengths,
+                improvement_areas: analysis.improvement_areas,
+                experience_alignment: analysis.experience_alignment,
+                track_alignment: analysis.track_alignment,
+                skill_overlap: analysis.skill_overlap,
+            }
+        })
+        .filter(|recommendation| recommendation.match_score >= min_score)
+        .collect();
+    // Stable, so equal scores keep internal jobs first
+    recommendations.sort_by(|a, b| b.match_score.partial_cmp(&a.match_score).unwrap_or(Ordering::Equal));
+
+    let offset = query.offset.unwrap_or(0).max(0) as usize;
+    let limit = query.limit.unwrap_or(DEFAULT_FEED_LIMIT).clamp(1, MAX_FEED_LIMIT) as usize;
+    let page: Vec<FeedRecommendation> = recommendations.into_iter().skip(offset).take(limit).collect();
+
+    let now = Utc::now();
+    let sources = source_health(pool, None, None, true)
+        .await?
+        .into_iter()
+        .filter(|health| filters.includes_source(&health.name))
+        .map(|health| source_status(health, now))
+        .collect();
+
+    info!("Returning {} feed jobs for user: {}", page.len(), auth_user.user_id);
+    Ok(Json(JobFeedResponse { jobs: page, sources }))
+}
@@ -3,6 +3,7 @@
 use axum::{extract::{State, Path}, Json};
 use tracing::{info, debug};
 use crate::models::{User, Job, LearningResource, ExperienceLevel, CareerTrack, JobType, CostIndicator};
+use crate::ai_matching::WEAK_SKILL_LEVEL;
 use crate::errors::AppResult;
 use crate::auth::AuthUser;
 use crate::AppState;
@@ -39,6 +40,7 @@ pub async fn get_learning_recommendations(
         SELECT 
             id, full_name, email, education_level,
             experience_level as "experience_level: ExperienceLevel",
+            experience_years_min, experience_years_max,
             preferred_track as "preferred_track: CareerTrack",
             profile_completed as "profile_completed!",
             skills, projects, target_roles, raw_cv_text, password_hash
@@ -125,6 +127,7 @@ pub async fn get_learning_recommendations(
 /// - All required skills for the role
 /// - Skill gaps (what's missing)
 /// - Matching skills (what user already has)
+/// - Weak skills (matching skills self-rated or tested below 5/10)
 /// - Match percentage
 /// - Recommended learning resources to close gaps
 /// 
@@ -148,6 +151,7 @@ pub async fn analyze_skill_gap(
         SELECT 
             id, full_name, email, education_level,
             experience_level as "experience_level: ExperienceLevel",
+            experience_years_min, experience_years_max,
             preferred_track as "preferred_track: CareerTrack",
             profile_completed as "profile_completed!",
             skills, projects, target_roles, raw_cv_text, password_hash
@@ -166,6 +170,7 @@ pub async fn analyze_skill_gap(
         SELECT 
             id, job_title, company, location, job_description, required_skills,
             experience_level as "experience_level: ExperienceLevel",
+            experience_years_min, experience_years_max,
             job_type as "job_type: JobType",
             salary_min, salary_max, responsibilities, requirements, benefits
         FROM jobs 
@@ -212,14 +217,27 @@ pub async fn analyze_skill_gap(
         .filter_map(|lower_key| required_skills_lower.get(lower_key).cloned())
         .collect();
 
+    // Skills the user has, but rated or tested as weak
+    let skill_levels = super::skills::load_skill_levels(&app_state.db_pool, auth_user.user_id).await?;
+    let weak_skills: Vec<String> = matching_skills
+        .iter()
+        .filter(|skill| {
+            skill_levels
+                .get(&skill.to_lowercase())
+                .is_some_and(|level| *level < WEAK_SKILL_LEVEL)
+        })
+        .cloned()
+        .collect();
+
     let match_percentage = if !required_skills.is_empty() {
         (matching_skills.len() as f64 / required_skills.len() as f64) * 100.0
     } else {
         0.0
     };
 
-    // Find resources for skill gaps
-    let recommended_resources = if !skill_gaps.is_empty() {
+    // Find resources for skill gaps and weak skills
+    let practice_skills: Vec<String> = skill_gaps.iter().chain(&weak_skills).cloned().collect();
+    let recommended_resources = if !practice_skills.is_empty() {
         sqlx::query_as!(
             LearningResource,
             r#"
@@ -230,7 +248,7 @@ pub async fn analyze_skill_gap(
             WHERE related_skills && $1
             LIMIT 10
             "#,
-            &skill_gaps
+            &practice_skills
         )
         .fetch_all(&app_state.db_pool)
         .await?
@@ -249,6 +267,7 @@ pub async fn analyze_skill_gap(
         required_skills,
         skill_gaps,
         matching_skills,
+        weak_skills,
         match_percentage,
         recommended_resources,
     }))
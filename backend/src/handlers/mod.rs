@@ -2,31 +2,64 @@
 //!
 //! This module contains all endpoint handlers organized by feature:
 //! - `auth` - Authentication and registration
+//! - `account` - Data export and account deletion
 //! - `profile` - User profile management
+//! - `profile_entries` - Education history, work experience and projects
+//! - `profile_history` - Change history of skills and target roles
+//! - `public_profiles` - Opt-in public profile pages
+//! - `cv` - Structured CV review and enrichment
+//! - `cv_versions` - Stored CV files and version history
 //! - `jobs` - Job recommendations
+//! - `saved_searches` - Saved job searches with alerts and digests
+//! - `tailored_cv` - Job-tailored CVs saved with applications
 //! - `learning` - Learning resources and skill gap analysis
+//! - `skills` - Skill self-ratings and proficiency
+//! - `quizzes` - Skill quizzes and question review
 //! - `applications` - Application tracking
+//! - `cover_letters` - AI cover letters for applications
 //! - `progress` - Learning progress tracking
+//! - `notifications` - In-app notifications and preferences
+//! - `events` - Real-time event stream (SSE and WebSocket)
+//! - `background_jobs` - Background job queue administration
 //! - `types` - Shared request/response types
 
+mod account;
 mod ai;
 mod applications;
 mod auth;
+mod background_jobs;
+mod cover_letters;
+mod cv;
+mod cv_versions;
+mod events;
 mod external_jobs;
 mod jobs;
 mod learning;
+mod notifications;
 mod oauth;
 mod profile;
+mod profile_entries;
+mod profile_history;
+mod public_profiles;
 mod progress;
+mod quizzes;
+mod saved_searches;
+mod skills;
+mod tailored_cv;
 mod types;
 
 #[allow(unused_imports)]
 pub use types::*;
+pub(crate) use ai::run_roadmap_job;
+pub(crate) use cover_letters::run_cover_letter_job;
+pub(crate) use cv::run_cv_enrichment_job;
 
 use crate::AppState;
 use crate::errors::AppResult;
 use axum::{
     Router,
+    extract::DefaultBodyLimit,
+    http::{HeaderName, header},
     routing::{get, post, put},
 };
 use tower_http::cors::{Any, CorsLayer};
@@ -44,12 +77,14 @@ pub fn create_router(app_state: AppState) -> Router {
     use tracing::info;
 
     info!("Setting up API routes:");
-    info!("  ✓ Public routes: /, /api/register, /api/login");
+    info!("  ✓ Public routes: /, /api/register, /api/login, /p/{{slug}}, /api/public/profiles");
     info!("  ✓ OAuth routes: /api/auth/google, /api/auth/github");
-    info!("  ✓ Protected routes: profile (+ CV upload), jobs, learning, applications, progress");
+    info!("  ✓ Protected routes: profile (+ CV upload), jobs, learning, applications, progress, notifications, events");
     info!("  ✓ External jobs: ReliefWeb NGO, govt portals, local boards");
     info!("  ✓ AI routes: /api/ai/assist, /api/roadmaps");
 
+    let upload_body_limit = app_state.cv_extractor.limits().max_file_bytes + 64 * 1024;
+
     Router::new()
         // Public routes
         .route("/", get(root))
@@ -60,17 +95,98 @@ pub fn create_router(app_state: AppState) -> Router {
         .route("/api/auth/google/callback", get(oauth::google_callback))
         .route("/api/auth/github", get(oauth::github_login))
         .route("/api/auth/github/callback", get(oauth::github_callback))
+        // Public profile pages
+        .route("/p/{slug}", get(public_profiles::public_profile_page))
+        .route("/api/public/profiles/{slug}", get(public_profiles::get_public_profile))
+        .route("/api/public/profiles/{slug}/cv", get(public_profiles::download_public_cv))
         // Protected routes - Profile
         .route("/api/profile", get(profile::get_profile))
         .route("/api/profile/complete", post(profile::complete_profile))
         .route("/api/profile", put(profile::update_profile))
-        .route("/api/profile/cv/upload", post(profile::upload_cv))
+        .route("/api/profile/completeness", get(profile::get_profile_completeness))
+        .route("/api/profile/history", get(profile_history::list_profile_history))
+        .route(
+            "/api/profile/history/{id}/revert",
+            post(profile_history::revert_profile_change),
+        )
+        .route(
+            "/api/profile/cv/upload",
+            // Room for the multipart framing on top of the file itself
+            post(profile::upload_cv).layer(DefaultBodyLimit::max(upload_body_limit)),
+        )
+        .route("/api/profile/cv/parsed", get(cv::get_parsed_cv))
+        .route("/api/profile/cv/parsed", put(cv::update_parsed_cv))
+        .route("/api/profile/cv/parsed/enrich", post(cv::enrich_parsed_cv))
+        .route("/api/profile/cv/versions", get(cv_versions::list_cv_versions))
+        .route("/api/profile/cv/versions/{id}", get(cv_versions::get_cv_version))
+        .route(
+            "/api/profile/cv/versions/{id}",
+            axum::routing::delete(cv_versions::delete_cv_version),
+        )
+        .route(
+            "/api/profile/cv/versions/{id}/download",
+            get(cv_versions::download_cv_version),
+        )
+        .route(
+            "/api/profile/cv/versions/{id}/activate",
+            post(cv_versions::activate_cv_version),
+        )
         .route("/api/profile/generate-cv", get(profile::generate_cv))
+        .route("/api/profile/cv/export", get(profile::generate_cv))
+        .route("/api/profile/cv/import", post(cv::import_json_resume))
+        .route("/api/profile/education", get(profile_entries::list_education))
+        .route("/api/profile/education", post(profile_entries::create_education))
+        .route("/api/profile/education/{id}", put(profile_entries::update_education))
+        .route(
+            "/api/profile/education/{id}",
+            axum::routing::delete(profile_entries::delete_education),
+        )
+        .route("/api/profile/experience", get(profile_entries::list_work_experience))
+        .route("/api/profile/experience", post(profile_entries::create_work_experience))
+        .route(
+            "/api/profile/experience/{id}",
+            put(profile_entries::update_work_experience),
+        )
+        .route(
+            "/api/profile/experience/{id}",
+            axum::routing::delete(profile_entries::delete_work_experience),
+        )
+        .route("/api/profile/projects", get(profile_entries::list_projects))
+        .route("/api/profile/projects", post(profile_entries::create_project))
+        .route("/api/profile/projects/{id}", put(profile_entries::update_project))
+        .route(
+            "/api/profile/projects/{id}",
+            axum::routing::delete(profile_entries::delete_project),
+        )
+        .route("/api/profile/privacy", get(profile::get_privacy_settings))
+        .route("/api/profile/privacy", put(profile::update_privacy_settings))
+        .route("/api/profile/public", get(public_profiles::get_public_profile_settings))
+        .route("/api/profile/public", put(public_profiles::update_public_profile_settings))
+        // Protected routes - Account data export and deletion
+        .route("/api/me/export", get(account::export_my_data))
+        .route("/api/me", axum::routing::delete(account::delete_account))
+        .route("/api/me/deletion", get(account::get_deletion_status))
+        .route("/api/me/deletion/cancel", post(account::cancel_account_deletion))
         // Protected routes - Job Recommendations
         .route(
             "/api/jobs/recommendations",
             get(jobs::get_job_recommendations),
         )
+        .route("/api/jobs/feed", get(jobs::get_job_feed))
+        .route("/api/jobs/{id}/tailored-cv", post(tailored_cv::create_tailored_cv))
+        // Protected routes - Saved Job Searches
+        .route("/api/saved-searches", get(saved_searches::list_saved_searches))
+        .route("/api/saved-searches", post(saved_searches::create_saved_search))
+        .route("/api/saved-searches/{id}", get(saved_searches::get_saved_search))
+        .route("/api/saved-searches/{id}", put(saved_searches::update_saved_search))
+        .route(
+            "/api/saved-searches/{id}",
+            axum::routing::delete(saved_searches::delete_saved_search),
+        )
+        .route(
+            "/api/saved-searches/{id}/matches",
+            get(saved_searches::list_saved_search_matches),
+        )
         // Protected routes - External Jobs Integration
         .route("/api/jobs/external", get(external_jobs::get_external_jobs))
         .route("/api/jobs/ngo", get(external_jobs::get_ngo_jobs))
@@ -86,6 +202,28 @@ pub fn create_router(app_state: AppState) -> Router {
             "/api/skill-gap/{target_role}",
             get(learning::analyze_skill_gap),
         )
+        // Protected routes - Skill Proficiency and Quizzes
+        .route("/api/skills/ratings", post(skills::rate_skill))
+        .route("/api/skills/proficiency", get(skills::get_skill_proficiency))
+        .route("/api/skills/assessments", get(skills::list_skill_assessments))
+        .route("/api/quizzes", get(quizzes::list_quizzes))
+        .route("/api/quizzes/{skill}/attempts", post(quizzes::start_quiz))
+        .route("/api/quizzes/attempts/{id}/submit", post(quizzes::submit_quiz))
+        // Admin routes - Quiz question review (ADMIN_EMAILS)
+        .route(
+            "/api/admin/quizzes/{skill}/generate",
+            post(quizzes::generate_quiz_questions),
+        )
+        .route("/api/admin/quiz-questions", get(quizzes::list_quiz_questions))
+        .route("/api/admin/quiz-questions/{id}", put(quizzes::review_quiz_question))
+        // Admin routes - Background jobs (ADMIN_EMAILS)
+        .route("/api/admin/background-jobs", get(background_jobs::list_background_jobs))
+        .route(
+            "/api/admin/background-jobs/{id}/retry",
+            post(background_jobs::retry_background_job),
+        )
+        // Admin routes - External job source health (ADMIN_EMAILS)
+        .route("/api/admin/external-job-sources", get(external_jobs::list_source_health))
         // Protected routes - Application Tracking
         .route("/api/applications", post(applications::create_application))
         .route("/api/applications", get(applications::get_my_applications))
@@ -93,6 +231,26 @@ pub fn create_router(app_state: AppState) -> Router {
             "/api/applications/{id}",
             put(applications::update_application),
         )
+        .route(
+            "/api/applications/{id}/tailored-cv",
+            get(tailored_cv::get_application_tailored_cv),
+        )
+        .route(
+            "/api/applications/{id}/cover-letter",
+            post(cover_letters::generate_cover_letter),
+        )
+        .route(
+            "/api/applications/{id}/cover-letter",
+            get(cover_letters::get_cover_letter),
+        )
+        .route(
+            "/api/applications/{id}/cover-letter",
+            put(cover_letters::update_cover_letter),
+        )
+        .route(
+            "/api/applications/{id}/cover-letter/pdf",
+            get(cover_letters::export_cover_letter_pdf),
+        )
         // Protected routes - Progress Tracking
         .route(
             "/api/progress/resource/{id}/start",
@@ -103,10 +261,27 @@ pub fn create_router(app_state: AppState) -> Router {
             put(progress::update_resource_progress),
         )
         .route("/api/progress", get(progress::get_my_progress))
+        // Protected routes - Real-time events (token in header or ?access_token=)
+        .route("/api/events", get(events::stream_events))
+        .route("/api/events/ws", get(events::events_websocket))
+        // Protected routes - Notifications
+        .route("/api/notifications", get(notifications::list_notifications))
+        .route("/api/notifications/unread-count", get(notifications::get_unread_count))
+        .route("/api/notifications/read-all", post(notifications::mark_all_notifications_read))
+        .route("/api/notifications/{id}/read", post(notifications::mark_notification_read))
+        .route(
+            "/api/notifications/preferences",
+            get(notifications::get_notification_preferences),
+        )
+        .route(
+            "/api/notifications/preferences",
+            put(notifications::update_notification_preferences),
+        )
         // Protected routes - AI Actions
         .route("/api/ai/action", post(ai::process_ai_action))
         .route("/api/ai/extract-skills", post(ai::extract_and_save_skills))
         .route("/api/ai/roadmap", post(ai::generate_roadmap))
+        .route("/api/ai/jobs/{id}", get(ai::get_ai_job))
         .route("/api/ai/roadmaps", get(ai::get_my_roadmaps))
         .route("/api/ai/roadmaps/{id}", get(ai::get_roadmap_by_id))
         .route(
@@ -139,6 +314,7 @@ pub fn create_router(app_state: AppState) -> Router {
                 .allow_origin(Any)
                 .allow_methods(Any)
                 .allow_headers(Any)
+                .expose_headers([header::ETAG, HeaderName::from_static(tailored_cv::TAILORED_CV_ID_HEADER)])
                 .allow_credentials(false),
         )
         .with_state(app_state)
@@ -0,0 +1,258 @@
+//! Notification handlers.
+//!
+//! Lists the user's notifications (see [`crate::notifications`] for how they
+//! are created), marks them read and manages which types the user receives.
+
+use super::types::{
+    NotificationPage, NotificationPreferences, NotificationQuery, UpdateNotificationPreferencesPayload,
+};
+use crate::AppState;
+use crate::auth::AuthUser;
+use crate::errors::{AppError, AppResult};
+use crate::models::Notification;
+use crate::notifications::NotificationType;
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use serde_json::{Value, json};
+use sqlx::PgPool;
+use tracing::info;
+use uuid::Uuid;
+
+/// Default and largest page size for the notification list.
+const DEFAULT_LIMIT: i64 = 20;
+const MAX_LIMIT: i64 = 100;
+
+/// Lists the authenticated user's notifications, newest first.
+///
+/// # Endpoint
+/// `GET /api/notifications?unread_only=true&type=job_match&limit=20&offset=0`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - The type is unknown (400)
+/// - Database operation fails
+pub async fn list_notifications(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Query(query): Query<NotificationQuery>,
+) -> AppResult<Json<NotificationPage>> {
+    let pool = &app_state.db_pool;
+    let notification_type = query.notification_type.map(NotificationType::as_str);
+
+    let notifications = sqlx::query_as!(
+        Notification,
+        r#"
+        SELECT id, user_id, title, message, type as notification_type, is_read, link, created_at
+        FROM notifications
+        WHERE user_id = $1 AND (NOT $2 OR NOT is_read) AND ($3::TEXT IS NULL OR type = $3)
+        ORDER BY created_at DESC, id DESC
+        LIMIT $4 OFFSET $5
+        "#,
+        auth_user.user_id,
+        query.unread_only,
+        notification_type,
+        query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT),
+        query.offset.unwrap_or(0).max(0)
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let total: i64 = sqlx::query_scalar!(
+        r#"
+        SELECT COUNT(*) AS "count!" FROM notifications
+        WHERE user_id = $1 AND (NOT $2 OR NOT is_read) AND ($3::TEXT IS NULL OR type = $3)
+        "#,
+        auth_user.user_id,
+        query.unread_only,
+        notification_type
+    )
+    .fetch_one(pool)
+    .await?;
+
+    let unread_count = count_unread(pool, auth_user.user_id).await?;
+
+    Ok(Json(NotificationPage { notifications, total, unread_count }))
+}
+
+/// Counts the authenticated user's unread notifications.
+///
+/// # Endpoint
+/// `GET /api/notifications/unread-count`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Database operation fails
+pub async fn get_unread_count(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+) -> AppResult<Json<Value>> {
+    let unread_count = count_unread(&app_state.db_pool, auth_user.user_id).await?;
+    Ok(Json(json!({ "unread_count": unread_count })))
+}
+
+/// Marks a notification as read.
+///
+/// # Endpoint
+/// `POST /api/notifications/{id}/read`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Notification doesn't exist or doesn't belong to user (404)
+/// - Database operation fails
+pub async fn mark_notification_read(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Path(notification_id): Path<i32>,
+) -> AppResult<Json<Notification>> {
+    sqlx::query_as!(
+        Notification,
+        r#"
+        UPDATE notifications SET is_read = TRUE
+        WHERE id = $1 AND user_id = $2
+        RETURNING id, user_id, title, message, type as notification_type, is_read, link, created_at
+        "#,
+        notification_id,
+        auth_user.user_id
+    )
+    .fetch_optional(&app_state.db_pool)
+    .await?
+    .map(Json)
+    .ok_or(AppError::NotFound)
+}
+
+/// Marks all of the authenticated user's notifications as read.
+///
+/// # Endpoint
+/// `POST /api/notifications/read-all`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Database operation fails
+pub async fn mark_all_notifications_read(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+) -> AppResult<Json<Value>> {
+    let result = sqlx::query!(
+        "UPDATE notifications SET is_read = TRUE WHERE user_id = $1 AND NOT is_read",
+        auth_user.user_id
+    )
+    .execute(&app_state.db_pool)
+    .await?;
+
+    info!(
+        "Marked {} notification(s) read for user {}",
+        result.rows_affected(),
+        auth_user.user_id
+    );
+    Ok(Json(json!({ "marked_read": result.rows_affected() })))
+}
+
+/// Retrieves which notification types the authenticated user receives.
+///
+/// # Endpoint
+/// `GET /api/notifications/preferences`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Database operation fails
+pub async fn get_notification_preferences(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+) -> AppResult<Json<NotificationPreferences>> {
+    load_preferences(&app_state.db_pool, auth_user.user_id).await.map(Json)
+}
+
+/// Turns notification types on or off for the authenticated user.
+///
+/// Turning a type off stops new notifications of that type; existing ones
+/// are kept.
+///
+/// # Endpoint
+/// `PUT /api/notifications/preferences`
+///
+/// # Request Body
+/// ```json
+/// { "job_match": false, "stalled_progress": true }
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Database operation fails
+pub async fn update_notification_preferences(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Json(payload): Json<UpdateNotificationPreferencesPayload>,
+) -> AppResult<Json<NotificationPreferences>> {
+    let pool = &app_state.db_pool;
+    let changes = [
+        (NotificationType::JobMatch, payload.job_match),
+        (NotificationType::ApplicationStatus, payload.application_status),
+        (NotificationType::RoadmapMilestone, payload.roadmap_milestone),
+        (NotificationType::StalledProgress, payload.stalled_progress),
+        (NotificationType::SavedSearch, payload.saved_search),
+    ];
+
+    let mut tx = pool.begin().await?;
+    for (kind, enabled) in changes {
+        let Some(enabled) = enabled else { continue };
+        sqlx::query!(
+            r#"
+            INSERT INTO notification_preferences (user_id, type, enabled)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (user_id, type) DO UPDATE
+            SET enabled = EXCLUDED.enabled, updated_at = CURRENT_TIMESTAMP
+            "#,
+            auth_user.user_id,
+            kind.as_str(),
+            enabled
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+
+    info!("Updated notification preferences for user {}", auth_user.user_id);
+    load_preferences(pool, auth_user.user_id).await.map(Json)
+}
+
+async fn count_unread(pool: &PgPool, user_id: Uuid) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "count!" FROM notifications WHERE user_id = $1 AND NOT is_read"#,
+        user_id
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Preferences with every type enabled unless the user turned it off.
+async fn load_preferences(pool: &PgPool, user_id: Uuid) -> AppResult<NotificationPreferences> {
+    let disabled: Vec<String> = sqlx::query_scalar!(
+        "SELECT type FROM notification_preferences WHERE user_id = $1 AND NOT enabled",
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+    let enabled = |kind: NotificationType| !disabled.iter().any(|t| t == kind.as_str());
+
+    Ok(NotificationPreferences {
+        job_match: enabled(NotificationType::JobMatch),
+        application_status: enabled(NotificationType::ApplicationStatus),
+        roadmap_milestone: enabled(NotificationType::RoadmapMilestone),
+        stalled_progress: enabled(NotificationType::StalledProgress),
+        saved_search: enabled(NotificationType::SavedSearch),
+    })
+}
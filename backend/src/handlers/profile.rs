@@ -1,27 +1,34 @@
 //! User profile management handlers.
 
-use super::types::{UpdateProfilePayload, UserProfile};
+use super::types::{GenerateCvQuery, PrivacySettings, UpdateProfilePayload, UserProfile};
 use crate::AppState;
 use crate::auth::AuthUser;
+use crate::completeness::{self, CompletenessReport, ProfileFacts};
+use crate::cv::document::{CvDocument, CvTemplate, description_points};
+use crate::cv::export::{self, ExportFormat};
+use crate::cv::sandbox::UploadRejection;
 use crate::errors::{AppError, AppResult};
 use crate::models::{CareerTrack, ExperienceLevel, User};
 use axum::{
     Json,
-    extract::{Multipart, State},
-    http::{StatusCode, header},
+    extract::{Multipart, Query, State},
+    http::{HeaderMap, StatusCode, header},
     response::{IntoResponse, Response},
 };
-use printpdf::*;
-use std::io::Write;
-use tempfile::NamedTempFile;
 use tracing::{debug, error, info, warn};
 use validator::Validate;
 
+/// Error for a years-of-experience range in the wrong order.
+const EXPERIENCE_YEARS_ORDER: &str = "experience_years_max must not be below experience_years_min";
+
 /// Retrieves the authenticated user's profile.
 ///
 /// Returns user profile information including `profile_completed` flag.
 /// Frontend can use this to show onboarding prompts if needed.
 ///
+/// The `ETag` header holds the profile version to send in `If-Match` when
+/// updating the profile.
+///
 /// # Errors
 ///
 /// Returns an error if:
@@ -31,7 +38,7 @@ use validator::Validate;
 pub async fn get_profile(
     auth_user: AuthUser,
     State(app_state): State<AppState>,
-) -> AppResult<Json<UserProfile>> {
+) -> AppResult<([(header::HeaderName, String); 1], Json<UserProfile>)> {
     info!("Fetching profile for user: {}", auth_user.user_id);
 
     let user = sqlx::query_as!(
@@ -40,6 +47,7 @@ pub async fn get_profile(
         SELECT 
             id, full_name, email, education_level,
             experience_level as "experience_level: ExperienceLevel",
+            experience_years_min, experience_years_max,
             preferred_track as "preferred_track: CareerTrack",
             profile_completed as "profile_completed!",
             skills, projects, target_roles, raw_cv_text, password_hash
@@ -60,18 +68,29 @@ pub async fn get_profile(
         user.id, user.profile_completed
     );
 
-    Ok(Json(UserProfile {
+    let entries = super::profile_entries::load_profile_entries(&app_state.db_pool, user.id).await?;
+    let version = sqlx::query_scalar!("SELECT profile_version FROM users WHERE id = $1", user.id)
+        .fetch_one(&app_state.db_pool)
+        .await?;
+
+    Ok(([(header::ETAG, profile_etag(version))], Json(UserProfile {
         id: user.id,
         full_name: user.full_name,
         email: user.email,
         profile_completed: user.profile_completed,
         education_level: user.education_level,
         experience_level: user.experience_level,
+        experience_years_min: user.experience_years_min,
+        experience_years_max: user.experience_years_max,
         preferred_track: user.preferred_track,
         skills: user.skills,
         projects: user.projects,
         target_roles: user.target_roles,
-    }))
+        education: entries.education,
+        work_experience: entries.work_experience,
+        project_details: entries.projects,
+        profile_version: version,
+    })))
 }
 
 /// Completes the user's profile after initial registration (onboarding step).
@@ -79,7 +98,7 @@ pub async fn get_profile(
 /// This is the second step in the user onboarding flow. After registering with
 /// just name, email, and password, users call this endpoint to provide:
 /// - Education level
-/// - Experience level (required)
+/// - Experience level (required) and years of experience (optional)
 /// - Preferred career track (required)
 /// - Skills, projects, and target roles (optional)
 ///
@@ -105,6 +124,7 @@ pub async fn complete_profile(
         );
         e
     })?;
+    check_experience_years(payload.experience_years_min, payload.experience_years_max)?;
 
     debug!(
         "Profile data: experience_level={:?}, preferred_track={:?}",
@@ -120,6 +140,8 @@ pub async fn complete_profile(
             skills = $4,
             projects = $5,
             target_roles = $6,
+            experience_years_min = $8,
+            experience_years_max = $9,
             profile_completed = TRUE,
             updated_at = CURRENT_TIMESTAMP
         WHERE id = $7
@@ -130,7 +152,9 @@ pub async fn complete_profile(
         &payload.skills.unwrap_or_default(),
         &payload.projects.unwrap_or_default(),
         &payload.target_roles.unwrap_or_default(),
-        auth_user.user_id
+        auth_user.user_id,
+        payload.experience_years_min,
+        payload.experience_years_max
     )
     .execute(&app_state.db_pool)
     .await
@@ -158,17 +182,28 @@ pub async fn complete_profile(
 /// Updates only the fields provided in the payload. Omitted fields remain unchanged.
 /// Can update experience level, preferred track, and all other profile fields.
 ///
+/// All fields are written in a single statement, so an update is applied
+/// completely or not at all. For optimistic concurrency, send the `ETag`
+/// from `GET /api/profile` in `If-Match`; if the profile changed since, the
+/// update is rejected with `412 Precondition Failed`. Without `If-Match` the
+/// update always applies. The response carries the new `ETag`.
+///
+/// Changes to skills and target roles are recorded in the profile history
+/// (see [`super::profile_history`]).
+///
 /// # Errors
 ///
 /// Returns an error if:
 /// - User is not authenticated
 /// - Validation fails
+/// - `If-Match` does not match the current profile version (412)
 /// - Database operation fails
 pub async fn update_profile(
     auth_user: AuthUser,
     State(app_state): State<AppState>,
+    headers: HeaderMap,
     Json(payload): Json<UpdateProfilePayload>,
-) -> AppResult<Json<serde_json::Value>> {
+) -> AppResult<([(header::HeaderName, String); 1], Json<serde_json::Value>)> {
     info!("Updating profile for user: {}", auth_user.user_id);
 
     payload.validate().map_err(|e| {
@@ -178,121 +213,307 @@ pub async fn update_profile(
         );
         e
     })?;
+    check_experience_years(payload.experience_years_min, payload.experience_years_max)?;
+
+    let expected_versions = if_match_versions(&headers);
+    let updated_fields: Vec<&str> = [
+        ("full_name", payload.full_name.is_some()),
+        ("education_level", payload.education_level.is_some()),
+        ("experience_level", payload.experience_level.is_some()),
+        ("experience_years_min", payload.experience_years_min.is_some()),
+        ("experience_years_max", payload.experience_years_max.is_some()),
+        ("preferred_track", payload.preferred_track.is_some()),
+        ("skills", payload.skills.is_some()),
+        ("projects", payload.projects.is_some()),
+        ("target_roles", payload.target_roles.is_some()),
+        ("raw_cv_text", payload.raw_cv_text.is_some()),
+    ]
+    .into_iter()
+    .filter_map(|(field, present)| present.then_some(field))
+    .collect();
+
+    let version = sqlx::query_scalar!(
+        r#"
+        UPDATE users
+        SET full_name = COALESCE($1, full_name),
+            education_level = COALESCE($2, education_level),
+            experience_level = COALESCE($3, experience_level),
+            preferred_track = COALESCE($4, preferred_track),
+            skills = COALESCE($5, skills),
+            projects = COALESCE($6, projects),
+            target_roles = COALESCE($7, target_roles),
+            raw_cv_text = COALESCE($8, raw_cv_text),
+            experience_years_min = COALESCE($11, experience_years_min),
+            experience_years_max = COALESCE($12, experience_years_max),
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $9 AND ($10::INTEGER[] IS NULL OR profile_version = ANY($10))
+        RETURNING profile_version
+        "#,
+        payload.full_name,
+        payload.education_level,
+        payload.experience_level as _,
+        payload.preferred_track as _,
+        payload.skills.as_deref(),
+        payload.projects.as_deref(),
+        payload.target_roles.as_deref(),
+        payload.raw_cv_text,
+        auth_user.user_id,
+        expected_versions.as_deref(),
+        payload.experience_years_min,
+        payload.experience_years_max
+    )
+    .fetch_optional(&app_state.db_pool)
+    .await
+    .map_err(|e| match e.as_database_error() {
+        // Only one bound given, crossing the stored other one
+        Some(db_err) if db_err.is_check_violation() => AppError::ValidationError(EXPERIENCE_YEARS_ORDER.to_string()),
+        _ => AppError::from(e),
+    })?;
 
-    let mut updated_fields = Vec::new();
+    let Some(version) = version else {
+        return Err(version_conflict(&app_state.db_pool, auth_user.user_id).await);
+    };
 
-    // Simple approach: update each field if provided
-    if let Some(full_name) = payload.full_name {
-        debug!("Updating full_name for user: {}", auth_user.user_id);
-        updated_fields.push("full_name");
-        sqlx::query!(
-            "UPDATE users SET full_name = $1, updated_at = CURRENT_TIMESTAMP WHERE id = $2",
-            full_name,
-            auth_user.user_id
-        )
-        .execute(&app_state.db_pool)
-        .await?;
-    }
-    if let Some(education_level) = payload.education_level {
-        updated_fields.push("education_level");
-        sqlx::query!(
-            "UPDATE users SET education_level = $1, updated_at = CURRENT_TIMESTAMP WHERE id = $2",
-            education_level,
-            auth_user.user_id
-        )
-        .execute(&app_state.db_pool)
-        .await?;
-    }
-    if let Some(experience_level) = payload.experience_level {
-        updated_fields.push("experience_level");
-        sqlx::query!(
-            "UPDATE users SET experience_level = $1, updated_at = CURRENT_TIMESTAMP WHERE id = $2",
-            experience_level as _,
-            auth_user.user_id
-        )
-        .execute(&app_state.db_pool)
-        .await?;
-    }
-    if let Some(preferred_track) = payload.preferred_track {
-        updated_fields.push("preferred_track");
-        sqlx::query!(
-            "UPDATE users SET preferred_track = $1, updated_at = CURRENT_TIMESTAMP WHERE id = $2",
-            preferred_track as _,
-            auth_user.user_id
-        )
-        .execute(&app_state.db_pool)
-        .await?;
-    }
-    if let Some(skills) = payload.skills {
-        updated_fields.push("skills");
-        sqlx::query!(
-            "UPDATE users SET skills = $1, updated_at = CURRENT_TIMESTAMP WHERE id = $2",
-            &skills,
-            auth_user.user_id
-        )
-        .execute(&app_state.db_pool)
-        .await?;
-    }
-    if let Some(projects) = payload.projects {
-        updated_fields.push("projects");
-        sqlx::query!(
-            "UPDATE users SET projects = $1, updated_at = CURRENT_TIMESTAMP WHERE id = $2",
-            &projects,
-            auth_user.user_id
-        )
-        .execute(&app_state.db_pool)
-        .await?;
+    info!(
+        "Profile updated successfully for user {}: fields updated: {:?} (version {})",
+        auth_user.user_id, updated_fields, version
+    );
+
+    Ok((
+        [(header::ETAG, profile_etag(version))],
+        Json(serde_json::json!({
+            "message": "Profile updated successfully",
+            "profile_version": version
+        })),
+    ))
+}
+
+/// Rejects a years-of-experience range whose maximum is below its minimum.
+fn check_experience_years(min: Option<i32>, max: Option<i32>) -> AppResult<()> {
+    if let (Some(min), Some(max)) = (min, max)
+        && max < min
+    {
+        return Err(AppError::ValidationError(EXPERIENCE_YEARS_ORDER.to_string()));
     }
-    if let Some(target_roles) = payload.target_roles {
-        updated_fields.push("target_roles");
-        sqlx::query!(
-            "UPDATE users SET target_roles = $1, updated_at = CURRENT_TIMESTAMP WHERE id = $2",
-            &target_roles,
-            auth_user.user_id
-        )
-        .execute(&app_state.db_pool)
-        .await?;
+    Ok(())
+}
+
+/// `ETag` value for a profile version.
+pub(super) fn profile_etag(version: i32) -> String {
+    format!("\"{}\"", version)
+}
+
+/// Profile versions accepted by the `If-Match` header; `None` when the
+/// header is absent or `*`.
+///
+/// Entity tags that are not profile versions are kept as `-1`, which never
+/// matches, so a stale or foreign tag fails the precondition.
+pub(super) fn if_match_versions(headers: &HeaderMap) -> Option<Vec<i32>> {
+    let value = headers.get(header::IF_MATCH)?.to_str().unwrap_or_default().trim();
+    if value == "*" {
+        return None;
     }
-    if let Some(raw_cv_text) = payload.raw_cv_text {
-        updated_fields.push("raw_cv_text");
-        sqlx::query!(
-            "UPDATE users SET raw_cv_text = $1, updated_at = CURRENT_TIMESTAMP WHERE id = $2",
-            raw_cv_text,
-            auth_user.user_id
-        )
-        .execute(&app_state.db_pool)
-        .await?;
+    Some(
+        value
+            .split(',')
+            .map(|tag| {
+                let tag = tag.trim();
+                tag.strip_prefix("W/")
+                    .unwrap_or(tag)
+                    .trim_matches('"')
+                    .parse()
+                    .unwrap_or(-1)
+            })
+            .collect(),
+    )
+}
+
+/// Error for a conditional profile update that matched no row: the user is
+/// gone (404) or the profile has a newer version (412).
+pub(super) async fn version_conflict(pool: &sqlx::PgPool, user_id: uuid::Uuid) -> AppError {
+    let current = sqlx::query_scalar!("SELECT profile_version FROM users WHERE id = $1", user_id)
+        .fetch_optional(pool)
+        .await;
+    match current {
+        Ok(Some(version)) => {
+            warn!("Profile update for user {} rejected: profile is at version {}", user_id, version);
+            AppError::PreconditionFailed(
+                "The profile was changed since you loaded it. Reload it and try again.".to_string(),
+            )
+        }
+        Ok(None) => AppError::NotFound,
+        Err(e) => e.into(),
     }
+}
 
+/// Retrieves the authenticated user's AI privacy settings.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - User not found in database
+/// - Database operation fails
+pub async fn get_privacy_settings(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+) -> AppResult<Json<PrivacySettings>> {
+    let share_cv_with_ai =
+        sqlx::query_scalar!("SELECT share_cv_with_ai FROM users WHERE id = $1", auth_user.user_id)
+            .fetch_optional(&app_state.db_pool)
+            .await?
+            .ok_or(AppError::NotFound)?;
+
+    Ok(Json(PrivacySettings { share_cv_with_ai }))
+}
+
+/// Updates the authenticated user's AI privacy settings.
+///
+/// When `share_cv_with_ai` is `false`, CV text, project details and the
+/// user's name are never sent to external AI providers. AI features that
+/// cannot work without them respond with `403 Forbidden`.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - User not found in database
+/// - Database operation fails
+pub async fn update_privacy_settings(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Json(payload): Json<PrivacySettings>,
+) -> AppResult<Json<PrivacySettings>> {
     info!(
-        "Profile updated successfully for user {}: fields updated: {:?}",
-        auth_user.user_id, updated_fields
+        "Updating AI privacy settings for user {}: share_cv_with_ai={}",
+        auth_user.user_id, payload.share_cv_with_ai
     );
 
-    Ok(Json(serde_json::json!({
-        "message": "Profile updated successfully"
-    })))
+    let result = sqlx::query!(
+        "UPDATE users SET share_cv_with_ai = $1, updated_at = CURRENT_TIMESTAMP WHERE id = $2",
+        payload.share_cv_with_ai,
+        auth_user.user_id
+    )
+    .execute(&app_state.db_pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+
+    Ok(Json(payload))
 }
 
-/// Uploads and processes a CV/resume PDF file.
+/// Scores how complete the authenticated user's profile is.
 ///
-/// Accepts a PDF file via multipart form upload, extracts the text content,
-/// and saves it to the user's profile as `raw_cv_text`.
+/// Sections are weighted for the user's career track and the response
+/// includes a checklist of next actions, biggest gain first. Scoring is
+/// deterministic and does not call any AI provider.
 ///
-/// # File Requirements
+/// # Endpoint
 ///
-/// - Format: PDF only
-/// - Max size: 10MB
-/// - Field name: `cv_file`
+/// `GET /api/profile/completeness`
 ///
 /// # Errors
 ///
 /// Returns an error if:
 /// - User is not authenticated
-/// - File is not a PDF
-/// - File exceeds size limit
-/// - PDF text extraction fails
+/// - User not found in database
 /// - Database operation fails
+pub async fn get_profile_completeness(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+) -> AppResult<Json<CompletenessReport>> {
+    let pool = &app_state.db_pool;
+
+    let user = sqlx::query!(
+        r#"
+        SELECT preferred_track as "preferred_track: CareerTrack", target_roles,
+               raw_cv_text IS NOT NULL
+               OR EXISTS (SELECT 1 FROM cv_versions WHERE user_id = users.id) AS "has_cv!"
+        FROM users
+        WHERE id = $1
+        "#,
+        auth_user.user_id
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or(AppError::NotFound)?;
+
+    let rated = sqlx::query_scalar!(
+        "SELECT skill_name FROM skill_assessments WHERE user_id = $1 AND proficiency_level IS NOT NULL",
+        auth_user.user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    // The same merged view the CV export uses: structured entries, then the
+    // parsed CV, then legacy profile fields
+    let cv = super::cv::load_cv_document(pool, auth_user.user_id).await?.cv;
+    let (detailed_projects, other_projects): (Vec<_>, Vec<_>) = cv
+        .projects
+        .iter()
+        .partition(|p| p.description.as_deref().is_some_and(|d| !d.trim().is_empty()));
+    let filled = |value: &Option<String>| value.as_deref().is_some_and(|v| !v.trim().is_empty());
+
+    let facts = ProfileFacts {
+        has_phone: filled(&cv.contact.phone),
+        has_location: filled(&cv.contact.location),
+        has_links: !cv.contact.links.is_empty(),
+        education_entries: cv.education.iter().filter(|e| filled(&e.organization)).count(),
+        has_education_level: !cv.education.is_empty(),
+        experience_entries: cv.experience.len(),
+        experience_with_bullets: cv
+            .experience
+            .iter()
+            .filter(|e| !description_points(e).is_empty())
+            .count(),
+        skills: cv.skills.len(),
+        rated_skills: cv
+            .skills
+            .iter()
+            .filter(|s| rated.iter().any(|r| r.eq_ignore_ascii_case(s)))
+            .count(),
+        detailed_projects: detailed_projects.len(),
+        other_projects: other_projects.len(),
+        has_cv: user.has_cv,
+        target_roles: user.target_roles.len(),
+    };
+
+    let report = completeness::evaluate(&facts, user.preferred_track);
+    debug!(
+        "Profile completeness for user {}: {}/100",
+        auth_user.user_id, report.score
+    );
+
+    Ok(Json(report))
+}
+
+/// Uploads and processes a CV/resume file.
+///
+/// Accepts a CV via multipart form upload, extracts the text content,
+/// and saves it to the user's profile as `raw_cv_text`.
+///
+/// # File Requirements
+///
+/// - Format: PDF, DOCX, ODT, plain text, Markdown, or an image (PNG, JPEG,
+///   TIFF, WebP, BMP) when an OCR engine is installed. The format is detected
+///   from the file content, not the filename.
+/// - Max size: `CV_MAX_UPLOAD_MB` (default 10MB)
+/// - Field name: `cv_file`
+///
+/// Extraction runs in a resource-limited worker (see [`crate::cv::sandbox`]).
+///
+/// # Errors
+///
+/// Returns an error with a `code` (see [`UploadRejection`]) if:
+/// - The `cv_file` field is missing or empty
+/// - File exceeds the size limit (413)
+/// - File type is not supported (415)
+/// - File is corrupt, has no text, or extraction times out or hits the memory ceiling (422)
+///
+/// Also returns an error if the user is not authenticated or a database
+/// operation fails.
 pub async fn upload_cv(
     auth_user: AuthUser,
     State(app_state): State<AppState>,
@@ -300,12 +521,19 @@ pub async fn upload_cv(
 ) -> AppResult<Json<serde_json::Value>> {
     info!("Processing CV upload for user: {}", auth_user.user_id);
 
-    const MAX_FILE_SIZE: usize = 10 * 1024 * 1024; // 10MB
+    let max_file_bytes = app_state.cv_extractor.limits().max_file_bytes;
+    let too_large = || UploadRejection::FileTooLarge {
+        limit_mb: max_file_bytes.div_ceil(1024 * 1024),
+    };
 
     // Process multipart form
-    while let Some(field) = multipart.next_field().await.map_err(|e| {
+    while let Some(mut field) = multipart.next_field().await.map_err(|e| {
         error!("Failed to read multipart field: {}", e);
-        AppError::BadRequest("Invalid multipart data".to_string())
+        if e.status() == StatusCode::PAYLOAD_TOO_LARGE {
+            AppError::from(too_large())
+        } else {
+            AppError::BadRequest("Invalid multipart data".to_string())
+        }
     })? {
         let field_name = field.name().unwrap_or("").to_string();
 
@@ -327,122 +555,91 @@ pub async fn upload_cv(
 
         debug!("Uploaded file: {}", file_name);
 
-        // Verify it's a PDF file
-        if !file_name.to_lowercase().ends_with(".pdf") {
-            warn!(
-                "CV upload failed: invalid file type '{}' for user {}",
-                file_name, auth_user.user_id
-            );
-            return Err(AppError::BadRequest(
-                "Only PDF files are supported".to_string(),
-            ));
-        }
-
-        // Read file data
-        let file_data = field.bytes().await.map_err(|e| {
+        // Read file data chunk by chunk, stopping as soon as the limit is exceeded
+        let mut file_data = Vec::new();
+        while let Some(chunk) = field.chunk().await.map_err(|e| {
             error!("Failed to read file data: {}", e);
-            AppError::BadRequest("Failed to read file data".to_string())
-        })?;
-
-        debug!("File size: {} bytes", file_data.len());
-
-        // Check file size
-        if file_data.len() > MAX_FILE_SIZE {
-            warn!(
-                "CV upload failed: file too large ({} bytes) for user {}",
-                file_data.len(),
-                auth_user.user_id
-            );
-            return Err(AppError::BadRequest(
-                "File size exceeds 10MB limit".to_string(),
-            ));
-        }
-
-        // Verify PDF header (should start with %PDF)
-        if file_data.len() < 5 || !file_data.starts_with(b"%PDF") {
-            error!(
-                "Invalid PDF header for user {}. First bytes: {:?}",
-                auth_user.user_id,
-                &file_data.get(0..10)
-            );
-            return Err(AppError::BadRequest(
-                "Invalid PDF file. The file may be corrupted or not a valid PDF.".to_string(),
-            ));
+            if e.status() == StatusCode::PAYLOAD_TOO_LARGE {
+                AppError::from(too_large())
+            } else {
+                AppError::BadRequest("Failed to read file data".to_string())
+            }
+        })? {
+            if file_data.len() + chunk.len() > max_file_bytes {
+                warn!(
+                    "CV upload failed: file larger than {} bytes for user {}",
+                    max_file_bytes, auth_user.user_id
+                );
+                return Err(too_large().into());
+            }
+            file_data.extend_from_slice(&chunk);
         }
 
-        // Create a temporary file to write the PDF data
-        let mut temp_file = NamedTempFile::new().map_err(|e| {
-            error!(
-                "Failed to create temporary file for user {}: {}",
-                auth_user.user_id, e
-            );
-            AppError::InternalServerError
-        })?;
-
-        temp_file.write_all(&file_data).map_err(|e| {
-            error!(
-                "Failed to write PDF data for user {}: {}",
-                auth_user.user_id, e
-            );
-            AppError::InternalServerError
-        })?;
-
-        // Flush to ensure all data is written
-        temp_file.flush().map_err(|e| {
-            error!(
-                "Failed to flush PDF data for user {}: {}",
-                auth_user.user_id, e
-            );
-            AppError::InternalServerError
-        })?;
-
-        let temp_path = temp_file.path();
-
-        // Extract text from PDF
-        let extracted_text = pdf_extract::extract_text(temp_path).map_err(|e| {
-            error!(
-                "PDF text extraction failed for user {}: {}",
-                auth_user.user_id, e
-            );
-            AppError::BadRequest(format!("Failed to extract text from PDF: {}", e))
-        })?;
-
-        debug!("Extracted {} characters from PDF", extracted_text.len());
+        debug!("File size: {} bytes", file_data.len());
 
-        if extracted_text.trim().is_empty() {
-            warn!(
-                "CV upload: extracted text is empty for user {}",
-                auth_user.user_id
-            );
-            return Err(AppError::BadRequest(
-                "PDF appears to be empty or contains no extractable text".to_string(),
-            ));
-        }
+        // Check the magic bytes and extract the text in an isolated worker
+        let extracted = app_state
+            .cv_extractor
+            .extract(&file_data)
+            .await
+            .map_err(|e| {
+                warn!(
+                    "CV text extraction failed for user {} (file '{}'): {} [{}]",
+                    auth_user.user_id,
+                    file_name,
+                    e,
+                    e.code()
+                );
+                AppError::from(e)
+            })?;
+        debug!(
+            "Extracted {} characters from {} CV",
+            extracted.text.len(),
+            extracted.format.as_str()
+        );
 
-        // Save extracted text to database
-        sqlx::query!(
-            "UPDATE users SET raw_cv_text = $1, updated_at = CURRENT_TIMESTAMP WHERE id = $2",
-            extracted_text,
-            auth_user.user_id
+        // Keep the original file as a new version; the deterministic parse is
+        // reviewed via /api/profile/cv/parsed
+        let saved = super::cv_versions::save_upload(
+            &app_state,
+            auth_user.user_id,
+            &file_name,
+            &file_data,
+            &extracted,
         )
-        .execute(&app_state.db_pool)
         .await
         .map_err(|e| {
             error!(
-                "Failed to save CV text for user {}: {}",
+                "Failed to save CV for user {}: {}",
                 auth_user.user_id, e
             );
             e
         })?;
+        let parsed = saved.parsed;
 
         info!(
-            "CV uploaded and processed successfully for user: {}",
-            auth_user.user_id
+            "CV uploaded and processed successfully for user: {} (version {})",
+            auth_user.user_id, saved.version.version_number
         );
 
         return Ok(Json(serde_json::json!({
             "message": "CV uploaded and processed successfully",
-            "extracted_length": extracted_text.len()
+            "extracted_length": extracted.text.len(),
+            "format": extracted.format.as_str(),
+            "version": {
+                "id": saved.version.id,
+                "version_number": saved.version.version_number,
+                "sha256": saved.version.sha256,
+                "size_bytes": saved.version.size_bytes,
+                "duplicate": saved.duplicate
+            },
+            "parsed": {
+                "education": parsed.education.len(),
+                "experience": parsed.experience.len(),
+                "projects": parsed.projects.len(),
+                "skills": parsed.skills.len(),
+                "certifications": parsed.certifications.len()
+            }
         })));
     }
 
@@ -451,206 +648,118 @@ pub async fn upload_cv(
         "CV upload failed: no cv_file field found for user {}",
         auth_user.user_id
     );
-    Err(AppError::BadRequest(
-        "No cv_file field found in request".to_string(),
-    ))
+    Err(UploadRejection::MissingFile.into())
 }
 
-/// Generates a PDF CV based on the user's profile data.
+/// Generates a CV from the user's profile and reviewed CV data.
 ///
-/// Creates a professional CV document using the user's profile information including:
-/// - Full name and email
-/// - Education level
-/// - Experience level and preferred career track
-/// - Skills
-/// - Projects
-/// - Target roles
+/// The document combines the profile (name, email, skills, target roles) with
+/// the parsed CV sections: summary, experience, projects, education and
+/// certifications, and is rendered as PDF (default), DOCX, standalone HTML,
+/// Markdown or JSON Resume. The format comes from the `format` parameter or,
+/// without it, the `Accept` header. PDFs use embedded Unicode fonts, so
+/// Bengali names and descriptions render correctly, and long CVs flow onto
+/// additional pages.
+///
+/// # Endpoint
+/// `GET /api/profile/generate-cv?template=classic|modern|ats&format=pdf|docx|html|markdown|json`
 ///
-/// Returns the PDF as a downloadable file.
+/// Also available as `GET /api/profile/cv/export`.
+///
+/// Returns the document as a downloadable file.
 ///
 /// # Errors
 ///
 /// Returns an error if:
 /// - User is not authenticated
 /// - User profile not found
-/// - PDF generation fails
+/// - The template or format is unknown (400)
+/// - Rendering fails
 pub async fn generate_cv(
     auth_user: AuthUser,
     State(app_state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<GenerateCvQuery>,
 ) -> AppResult<Response> {
-    info!("Generating CV for user: {}", auth_user.user_id);
+    let format = negotiate_format(query.format, &headers);
+    info!(
+        "Generating CV for user: {} (format: {:?}, template: {:?})",
+        auth_user.user_id, format, query.template
+    );
 
-    // Fetch user profile
-    let user = sqlx::query_as!(
-        User,
-        r#"
-        SELECT 
-            id, full_name, email, education_level,
-            experience_level as "experience_level: ExperienceLevel",
-            preferred_track as "preferred_track: CareerTrack",
-            profile_completed as "profile_completed!",
-            skills, projects, target_roles, raw_cv_text, password_hash
-        FROM users 
-        WHERE id = $1
-        "#,
-        auth_user.user_id
-    )
-    .fetch_optional(&app_state.db_pool)
-    .await?
-    .ok_or_else(|| {
-        error!("User not found for CV generation: {}", auth_user.user_id);
-        AppError::NotFound
-    })?;
+    let document = super::cv::load_cv_document(&app_state.db_pool, auth_user.user_id).await?;
+    debug!("Generating CV for: {}", document.name());
 
-    debug!("Generating CV for: {}", user.full_name);
+    let name = document.name().to_string();
+    let response = cv_download(document, format, query.template, &name).await?;
+    info!("CV generated successfully for user: {}", auth_user.user_id);
 
-    // Create PDF document
-    let (doc, page1, layer1) = PdfDocument::new(
-        &format!("{}_CV", user.full_name.replace(' ', "_")),
-        Mm(210.0), // A4 width
-        Mm(297.0), // A4 height
-        "Layer 1",
-    );
+    Ok(response)
+}
 
-    let font = doc.add_builtin_font(BuiltinFont::Helvetica).map_err(|e| {
-        error!("Failed to add font: {}", e);
-        AppError::InternalServerError
-    })?;
-    let font_bold = doc
-        .add_builtin_font(BuiltinFont::HelveticaBold)
+/// Picks the export format from the `format` parameter or, without it, the
+/// `Accept` header; PDF otherwise.
+pub(super) fn negotiate_format(format: Option<ExportFormat>, headers: &HeaderMap) -> ExportFormat {
+    format
+        .or_else(|| {
+            headers
+                .get(header::ACCEPT)
+                .and_then(|v| v.to_str().ok())
+                .and_then(ExportFormat::from_accept)
+        })
+        .unwrap_or_default()
+}
+
+/// Renders a CV document and wraps it in a download response named
+/// `<file_stem>_CV.<ext>`.
+pub(super) async fn cv_download(
+    document: CvDocument,
+    format: ExportFormat,
+    template: CvTemplate,
+    file_stem: &str,
+) -> AppResult<Response> {
+    // Layout, font embedding and zipping are CPU-bound
+    let bytes = tokio::task::spawn_blocking(move || export::render(&document, format, template))
+        .await
         .map_err(|e| {
-            error!("Failed to add bold font: {}", e);
+            error!("CV rendering task failed: {}", e);
+            AppError::InternalServerError
+        })?
+        .map_err(|e| {
+            error!("Failed to generate CV: {}", e);
             AppError::InternalServerError
         })?;
+    debug!("Rendered CV as {:?} ({} bytes)", format, bytes.len());
 
-    let current_layer = doc.get_page(page1).get_layer(layer1);
-
-    let mut y_position = 270.0; // Start from top (A4 is 297mm high)
-    let left_margin = 20.0;
-    let line_height = 6.0;
-
-    // Helper function to add text
-    let add_text = |text: &str, font_ref: &IndirectFontRef, size: f32, y: &mut f32| {
-        current_layer.use_text(text, size, Mm(left_margin), Mm(*y), font_ref);
-        *y -= line_height;
-    };
-
-    // Helper function to add bold heading
-    let add_heading = |text: &str, y: &mut f32| {
-        current_layer.use_text(text, 16.0, Mm(left_margin), Mm(*y), &font_bold);
-        *y -= line_height * 1.5;
-    };
-
-    let add_section_title = |text: &str, y: &mut f32| {
-        *y -= 3.0; // Extra spacing before sections
-        current_layer.use_text(text, 14.0, Mm(left_margin), Mm(*y), &font_bold);
-        *y -= line_height * 1.2;
-    };
-
-    // Title - Name
-    add_heading(&user.full_name, &mut y_position);
-
-    // Contact Information
-    add_text(&user.email, &font, 11.0, &mut y_position);
-
-    // Education & Experience
-    add_section_title("Education & Experience", &mut y_position);
-    if let Some(edu) = &user.education_level {
-        add_text(&format!("Education: {}", edu), &font, 11.0, &mut y_position);
-    }
-    add_text(
-        &format!(
-            "Experience Level: {}",
-            match &user.experience_level {
-                Some(ExperienceLevel::Fresher) => "Fresher (0-1 years)",
-                Some(ExperienceLevel::Junior) => "Junior (1-3 years)",
-                Some(ExperienceLevel::Mid) => "Mid-level (3-5 years)",
-                None => "Not specified",
-            }
-        ),
-        &font,
-        11.0,
-        &mut y_position,
-    );
-
-    // Career Track
-    add_section_title("Career Track", &mut y_position);
-    add_text(
-        &format!(
-            "Preferred Track: {}",
-            match &user.preferred_track {
-                Some(CareerTrack::WebDevelopment) => "Web Development",
-                Some(CareerTrack::Data) => "Data Science & Analytics",
-                Some(CareerTrack::Design) => "UI/UX Design",
-                Some(CareerTrack::Marketing) => "Digital Marketing",
-                None => "Not specified",
-            }
-        ),
-        &font,
-        11.0,
-        &mut y_position,
-    );
-
-    // Skills
-    if !user.skills.is_empty() {
-        add_section_title("Skills", &mut y_position);
-        let skills_text = user.skills.join(", ");
-        // Word wrap for long skills list
-        let max_chars_per_line = 80;
-        for chunk in skills_text.as_bytes().chunks(max_chars_per_line) {
-            if let Ok(chunk_str) = std::str::from_utf8(chunk) {
-                add_text(chunk_str, &font, 11.0, &mut y_position);
-            }
-        }
-    }
-
-    // Projects
-    if !user.projects.is_empty() {
-        add_section_title("Projects", &mut y_position);
-        for (i, project) in user.projects.iter().enumerate() {
-            add_text(
-                &format!("{}. {}", i + 1, project),
-                &font,
-                11.0,
-                &mut y_position,
-            );
-        }
-    }
-
-    // Target Roles
-    if !user.target_roles.is_empty() {
-        add_section_title("Target Roles", &mut y_position);
-        for role in &user.target_roles {
-            add_text(&format!("• {}", role), &font, 11.0, &mut y_position);
-        }
-    }
-
-    // Save PDF to bytes
-    let pdf_bytes = doc.save_to_bytes().map_err(|e| {
-        error!("Failed to generate PDF bytes: {}", e);
-        AppError::InternalServerError
-    })?;
-
-    info!(
-        "CV generated successfully for user: {} ({} bytes)",
-        auth_user.user_id,
-        pdf_bytes.len()
+    let file_name = format!(
+        "{}_CV.{}",
+        file_stem.split_whitespace().collect::<Vec<_>>().join("_"),
+        format.extension()
     );
 
-    // Return PDF as downloadable file
     Ok((
         StatusCode::OK,
         [
-            (header::CONTENT_TYPE, "application/pdf"),
-            (
-                header::CONTENT_DISPOSITION,
-                &format!(
-                    "attachment; filename=\"{}_CV.pdf\"",
-                    user.full_name.replace(' ', "_")
-                ),
-            ),
+            (header::CONTENT_TYPE, format.mime_type().to_string()),
+            (header::CONTENT_DISPOSITION, attachment(&file_name)),
+            (header::VARY, "Accept".to_string()),
         ],
-        pdf_bytes,
+        bytes,
     )
         .into_response())
 }
+
+/// `Content-Disposition` value for downloading a file.
+///
+/// Carries an ASCII fallback for old clients and the UTF-8 name for the rest.
+pub(super) fn attachment(file_name: &str) -> String {
+    let ascii_name: String = file_name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || "._-".contains(c) { c } else { '_' })
+        .collect();
+    format!(
+        "attachment; filename=\"{}\"; filename*=UTF-8''{}",
+        ascii_name,
+        urlencoding::encode(file_name)
+    )
+}
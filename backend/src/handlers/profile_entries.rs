@@ -0,0 +1,695 @@
+//! Structured profile entries: education history, work experience and projects.
+//!
+//! These replace the free-text `education_level` and `projects` fields for
+//! users who fill them in, and feed the generated CV, the AI summary and the
+//! career mentor.
+
+use super::types::{EducationPayload, ProjectPayload, WorkExperiencePayload};
+use crate::AppState;
+use crate::auth::AuthUser;
+use crate::cv::CvEntry;
+use crate::errors::{AppError, AppResult};
+use crate::models::{Education, Project, WorkExperience};
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use chrono::NaiveDate;
+use sqlx::PgPool;
+use tracing::{info, warn};
+use uuid::Uuid;
+use validator::Validate;
+
+/// A user's education history, work experience and projects.
+#[derive(Debug, Default)]
+pub(super) struct ProfileEntries {
+    pub education: Vec<Education>,
+    pub work_experience: Vec<WorkExperience>,
+    pub projects: Vec<Project>,
+}
+
+/// Loads all structured profile entries of a user, most recent first.
+pub(super) async fn load_profile_entries(pool: &PgPool, user_id: Uuid) -> AppResult<ProfileEntries> {
+    Ok(ProfileEntries {
+        education: load_education(pool, user_id).await?,
+        work_experience: load_work_experience(pool, user_id).await?,
+        projects: load_projects(pool, user_id).await?,
+    })
+}
+
+async fn load_education(pool: &PgPool, user_id: Uuid) -> AppResult<Vec<Education>> {
+    // Current entries first, then by start date, undated last
+    let entries = sqlx::query_as!(
+        Education,
+        "SELECT * FROM education_entries WHERE user_id = $1 \
+         ORDER BY is_current DESC, start_date DESC NULLS LAST, id DESC",
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(entries)
+}
+
+async fn load_work_experience(pool: &PgPool, user_id: Uuid) -> AppResult<Vec<WorkExperience>> {
+    let entries = sqlx::query_as!(
+        WorkExperience,
+        "SELECT * FROM work_experiences WHERE user_id = $1 \
+         ORDER BY is_current DESC, start_date DESC NULLS LAST, id DESC",
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(entries)
+}
+
+async fn load_projects(pool: &PgPool, user_id: Uuid) -> AppResult<Vec<Project>> {
+    // Projects have no "current" flag; an open end date means ongoing
+    let projects = sqlx::query_as!(
+        Project,
+        "SELECT * FROM projects WHERE user_id = $1 \
+         ORDER BY (start_date IS NOT NULL AND end_date IS NULL) DESC, start_date DESC NULLS LAST, id DESC",
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(projects)
+}
+
+impl ProfileEntries {
+    /// Education entries as CV entries.
+    pub fn education_cv_entries(&self) -> Vec<CvEntry> {
+        self.education
+            .iter()
+            .map(|e| {
+                let title = match (e.degree.as_deref(), e.field_of_study.as_deref()) {
+                    (Some(degree), Some(field)) => format!("{} in {}", degree, field),
+                    (Some(degree), None) => degree.to_string(),
+                    (None, Some(field)) => field.to_string(),
+                    (None, None) => e.institution.clone(),
+                };
+                let organization = (title != e.institution).then(|| e.institution.clone());
+                CvEntry {
+                    title,
+                    organization,
+                    start_date: e.start_date,
+                    end_date: e.end_date,
+                    is_current: e.is_current,
+                    description: e.grade.as_ref().map(|g| format!("Grade: {}", g)),
+                    ..CvEntry::default()
+                }
+            })
+            .collect()
+    }
+
+    /// Work experience as CV entries; skills used become the last bullet point.
+    pub fn experience_cv_entries(&self) -> Vec<CvEntry> {
+        self.work_experience
+            .iter()
+            .map(|w| {
+                let mut points = w.bullet_points.clone();
+                if !w.skills_used.is_empty() {
+                    points.push(format!("Skills: {}", w.skills_used.join(", ")));
+                }
+                CvEntry {
+                    title: w.title.clone(),
+                    organization: Some(w.employer.clone()),
+                    location: w.location.clone(),
+                    start_date: w.start_date,
+                    end_date: w.end_date,
+                    is_current: w.is_current,
+                    description: (!points.is_empty()).then(|| points.join("\n")),
+                }
+            })
+            .collect()
+    }
+
+    /// Projects as CV entries, with the tech stack and link after the description.
+    pub fn project_cv_entries(&self) -> Vec<CvEntry> {
+        self.projects
+            .iter()
+            .map(|p| {
+                let mut lines: Vec<String> = p
+                    .description
+                    .iter()
+                    .flat_map(|d| d.lines())
+                    .map(str::to_string)
+                    .collect();
+                if !p.tech_stack.is_empty() {
+                    lines.push(format!("Tech stack: {}", p.tech_stack.join(", ")));
+                }
+                lines.extend(p.url.clone());
+                CvEntry {
+                    title: p.title.clone(),
+                    start_date: p.start_date,
+                    end_date: p.end_date,
+                    description: (!lines.is_empty()).then(|| lines.join("\n")),
+                    ..CvEntry::default()
+                }
+            })
+            .collect()
+    }
+
+    /// One line per section for AI prompts, e.g.
+    /// `Work Experience: Data Analyst at bKash (2023 – present)`.
+    ///
+    /// Sections without entries are left out.
+    pub fn context_lines(&self) -> Vec<String> {
+        let years = |start: Option<NaiveDate>, end: Option<NaiveDate>, current: bool| {
+            let start = start.map(|d| d.format("%Y").to_string());
+            let end = if current { Some("present".to_string()) } else { end.map(|d| d.format("%Y").to_string()) };
+            match (start, end) {
+                (Some(s), Some(e)) => format!(" ({} – {})", s, e),
+                (Some(d), None) | (None, Some(d)) => format!(" ({})", d),
+                (None, None) => String::new(),
+            }
+        };
+
+        let education: Vec<String> = self
+            .education
+            .iter()
+            .map(|e| {
+                let qualification = [e.degree.as_deref(), e.field_of_study.as_deref()]
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<_>>()
+                    .join(" in ");
+                let grade = e.grade.as_ref().map(|g| format!(", grade {}", g)).unwrap_or_default();
+                if qualification.is_empty() {
+                    format!("{}{}{}", e.institution, years(e.start_date, e.end_date, e.is_current), grade)
+                } else {
+                    format!("{} at {}{}{}", qualification, e.institution, years(e.start_date, e.end_date, e.is_current), grade)
+                }
+            })
+            .collect();
+
+        let experience: Vec<String> = self
+            .work_experience
+            .iter()
+            .map(|w| {
+                let mut line = format!("{} at {}{}", w.title, w.employer, years(w.start_date, w.end_date, w.is_current));
+                if !w.bullet_points.is_empty() {
+                    line.push_str(&format!(": {}", w.bullet_points.join("; ")));
+                }
+                if !w.skills_used.is_empty() {
+                    line.push_str(&format!(" [skills: {}]", w.skills_used.join(", ")));
+                }
+                line
+            })
+            .collect();
+
+        let projects: Vec<String> = self
+            .projects
+            .iter()
+            .map(|p| {
+                let mut line = p.title.clone();
+                if let Some(description) = p.description.as_deref().filter(|d| !d.trim().is_empty()) {
+                    line.push_str(&format!(": {}", description.trim()));
+                }
+                if !p.tech_stack.is_empty() {
+                    line.push_str(&format!(" [tech: {}]", p.tech_stack.join(", ")));
+                }
+                line
+            })
+            .collect();
+
+        [("Education", education), ("Work Experience", experience), ("Projects", projects)]
+            .into_iter()
+            .filter(|(_, items)| !items.is_empty())
+            .map(|(label, items)| format!("{}: {}", label, items.join("; ")))
+            .collect()
+    }
+}
+
+/// Rejects end dates before start dates and end dates on current entries.
+fn check_dates(start: Option<NaiveDate>, end: Option<NaiveDate>, is_current: bool) -> AppResult<()> {
+    if let (Some(start), Some(end)) = (start, end)
+        && end < start
+    {
+        return Err(AppError::ValidationError("end_date must not be before start_date".to_string()));
+    }
+    if is_current && end.is_some() {
+        return Err(AppError::ValidationError("A current entry cannot have an end_date".to_string()));
+    }
+    Ok(())
+}
+
+/// Trims list items and drops empty ones and case-insensitive duplicates.
+pub(super) fn clean_list(items: Vec<String>) -> Vec<String> {
+    let mut cleaned: Vec<String> = Vec::with_capacity(items.len());
+    for item in items {
+        let item = item.trim();
+        if !item.is_empty() && !cleaned.iter().any(|c| c.eq_ignore_ascii_case(item)) {
+            cleaned.push(item.to_string());
+        }
+    }
+    cleaned
+}
+
+/// Trims an optional text field, treating blank text as absent.
+pub(super) fn clean_text(text: Option<String>) -> Option<String> {
+    text.map(|t| t.trim().to_string()).filter(|t| !t.is_empty())
+}
+
+/// Lists the user's education history, most recent first.
+///
+/// # Endpoint
+/// `GET /api/profile/education`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Database operation fails
+pub async fn list_education(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+) -> AppResult<Json<Vec<Education>>> {
+    load_education(&app_state.db_pool, auth_user.user_id).await.map(Json)
+}
+
+/// Adds an education entry.
+///
+/// # Endpoint
+/// `POST /api/profile/education`
+///
+/// # Request Body
+/// ```json
+/// {
+///   "institution": "Chittagong University of Engineering & Technology",
+///   "degree": "BSc",
+///   "field_of_study": "Computer Science and Engineering",
+///   "start_date": "2018-01-01",
+///   "end_date": "2022-06-30",
+///   "grade": "CGPA 3.70/4.00"
+/// }
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Validation fails (missing institution, end date before start date) (400)
+/// - Database operation fails
+pub async fn create_education(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Json(payload): Json<EducationPayload>,
+) -> AppResult<(StatusCode, Json<Education>)> {
+    info!("Adding education entry for user: {}", auth_user.user_id);
+    payload.validate().map_err(|e| {
+        warn!("Education validation failed for user {}: {}", auth_user.user_id, e);
+        e
+    })?;
+    check_dates(payload.start_date, payload.end_date, payload.is_current)?;
+
+    let entry = sqlx::query_as!(
+        Education,
+        r#"
+        INSERT INTO education_entries
+            (user_id, institution, degree, field_of_study, start_date, end_date, is_current, grade)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        RETURNING *
+        "#,
+        auth_user.user_id,
+        payload.institution.trim(),
+        clean_text(payload.degree),
+        clean_text(payload.field_of_study),
+        payload.start_date,
+        payload.end_date,
+        payload.is_current,
+        clean_text(payload.grade)
+    )
+    .fetch_one(&app_state.db_pool)
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(entry)))
+}
+
+/// Replaces an education entry.
+///
+/// # Endpoint
+/// `PUT /api/profile/education/{id}` (same body as `POST`)
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Validation fails (400)
+/// - Entry doesn't exist or doesn't belong to user (404)
+/// - Database operation fails
+pub async fn update_education(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Path(entry_id): Path<i32>,
+    Json(payload): Json<EducationPayload>,
+) -> AppResult<Json<Education>> {
+    info!("Updating education entry {} for user: {}", entry_id, auth_user.user_id);
+    payload.validate()?;
+    check_dates(payload.start_date, payload.end_date, payload.is_current)?;
+
+    let entry = sqlx::query_as!(
+        Education,
+        r#"
+        UPDATE education_entries
+        SET institution = $1, degree = $2, field_of_study = $3, start_date = $4,
+            end_date = $5, is_current = $6, grade = $7, updated_at = CURRENT_TIMESTAMP
+        WHERE id = $8 AND user_id = $9
+        RETURNING *
+        "#,
+        payload.institution.trim(),
+        clean_text(payload.degree),
+        clean_text(payload.field_of_study),
+        payload.start_date,
+        payload.end_date,
+        payload.is_current,
+        clean_text(payload.grade),
+        entry_id,
+        auth_user.user_id
+    )
+    .fetch_optional(&app_state.db_pool)
+    .await?
+    .ok_or(AppError::NotFound)?;
+
+    Ok(Json(entry))
+}
+
+/// Deletes an education entry.
+///
+/// # Endpoint
+/// `DELETE /api/profile/education/{id}`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Entry doesn't exist or doesn't belong to user (404)
+/// - Database operation fails
+pub async fn delete_education(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Path(entry_id): Path<i32>,
+) -> AppResult<StatusCode> {
+    delete_entry(&app_state.db_pool, "education_entries", entry_id, auth_user.user_id).await
+}
+
+/// Lists the user's work experience, most recent first.
+///
+/// # Endpoint
+/// `GET /api/profile/experience`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Database operation fails
+pub async fn list_work_experience(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+) -> AppResult<Json<Vec<WorkExperience>>> {
+    load_work_experience(&app_state.db_pool, auth_user.user_id).await.map(Json)
+}
+
+/// Adds a work experience entry.
+///
+/// # Endpoint
+/// `POST /api/profile/experience`
+///
+/// # Request Body
+/// ```json
+/// {
+///   "employer": "bKash",
+///   "title": "Data Analyst",
+///   "location": "Dhaka",
+///   "start_date": "2023-02-01",
+///   "is_current": true,
+///   "bullet_points": ["Built Power BI dashboards for 12 teams"],
+///   "skills_used": ["SQL", "Power BI"]
+/// }
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Validation fails (missing employer or title, end date before start date) (400)
+/// - Database operation fails
+pub async fn create_work_experience(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Json(payload): Json<WorkExperiencePayload>,
+) -> AppResult<(StatusCode, Json<WorkExperience>)> {
+    info!("Adding work experience for user: {}", auth_user.user_id);
+    payload.validate().map_err(|e| {
+        warn!("Work experience validation failed for user {}: {}", auth_user.user_id, e);
+        e
+    })?;
+    check_dates(payload.start_date, payload.end_date, payload.is_current)?;
+
+    let entry = sqlx::query_as!(
+        WorkExperience,
+        r#"
+        INSERT INTO work_experiences
+            (user_id, employer, title, location, start_date, end_date, is_current, bullet_points, skills_used)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        RETURNING *
+        "#,
+        auth_user.user_id,
+        payload.employer.trim(),
+        payload.title.trim(),
+        clean_text(payload.location),
+        payload.start_date,
+        payload.end_date,
+        payload.is_current,
+        &clean_list(payload.bullet_points),
+        &clean_list(payload.skills_used)
+    )
+    .fetch_one(&app_state.db_pool)
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(entry)))
+}
+
+/// Replaces a work experience entry.
+///
+/// # Endpoint
+/// `PUT /api/profile/experience/{id}` (same body as `POST`)
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Validation fails (400)
+/// - Entry doesn't exist or doesn't belong to user (404)
+/// - Database operation fails
+pub async fn update_work_experience(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Path(entry_id): Path<i32>,
+    Json(payload): Json<WorkExperiencePayload>,
+) -> AppResult<Json<WorkExperience>> {
+    info!("Updating work experience {} for user: {}", entry_id, auth_user.user_id);
+    payload.validate()?;
+    check_dates(payload.start_date, payload.end_date, payload.is_current)?;
+
+    let entry = sqlx::query_as!(
+        WorkExperience,
+        r#"
+        UPDATE work_experiences
+        SET employer = $1, title = $2, location = $3, start_date = $4, end_date = $5,
+            is_current = $6, bullet_points = $7, skills_used = $8, updated_at = CURRENT_TIMESTAMP
+        WHERE id = $9 AND user_id = $10
+        RETURNING *
+        "#,
+        payload.employer.trim(),
+        payload.title.trim(),
+        clean_text(payload.location),
+        payload.start_date,
+        payload.end_date,
+        payload.is_current,
+        &clean_list(payload.bullet_points),
+        &clean_list(payload.skills_used),
+        entry_id,
+        auth_user.user_id
+    )
+    .fetch_optional(&app_state.db_pool)
+    .await?
+    .ok_or(AppError::NotFound)?;
+
+    Ok(Json(entry))
+}
+
+/// Deletes a work experience entry.
+///
+/// # Endpoint
+/// `DELETE /api/profile/experience/{id}`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Entry doesn't exist or doesn't belong to user (404)
+/// - Database operation fails
+pub async fn delete_work_experience(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Path(entry_id): Path<i32>,
+) -> AppResult<StatusCode> {
+    delete_entry(&app_state.db_pool, "work_experiences", entry_id, auth_user.user_id).await
+}
+
+/// Lists the user's projects, ongoing and most recent first.
+///
+/// # Endpoint
+/// `GET /api/profile/projects`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Database operation fails
+pub async fn list_projects(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+) -> AppResult<Json<Vec<Project>>> {
+    load_projects(&app_state.db_pool, auth_user.user_id).await.map(Json)
+}
+
+/// Adds a project.
+///
+/// # Endpoint
+/// `POST /api/profile/projects`
+///
+/// # Request Body
+/// ```json
+/// {
+///   "title": "Churn Model",
+///   "description": "Predicted mobile wallet churn with gradient boosting",
+///   "url": "https://github.com/ayesha/churn",
+///   "tech_stack": ["Python", "XGBoost"],
+///   "start_date": "2023-05-01",
+///   "end_date": "2023-08-31"
+/// }
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Validation fails (missing title, invalid URL, end date before start date) (400)
+/// - Database operation fails
+pub async fn create_project(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Json(payload): Json<ProjectPayload>,
+) -> AppResult<(StatusCode, Json<Project>)> {
+    info!("Adding project for user: {}", auth_user.user_id);
+    payload.validate().map_err(|e| {
+        warn!("Project validation failed for user {}: {}", auth_user.user_id, e);
+        e
+    })?;
+    check_dates(payload.start_date, payload.end_date, false)?;
+
+    let project = sqlx::query_as!(
+        Project,
+        r#"
+        INSERT INTO projects (user_id, title, description, url, tech_stack, start_date, end_date)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING *
+        "#,
+        auth_user.user_id,
+        payload.title.trim(),
+        clean_text(payload.description),
+        clean_text(payload.url),
+        &clean_list(payload.tech_stack),
+        payload.start_date,
+        payload.end_date
+    )
+    .fetch_one(&app_state.db_pool)
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(project)))
+}
+
+/// Replaces a project.
+///
+/// # Endpoint
+/// `PUT /api/profile/projects/{id}` (same body as `POST`)
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Validation fails (400)
+/// - Project doesn't exist or doesn't belong to user (404)
+/// - Database operation fails
+pub async fn update_project(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Path(project_id): Path<i32>,
+    Json(payload): Json<ProjectPayload>,
+) -> AppResult<Json<Project>> {
+    info!("Updating project {} for user: {}", project_id, auth_user.user_id);
+    payload.validate()?;
+    check_dates(payload.start_date, payload.end_date, false)?;
+
+    let project = sqlx::query_as!(
+        Project,
+        r#"
+        UPDATE projects
+        SET title = $1, description = $2, url = $3, tech_stack = $4, start_date = $5,
+            end_date = $6, updated_at = CURRENT_TIMESTAMP
+        WHERE id = $7 AND user_id = $8
+        RETURNING *
+        "#,
+        payload.title.trim(),
+        clean_text(payload.description),
+        clean_text(payload.url),
+        &clean_list(payload.tech_stack),
+        payload.start_date,
+        payload.end_date,
+        project_id,
+        auth_user.user_id
+    )
+    .fetch_optional(&app_state.db_pool)
+    .await?
+    .ok_or(AppError::NotFound)?;
+
+    Ok(Json(project))
+}
+
+/// Deletes a project.
+///
+/// # Endpoint
+/// `DELETE /api/profile/projects/{id}`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Project doesn't exist or doesn't belong to user (404)
+/// - Database operation fails
+pub async fn delete_project(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Path(project_id): Path<i32>,
+) -> AppResult<StatusCode> {
+    delete_entry(&app_state.db_pool, "projects", project_id, auth_user.user_id).await
+}
+
+/// Deletes one of the user's rows from a profile entry table.
+///
+/// `table` must be one of the fixed table names above, never user input.
+async fn delete_entry(pool: &PgPool, table: &'static str, id: i32, user_id: Uuid) -> AppResult<StatusCode> {
+    info!("Deleting {} row {} for user: {}", table, id, user_id);
+    let result = sqlx::query(&format!("DELETE FROM {} WHERE id = $1 AND user_id = $2", table))
+        .bind(id)
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,137 @@
+//! Profile history handlers.
+//!
+//! Every change to a user's skills or target roles is recorded by a database
+//! trigger, whichever endpoint makes it (profile updates, CV review, AI skill
+//! extraction). Users can list the changes and revert any of them.
+
+use super::profile::{if_match_versions, profile_etag, version_conflict};
+use super::types::ProfileHistoryQuery;
+use crate::AppState;
+use crate::auth::AuthUser;
+use crate::errors::{AppError, AppResult};
+use crate::models::ProfileHistoryEntry;
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::{HeaderMap, header},
+};
+use serde_json::{Value, json};
+use tracing::info;
+
+/// Default and largest page size for the history list.
+const DEFAULT_LIMIT: i64 = 50;
+const MAX_LIMIT: i64 = 200;
+
+/// Lists changes to the authenticated user's skills and target roles, newest first.
+///
+/// # Endpoint
+/// `GET /api/profile/history?field=skills|target_roles&limit=50&offset=0`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - The field is unknown (400)
+/// - Database operation fails
+pub async fn list_profile_history(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Query(query): Query<ProfileHistoryQuery>,
+) -> AppResult<Json<Vec<ProfileHistoryEntry>>> {
+    let entries = sqlx::query_as!(
+        ProfileHistoryEntry,
+        r#"
+        SELECT id, field, old_value, new_value, profile_version, changed_at
+        FROM profile_history
+        WHERE user_id = $1 AND ($2::TEXT IS NULL OR field = $2)
+        ORDER BY changed_at DESC, id DESC
+        LIMIT $3 OFFSET $4
+        "#,
+        auth_user.user_id,
+        query.field.map(|f| f.as_str()),
+        query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT),
+        query.offset.unwrap_or(0).max(0)
+    )
+    .fetch_all(&app_state.db_pool)
+    .await?;
+
+    Ok(Json(entries))
+}
+
+/// Reverts a recorded change, restoring the field's value from before it.
+///
+/// The revert is itself recorded as a change, so it can be undone too. Like
+/// `PUT /api/profile`, it honours `If-Match` and returns the new `ETag`.
+///
+/// # Endpoint
+/// `POST /api/profile/history/{id}/revert`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - The history entry does not exist or belongs to another user (404)
+/// - `If-Match` does not match the current profile version (412)
+/// - Database operation fails
+pub async fn revert_profile_change(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Path(entry_id): Path<i32>,
+    headers: HeaderMap,
+) -> AppResult<([(header::HeaderName, String); 1], Json<Value>)> {
+    let pool = &app_state.db_pool;
+
+    let entry = sqlx::query!(
+        "SELECT field, old_value FROM profile_history WHERE id = $1 AND user_id = $2",
+        entry_id,
+        auth_user.user_id
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or(AppError::NotFound)?;
+    let old_value = entry.old_value;
+
+    // The field comes from a CHECK-constrained column, never from input
+    let column = match entry.field.as_str() {
+        "skills" => "skills",
+        "target_roles" => "target_roles",
+        _ => return Err(AppError::InternalServerError),
+    };
+
+    let expected_versions = if_match_versions(&headers);
+    let version = sqlx::query_scalar!(
+        r#"
+        UPDATE users
+        SET skills = CASE WHEN $4 = 'skills' THEN $1 ELSE skills END,
+            target_roles = CASE WHEN $4 = 'target_roles' THEN $1 ELSE target_roles END,
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $2 AND ($3::INTEGER[] IS NULL OR profile_version = ANY($3))
+        RETURNING profile_version
+        "#,
+        &old_value,
+        auth_user.user_id,
+        expected_versions.as_deref(),
+        column
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    let Some(version) = version else {
+        return Err(version_conflict(pool, auth_user.user_id).await);
+    };
+
+    info!(
+        "Reverted profile change {} ({}) for user {} (version {})",
+        entry_id, column, auth_user.user_id, version
+    );
+
+    Ok((
+        [(header::ETAG, profile_etag(version))],
+        Json(json!({
+            "message": "Change reverted",
+            "field": column,
+            "value": old_value,
+            "profile_version": version
+        })),
+    ))
+}
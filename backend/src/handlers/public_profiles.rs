@@ -0,0 +1,552 @@
+//! Public profile handlers.
+//!
+//! Users opt in to a shareable profile at a slug of their choice. Anyone with
+//! the link can read it as JSON or as a server-rendered page with Open Graph
+//! tags for link previews. Email and CV are hidden unless the user shows
+//! them, skills, learning and roadmaps can each be hidden, and phone numbers
+//! are never published.
+
+use super::cv::{load_cv_document, profile_headline};
+use super::profile::{cv_download, negotiate_format};
+use super::types::{
+    CompletedLearning, GenerateCvQuery, PublicProfile, PublicProfilePayload, PublicSkill,
+    RoadmapSummary,
+};
+use crate::AppState;
+use crate::auth::AuthUser;
+use crate::cv::html::escape;
+use crate::errors::{AppError, AppResult};
+use crate::models::{CareerTrack, ExperienceLevel, PublicProfileSettings};
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::{HeaderMap, header},
+    response::{Html, Response},
+};
+use sqlx::PgPool;
+use std::fmt::Write;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+use validator::Validate;
+
+/// Longest Open Graph description; previews truncate beyond this anyway.
+const OG_DESCRIPTION_LEN: usize = 200;
+
+/// Owner and visibility of a public profile, joined with the user record.
+#[derive(sqlx::FromRow)]
+struct PublicProfileRow {
+    user_id: Uuid,
+    slug: String,
+    show_email: bool,
+    show_cv: bool,
+    show_skills: bool,
+    show_learning: bool,
+    show_roadmaps: bool,
+    full_name: String,
+    email: String,
+    experience_level: Option<ExperienceLevel>,
+    preferred_track: Option<CareerTrack>,
+    skills: Vec<String>,
+    target_roles: Vec<String>,
+}
+
+/// Retrieves the authenticated user's public profile settings.
+///
+/// # Endpoint
+/// `GET /api/profile/public`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - The user has not set up a public profile (404)
+/// - Database operation fails
+pub async fn get_public_profile_settings(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+) -> AppResult<Json<PublicProfileSettings>> {
+    sqlx::query_as!(
+        PublicProfileSettings,
+        r#"
+        SELECT slug, is_public, show_email, show_cv, show_skills, show_learning, show_roadmaps, updated_at
+        FROM public_profiles
+        WHERE user_id = $1
+        "#,
+        auth_user.user_id
+    )
+    .fetch_optional(&app_state.db_pool)
+    .await?
+    .map(Json)
+    .ok_or(AppError::NotFound)
+}
+
+/// Creates or updates the authenticated user's public profile.
+///
+/// The slug is stored in lowercase. Changing it breaks links shared under
+/// the old slug.
+///
+/// # Endpoint
+/// `PUT /api/profile/public`
+///
+/// # Request Body
+/// ```json
+/// { "slug": "ayesha-rahman", "is_public": true, "show_email": false, "show_cv": true }
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - The slug is not 3-40 lowercase letters, digits and single hyphens (400)
+/// - The slug is taken by another user (409)
+/// - Database operation fails
+pub async fn update_public_profile_settings(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Json(payload): Json<PublicProfilePayload>,
+) -> AppResult<Json<PublicProfileSettings>> {
+    payload.validate().map_err(|e| {
+        warn!("Public profile validation failed for user {}: {}", auth_user.user_id, e);
+        e
+    })?;
+
+    let slug = payload.slug.trim().to_lowercase();
+    if !is_valid_slug(&slug) {
+        return Err(AppError::ValidationError(
+            "Slug may only contain lowercase letters, digits and single hyphens, and must start and end with a letter or digit".to_string(),
+        ));
+    }
+
+    let settings = sqlx::query_as!(
+        PublicProfileSettings,
+        r#"
+        INSERT INTO public_profiles
+            (user_id, slug, is_public, show_email, show_cv, show_skills, show_learning, show_roadmaps)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        ON CONFLICT (user_id) DO UPDATE
+        SET slug = EXCLUDED.slug,
+            is_public = EXCLUDED.is_public,
+            show_email = EXCLUDED.show_email,
+            show_cv = EXCLUDED.show_cv,
+            show_skills = EXCLUDED.show_skills,
+            show_learning = EXCLUDED.show_learning,
+            show_roadmaps = EXCLUDED.show_roadmaps,
+            updated_at = CURRENT_TIMESTAMP
+        RETURNING slug, is_public, show_email, show_cv, show_skills, show_learning, show_roadmaps, updated_at
+        "#,
+        auth_user.user_id,
+        &slug,
+        payload.is_public,
+        payload.show_email,
+        payload.show_cv,
+        payload.show_skills,
+        payload.show_learning,
+        payload.show_roadmaps
+    )
+    .fetch_one(&app_state.db_pool)
+    .await?;
+
+    info!(
+        "Public profile saved for user {}: slug={}, public={}",
+        auth_user.user_id, settings.slug, settings.is_public
+    );
+
+    Ok(Json(settings))
+}
+
+/// Retrieves a public profile as JSON. No authentication required.
+///
+/// Fields the owner has hidden are omitted from the response.
+///
+/// # Endpoint
+/// `GET /api/public/profiles/{slug}`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - No public profile has this slug, its owner made it private, or the
+///   account is scheduled for deletion (404)
+/// - Database operation fails
+pub async fn get_public_profile(
+    Path(slug): Path<String>,
+    State(app_state): State<AppState>,
+) -> AppResult<Json<PublicProfile>> {
+    let profile = load_public_profile(&app_state.db_pool, &slug).await?;
+    Ok(Json(profile))
+}
+
+/// Renders a public profile as an HTML page. No authentication required.
+///
+/// The page carries Open Graph and Twitter card tags, so links shared on
+/// LinkedIn, Facebook or messaging apps show the user's name, headline and
+/// a short description. Absolute URLs use `PUBLIC_BASE_URL` when set and the
+/// request's `Host` header otherwise.
+///
+/// # Endpoint
+/// `GET /p/{slug}`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - No public profile has this slug, its owner made it private, or the
+///   account is scheduled for deletion (404)
+/// - Database operation fails
+pub async fn public_profile_page(
+    Path(slug): Path<String>,
+    State(app_state): State<AppState>,
+    headers: HeaderMap,
+) -> AppResult<Html<String>> {
+    let profile = load_public_profile(&app_state.db_pool, &slug).await?;
+    let page_url = format!("{}/p/{}", base_url(&headers), profile.slug);
+    Ok(Html(render_profile_page(&profile, &page_url)))
+}
+
+/// Downloads the CV of a public profile. No authentication required.
+///
+/// Only available when the owner shows their CV. The phone number is left
+/// out, and the email too unless the owner shows it.
+///
+/// # Endpoint
+/// `GET /api/public/profiles/{slug}/cv?template=classic|modern|ats&format=pdf|docx|html|markdown|json`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - No public profile has this slug, it is private, or its CV is hidden (404)
+/// - The template or format is unknown (400)
+/// - Rendering fails
+pub async fn download_public_cv(
+    Path(slug): Path<String>,
+    State(app_state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<GenerateCvQuery>,
+) -> AppResult<Response> {
+    let row = find_public_profile(&app_state.db_pool, &slug).await?;
+    if !row.show_cv {
+        return Err(AppError::NotFound);
+    }
+
+    let mut document = load_cv_document(&app_state.db_pool, row.user_id).await?;
+    redact_contact(&mut document.cv.contact, row.show_email);
+
+    let format = negotiate_format(query.format, &headers);
+    debug!("Public CV download for {} as {:?}", row.slug, format);
+    let name = document.name().to_string();
+    cv_download(document, format, query.template, &name).await
+}
+
+/// Slugs are lowercase ASCII letters and digits separated by single hyphens.
+fn is_valid_slug(slug: &str) -> bool {
+    !slug.starts_with('-')
+        && !slug.ends_with('-')
+        && !slug.contains("--")
+        && slug
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+async fn find_public_profile(pool: &PgPool, slug: &str) -> AppResult<PublicProfileRow> {
+    sqlx::query_as!(
+        PublicProfileRow,
+        r#"
+        SELECT p.user_id, p.slug, p.show_email, p.show_cv, p.show_skills, p.show_learning,
+               p.show_roadmaps, u.full_name, u.email,
+               u.experience_level as "experience_level: ExperienceLevel",
+               u.preferred_track as "preferred_track: CareerTrack",
+               u.skills, u.target_roles
+        FROM public_profiles p
+        JOIN users u ON u.id = p.user_id
+        WHERE p.slug = $1 AND p.is_public AND u.deletion_scheduled_for IS NULL
+        "#,
+        slug.to_lowercase()
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or(AppError::NotFound)
+}
+
+/// Builds the public view of a profile, leaving out hidden sections.
+async fn load_public_profile(pool: &PgPool, slug: &str) -> AppResult<PublicProfile> {
+    let row = find_public_profile(pool, slug).await?;
+
+    // Needed for skill verification even when the list itself is hidden
+    let completed_learning = sqlx::query_as!(
+        CompletedLearning,
+        r#"
+        SELECT lr.title, lr.platform, lr.url, lr.related_skills, up.completed_at
+        FROM user_progress up
+        JOIN learning_resources lr ON lr.id = up.resource_id
+        WHERE up.user_id = $1 AND up.completed_at IS NOT NULL
+        ORDER BY up.completed_at DESC
+        "#,
+        row.user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let skills = if row.show_skills {
+        // Self-ratings are not verification; quiz results are
+        let assessed: Vec<String> = sqlx::query_scalar!(
+            "SELECT skill_name FROM skill_assessments WHERE user_id = $1 AND source = 'quiz'",
+            row.user_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut skills: Vec<PublicSkill> = row
+            .skills
+            .iter()
+            .map(|name| PublicSkill {
+                verified: assessed.iter().any(|s| s.eq_ignore_ascii_case(name))
+                    || completed_learning
+                        .iter()
+                        .any(|r| r.related_skills.iter().any(|s| s.eq_ignore_ascii_case(name))),
+                name: name.clone(),
+            })
+            .collect();
+        // Stable, so each group keeps the user's order
+        skills.sort_by_key(|s| !s.verified);
+        Some(skills)
+    } else {
+        None
+    };
+
+    let roadmaps = if row.show_roadmaps {
+        Some(
+            sqlx::query_as!(
+                RoadmapSummary,
+                r#"
+                SELECT title, target_role, progress_percentage
+                FROM career_roadmaps
+                WHERE user_id = $1
+                ORDER BY created_at DESC
+                "#,
+                row.user_id
+            )
+            .fetch_all(pool)
+            .await?,
+        )
+    } else {
+        None
+    };
+
+    let cv = if row.show_cv {
+        let mut cv = load_cv_document(pool, row.user_id).await?.cv;
+        redact_contact(&mut cv.contact, row.show_email);
+        Some(cv)
+    } else {
+        None
+    };
+
+    Ok(PublicProfile {
+        headline: profile_headline(&row.target_roles, row.preferred_track),
+        email: row.show_email.then_some(row.email),
+        experience_level: row.experience_level,
+        preferred_track: row.preferred_track,
+        skills,
+        completed_learning: row.show_learning.then_some(completed_learning),
+        roadmaps,
+        cv_url: row.show_cv.then(|| format!("/api/public/profiles/{}/cv", row.slug)),
+        cv,
+        full_name: row.full_name,
+        slug: row.slug,
+    })
+}
+
+/// Removes contact details that must not be published.
+fn redact_contact(contact: &mut crate::cv::ContactInfo, show_email: bool) {
+    contact.phone = None;
+    if !show_email {
+        contact.email = None;
+    }
+}
+
+/// Scheme and host for absolute URLs in the page.
+fn base_url(headers: &HeaderMap) -> String {
+    if let Ok(url) = std::env::var("PUBLIC_BASE_URL") {
+        return url.trim_end_matches('/').to_string();
+    }
+    let host = headers
+        .get(header::HOST)
+        .and_then(|h| h.to_str().ok())
+        .unwrap_or("localhost:3000");
+    format!("http://{}", host)
+}
+
+/// Short description for link previews: the CV summary when shown,
+/// otherwise the headline and top skills.
+fn og_description(profile: &PublicProfile) -> String {
+    let summary = profile
+        .cv
+        .as_ref()
+        .and_then(|cv| cv.summary.as_deref())
+        .map(str::trim)
+        .filter(|s| !s.is_empty());
+
+    let text = match summary {
+        Some(summary) => summary.to_string(),
+        None => {
+            let mut parts: Vec<String> = profile.headline.iter().cloned().collect();
+            if let Some(skills) = profile.skills.as_ref().filter(|s| !s.is_empty()) {
+                let names: Vec<&str> = skills.iter().take(8).map(|s| s.name.as_str()).collect();
+                parts.push(format!("Skills: {}", names.join(", ")));
+            }
+            if parts.is_empty() {
+                format!("{} on CareerBridge", profile.full_name)
+            } else {
+                parts.join(". ")
+            }
+        }
+    };
+
+    if text.chars().count() > OG_DESCRIPTION_LEN {
+        let cut: String = text.chars().take(OG_DESCRIPTION_LEN - 3).collect();
+        format!("{}...", cut.trim_end())
+    } else {
+        text
+    }
+}
+
+const PAGE_CSS: &str = r#"
+*{box-sizing:border-box}
+body{margin:0;padding:24px;background:#f3f4f6;color:#21262e;line-height:1.5;font-family:"Segoe UI",Roboto,"DejaVu Sans",Arial,"Noto Sans Bengali",sans-serif}
+main{max-width:820px;margin:0 auto;padding:32px;background:#fff;border-radius:8px}
+h1{margin:0}
+h2{margin:24px 0 8px;color:#175c9e;font-size:1.15em;border-bottom:1.5px solid #175c9e;padding-bottom:2px}
+h3{margin:0;font-size:1em}
+ul{margin:4px 0 0;padding-left:20px}
+.muted{color:#666e7a}
+.skills{display:flex;flex-wrap:wrap;gap:6px;list-style:none;padding:0}
+.skills li{padding:2px 10px;border:1px solid #c9d3de;border-radius:12px}
+.skills li.verified{border-color:#1a7f37;color:#1a7f37}
+.entry{margin:10px 0}
+progress{width:160px;vertical-align:middle}
+"#;
+
+/// Renders the public profile page.
+fn render_profile_page(profile: &PublicProfile, page_url: &str) -> String {
+    let name = escape(&profile.full_name);
+    let title = match &profile.headline {
+        Some(headline) => format!("{} - {}", profile.full_name, headline),
+        None => profile.full_name.clone(),
+    };
+    let title = escape(&title);
+    let description = escape(&og_description(profile));
+    let url = escape(page_url);
+
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{title} | CareerBridge</title>\n\
+         <meta name=\"description\" content=\"{description}\">\n\
+         <link rel=\"canonical\" href=\"{url}\">\n\
+         <meta property=\"og:type\" content=\"profile\">\n\
+         <meta property=\"og:site_name\" content=\"CareerBridge\">\n\
+         <meta property=\"og:title\" content=\"{title}\">\n\
+         <meta property=\"og:description\" content=\"{description}\">\n\
+         <meta property=\"og:url\" content=\"{url}\">\n\
+         <meta name=\"twitter:card\" content=\"summary\">\n\
+         <meta name=\"twitter:title\" content=\"{title}\">\n\
+         <meta name=\"twitter:description\" content=\"{description}\">\n\
+         <style>{PAGE_CSS}</style>\n</head>\n<body>\n<main>\n<header>\n<h1>{name}</h1>\n"
+    );
+    if let Some(headline) = &profile.headline {
+        let _ = writeln!(html, "<p class=\"muted\">{}</p>", escape(headline));
+    }
+    if let Some(email) = &profile.email {
+        let _ = writeln!(html, "<p><a href=\"mailto:{0}\">{0}</a></p>", escape(email));
+    }
+    if let Some(cv_url) = &profile.cv_url {
+        let _ = writeln!(html, "<p><a href=\"{}\">Download CV (PDF)</a></p>", escape(cv_url));
+    }
+    html.push_str("</header>\n");
+
+    if let Some(summary) = profile
+        .cv
+        .as_ref()
+        .and_then(|cv| cv.summary.as_deref())
+        .filter(|s| !s.trim().is_empty())
+    {
+        let _ = writeln!(html, "<section>\n<h2>About</h2>\n<p>{}</p>\n</section>", escape(summary));
+    }
+
+    if let Some(skills) = profile.skills.as_ref().filter(|s| !s.is_empty()) {
+        html.push_str("<section>\n<h2>Skills</h2>\n<ul class=\"skills\">\n");
+        for skill in skills {
+            if skill.verified {
+                let _ = writeln!(
+                    html,
+                    "<li class=\"verified\" title=\"Verified\">&#10003; {}</li>",
+                    escape(&skill.name)
+                );
+            } else {
+                let _ = writeln!(html, "<li>{}</li>", escape(&skill.name));
+            }
+        }
+        html.push_str("</ul>\n</section>\n");
+    }
+
+    if let Some(cv) = &profile.cv {
+        for (section, entries) in [
+            ("Experience", &cv.experience),
+            ("Projects", &cv.projects),
+            ("Education", &cv.education),
+            ("Certifications", &cv.certifications),
+        ] {
+            if entries.is_empty() {
+                continue;
+            }
+            let _ = writeln!(html, "<section>\n<h2>{}</h2>", section);
+            for entry in entries {
+                let _ = write!(html, "<div class=\"entry\">\n<h3>{}</h3>\n", escape(&entry.title));
+                if let Some(org) = entry.organization.as_deref().filter(|o| !o.trim().is_empty()) {
+                    let _ = writeln!(html, "<p class=\"muted\">{}</p>", escape(org));
+                }
+                let points = crate::cv::document::description_points(entry);
+                if !points.is_empty() {
+                    html.push_str("<ul>\n");
+                    for point in points {
+                        let _ = writeln!(html, "<li>{}</li>", escape(&point));
+                    }
+                    html.push_str("</ul>\n");
+                }
+                html.push_str("</div>\n");
+            }
+            html.push_str("</section>\n");
+        }
+    }
+
+    if let Some(learning) = profile.completed_learning.as_ref().filter(|l| !l.is_empty()) {
+        html.push_str("<section>\n<h2>Completed Learning</h2>\n<ul>\n");
+        for resource in learning {
+            let _ = writeln!(
+                html,
+                "<li><a href=\"{}\" rel=\"nofollow noopener\">{}</a> <span class=\"muted\">({})</span></li>",
+                escape(&resource.url),
+                escape(&resource.title),
+                escape(&resource.platform)
+            );
+        }
+        html.push_str("</ul>\n</section>\n");
+    }
+
+    if let Some(roadmaps) = profile.roadmaps.as_ref().filter(|r| !r.is_empty()) {
+        html.push_str("<section>\n<h2>Career Roadmaps</h2>\n<ul>\n");
+        for roadmap in roadmaps {
+            let progress = roadmap.progress_percentage.unwrap_or(0).clamp(0, 100);
+            let _ = writeln!(
+                html,
+                "<li>{} <span class=\"muted\">&rarr; {}</span> \
+                 <progress max=\"100\" value=\"{progress}\">{progress}%</progress> {progress}%</li>",
+                escape(&roadmap.title),
+                escape(&roadmap.target_role)
+            );
+        }
+        html.push_str("</ul>\n</section>\n");
+    }
+
+    html.push_str("</main>\n</body>\n</html>\n");
+    html
+}
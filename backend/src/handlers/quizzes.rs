@@ -0,0 +1,566 @@
+//! Skill quiz handlers.
+//!
+//! Quizzes are short multiple-choice tests drawn at random from a skill's
+//! approved questions (see [`crate::quiz`]). Grading happens here: answers
+//! are only revealed after an attempt is submitted, an attempt must be
+//! submitted within its time limit and only once, and the same skill can
+//! only be retaken after a cooldown. The result is recorded as a quiz
+//! assessment in `skill_assessments`.
+//!
+//! Administrators (`ADMIN_EMAILS`) generate questions with AI and review
+//! them before they are used.
+
+use super::types::{
+    GenerateQuizQuestionsPayload, QuizAttemptStarted, QuizQuestionQuery, QuizQuestionResult,
+    QuizQuestionStatus, QuizQuestionView, QuizResult, QuizSummary, ReviewQuizQuestionPayload,
+    SubmitQuizPayload,
+};
+use crate::AppState;
+use crate::ai::types::{AIActionRequest, AIProvider, ActionType};
+use crate::auth::{AdminUser, AuthUser};
+use crate::errors::{AppError, AppResult};
+use crate::models::QuizQuestion;
+use crate::quiz::{self, BankQuestion, QUIZ_LENGTH, SUBMIT_GRACE_SECS};
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use chrono::Utc;
+use serde_json::json;
+use std::collections::HashMap;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+use validator::Validate;
+
+/// Questions generated per request when no count is given.
+const DEFAULT_GENERATED_QUESTIONS: u32 = 10;
+
+/// Lists the skills with a quiz available.
+///
+/// # Endpoint
+/// `GET /api/quizzes`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Database operation fails
+pub async fn list_quizzes(
+    _auth_user: AuthUser,
+    State(app_state): State<AppState>,
+) -> AppResult<Json<Vec<QuizSummary>>> {
+    let quizzes = sqlx::query_as!(
+        QuizSummary,
+        r#"
+        SELECT MIN(skill_name) AS "skill_name!", COUNT(*) AS "question_count!"
+        FROM quiz_questions
+        WHERE status = 'approved'
+        GROUP BY LOWER(skill_name)
+        HAVING COUNT(*) >= $1
+        ORDER BY LOWER(skill_name)
+        "#,
+        QUIZ_LENGTH
+    )
+    .fetch_all(&app_state.db_pool)
+    .await?;
+
+    Ok(Json(quizzes))
+}
+
+/// Starts a quiz for a skill.
+///
+/// Questions are drawn at random from the skill's approved questions and
+/// returned without their answers. If an attempt for the skill is still
+/// running, it is returned again instead of drawing new questions.
+///
+/// # Endpoint
+/// `POST /api/quizzes/{skill}/attempts`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - No quiz is available for the skill (404)
+/// - The skill's quiz was taken within the retake cooldown (429)
+/// - Database operation fails
+pub async fn start_quiz(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Path(skill): Path<String>,
+) -> AppResult<(StatusCode, Json<QuizAttemptStarted>)> {
+    let pool = &app_state.db_pool;
+    let skill = skill.trim();
+
+    let last_attempt = sqlx::query!(
+        r#"
+        SELECT id, skill_name, question_ids, started_at, expires_at, submitted_at IS NOT NULL AS "submitted!"
+        FROM quiz_attempts
+        WHERE user_id = $1 AND LOWER(skill_name) = LOWER($2)
+        ORDER BY started_at DESC
+        LIMIT 1
+        "#,
+        auth_user.user_id,
+        skill
+    )
+    .fetch_optional(pool)
+    .await?
+    .map(|a| (a.id, a.skill_name, a.question_ids, a.started_at, a.expires_at, a.submitted));
+
+    if let Some((attempt_id, skill_name, question_ids, started_at, expires_at, submitted)) = last_attempt {
+        let now = Utc::now();
+        if !submitted && expires_at > now {
+            let questions = load_question_views(pool, &question_ids).await?;
+            return Ok((
+                StatusCode::OK,
+                Json(QuizAttemptStarted { attempt_id, skill_name, started_at, expires_at, questions }),
+            ));
+        }
+        let retake_at = started_at + quiz::retake_cooldown();
+        if retake_at > now {
+            let minutes = (retake_at - now).num_minutes() + 1;
+            return Err(AppError::TooManyRequests(format!(
+                "You can retake the {} quiz in {} minute(s)",
+                skill_name, minutes
+            )));
+        }
+    }
+
+    let questions: Vec<(i32, String, String, Vec<String>)> = sqlx::query!(
+        r#"
+        SELECT id, skill_name, question, options
+        FROM quiz_questions
+        WHERE LOWER(skill_name) = LOWER($1) AND status = 'approved'
+        ORDER BY random()
+        LIMIT $2
+        "#,
+        skill,
+        QUIZ_LENGTH
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|q| (q.id, q.skill_name, q.question, q.options))
+    .collect();
+
+    if (questions.len() as i64) < QUIZ_LENGTH {
+        warn!("No quiz available for skill {:?}", skill);
+        return Err(AppError::NotFound);
+    }
+
+    let skill_name = questions[0].1.clone();
+    let question_ids: Vec<i32> = questions.iter().map(|q| q.0).collect();
+    let started_at = Utc::now();
+    let expires_at = started_at + quiz::time_limit(questions.len());
+    let attempt_id = Uuid::new_v4();
+
+    sqlx::query!(
+        r#"
+        INSERT INTO quiz_attempts (id, user_id, skill_name, question_ids, started_at, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+        attempt_id,
+        auth_user.user_id,
+        &skill_name,
+        &question_ids,
+        started_at,
+        expires_at
+    )
+    .execute(pool)
+    .await?;
+
+    info!("User {} started {} quiz {}", auth_user.user_id, skill_name, attempt_id);
+
+    let questions = questions
+        .into_iter()
+        .map(|(id, _, question, options)| QuizQuestionView { id, question, options })
+        .collect();
+    Ok((
+        StatusCode::CREATED,
+        Json(QuizAttemptStarted { attempt_id, skill_name, started_at, expires_at, questions }),
+    ))
+}
+
+/// Submits the answers of a quiz attempt and grades them.
+///
+/// The result is recorded as the skill's quiz assessment. An attempt can be
+/// submitted once; answers arriving after the time limit are rejected and
+/// use up the attempt.
+///
+/// # Endpoint
+/// `POST /api/quizzes/attempts/{id}/submit`
+///
+/// # Request Body
+/// ```json
+/// { "answers": [{ "question_id": 12, "option": 1 }, { "question_id": 7, "option": 0 }] }
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - The attempt does not exist or belongs to another user (404)
+/// - The attempt was already submitted (409)
+/// - An answer is for a question outside the attempt, duplicated or not an option (400)
+/// - The time limit has passed (400)
+/// - Database operation fails
+pub async fn submit_quiz(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Path(attempt_id): Path<Uuid>,
+    Json(payload): Json<SubmitQuizPayload>,
+) -> AppResult<Json<QuizResult>> {
+    let pool = &app_state.db_pool;
+    let mut tx = pool.begin().await?;
+
+    // Claiming the attempt in the update makes a second submission find nothing
+    let claimed = sqlx::query!(
+        r#"
+        UPDATE quiz_attempts
+        SET submitted_at = CURRENT_TIMESTAMP
+        WHERE id = $1 AND user_id = $2 AND submitted_at IS NULL
+        RETURNING skill_name, question_ids, expires_at, submitted_at AS "submitted_at!"
+        "#,
+        attempt_id,
+        auth_user.user_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .map(|a| (a.skill_name, a.question_ids, a.expires_at, a.submitted_at));
+
+    let Some((skill_name, question_ids, expires_at, submitted_at)) = claimed else {
+        let exists: bool = sqlx::query_scalar!(
+            r#"SELECT EXISTS (SELECT 1 FROM quiz_attempts WHERE id = $1 AND user_id = $2) AS "exists!""#,
+            attempt_id,
+            auth_user.user_id
+        )
+        .fetch_one(pool)
+        .await?;
+        return Err(if exists {
+            AppError::Conflict("This quiz attempt was already submitted".to_string())
+        } else {
+            AppError::NotFound
+        });
+    };
+
+    if submitted_at > expires_at + chrono::Duration::seconds(SUBMIT_GRACE_SECS) {
+        tx.commit().await?;
+        warn!("Late quiz submission {} from user {}", attempt_id, auth_user.user_id);
+        return Err(AppError::BadRequest("The time limit for this quiz has passed".to_string()));
+    }
+
+    let questions = sqlx::query!(
+        "SELECT id, options, correct_option, explanation FROM quiz_questions WHERE id = ANY($1)",
+        &question_ids
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+    let questions: HashMap<i32, (Vec<String>, i32, Option<String>)> = questions
+        .into_iter()
+        .map(|q| (q.id, (q.options, q.correct_option, q.explanation)))
+        .collect();
+
+    // Invalid answers roll the claim back, so the attempt can be resubmitted
+    let mut answers = HashMap::new();
+    for answer in &payload.answers {
+        let Some((options, _, _)) = questions.get(&answer.question_id) else {
+            return Err(AppError::BadRequest(format!(
+                "Question {} is not part of this quiz",
+                answer.question_id
+            )));
+        };
+        if answer.option < 0 || answer.option as usize >= options.len() {
+            return Err(AppError::BadRequest(format!(
+                "Option {} does not exist for question {}",
+                answer.option, answer.question_id
+            )));
+        }
+        if answers.insert(answer.question_id, answer.option).is_some() {
+            return Err(AppError::BadRequest(format!(
+                "Question {} was answered more than once",
+                answer.question_id
+            )));
+        }
+    }
+
+    let correct_options: Vec<(i32, i32)> = question_ids
+        .iter()
+        .filter_map(|id| questions.get(id).map(|(_, correct, _)| (*id, *correct)))
+        .collect();
+    let graded = quiz::grade(&correct_options, &answers);
+    let correct_count = graded.iter().filter(|(_, correct)| *correct).count();
+    let total = graded.len();
+    let proficiency_level = quiz::proficiency_from_score(correct_count, total);
+
+    sqlx::query!(
+        "UPDATE quiz_attempts SET correct_count = $1, proficiency_level = $2 WHERE id = $3",
+        correct_count as i32,
+        proficiency_level,
+        attempt_id
+    )
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query!(
+        r#"
+        INSERT INTO skill_assessments (user_id, skill_name, proficiency_level, source, quiz_attempt_id)
+        VALUES ($1, $2, $3, 'quiz', $4)
+        "#,
+        auth_user.user_id,
+        &skill_name,
+        proficiency_level,
+        attempt_id
+    )
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    info!(
+        "User {} scored {}/{} on {} quiz (level {})",
+        auth_user.user_id, correct_count, total, skill_name, proficiency_level
+    );
+
+    let results = correct_options
+        .iter()
+        .zip(graded)
+        .map(|((id, correct_option), (selected_option, correct))| QuizQuestionResult {
+            question_id: *id,
+            selected_option,
+            correct_option: *correct_option,
+            correct,
+            explanation: questions.get(id).and_then(|(_, _, e)| e.clone()),
+        })
+        .collect();
+
+    Ok(Json(QuizResult {
+        attempt_id,
+        skill_name,
+        correct_count: correct_count as i32,
+        total_questions: total as i32,
+        proficiency_level,
+        results,
+    }))
+}
+
+/// Generates quiz questions for a skill with AI, pending review.
+///
+/// Malformed questions in the AI response and duplicates of existing
+/// questions are dropped.
+///
+/// # Endpoint
+/// `POST /api/admin/quizzes/{skill}/generate`
+///
+/// # Request Body
+/// ```json
+/// { "count": 10, "provider": "groq" }
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated (401) or not an administrator (403)
+/// - Validation fails (count outside 1-20)
+/// - AI service is not configured
+/// - The AI provider fails or returns no usable question (502)
+/// - Database operation fails
+pub async fn generate_quiz_questions(
+    AdminUser(admin): AdminUser,
+    State(app_state): State<AppState>,
+    Path(skill): Path<String>,
+    payload: Option<Json<GenerateQuizQuestionsPayload>>,
+) -> AppResult<(StatusCode, Json<Vec<QuizQuestion>>)> {
+    let payload = payload.map(|Json(p)| p).unwrap_or_default();
+    payload.validate().map_err(|e| {
+        warn!("Quiz generation validation failed for user {}: {}", admin.user_id, e);
+        e
+    })?;
+
+    let skill = skill.trim();
+    if skill.is_empty() || skill.len() > 100 {
+        return Err(AppError::ValidationError("Skill name must be 1-100 characters".to_string()));
+    }
+    let ai_service = app_state
+        .ai_service
+        .as_ref()
+        .ok_or_else(|| AppError::ConfigurationError("AI service not configured".to_string()))?;
+
+    let count = payload.count.unwrap_or(DEFAULT_GENERATED_QUESTIONS);
+    let request = AIActionRequest {
+        action: ActionType::GenerateContent,
+        provider: payload.provider.unwrap_or(AIProvider::Gemini),
+        input: format!(
+            "{} multiple-choice questions testing practical knowledge of {} at junior level. \
+             Each question has 4 options and exactly one correct answer. \
+             Put a JSON array in \"content\", each item shaped as \
+             {{\"question\": \"...\", \"options\": [\"...\"], \"answer\": <index of the correct option>, \
+             \"explanation\": \"one sentence on why\"}}.",
+            count, skill
+        ),
+        parameters: Some(json!({ "content_type": "quiz_questions", "skill": skill })),
+    };
+    let response = ai_service.process_action(request).await?;
+    if !response.success {
+        return Err(AppError::ExternalServiceError(
+            response.message.unwrap_or_else(|| "AI provider failed".to_string()),
+        ));
+    }
+
+    let generated = quiz::parse_generated_questions(response.data.get("content").unwrap_or(&response.data));
+    if generated.is_empty() {
+        error!("AI provider returned no usable quiz question for {}", skill);
+        return Err(AppError::ExternalServiceError(
+            "AI provider returned no usable quiz question".to_string(),
+        ));
+    }
+
+    let mut inserted = Vec::new();
+    for question in generated.iter().take(count as usize) {
+        let row = sqlx::query_as!(
+            QuizQuestion,
+            r#"
+            INSERT INTO quiz_questions (skill_name, question, options, correct_option, explanation, source)
+            VALUES ($1, $2, $3, $4, $5, 'ai')
+            ON CONFLICT (LOWER(skill_name), md5(question)) DO NOTHING
+            RETURNING id, skill_name, question, options, correct_option, explanation, source, status,
+                      reviewed_at, created_at AS "created_at!"
+            "#,
+            skill,
+            question.question.trim(),
+            &question.options,
+            question.answer as i32,
+            question.explanation.as_deref()
+        )
+        .fetch_optional(&app_state.db_pool)
+        .await?;
+        inserted.extend(row);
+    }
+
+    info!(
+        "Generated {} quiz question(s) for {} (requested by {})",
+        inserted.len(),
+        skill,
+        admin.user_id
+    );
+    Ok((StatusCode::CREATED, Json(inserted)))
+}
+
+/// Lists quiz questions for review, including their answers.
+///
+/// # Endpoint
+/// `GET /api/admin/quiz-questions?status=pending&skill=SQL`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated (401) or not an administrator (403)
+/// - Database operation fails
+pub async fn list_quiz_questions(
+    _admin: AdminUser,
+    State(app_state): State<AppState>,
+    Query(query): Query<QuizQuestionQuery>,
+) -> AppResult<Json<Vec<QuizQuestion>>> {
+    let status = query.status.unwrap_or(QuizQuestionStatus::Pending);
+    let questions = sqlx::query_as!(
+        QuizQuestion,
+        r#"
+        SELECT id, skill_name, question, options, correct_option, explanation, source, status,
+               reviewed_at, created_at AS "created_at!"
+        FROM quiz_questions
+        WHERE status = $1 AND ($2::TEXT IS NULL OR LOWER(skill_name) = LOWER($2))
+        ORDER BY created_at, id
+        "#,
+        status.as_str(),
+        query.skill.as_deref().map(str::trim)
+    )
+    .fetch_all(&app_state.db_pool)
+    .await?;
+
+    Ok(Json(questions))
+}
+
+/// Approves or rejects a quiz question, optionally correcting it first.
+///
+/// # Endpoint
+/// `PUT /api/admin/quiz-questions/{id}`
+///
+/// # Request Body
+/// ```json
+/// { "status": "approved", "correct_option": 2, "explanation": "..." }
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated (401) or not an administrator (403)
+/// - Question not found (404)
+/// - The corrected question is malformed (400)
+/// - Database operation fails
+pub async fn review_quiz_question(
+    AdminUser(admin): AdminUser,
+    State(app_state): State<AppState>,
+    Path(question_id): Path<i32>,
+    Json(payload): Json<ReviewQuizQuestionPayload>,
+) -> AppResult<Json<QuizQuestion>> {
+    let pool = &app_state.db_pool;
+    let current = sqlx::query!(
+        "SELECT question, options, correct_option, explanation FROM quiz_questions WHERE id = $1",
+        question_id
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or(AppError::NotFound)?;
+
+    let correct_option = payload.correct_option.unwrap_or(current.correct_option);
+    let corrected = BankQuestion {
+        question: payload.question.unwrap_or(current.question).trim().to_string(),
+        options: payload.options.unwrap_or(current.options),
+        answer: usize::try_from(correct_option).unwrap_or(usize::MAX),
+        explanation: payload.explanation.or(current.explanation),
+    };
+    corrected.validate().map_err(AppError::ValidationError)?;
+
+    let reviewed = sqlx::query_as!(
+        QuizQuestion,
+        r#"
+        UPDATE quiz_questions
+        SET question = $1, options = $2, correct_option = $3, explanation = $4,
+            status = $5, reviewed_at = CURRENT_TIMESTAMP
+        WHERE id = $6
+        RETURNING id, skill_name, question, options, correct_option, explanation, source, status,
+                  reviewed_at, created_at AS "created_at!"
+        "#,
+        &corrected.question,
+        &corrected.options,
+        correct_option,
+        corrected.explanation.as_deref(),
+        payload.status.as_str(),
+        question_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    info!(
+        "Quiz question {} marked {} by {}",
+        question_id,
+        reviewed.status,
+        admin.user_id
+    );
+    Ok(Json(reviewed))
+}
+
+/// Questions of an attempt without their answers, in the attempt's order.
+async fn load_question_views(
+    pool: &sqlx::PgPool,
+    question_ids: &[i32],
+) -> Result<Vec<QuizQuestionView>, sqlx::Error> {
+    let mut questions = sqlx::query_as!(
+        QuizQuestionView,
+        "SELECT id, question, options FROM quiz_questions WHERE id = ANY($1)",
+        question_ids
+    )
+    .fetch_all(pool)
+    .await?;
+    let order: HashMap<i32, usize> = question_ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+    questions.sort_by_key(|q| order.get(&q.id).copied().unwrap_or(usize::MAX));
+    Ok(questions)
+}
+
@@ -0,0 +1,304 @@
+//! Saved job search handlers.
+//!
+//! Users save job filters with a minimum match score; the background check
+//! in [`crate::saved_searches`] records new matching jobs, alerts in-app and
+//! sends the email digests.
+
+use super::profile_entries::{clean_list, clean_text};
+use super::types::{SavedSearchMatchQuery, SavedSearchPayload};
+use crate::AppState;
+use crate::auth::AuthUser;
+use crate::errors::{AppError, AppResult};
+use crate::models::{ExperienceLevel, JobType, SavedSearch, SavedSearchMatch};
+use crate::saved_searches::MAX_SAVED_SEARCHES;
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use tracing::{info, warn};
+use validator::Validate;
+
+/// Default and largest number of matches listed.
+const DEFAULT_MATCH_LIMIT: i64 = 50;
+const MAX_MATCH_LIMIT: i64 = 200;
+
+/// Lists the authenticated user's saved searches.
+///
+/// # Endpoint
+/// `GET /api/saved-searches`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Database operation fails
+pub async fn list_saved_searches(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+) -> AppResult<Json<Vec<SavedSearch>>> {
+    let searches = sqlx::query_as!(
+        SavedSearch,
+        r#"
+        SELECT id, user_id, name, keywords, location,
+               experience_level as "experience_level: ExperienceLevel",
+               job_type as "job_type: JobType",
+               skills, min_match_score, include_external, alerts_enabled, digest_frequency,
+               last_checked_at, last_digest_at, created_at, updated_at
+        FROM saved_searches
+        WHERE user_id = $1
+        ORDER BY created_at DESC, id DESC
+        "#,
+        auth_user.user_id
+    )
+    .fetch_all(&app_state.db_pool)
+    .await?;
+
+    Ok(Json(searches))
+}
+
+/// Saves a job search. Jobs added from now on are checked against it.
+///
+/// # Endpoint
+/// `POST /api/saved-searches`
+///
+/// # Request Body
+/// ```json
+/// {
+///   "name": "Data roles in Dhaka",
+///   "keywords": "data analyst",
+///   "location": "Dhaka",
+///   "job_type": "full_time",
+///   "skills": ["SQL", "Python"],
+///   "min_match_score": 60,
+///   "digest_frequency": "weekly"
+/// }
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Validation fails (name length, too many skills, score outside 0-100)
+/// - The user already has the maximum number of saved searches (409)
+/// - Database operation fails
+pub async fn create_saved_search(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Json(payload): Json<SavedSearchPayload>,
+) -> AppResult<(StatusCode, Json<SavedSearch>)> {
+    payload.validate().map_err(|e| {
+        warn!("Saved search validation failed for user {}: {}", auth_user.user_id, e);
+        e
+    })?;
+
+    let search = sqlx::query_as!(
+        SavedSearch,
+        r#"
+        INSERT INTO saved_searches
+            (user_id, name, keywords, location, experience_level, job_type, skills,
+             min_match_score, include_external, alerts_enabled, digest_frequency)
+        SELECT $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11
+        WHERE (SELECT COUNT(*) FROM saved_searches WHERE user_id = $1) < $12
+        RETURNING id, user_id, name, keywords, location,
+                  experience_level as "experience_level: ExperienceLevel",
+                  job_type as "job_type: JobType",
+                  skills, min_match_score, include_external, alerts_enabled, digest_frequency,
+                  last_checked_at, last_digest_at, created_at, updated_at
+        "#,
+        auth_user.user_id,
+        payload.name.trim(),
+        clean_text(payload.keywords),
+        clean_text(payload.location),
+        payload.experience_level as _,
+        payload.job_type as _,
+        &clean_list(payload.skills),
+        payload.min_match_score,
+        payload.include_external,
+        payload.alerts_enabled,
+        payload.digest_frequency.as_str(),
+        MAX_SAVED_SEARCHES
+    )
+    .fetch_optional(&app_state.db_pool)
+    .await?
+    .ok_or_else(|| AppError::Conflict(format!("You can save at most {} searches", MAX_SAVED_SEARCHES)))?;
+
+    info!("User {} saved search {} ({})", auth_user.user_id, search.id, search.name);
+    Ok((StatusCode::CREATED, Json(search)))
+}
+
+/// Retrieves a saved search.
+///
+/// # Endpoint
+/// `GET /api/saved-searches/{id}`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Saved search doesn't exist or doesn't belong to user (404)
+/// - Database operation fails
+pub async fn get_saved_search(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Path(search_id): Path<i32>,
+) -> AppResult<Json<SavedSearch>> {
+    sqlx::query_as!(
+        SavedSearch,
+        r#"
+        SELECT id, user_id, name, keywords, location,
+               experience_level as "experience_level: ExperienceLevel",
+               job_type as "job_type: JobType",
+               skills, min_match_score, include_external, alerts_enabled, digest_frequency,
+               last_checked_at, last_digest_at, created_at, updated_at
+        FROM saved_searches
+        WHERE id = $1 AND user_id = $2
+        "#,
+        search_id,
+        auth_user.user_id
+    )
+    .fetch_optional(&app_state.db_pool)
+    .await?
+    .map(Json)
+    .ok_or(AppError::NotFound)
+}
+
+/// Replaces a saved search's filters and alert settings.
+///
+/// # Endpoint
+/// `PUT /api/saved-searches/{id}`
+///
+/// # Request Body
+/// Same as `POST /api/saved-searches`; omitted fields take their defaults.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Validation fails
+/// - Saved search doesn't exist or doesn't belong to user (404)
+/// - Database operation fails
+pub async fn update_saved_search(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Path(search_id): Path<i32>,
+    Json(payload): Json<SavedSearchPayload>,
+) -> AppResult<Json<SavedSearch>> {
+    payload.validate().map_err(|e| {
+        warn!("Saved search validation failed for user {}: {}", auth_user.user_id, e);
+        e
+    })?;
+
+    let search = sqlx::query_as!(
+        SavedSearch,
+        r#"
+        UPDATE saved_searches
+        SET name = $3, keywords = $4, location = $5, experience_level = $6, job_type = $7,
+            skills = $8, min_match_score = $9, include_external = $10, alerts_enabled = $11,
+            digest_frequency = $12, updated_at = CURRENT_TIMESTAMP
+        WHERE id = $1 AND user_id = $2
+        RETURNING id, user_id, name, keywords, location,
+                  experience_level as "experience_level: ExperienceLevel",
+                  job_type as "job_type: JobType",
+                  skills, min_match_score, include_external, alerts_enabled, digest_frequency,
+                  last_checked_at, last_digest_at, created_at, updated_at
+        "#,
+        search_id,
+        auth_user.user_id,
+        payload.name.trim(),
+        clean_text(payload.keywords),
+        clean_text(payload.location),
+        payload.experience_level as _,
+        payload.job_type as _,
+        &clean_list(payload.skills),
+        payload.min_match_score,
+        payload.include_external,
+        payload.alerts_enabled,
+        payload.digest_frequency.as_str()
+    )
+    .fetch_optional(&app_state.db_pool)
+    .await?
+    .ok_or(AppError::NotFound)?;
+
+    info!("User {} updated saved search {}", auth_user.user_id, search_id);
+    Ok(Json(search))
+}
+
+/// Deletes a saved search and its matches.
+///
+/// # Endpoint
+/// `DELETE /api/saved-searches/{id}`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Saved search doesn't exist or doesn't belong to user (404)
+/// - Database operation fails
+pub async fn delete_saved_search(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Path(search_id): Path<i32>,
+) -> AppResult<StatusCode> {
+    let result = sqlx::query!(
+        "DELETE FROM saved_searches WHERE id = $1 AND user_id = $2",
+        search_id,
+        auth_user.user_id
+    )
+    .execute(&app_state.db_pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+    info!("User {} deleted saved search {}", auth_user.user_id, search_id);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Lists the jobs a saved search found, newest first.
+///
+/// # Endpoint
+/// `GET /api/saved-searches/{id}/matches?limit=50`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Saved search doesn't exist or doesn't belong to user (404)
+/// - Database operation fails
+pub async fn list_saved_search_matches(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Path(search_id): Path<i32>,
+    Query(query): Query<SavedSearchMatchQuery>,
+) -> AppResult<Json<Vec<SavedSearchMatch>>> {
+    let pool = &app_state.db_pool;
+    let exists: bool = sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM saved_searches WHERE id = $1 AND user_id = $2) AS "exists!""#,
+        search_id,
+        auth_user.user_id
+    )
+    .fetch_one(pool)
+    .await?;
+    if !exists {
+        return Err(AppError::NotFound);
+    }
+
+    let matches = sqlx::query_as!(
+        SavedSearchMatch,
+        r#"
+        SELECT id, saved_search_id, job_key, job_id, title, company, location, url, source,
+               match_score, matched_at, emailed_at
+        FROM saved_search_matches
+        WHERE saved_search_id = $1
+        ORDER BY matched_at DESC, match_score DESC
+        LIMIT $2
+        "#,
+        search_id,
+        query.limit.unwrap_or(DEFAULT_MATCH_LIMIT).clamp(1, MAX_MATCH_LIMIT)
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(Json(matches))
+}
@@ -0,0 +1,188 @@
+//! Skill proficiency handlers.
+//!
+//! Users rate their own proficiency in a skill (1-10) or take a quiz (see
+//! [`super::quizzes`]). Every assessment is kept, so progress can be followed
+//! over time; the latest quiz result, or else the latest self-rating, is the
+//! skill's current level. Levels below 7 reduce the credit a matched skill
+//! gets in job match scores, and the skill gap analysis suggests practice.
+
+use super::types::{SelfRatingPayload, SkillAssessmentQuery, SkillProficiency};
+use crate::AppState;
+use crate::auth::AuthUser;
+use crate::errors::AppResult;
+use crate::models::SkillAssessment;
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::StatusCode,
+};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use tracing::{info, warn};
+use uuid::Uuid;
+use validator::Validate;
+
+/// Records the authenticated user's rating of their own proficiency in a skill.
+///
+/// # Endpoint
+/// `POST /api/skills/ratings`
+///
+/// # Request Body
+/// ```json
+/// { "skill_name": "SQL", "proficiency_level": 6 }
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Validation fails (skill name length, level outside 1-10)
+/// - Database operation fails
+pub async fn rate_skill(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Json(payload): Json<SelfRatingPayload>,
+) -> AppResult<(StatusCode, Json<SkillAssessment>)> {
+    payload.validate().map_err(|e| {
+        warn!("Skill rating validation failed for user {}: {}", auth_user.user_id, e);
+        e
+    })?;
+
+    let assessment = sqlx::query_as!(
+        SkillAssessment,
+        r#"
+        INSERT INTO skill_assessments (user_id, skill_name, proficiency_level, source)
+        VALUES ($1, $2, $3, 'self')
+        RETURNING id, user_id, skill_name, proficiency_level AS "proficiency_level!", source, quiz_attempt_id,
+                  assessed_at AS "assessed_at!"
+        "#,
+        auth_user.user_id,
+        payload.skill_name.trim(),
+        payload.proficiency_level
+    )
+    .fetch_one(&app_state.db_pool)
+    .await?;
+
+    info!(
+        "User {} rated {} at {}",
+        auth_user.user_id, assessment.skill_name, assessment.proficiency_level
+    );
+    Ok((StatusCode::CREATED, Json(assessment)))
+}
+
+/// Retrieves the authenticated user's current level in each assessed skill.
+///
+/// # Endpoint
+/// `GET /api/skills/proficiency`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Database operation fails
+pub async fn get_skill_proficiency(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+) -> AppResult<Json<Vec<SkillProficiency>>> {
+    let proficiency = load_skill_proficiency(&app_state.db_pool, auth_user.user_id).await?;
+    Ok(Json(proficiency))
+}
+
+/// Lists the authenticated user's assessments over time, newest first.
+///
+/// # Endpoint
+/// `GET /api/skills/assessments?skill=SQL`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Database operation fails
+pub async fn list_skill_assessments(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Query(query): Query<SkillAssessmentQuery>,
+) -> AppResult<Json<Vec<SkillAssessment>>> {
+    let assessments = sqlx::query_as!(
+        SkillAssessment,
+        r#"
+        SELECT id, user_id, skill_name, proficiency_level AS "proficiency_level!", source, quiz_attempt_id,
+               assessed_at AS "assessed_at!"
+        FROM skill_assessments
+        WHERE user_id = $1 AND proficiency_level IS NOT NULL
+          AND ($2::TEXT IS NULL OR LOWER(skill_name) = LOWER($2))
+        ORDER BY assessed_at DESC, id DESC
+        "#,
+        auth_user.user_id,
+        query.skill.as_deref().map(str::trim)
+    )
+    .fetch_all(&app_state.db_pool)
+    .await?;
+
+    Ok(Json(assessments))
+}
+
+/// Current level per skill, from the latest self-rating and quiz result.
+///
+/// Skills are compared case-insensitively; sorted by skill name.
+pub(super) async fn load_skill_proficiency(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<SkillProficiency>, sqlx::Error> {
+    let assessments = sqlx::query!(
+        r#"
+        SELECT skill_name, proficiency_level AS "proficiency_level!", source,
+               assessed_at AS "assessed_at!"
+        FROM skill_assessments
+        WHERE user_id = $1 AND proficiency_level IS NOT NULL
+        ORDER BY assessed_at DESC, id DESC
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    // Newest first, so the first assessment of each kind is the current one
+    let mut by_skill: HashMap<String, SkillProficiency> = HashMap::new();
+    for assessment in assessments {
+        let level = assessment.proficiency_level;
+        let entry = by_skill
+            .entry(assessment.skill_name.to_lowercase())
+            .or_insert_with(|| SkillProficiency {
+                skill_name: assessment.skill_name,
+                self_rating: None,
+                quiz_level: None,
+                proficiency_level: level,
+                verified: false,
+                assessed_at: assessment.assessed_at,
+            });
+        match assessment.source.as_str() {
+            "quiz" => {
+                entry.quiz_level.get_or_insert(level);
+            }
+            _ => {
+                entry.self_rating.get_or_insert(level);
+            }
+        }
+    }
+
+    let mut proficiency: Vec<SkillProficiency> = by_skill
+        .into_values()
+        .map(|mut p| {
+            p.verified = p.quiz_level.is_some();
+            p.proficiency_level = p.quiz_level.or(p.self_rating).unwrap_or(p.proficiency_level);
+            p
+        })
+        .collect();
+    proficiency.sort_by_key(|p| p.skill_name.to_lowercase());
+    Ok(proficiency)
+}
+
+/// Current levels keyed by lowercase skill name, as used in match scores.
+pub(super) async fn load_skill_levels(pool: &PgPool, user_id: Uuid) -> Result<HashMap<String, i32>, sqlx::Error> {
+    Ok(load_skill_proficiency(pool, user_id)
+        .await?
+        .into_iter()
+        .map(|p| (p.skill_name.to_lowercase(), p.proficiency_level))
+        .collect())
+}
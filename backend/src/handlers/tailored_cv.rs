@@ -0,0 +1,388 @@
+//! Job-tailored CV handlers.
+//!
+//! A tailored CV reorders the user's CV for one job and can optionally have
+//! its summary and project bullet points rewritten by AI. Every tailored CV is
+//! stored as a snapshot and linked to the application it was sent with.
+
+use super::ai::{cv_sharing_disabled, load_ai_privacy};
+use super::profile::{cv_download, negotiate_format};
+use super::types::{SavedCvQuery, TailoredCvQuery};
+use crate::AppState;
+use crate::ai::AIService;
+use crate::ai::redaction::Redactor;
+use crate::ai::types::{AIActionRequest, AIProvider, ActionType};
+use crate::auth::AuthUser;
+use crate::cv::document::{CvDocument, CvTemplate, description_points};
+use crate::cv::tailor::{TailoringSummary, tailor_document};
+use crate::errors::{AppError, AppResult};
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, HeaderValue, StatusCode},
+    response::Response,
+};
+use serde_json::{Value, json};
+use tracing::{debug, info, warn};
+
+/// Response header carrying the ID of a tailored CV snapshot.
+pub const TAILORED_CV_ID_HEADER: &str = "x-tailored-cv-id";
+
+/// Maximum number of projects sent to the AI provider for rewriting.
+const MAX_REWRITTEN_PROJECTS: usize = 5;
+
+/// Generates a CV tailored to a job and saves it against the application.
+///
+/// Skills, experience and projects that match the job's `required_skills`
+/// are moved to the top, with the matching bullet points first, and the
+/// headline becomes the job title. With `rewrite=true` the summary and the
+/// bullet points of the most relevant projects are rewritten for the role by
+/// the chosen AI provider (PII is redacted first).
+///
+/// Every call saves a new snapshot and responds with `201 Created`. If the
+/// user has already applied to the job it is linked to that application;
+/// otherwise it is linked when the application is created. The snapshot ID
+/// is returned in the `X-Tailored-Cv-Id` header.
+///
+/// # Endpoint
+/// `POST /api/jobs/{id}/tailored-cv?template=classic|modern|ats&format=pdf|docx|html|markdown|json&rewrite=true&provider=gemini|groq`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Job doesn't exist (404)
+/// - The template, format or provider is unknown (400)
+/// - `rewrite` is set but the user opted out of sharing CV data with AI (403)
+/// - `rewrite` is set but the AI service is not configured
+/// - Database operation or rendering fails
+pub async fn create_tailored_cv(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Path(job_id): Path<i32>,
+    headers: HeaderMap,
+    Query(query): Query<TailoredCvQuery>,
+) -> AppResult<Response> {
+    let format = negotiate_format(query.format, &headers);
+    info!(
+        "Tailoring CV for user: {}, job_id: {} (format: {:?}, rewrite: {})",
+        auth_user.user_id, job_id, format, query.rewrite
+    );
+
+    let job = sqlx::query!(
+        "SELECT job_title, company, required_skills FROM jobs WHERE id = $1",
+        job_id
+    )
+    .fetch_optional(&app_state.db_pool)
+    .await?
+    .ok_or(AppError::NotFound)?;
+    let (job_title, company, required_skills) = (job.job_title, job.company, job.required_skills);
+
+    let mut document = super::cv::load_cv_document(&app_state.db_pool, auth_user.user_id).await?;
+    let summary = tailor_document(&mut document, &job_title, &required_skills);
+    debug!(
+        "Tailored CV: {} matched, {} missing, {} highlighted entries",
+        summary.matched_skills.len(),
+        summary.missing_skills.len(),
+        summary.highlighted_entries.len()
+    );
+
+    let mut ai_rewritten = false;
+    if query.rewrite {
+        let (redactor, share_cv_with_ai) = load_ai_privacy(&app_state.db_pool, auth_user.user_id).await?;
+        if !share_cv_with_ai {
+            return Err(cv_sharing_disabled());
+        }
+        let ai_service = app_state
+            .ai_service
+            .as_ref()
+            .ok_or_else(|| AppError::ConfigurationError("AI service not configured".to_string()))?;
+
+        let role = RoleContext {
+            job_title: &job_title,
+            company: &company,
+            required_skills: &required_skills,
+            summary: &summary,
+        };
+        let provider = query.provider.unwrap_or(AIProvider::Gemini);
+        ai_rewritten = rewrite_with_ai(ai_service, &redactor, provider, &role, &mut document).await?;
+    }
+
+    let application_id = sqlx::query_scalar!(
+        "SELECT id FROM application_tracking WHERE user_id = $1 AND job_id = $2",
+        auth_user.user_id,
+        job_id
+    )
+    .fetch_optional(&app_state.db_pool)
+    .await?;
+
+    let tailored_cv_id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO tailored_cvs
+            (user_id, job_id, application_id, document, template, matched_skills, missing_skills, ai_rewritten)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        RETURNING id
+        "#,
+        auth_user.user_id,
+        job_id,
+        application_id,
+        sqlx::types::Json(&document) as _,
+        template_name(query.template),
+        &summary.matched_skills,
+        &summary.missing_skills,
+        ai_rewritten
+    )
+    .fetch_one(&app_state.db_pool)
+    .await?;
+
+    info!(
+        "Tailored CV saved: tailored_cv_id={}, user_id={}, job_id={}, application_id={:?}",
+        tailored_cv_id, auth_user.user_id, job_id, application_id
+    );
+
+    let file_stem = format!("{} {}", document.name(), job_title);
+    let mut response = cv_download(document, format, query.template, &file_stem).await?;
+    *response.status_mut() = StatusCode::CREATED;
+    response
+        .headers_mut()
+        .insert(TAILORED_CV_ID_HEADER, HeaderValue::from(tailored_cv_id));
+    Ok(response)
+}
+
+/// Downloads the tailored CV that was sent with an application.
+///
+/// Renders the most recent snapshot linked to the application, in the
+/// template it was tailored with unless `template` is given.
+///
+/// # Endpoint
+/// `GET /api/applications/{id}/tailored-cv?template=classic|modern|ats&format=pdf|docx|html|markdown|json`
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - User is not authenticated
+/// - Application doesn't exist, doesn't belong to user or has no tailored CV (404)
+/// - Database operation or rendering fails
+pub async fn get_application_tailored_cv(
+    auth_user: AuthUser,
+    State(app_state): State<AppState>,
+    Path(application_id): Path<i32>,
+    headers: HeaderMap,
+    Query(query): Query<SavedCvQuery>,
+) -> AppResult<Response> {
+    info!(
+        "Fetching tailored CV for application: application_id={}, user_id={}",
+        application_id, auth_user.user_id
+    );
+
+    let saved = sqlx::query!(
+        r#"
+        SELECT t.id, t.document AS "document: sqlx::types::Json<CvDocument>", t.template, j.job_title
+        FROM tailored_cvs t
+        JOIN application_tracking a ON a.id = t.application_id
+        JOIN jobs j ON j.id = t.job_id
+        WHERE a.id = $1 AND a.user_id = $2
+        ORDER BY t.created_at DESC, t.id DESC
+        LIMIT 1
+        "#,
+        application_id,
+        auth_user.user_id
+    )
+    .fetch_optional(&app_state.db_pool)
+    .await?
+    .ok_or(AppError::NotFound)?;
+    let (tailored_cv_id, document, template, job_title) = (saved.id, saved.document, saved.template, saved.job_title);
+
+    let template = query.template.unwrap_or_else(|| {
+        serde_json::from_value(Value::String(template)).unwrap_or_default()
+    });
+    let format = negotiate_format(query.format, &headers);
+    let document = document.0;
+
+    let file_stem = format!("{} {}", document.name(), job_title);
+    let mut response = cv_download(document, format, template, &file_stem).await?;
+    response
+        .headers_mut()
+        .insert(TAILORED_CV_ID_HEADER, HeaderValue::from(tailored_cv_id));
+    Ok(response)
+}
+
+/// Links the user's latest unlinked tailored CV for a job to a new application.
+///
+/// Returns the ID of the linked snapshot, if there was one.
+pub(super) async fn link_latest_tailored_cv(
+    pool: &sqlx::PgPool,
+    user_id: uuid::Uuid,
+    job_id: i32,
+    application_id: i32,
+) -> AppResult<Option<i32>> {
+    let linked = sqlx::query_scalar!(
+        r#"
+        UPDATE tailored_cvs SET application_id = $1
+        WHERE id = (
+            SELECT id FROM tailored_cvs
+            WHERE user_id = $2 AND job_id = $3 AND application_id IS NULL
+            ORDER BY created_at DESC, id DESC
+            LIMIT 1
+        )
+        RETURNING id
+        "#,
+        application_id,
+        user_id,
+        job_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(linked)
+}
+
+/// Name under which a template is stored.
+fn template_name(template: CvTemplate) -> &'static str {
+    match template {
+        CvTemplate::Classic => "classic",
+        CvTemplate::Modern => "modern",
+        CvTemplate::Ats => "ats",
+    }
+}
+
+/// The job a CV is being rewritten for.
+struct RoleContext<'a> {
+    job_title: &'a str,
+    company: &'a str,
+    required_skills: &'a [String],
+    summary: &'a TailoringSummary,
+}
+
+/// Rewrites the summary and project bullet points for the role.
+///
+/// Parts the provider fails to return in the expected shape are left as
+/// they were. Returns whether anything was rewritten.
+async fn rewrite_with_ai(
+    ai_service: &AIService,
+    redactor: &Redactor,
+    provider: AIProvider,
+    role: &RoleContext<'_>,
+    document: &mut CvDocument,
+) -> AppResult<bool> {
+    let cv = &mut document.cv;
+    let role_text = format!(
+        "Role: {} at {}\nRequired skills: {}\nCandidate's matching skills: {}\nCandidate's other skills: {}",
+        role.job_title,
+        role.company,
+        role.required_skills.join(", "),
+        role.summary.matched_skills.join(", "),
+        cv.skills
+            .iter()
+            .filter(|s| !role.summary.matched_skills.contains(s))
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(", "),
+    );
+    let mut rewritten = false;
+
+    let summary_prompt = format!(
+        "Rewrite this CV summary for the role below in 2-3 sentences. Lead with the experience and skills most relevant to the role. Only mention skills and experience stated here; do not invent facts. Return the summary text only as the content.\n\n{}\n\nCurrent summary:\n{}",
+        role_text,
+        cv.summary.as_deref().unwrap_or("(none - write one from the skills above)")
+    );
+    let content = generate(ai_service, redactor, provider.clone(), summary_prompt, "tailored_summary").await?;
+    if let Some(text) = content.as_ref().and_then(Value::as_str).map(str::trim).filter(|t| !t.is_empty()) {
+        cv.summary = Some(text.to_string());
+        rewritten = true;
+    } else {
+        warn!("AI summary rewrite returned no usable content; keeping the original");
+    }
+
+    // Projects are already ordered by relevance; only rewrite ones with details
+    let projects: Vec<usize> = cv
+        .projects
+        .iter()
+        .enumerate()
+        .filter(|(_, p)| !description_points(p).is_empty())
+        .map(|(i, _)| i)
+        .take(MAX_REWRITTEN_PROJECTS)
+        .collect();
+    if projects.is_empty() {
+        return Ok(rewritten);
+    }
+
+    let project_text = projects
+        .iter()
+        .enumerate()
+        .map(|(n, &i)| {
+            let project = &cv.projects[i];
+            format!(
+                "{}. {}\n- {}",
+                n + 1,
+                project.title,
+                description_points(project).join("\n- ")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    let bullets_prompt = format!(
+        "Rewrite the bullet points of these CV projects for the role below. Use action verbs, put the work most relevant to the required skills first, and keep every claim grounded in the original bullets. Return the content as a JSON array with one entry per project, in the same order, each entry being an array of bullet point strings.\n\n{}\n\nProjects:\n{}",
+        role_text, project_text
+    );
+    let content = generate(ai_service, redactor, provider, bullets_prompt, "tailored_project_bullets").await?;
+    match content.as_ref().and_then(parse_bullet_lists) {
+        Some(lists) if lists.len() == projects.len() => {
+            for (&i, bullets) in projects.iter().zip(lists) {
+                if !bullets.is_empty() {
+                    cv.projects[i].description = Some(bullets.join("\n"));
+                    rewritten = true;
+                }
+            }
+        }
+        _ => warn!("AI project rewrite returned no usable content; keeping the original bullet points"),
+    }
+
+    Ok(rewritten)
+}
+
+/// Runs a content generation request and returns its `content` field.
+///
+/// Returns `None` when the provider reports a failure.
+async fn generate(
+    ai_service: &AIService,
+    redactor: &Redactor,
+    provider: AIProvider,
+    prompt: String,
+    content_type: &str,
+) -> AppResult<Option<Value>> {
+    let request = AIActionRequest {
+        action: ActionType::GenerateContent,
+        provider,
+        input: prompt,
+        parameters: Some(json!({
+            "content_type": content_type,
+            "tone": "professional"
+        })),
+    };
+    let response = ai_service.process_action_with(request, redactor).await?;
+    if !response.success {
+        warn!("AI {} request failed: {:?}", content_type, response.message);
+        return Ok(None);
+    }
+    Ok(response.data.get("content").cloned())
+}
+
+/// Reads a list of bullet point lists, given either as JSON or as a string
+/// holding JSON.
+fn parse_bullet_lists(content: &Value) -> Option<Vec<Vec<String>>> {
+    let value = match content {
+        Value::String(text) => serde_json::from_str(text.trim()).ok()?,
+        other => other.clone(),
+    };
+    serde_json::from_value::<Vec<Vec<String>>>(value).ok().map(|lists| {
+        lists
+            .into_iter()
+            .map(|bullets| {
+                bullets
+                    .into_iter()
+                    .map(|b| b.trim().trim_start_matches(['-', '*', '•']).trim().to_string())
+                    .filter(|b| !b.is_empty())
+                    .collect()
+            })
+            .collect()
+    })
+}
@@ -7,6 +7,18 @@ use serde::{Deserialize, Serialize};
 use validator::Validate;
 use uuid::Uuid;
 use crate::models::*;
+use crate::ai::types::AIProvider;
+use crate::cv::ParsedCv;
+use crate::cv::document::CvTemplate;
+use crate::cv::export::ExportFormat;
+use crate::external_jobs::{ExternalJob, SourceHealth, SourceState};
+use crate::external_jobs::normalize::NormalizedFields;
+use crate::job_feed::FeedJob;
+use crate::notifications::NotificationType;
+use crate::queue::JobStatus;
+use crate::saved_searches::DigestFrequency;
+use chrono::{DateTime, NaiveDate, Utc};
+use std::collections::HashMap;
 
 /// User registration payload - simplified for initial registration.
 #[derive(Debug, Deserialize, Validate)]
@@ -57,6 +69,10 @@ pub struct UserProfile {
     pub education_level: Option<String>,
     /// Experience level
     pub experience_level: Option<ExperienceLevel>,
+    /// Fewest years of professional experience
+    pub experience_years_min: Option<i32>,
+    /// Most years of professional experience
+    pub experience_years_max: Option<i32>,
     /// Preferred career track
     pub preferred_track: Option<CareerTrack>,
     /// User's skills
@@ -65,6 +81,15 @@ pub struct UserProfile {
     pub projects: Vec<String>,
     /// Target job roles
     pub target_roles: Vec<String>,
+    /// Education history, most recent first
+    pub education: Vec<Education>,
+    /// Work experience, most recent first
+    pub work_experience: Vec<WorkExperience>,
+    /// Projects with details, most recent first
+    pub project_details: Vec<Project>,
+    /// Profile version, also sent as the `ETag`; send it back in `If-Match`
+    /// to make an update conditional
+    pub profile_version: i32,
 }
 
 /// Profile completion payload for onboarding.
@@ -74,6 +99,13 @@ pub struct CompleteProfilePayload {
     pub education_level: Option<String>,
     /// Experience level
     pub experience_level: ExperienceLevel,
+    /// Fewest years of professional experience (optional)
+    #[validate(range(min = 0, max = 60, message = "Years of experience must be between 0 and 60"))]
+    pub experience_years_min: Option<i32>,
+    /// Most years of professional experience (optional; the minimum for an
+    /// exact figure)
+    #[validate(range(min = 0, max = 60, message = "Years of experience must be between 0 and 60"))]
+    pub experience_years_max: Option<i32>,
     /// Preferred career track
     pub preferred_track: CareerTrack,
     /// User's skills (optional)
@@ -93,6 +125,12 @@ pub struct UpdateProfilePayload {
     pub education_level: Option<String>,
     /// Updated experience level
     pub experience_level: Option<ExperienceLevel>,
+    /// Updated fewest years of professional experience
+    #[validate(range(min = 0, max = 60, message = "Years of experience must be between 0 and 60"))]
+    pub experience_years_min: Option<i32>,
+    /// Updated most years of professional experience
+    #[validate(range(min = 0, max = 60, message = "Years of experience must be between 0 and 60"))]
+    pub experience_years_max: Option<i32>,
     /// Updated preferred track
     pub preferred_track: Option<CareerTrack>,
     /// Updated skills list
@@ -105,6 +143,217 @@ pub struct UpdateProfilePayload {
     pub raw_cv_text: Option<String>,
 }
 
+/// Payload for creating or replacing an education entry.
+#[derive(Debug, Deserialize, Validate)]
+pub struct EducationPayload {
+    /// School, college or university
+    #[validate(length(min = 1, max = 255, message = "Institution is required"))]
+    pub institution: String,
+    /// Degree or qualification
+    #[validate(length(max = 255))]
+    pub degree: Option<String>,
+    /// Field of study
+    #[validate(length(max = 255))]
+    pub field_of_study: Option<String>,
+    /// First day of study (`YYYY-MM-DD`)
+    pub start_date: Option<NaiveDate>,
+    /// Graduation date (`YYYY-MM-DD`)
+    pub end_date: Option<NaiveDate>,
+    /// Whether the user is still studying here
+    #[serde(default)]
+    pub is_current: bool,
+    /// Grade, GPA or class
+    #[validate(length(max = 100))]
+    pub grade: Option<String>,
+}
+
+/// Payload for creating or replacing a work experience entry.
+#[derive(Debug, Deserialize, Validate)]
+pub struct WorkExperiencePayload {
+    /// Company or organization
+    #[validate(length(min = 1, max = 255, message = "Employer is required"))]
+    pub employer: String,
+    /// Job title
+    #[validate(length(min = 1, max = 255, message = "Title is required"))]
+    pub title: String,
+    /// Where the job was based
+    #[validate(length(max = 255))]
+    pub location: Option<String>,
+    /// First day in the role (`YYYY-MM-DD`)
+    pub start_date: Option<NaiveDate>,
+    /// Last day in the role (`YYYY-MM-DD`)
+    pub end_date: Option<NaiveDate>,
+    /// Whether this is the user's current job
+    #[serde(default)]
+    pub is_current: bool,
+    /// Achievements and responsibilities
+    #[serde(default)]
+    #[validate(length(max = 20, message = "At most 20 bullet points are allowed"))]
+    pub bullet_points: Vec<String>,
+    /// Skills used in the role
+    #[serde(default)]
+    #[validate(length(max = 50, message = "At most 50 skills are allowed"))]
+    pub skills_used: Vec<String>,
+}
+
+/// Payload for creating or replacing a project.
+#[derive(Debug, Deserialize, Validate)]
+pub struct ProjectPayload {
+    /// Project name
+    #[validate(length(min = 1, max = 255, message = "Title is required"))]
+    pub title: String,
+    /// What the project does and the user's part in it
+    #[validate(length(max = 5000))]
+    pub description: Option<String>,
+    /// Link to the repository or live project
+    #[validate(url(message = "Invalid project URL"))]
+    pub url: Option<String>,
+    /// Languages, frameworks and tools used
+    #[serde(default)]
+    #[validate(length(max = 50, message = "At most 50 technologies are allowed"))]
+    pub tech_stack: Vec<String>,
+    /// When work on the project started (`YYYY-MM-DD`)
+    pub start_date: Option<NaiveDate>,
+    /// When work on the project ended (`YYYY-MM-DD`)
+    pub end_date: Option<NaiveDate>,
+}
+
+/// AI privacy settings for the authenticated user.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PrivacySettings {
+    /// Whether CV-derived data may be sent to external AI providers
+    pub share_cv_with_ai: bool,
+}
+
+/// Structured CV data with review status.
+#[derive(Debug, Serialize)]
+pub struct ParsedCvResponse {
+    /// Parsed sections
+    #[serde(flatten)]
+    pub cv: ParsedCv,
+    /// Whether the user has reviewed and saved the parse
+    pub reviewed: bool,
+    /// When the user last reviewed the parse
+    pub reviewed_at: Option<DateTime<Utc>>,
+    /// When AI enrichment last ran
+    pub enriched_at: Option<DateTime<Utc>>,
+    /// CV version the parse was produced from
+    pub cv_version_id: Option<i32>,
+    /// When the parse was last updated
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Corrected CV data submitted by the user.
+#[derive(Debug, Deserialize)]
+pub struct ReviewParsedCvPayload {
+    /// Corrected sections (replace the stored parse entirely)
+    #[serde(flatten)]
+    pub cv: ParsedCv,
+    /// Merge the reviewed skills into the user's profile
+    #[serde(default)]
+    pub apply_to_profile: bool,
+}
+
+/// Payload for optional AI enrichment of a parsed CV.
+#[derive(Debug, Deserialize)]
+pub struct EnrichParsedCvPayload {
+    /// AI provider to use (defaults to Gemini)
+    pub provider: Option<AIProvider>,
+}
+
+/// Response to a request queued as a background AI job (202 Accepted).
+///
+/// An `ai_job_completed` event with the same `job_id` is published when the
+/// result is saved.
+#[derive(Debug, Serialize)]
+pub struct AiJobAccepted {
+    /// ID of the background job
+    pub job_id: i64,
+    /// Kind of AI job: `roadmap`, `cover_letter` or `cv_enrichment`
+    pub job: &'static str,
+}
+
+/// State of one of the user's background AI jobs.
+#[derive(Debug, Serialize)]
+pub struct AiJobStatus {
+    /// ID of the background job
+    pub job_id: i64,
+    /// Kind of AI job: `roadmap`, `cover_letter` or `cv_enrichment`
+    pub job: &'static str,
+    /// "pending", "running", "succeeded" or "dead"
+    pub status: String,
+    /// Attempts made so far
+    pub attempts: i32,
+    /// Attempts before the job is given up
+    pub max_attempts: i32,
+    /// Error of the last failed attempt
+    pub error: Option<String>,
+    /// When the job was queued
+    pub created_at: DateTime<Utc>,
+    /// When the job succeeded or died
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// Roadmap generation, run by the background queue.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoadmapJob {
+    /// User the roadmap is for
+    pub user_id: Uuid,
+    /// Role the roadmap leads to
+    pub target_role: String,
+    /// Months the roadmap spans
+    pub timeframe_months: u32,
+    /// Hours of learning per week
+    pub learning_hours_per_week: u32,
+    /// AI provider to use
+    pub provider: AIProvider,
+    /// Base the roadmap on the user's current skills
+    pub include_current_skills: bool,
+}
+
+/// Enrichment of a parsed CV, run by the background queue.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CvEnrichmentJob {
+    /// Owner of the CV
+    pub user_id: Uuid,
+    /// AI provider to use
+    pub provider: AIProvider,
+}
+
+/// Query parameters for CV generation.
+#[derive(Debug, Deserialize)]
+pub struct GenerateCvQuery {
+    /// Layout template: `classic` (default), `modern` or `ats`
+    #[serde(default)]
+    pub template: CvTemplate,
+    /// Output format; overrides the `Accept` header
+    pub format: Option<ExportFormat>,
+}
+
+/// Query parameters for a job-tailored CV.
+#[derive(Debug, Deserialize)]
+pub struct TailoredCvQuery {
+    /// Layout template: `classic` (default), `modern` or `ats`
+    #[serde(default)]
+    pub template: CvTemplate,
+    /// Output format; overrides the `Accept` header
+    pub format: Option<ExportFormat>,
+    /// Rewrite the summary and project bullet points for the role with AI
+    #[serde(default)]
+    pub rewrite: bool,
+    /// AI provider used with `rewrite` (defaults to Gemini)
+    pub provider: Option<AIProvider>,
+}
+
+/// Query parameters for downloading the tailored CV sent with an application.
+#[derive(Debug, Deserialize)]
+pub struct SavedCvQuery {
+    /// Layout template; defaults to the one the CV was tailored with
+    pub template: Option<CvTemplate>,
+    /// Output format; overrides the `Accept` header
+    pub format: Option<ExportFormat>,
+}
+
 /// Query parameters for job recommendations.
 #[derive(Debug, Deserialize)]
 pub struct JobQueryParams {
@@ -117,6 +366,117 @@ pub struct JobQueryParams {
     pub limit: Option<i64>,
 }
 
+/// Query parameters of the unified job feed.
+#[derive(Debug, Deserialize)]
+pub struct JobFeedQuery {
+    /// Comma-separated sources: `internal`, `external` (every external
+    /// source) or source names such as `ReliefWeb`; all when unset
+    pub source: Option<String>,
+    /// Filter by experience level
+    pub experience_level: Option<ExperienceLevel>,
+    /// Filter by job type
+    pub job_type: Option<JobType>,
+    /// Text the location must contain
+    pub location: Option<String>,
+    /// Leave out jobs scoring below this (0-100)
+    pub min_score: Option<f64>,
+    /// Maximum number of results to return (default 50, max 200)
+    pub limit: Option<i64>,
+    /// Results to skip
+    pub offset: Option<i64>,
+}
+
+/// Query parameters for listing external jobs.
+#[derive(Debug, Deserialize)]
+pub struct ExternalJobQuery {
+    /// Words that must all appear in the title, company, description or skills
+    pub q: Option<String>,
+    /// Source name, e.g. `ReliefWeb`
+    pub source: Option<String>,
+    /// Text the location must contain
+    pub location: Option<String>,
+    /// Normalized job type, e.g. `full_time`
+    pub job_type: Option<JobType>,
+    /// Normalized experience level, e.g. `mid`
+    pub experience_level: Option<ExperienceLevel>,
+    /// Comma-separated skills; listings must need at least one
+    pub skills: Option<String>,
+    /// `match` (default, best match first) or `recent`
+    #[serde(default)]
+    pub sort: ExternalJobSort,
+    /// Maximum number of results to return (default 50, max 200)
+    pub limit: Option<i64>,
+    /// Results to skip
+    pub offset: Option<i64>,
+}
+
+/// Order of external job listings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExternalJobSort {
+    /// Highest match score first
+    #[default]
+    Match,
+    /// Most recently posted first
+    Recent,
+}
+
+/// External job listing with its match score for the user.
+#[derive(Debug, Serialize)]
+pub struct ExternalJobListing {
+    /// The listing
+    #[serde(flatten)]
+    pub job: ExternalJob,
+    /// "ngo", "government" or "local"
+    pub category: String,
+    /// Published fields mapped to the internal types
+    pub normalized: NormalizedFields,
+    /// Match score as percentage (0-100)
+    pub match_score: f64,
+    /// Skills of the listing the user has
+    pub matched_skills: Vec<String>,
+    /// Skills of the listing the user lacks
+    pub missing_skills: Vec<String>,
+    /// When the listing was first imported
+    pub first_seen_at: DateTime<Utc>,
+    /// When the listing expires unless the source still lists it
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Whether the listings of one external source are current.
+#[derive(Debug, Serialize)]
+pub struct ExternalSourceStatus {
+    /// Source name
+    pub source: String,
+    /// "ngo", "government" or "local"
+    pub category: String,
+    /// `live`, `cached` or `unavailable`
+    pub status: SourceState,
+    /// When the source's listings were last refreshed
+    pub synced_at: Option<DateTime<Utc>>,
+    /// Built-in sample listings, imported in demo mode only
+    pub demo: bool,
+}
+
+/// External job listings with the status of the sources they come from.
+#[derive(Debug, Serialize)]
+pub struct ExternalJobsResponse {
+    /// Listings, sorted and paged
+    pub jobs: Vec<ExternalJobListing>,
+    /// Status of each configured source the query covers
+    pub sources: Vec<ExternalSourceStatus>,
+}
+
+/// Import history and state of an external source, for administrators.
+#[derive(Debug, Serialize)]
+pub struct ExternalSourceHealth {
+    /// Import history
+    #[serde(flatten)]
+    pub health: SourceHealth,
+    /// `live`, `cached` or `unavailable`
+    pub status: SourceState,
+}
+
 /// Job recommendation with match analysis.
 #[derive(Debug, Serialize)]
 pub struct JobRecommendation {
@@ -144,6 +504,41 @@ pub struct JobRecommendation {
     pub platform_links: PlatformLinks,
 }
 
+/// Internal job or external listing in the feed, scored for the user.
+#[derive(Debug, Serialize)]
+pub struct FeedRecommendation {
+    /// The job
+    #[serde(flatten)]
+    pub job: FeedJob,
+    /// Match score as percentage (0-100)
+    pub match_score: f64,
+    /// Skills of the job the user has
+    pub matched_skills: Vec<String>,
+    /// Skills of the job the user lacks
+    pub missing_skills: Vec<String>,
+    /// Human-readable explanation of the match
+    pub match_explanation: String,
+    /// Key strengths (why it's a good match)
+    pub strengths: Vec<String>,
+    /// Areas for improvement
+    pub improvement_areas: Vec<String>,
+    /// Experience level alignment score (0-100)
+    pub experience_alignment: f64,
+    /// Track alignment score (0-100)
+    pub track_alignment: f64,
+    /// Skill overlap score (0-100)
+    pub skill_overlap: f64,
+}
+
+/// Unified job feed, with the status of the external sources it covers.
+#[derive(Debug, Serialize)]
+pub struct JobFeedResponse {
+    /// Jobs, best match first
+    pub jobs: Vec<FeedRecommendation>,
+    /// Status of each external source included
+    pub sources: Vec<ExternalSourceStatus>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct PlatformLinks {
     pub linkedin: String,
@@ -177,9 +572,11 @@ pub struct SkillGapAnalysis {
     pub skill_gaps: Vec<String>,
     /// Skills the user already has
     pub matching_skills: Vec<String>,
+    /// Skills the user has but assessed below 5/10, worth practising
+    pub weak_skills: Vec<String>,
     /// Percentage of required skills the user has
     pub match_percentage: f64,
-    /// Recommended resources to close skill gaps
+    /// Recommended resources to close skill gaps and strengthen weak skills
     pub recommended_resources: Vec<LearningResource>,
 }
 
@@ -192,6 +589,50 @@ pub struct CreateApplicationPayload {
     pub notes: Option<String>,
 }
 
+/// Payload for generating a cover letter for an application.
+#[derive(Debug, Default, Deserialize, Validate)]
+pub struct GenerateCoverLetterPayload {
+    /// AI provider to use (defaults to Gemini)
+    pub provider: Option<AIProvider>,
+    /// Tone of the letter, e.g. "formal" or "enthusiastic" (defaults to professional)
+    #[validate(length(max = 50, message = "Tone must be at most 50 characters"))]
+    pub tone: Option<String>,
+    /// Extra points the letter should mention
+    #[validate(length(max = 2000, message = "Instructions must be at most 2000 characters"))]
+    pub instructions: Option<String>,
+}
+
+/// Cover letter generation, run by the background queue.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CoverLetterJob {
+    /// Owner of the application
+    pub user_id: Uuid,
+    /// Application the letter is written for
+    pub application_id: i32,
+    /// AI provider to use
+    pub provider: AIProvider,
+    /// Tone of the letter
+    pub tone: Option<String>,
+    /// Extra points the letter should mention
+    pub instructions: Option<String>,
+}
+
+/// Payload for editing a cover letter.
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateCoverLetterPayload {
+    /// New letter text; paragraphs are separated by blank lines
+    #[validate(length(min = 1, max = 20000, message = "Cover letter must be 1 to 20000 characters"))]
+    pub content: String,
+}
+
+/// Query parameters for exporting a cover letter.
+#[derive(Debug, Deserialize)]
+pub struct CoverLetterExportQuery {
+    /// Layout template: `classic` (default), `modern` or `ats`
+    #[serde(default)]
+    pub template: CvTemplate,
+}
+
 /// Payload for updating an existing application.
 #[derive(Debug, Deserialize)]
 pub struct UpdateApplicationPayload {
@@ -227,3 +668,462 @@ pub struct OAuthLoginResponse {
     /// Whether this is a newly created user
     pub is_new_user: bool,
 }
+
+/// CV version with the text extracted from it.
+#[derive(Debug, Serialize)]
+pub struct CvVersionDetail {
+    /// Version metadata
+    #[serde(flatten)]
+    pub version: CvVersion,
+    /// Text extracted from the file
+    pub extracted_text: String,
+}
+
+/// Payload for creating or updating the public profile.
+///
+/// Visibility flags default to the safe choice: email and CV hidden, skills,
+/// learning and roadmaps shown.
+#[derive(Debug, Deserialize, Validate)]
+pub struct PublicProfilePayload {
+    /// URL slug: 3-40 lowercase letters, digits and single hyphens
+    #[validate(length(min = 3, max = 40, message = "Slug must be 3 to 40 characters"))]
+    pub slug: String,
+    /// Whether the profile is visible to anyone with the link
+    #[serde(default)]
+    pub is_public: bool,
+    /// Show the email address
+    #[serde(default)]
+    pub show_email: bool,
+    /// Show CV sections and allow downloading the CV
+    #[serde(default)]
+    pub show_cv: bool,
+    /// Show skills
+    #[serde(default = "default_true")]
+    pub show_skills: bool,
+    /// Show completed learning resources
+    #[serde(default = "default_true")]
+    pub show_learning: bool,
+    /// Show career roadmaps
+    #[serde(default = "default_true")]
+    pub show_roadmaps: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// A skill on a public profile.
+#[derive(Debug, Serialize)]
+pub struct PublicSkill {
+    /// Skill name as listed on the profile
+    pub name: String,
+    /// Backed by a completed learning resource or a skill quiz result
+    pub verified: bool,
+}
+
+/// A learning resource the user has completed.
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct CompletedLearning {
+    /// Resource title
+    pub title: String,
+    /// Platform offering the resource
+    pub platform: String,
+    /// Link to the resource
+    pub url: String,
+    /// Skills the resource teaches
+    pub related_skills: Vec<String>,
+    /// When the user finished it
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// Career roadmap progress shown on a public profile.
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct RoadmapSummary {
+    /// Roadmap title
+    pub title: String,
+    /// Role the roadmap leads to
+    pub target_role: String,
+    /// Overall progress (0-100)
+    pub progress_percentage: Option<i32>,
+}
+
+/// Public view of a profile. Hidden fields are omitted.
+#[derive(Debug, Serialize)]
+pub struct PublicProfile {
+    /// URL slug
+    pub slug: String,
+    /// User's name
+    pub full_name: String,
+    /// Target roles or career track
+    pub headline: Option<String>,
+    /// Email, when the user chose to show it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    /// Experience level
+    pub experience_level: Option<ExperienceLevel>,
+    /// Career track
+    pub preferred_track: Option<CareerTrack>,
+    /// Skills, verified first
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skills: Option<Vec<PublicSkill>>,
+    /// Completed learning resources, most recent first
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed_learning: Option<Vec<CompletedLearning>>,
+    /// Career roadmaps, most recent first
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub roadmaps: Option<Vec<RoadmapSummary>>,
+    /// CV sections (summary, experience, projects, education, certifications)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cv: Option<ParsedCv>,
+    /// Relative URL of the CV download, when the CV is shown
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cv_url: Option<String>,
+}
+
+/// Payload for deleting the account.
+#[derive(Debug, Default, Deserialize)]
+pub struct DeleteAccountPayload {
+    /// Current password; required for accounts that have one
+    pub password: Option<String>,
+}
+
+/// Whether the account is scheduled for deletion.
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct AccountDeletionStatus {
+    /// When deletion was requested, `null` if it is not scheduled
+    pub deletion_requested_at: Option<DateTime<Utc>>,
+    /// When the account and all its data will be permanently deleted
+    pub deletion_scheduled_for: Option<DateTime<Utc>>,
+}
+
+/// Profile field whose changes are kept in the profile history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProfileHistoryField {
+    /// The skills list
+    Skills,
+    /// The target roles list
+    TargetRoles,
+}
+
+impl ProfileHistoryField {
+    /// Column name, as stored in `profile_history.field`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ProfileHistoryField::Skills => "skills",
+            ProfileHistoryField::TargetRoles => "target_roles",
+        }
+    }
+}
+
+/// Query parameters for listing profile history.
+#[derive(Debug, Deserialize)]
+pub struct ProfileHistoryQuery {
+    /// Only changes to this field
+    pub field: Option<ProfileHistoryField>,
+    /// Maximum number of entries (default 50, at most 200)
+    pub limit: Option<i64>,
+    /// Number of entries to skip
+    pub offset: Option<i64>,
+}
+
+/// Payload for rating one's own proficiency in a skill.
+#[derive(Debug, Deserialize, Validate)]
+pub struct SelfRatingPayload {
+    /// Skill being rated
+    #[validate(length(min = 1, max = 100, message = "Skill name must be 1-100 characters"))]
+    pub skill_name: String,
+    /// Proficiency level, 1 (beginner) to 10 (expert)
+    #[validate(range(min = 1, max = 10, message = "Proficiency level must be between 1 and 10"))]
+    pub proficiency_level: i32,
+}
+
+/// Current proficiency in a skill, from the latest self-rating and quiz.
+#[derive(Debug, Clone, Serialize)]
+pub struct SkillProficiency {
+    /// Skill name, as last assessed
+    pub skill_name: String,
+    /// Latest self-rating (1-10)
+    pub self_rating: Option<i32>,
+    /// Latest quiz result (1-10)
+    pub quiz_level: Option<i32>,
+    /// Level used for matching: the quiz result if there is one, otherwise the self-rating
+    pub proficiency_level: i32,
+    /// Whether the level comes from a quiz
+    pub verified: bool,
+    /// When the skill was last assessed
+    pub assessed_at: DateTime<Utc>,
+}
+
+/// Query parameters for listing skill assessments.
+#[derive(Debug, Deserialize)]
+pub struct SkillAssessmentQuery {
+    /// Only assessments of this skill (case-insensitive)
+    pub skill: Option<String>,
+}
+
+/// A skill with a quiz available.
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct QuizSummary {
+    /// Skill the quiz tests
+    pub skill_name: String,
+    /// Approved questions in the bank
+    pub question_count: i64,
+}
+
+/// A quiz question as shown to the quiz taker, without the answer.
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct QuizQuestionView {
+    /// Question identifier, used when answering
+    pub id: i32,
+    /// Question text
+    pub question: String,
+    /// Answer options
+    pub options: Vec<String>,
+}
+
+/// A started quiz attempt.
+#[derive(Debug, Serialize)]
+pub struct QuizAttemptStarted {
+    /// Attempt identifier, used to submit the answers
+    pub attempt_id: Uuid,
+    /// Skill the quiz tests
+    pub skill_name: String,
+    /// When the attempt started
+    pub started_at: DateTime<Utc>,
+    /// Answers submitted after this are rejected
+    pub expires_at: DateTime<Utc>,
+    /// Questions to answer
+    pub questions: Vec<QuizQuestionView>,
+}
+
+/// Answer to one quiz question.
+#[derive(Debug, Deserialize)]
+pub struct QuizAnswer {
+    /// Question being answered
+    pub question_id: i32,
+    /// Index of the chosen option
+    pub option: i32,
+}
+
+/// Payload for submitting a quiz attempt.
+#[derive(Debug, Deserialize)]
+pub struct SubmitQuizPayload {
+    /// One answer per question; unanswered questions count as wrong
+    pub answers: Vec<QuizAnswer>,
+}
+
+/// Grading of one quiz question.
+#[derive(Debug, Serialize)]
+pub struct QuizQuestionResult {
+    /// Question identifier
+    pub question_id: i32,
+    /// Option the user chose, `null` if unanswered
+    pub selected_option: Option<i32>,
+    /// The correct option
+    pub correct_option: i32,
+    /// Whether the answer was correct
+    pub correct: bool,
+    /// Why the answer is correct
+    pub explanation: Option<String>,
+}
+
+/// Graded quiz attempt.
+#[derive(Debug, Serialize)]
+pub struct QuizResult {
+    /// Attempt identifier
+    pub attempt_id: Uuid,
+    /// Skill the quiz tested
+    pub skill_name: String,
+    /// Correctly answered questions
+    pub correct_count: i32,
+    /// Questions in the quiz
+    pub total_questions: i32,
+    /// Proficiency level (1-10) recorded for the skill
+    pub proficiency_level: i32,
+    /// Per-question grading
+    pub results: Vec<QuizQuestionResult>,
+}
+
+/// Payload for generating quiz questions with AI.
+#[derive(Debug, Default, Deserialize, Validate)]
+pub struct GenerateQuizQuestionsPayload {
+    /// AI provider to use (defaults to Gemini)
+    pub provider: Option<AIProvider>,
+    /// Questions to generate (default 10)
+    #[validate(range(min = 1, max = 20, message = "Count must be between 1 and 20"))]
+    pub count: Option<u32>,
+}
+
+/// Review decision for a quiz question.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuizQuestionStatus {
+    /// Waiting for review
+    Pending,
+    /// Used in quizzes
+    Approved,
+    /// Never used
+    Rejected,
+}
+
+impl QuizQuestionStatus {
+    /// Value stored in `quiz_questions.status`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            QuizQuestionStatus::Pending => "pending",
+            QuizQuestionStatus::Approved => "approved",
+            QuizQuestionStatus::Rejected => "rejected",
+        }
+    }
+}
+
+/// Query parameters for listing quiz questions to review.
+#[derive(Debug, Deserialize)]
+pub struct QuizQuestionQuery {
+    /// Only questions with this status (default pending)
+    pub status: Option<QuizQuestionStatus>,
+    /// Only questions for this skill (case-insensitive)
+    pub skill: Option<String>,
+}
+
+/// Query parameters for listing background jobs.
+#[derive(Debug, Deserialize)]
+pub struct BackgroundJobQuery {
+    /// Only jobs with this status (default dead)
+    pub status: Option<JobStatus>,
+    /// Only jobs of this kind
+    pub kind: Option<String>,
+    /// Maximum number of jobs to return (default 50, max 200)
+    pub limit: Option<i64>,
+}
+
+/// Background jobs with the number of jobs in each status.
+#[derive(Debug, Serialize)]
+pub struct BackgroundJobList {
+    /// Jobs per status, e.g. `{"pending": 2, "dead": 1}`
+    pub counts: HashMap<String, i64>,
+    /// Matching jobs, most recently changed first
+    pub jobs: Vec<BackgroundJob>,
+}
+
+/// Payload for reviewing a quiz question, optionally correcting it first.
+#[derive(Debug, Deserialize)]
+pub struct ReviewQuizQuestionPayload {
+    /// New review status
+    pub status: QuizQuestionStatus,
+    /// Corrected question text
+    pub question: Option<String>,
+    /// Corrected answer options
+    pub options: Option<Vec<String>>,
+    /// Corrected index of the correct option
+    pub correct_option: Option<i32>,
+    /// Corrected explanation
+    pub explanation: Option<String>,
+}
+
+/// Query parameters for listing notifications.
+#[derive(Debug, Deserialize)]
+pub struct NotificationQuery {
+    /// Only unread notifications
+    #[serde(default)]
+    pub unread_only: bool,
+    /// Only notifications of this type
+    #[serde(rename = "type")]
+    pub notification_type: Option<NotificationType>,
+    /// Maximum number of notifications (default 20, at most 100)
+    pub limit: Option<i64>,
+    /// Number of notifications to skip
+    pub offset: Option<i64>,
+}
+
+/// A page of notifications.
+#[derive(Debug, Serialize)]
+pub struct NotificationPage {
+    /// Notifications, newest first
+    pub notifications: Vec<Notification>,
+    /// Notifications matching the filters, across all pages
+    pub total: i64,
+    /// Unread notifications of the user, regardless of filters
+    pub unread_count: i64,
+}
+
+/// Which notification types the user receives.
+#[derive(Debug, Serialize)]
+pub struct NotificationPreferences {
+    /// New jobs that match the profile well
+    pub job_match: bool,
+    /// Application status changes
+    pub application_status: bool,
+    /// Career roadmap milestones
+    pub roadmap_milestone: bool,
+    /// Reminders about stalled learning
+    pub stalled_progress: bool,
+    /// New jobs for saved searches
+    pub saved_search: bool,
+}
+
+/// Payload for updating notification preferences; omitted types are unchanged.
+#[derive(Debug, Default, Deserialize)]
+pub struct UpdateNotificationPreferencesPayload {
+    /// New jobs that match the profile well
+    pub job_match: Option<bool>,
+    /// Application status changes
+    pub application_status: Option<bool>,
+    /// Career roadmap milestones
+    pub roadmap_milestone: Option<bool>,
+    /// Reminders about stalled learning
+    pub stalled_progress: Option<bool>,
+    /// New jobs for saved searches
+    pub saved_search: Option<bool>,
+}
+
+/// Query parameters for the event stream.
+#[derive(Debug, Default, Deserialize)]
+pub struct EventStreamQuery {
+    /// JWT for clients that cannot set the `Authorization` header
+    /// (`EventSource`, browser WebSockets)
+    pub access_token: Option<String>,
+}
+
+/// Payload for saving a job search; updating replaces every field.
+#[derive(Debug, Deserialize, Validate)]
+pub struct SavedSearchPayload {
+    /// Name shown in alerts and digests
+    #[validate(length(min = 1, max = 100, message = "Name must be 1-100 characters"))]
+    pub name: String,
+    /// Words that must all appear in the title, company or description
+    #[validate(length(max = 200))]
+    pub keywords: Option<String>,
+    /// Text the location must contain
+    #[validate(length(max = 100))]
+    pub location: Option<String>,
+    /// Required experience level
+    pub experience_level: Option<ExperienceLevel>,
+    /// Required job type
+    pub job_type: Option<JobType>,
+    /// The job must require at least one of these skills
+    #[serde(default)]
+    #[validate(length(max = 20, message = "At most 20 skills"))]
+    pub skills: Vec<String>,
+    /// Minimum match score against the profile, 0-100 (default 0)
+    #[serde(default)]
+    #[validate(range(min = 0, max = 100, message = "Minimum match score must be between 0 and 100"))]
+    pub min_match_score: i32,
+    /// Search external listings too (default true)
+    #[serde(default = "default_true")]
+    pub include_external: bool,
+    /// Notify in-app about new matches (default true)
+    #[serde(default = "default_true")]
+    pub alerts_enabled: bool,
+    /// Email digest of new matches (default none)
+    #[serde(default)]
+    pub digest_frequency: DigestFrequency,
+}
+
+/// Query parameters for a saved search's matches.
+#[derive(Debug, Deserialize)]
+pub struct SavedSearchMatchQuery {
+    /// Maximum matches to return (default 50, at most 200)
+    pub limit: Option<i64>,
+}
@@ -0,0 +1,254 @@
+//! Unified job feed.
+//!
+//! Merges internal jobs and current external listings into one list of
+//! [`FeedJob`]s, so both can be filtered and scored alike. The same posting
+//! is listed once: an internal job, or the first listing, absorbs later ones
+//! with the same normalized title, company and location (see [`fuzzy_key`]),
+//! which are kept as other places it is listed.
+
+use crate::external_jobs::normalize::SalaryRange;
+use crate::external_jobs::{ExternalJobFilters, StoredExternalJob, fuzzy_key, search_external_jobs};
+use crate::models::{ExperienceLevel, Job, JobType};
+use serde::Serialize;
+use sqlx::PgPool;
+use std::collections::HashMap;
+
+/// Source name of internal jobs.
+pub const INTERNAL_SOURCE: &str = "internal";
+
+/// Source filter value matching every external source.
+pub const EXTERNAL_SOURCES: &str = "external";
+
+/// Most recent jobs of each source scored for the feed.
+const CANDIDATES_PER_SOURCE: i64 = 300;
+
+/// Another source listing a feed job.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ListedOn {
+    /// Source name
+    pub source: String,
+    /// Where to read and apply, for external listings
+    pub url: Option<String>,
+}
+
+/// A job in the feed: an internal job or an external listing.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FeedJob {
+    /// Unique key, `job:<id>` or `external:<source ID>`
+    pub key: String,
+    /// ID of an internal job
+    pub job_id: Option<i32>,
+    /// `internal` or the external source's name
+    pub source: String,
+    /// "ngo", "government" or "local", for external listings
+    pub category: Option<String>,
+    /// Job title
+    pub title: String,
+    /// Employer
+    pub company: String,
+    /// Location
+    pub location: String,
+    /// Description
+    pub description: String,
+    /// Where to read and apply, for external listings
+    pub url: Option<String>,
+    /// Required skills; for external listings, those given by the source and
+    /// those found in the description
+    pub skills: Vec<String>,
+    /// Job type, if known
+    pub job_type: Option<JobType>,
+    /// Required experience level, if known
+    pub experience_level: Option<ExperienceLevel>,
+    /// Salary range; amounts of internal jobs are in BDT
+    pub salary: SalaryRange,
+    /// Other sources listing the same job
+    pub also_listed_on: Vec<ListedOn>,
+}
+
+impl FeedJob {
+    /// Feed entry for an internal job.
+    pub fn internal(job: Job) -> Self {
+        FeedJob {
+            key: format!("job:{}", job.id),
+            job_id: Some(job.id),
+            source: INTERNAL_SOURCE.to_string(),
+            category: None,
+            title: job.job_title,
+            company: job.company,
+            location: job.location,
+            description: job.job_description,
+            url: None,
+            skills: job.required_skills,
+            job_type: Some(job.job_type),
+            experience_level: Some(job.experience_level),
+            salary: SalaryRange {
+                min: job.salary_min,
+                max: job.salary_max,
+                currency: (job.salary_min.is_some() || job.salary_max.is_some()).then(|| "BDT".to_string()),
+                period: None,
+            },
+            also_listed_on: Vec::new(),
+        }
+    }
+
+    /// Feed entry for an imported external listing, with its normalized
+    /// fields.
+    pub fn external(stored: StoredExternalJob) -> Self {
+        let skills = stored.skills();
+        let job = stored.listing;
+        FeedJob {
+            key: format!("external:{}", job.id),
+            job_id: None,
+            source: job.source,
+            category: Some(stored.category),
+            title: job.title,
+            company: job.company,
+            location: job.location,
+            description: job.description,
+            url: Some(job.url),
+            skills,
+            job_type: stored.normalized.job_type,
+            experience_level: stored.normalized.experience_level,
+            salary: stored.normalized.salary,
+            also_listed_on: Vec::new(),
+        }
+    }
+}
+
+/// Folds jobs listed more than once into their first listing, which records
+/// the others' sources; order is otherwise kept.
+///
+/// # Example
+///
+/// ```
+/// use backend::job_feed::{FeedJob, dedupe};
+///
+/// let job = |key: &str, source: &str, company: &str| FeedJob {
+///     key: key.to_string(),
+///     source: source.to_string(),
+///     title: "Data Analyst".to_string(),
+///     company: company.to_string(),
+///     location: "Dhaka, Bangladesh".to_string(),
+///     url: (source != "internal").then(|| format!("https://example.org/{}", key)),
+///     ..Default::default()
+/// };
+/// let feed = dedupe(vec![
+///     job("job:1", "internal", "Acme Ltd."),
+///     job("external:bd_9", "BDJobs", "Acme"),
+///     job("external:rw_4", "ReliefWeb", "UNICEF"),
+/// ]);
+///
+/// assert_eq!(feed.len(), 2);
+/// assert_eq!(feed[0].key, "job:1");
+/// assert_eq!(feed[0].also_listed_on[0].source, "BDJobs");
+/// assert_eq!(feed[0].also_listed_on[0].url.as_deref(), Some("https://example.org/external:bd_9"));
+/// assert!(feed[1].also_listed_on.is_empty());
+/// ```
+pub fn dedupe(jobs: Vec<FeedJob>) -> Vec<FeedJob> {
+    let mut first_by_key: HashMap<String, usize> = HashMap::new();
+    let mut feed: Vec<FeedJob> = Vec::with_capacity(jobs.len());
+
+    for job in jobs {
+        let key = fuzzy_key(&job.title, &job.company, &job.location);
+        match first_by_key.get(&key) {
+            Some(&first) => feed[first].also_listed_on.push(ListedOn { source: job.source, url: job.url }),
+            None => {
+                first_by_key.insert(key, feed.len());
+                feed.push(job);
+            }
+        }
+    }
+    feed
+}
+
+/// Filters of the feed; unset filters match every job.
+#[derive(Debug, Default)]
+pub struct FeedFilters<'a> {
+    /// Lowercase source names to include: `internal`, `external` for every
+    /// external source, or an external source's name; empty for all
+    pub sources: &'a [String],
+    /// Required experience level
+    pub experience_level: Option<ExperienceLevel>,
+    /// Required job type
+    pub job_type: Option<JobType>,
+    /// Text the location must contain (case-insensitive)
+    pub location: Option<&'a str>,
+}
+
+impl FeedFilters<'_> {
+    /// Whether jobs of `source` are included.
+    pub fn includes_source(&self, source: &str) -> bool {
+        let source = source.to_lowercase();
+        let external = source != INTERNAL_SOURCE;
+        self.sources.is_empty()
+            || self
+                .sources
+                .iter()
+                .any(|wanted| *wanted == source || (external && wanted == EXTERNAL_SOURCES))
+    }
+}
+
+/// Internal jobs, then current external listings, each most recent first,
+/// passing `filters` and deduplicated (see [`dedupe`]).
+///
+/// Filters are applied by the database, and only the 300 most recent jobs
+/// of each source are loaded, so the cost of a feed page does not grow with
+/// the number of imported listings.
+///
+/// # Errors
+///
+/// Returns an error if a database operation fails.
+pub async fn load_feed(pool: &PgPool, filters: &FeedFilters<'_>) -> Result<Vec<FeedJob>, sqlx::Error> {
+    let mut jobs: Vec<FeedJob> = Vec::new();
+    let location = filters
+        .location
+        .map(|location| location.trim().to_lowercase())
+        .filter(|location| !location.is_empty());
+
+    if filters.includes_source(INTERNAL_SOURCE) {
+        let internal = sqlx::query_as!(
+            Job,
+            r#"
+            SELECT id, job_title, company, location, job_description, required_skills,
+                   experience_level as "experience_level: ExperienceLevel",
+                   experience_years_min, experience_years_max,
+                   job_type as "job_type: JobType",
+                   salary_min, salary_max, responsibilities, requirements, benefits
+            FROM jobs
+            WHERE ($1::experience_level IS NULL OR experience_level = $1)
+              AND ($2::job_type IS NULL OR job_type = $2)
+              AND ($3::TEXT IS NULL OR STRPOS(LOWER(location), $3) > 0)
+            ORDER BY id DESC
+            LIMIT $4
+            "#,
+            filters.experience_level as _,
+            filters.job_type as _,
+            location.as_deref(),
+            CANDIDATES_PER_SOURCE
+        )
+        .fetch_all(pool)
+        .await?;
+        jobs.extend(internal.into_iter().map(FeedJob::internal));
+    }
+
+    if filters.sources.is_empty() || filters.sources.iter().any(|source| source != INTERNAL_SOURCE) {
+        // Every external source, or only those named
+        let sources: Vec<String> = if filters.sources.iter().any(|source| source == EXTERNAL_SOURCES) {
+            Vec::new()
+        } else {
+            filters.sources.iter().filter(|source| *source != INTERNAL_SOURCE).cloned().collect()
+        };
+        let external_filters = ExternalJobFilters {
+            job_type: filters.job_type,
+            experience_level: filters.experience_level,
+            location: location.as_deref(),
+            sources: &sources,
+            per_source_limit: Some(CANDIDATES_PER_SOURCE),
+            ..Default::default()
+        };
+        let external = search_external_jobs(pool, &external_filters).await?;
+        jobs.extend(external.into_iter().map(FeedJob::external));
+    }
+
+    Ok(dedupe(jobs))
+}
@@ -33,7 +33,16 @@
 //!         .await
 //!         .expect("Failed to connect to database");
 //!     
-//!     let app_state = AppState { db_pool };
+//!     let app_state = AppState {
+//!         db_pool,
+//!         ai_service: None,
+//!         cv_extractor: std::sync::Arc::new(backend::cv::sandbox::CvExtractor::new(
+//!             backend::cv::sandbox::ExtractionLimits::default(),
+//!             None,
+//!         )),
+//!         cv_storage: std::sync::Arc::new(backend::cv::storage::LocalFsStorage::new("./data/cv")),
+//!         events: backend::events::EventBus::new(),
+//!     };
 //!     let app = backend::handlers::create_router(app_state);
 //!     
 //!     // Server setup...
@@ -62,6 +71,7 @@
 //! - `GET /api/profile` - Get user profile
 //! - `PUT /api/profile` - Update user profile
 //! - `GET /api/jobs/recommendations` - Get job recommendations
+//! - `GET /api/jobs/feed` - Get internal and external jobs ranked together
 //! - `GET /api/learning/recommendations` - Get learning resource recommendations
 //! - `GET /api/skill-gap/:target_role` - Analyze skill gaps for a role
 //! - `POST /api/applications` - Create job application
@@ -80,6 +90,7 @@
 
 use sqlx::PgPool;
 
+pub mod account;
 pub mod ai;
 pub mod errors;
 pub mod handlers;
@@ -87,6 +98,16 @@ pub mod models;
 pub mod security;
 pub mod auth;
 pub mod ai_matching;
+pub mod cv;
+pub mod completeness;
+pub mod events;
+pub mod external_jobs;
+pub mod job_feed;
+pub mod mailer;
+pub mod notifications;
+pub mod queue;
+pub mod quiz;
+pub mod saved_searches;
 
 /// Application state shared across all request handlers.
 /// 
@@ -97,4 +118,10 @@ pub struct AppState {
     pub db_pool: PgPool,
     /// AI service for intelligent features (optional)
     pub ai_service: Option<std::sync::Arc<ai::AIService>>,
+    /// Resource-limited CV text extraction (with OCR for images when available)
+    pub cv_extractor: std::sync::Arc<cv::sandbox::CvExtractor>,
+    /// Storage for original CV files
+    pub cv_storage: std::sync::Arc<dyn cv::storage::DocumentStorage>,
+    /// Real-time events for connected clients, fed by Postgres `LISTEN`
+    pub events: events::EventBus,
 }
@@ -0,0 +1,206 @@
+//! Outgoing email.
+//!
+//! The mailer is chosen per deployment:
+//!
+//! - `MAIL_BACKEND=none` (default): no email is sent
+//! - `MAIL_BACKEND=file`: each email is written as an `.eml` file under
+//!   `MAIL_FILE_DIR` (default `./data/mail`), for development and tests
+//! - `MAIL_BACKEND=smtp`: sent through `SMTP_HOST` (`SMTP_PORT`, default 587,
+//!   with STARTTLS) using `SMTP_USERNAME` and `SMTP_PASSWORD`
+//!
+//! `MAIL_FROM` sets the sender (default `CareerBridge <no-reply@careerbridge.local>`).
+
+use async_trait::async_trait;
+use lettre::message::{Mailbox, header::ContentType};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use std::path::PathBuf;
+use std::sync::Arc;
+use thiserror::Error;
+
+/// Default sender address.
+const DEFAULT_FROM: &str = "CareerBridge <no-reply@careerbridge.local>";
+
+/// Errors raised by a mailer.
+#[derive(Debug, Error)]
+pub enum MailError {
+    /// An address or the message could not be built
+    #[error("Invalid email: {0}")]
+    InvalidMessage(String),
+    /// Writing to the file sink failed
+    #[error("Mail I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    /// The SMTP server rejected or failed the delivery
+    #[error("SMTP error: {0}")]
+    Transport(String),
+    /// Missing or invalid configuration
+    #[error("Mail configuration error: {0}")]
+    Config(String),
+}
+
+/// A plain-text email.
+#[derive(Debug, Clone)]
+pub struct Email {
+    /// Recipient address
+    pub to: String,
+    /// Subject line
+    pub subject: String,
+    /// Plain-text body
+    pub body: String,
+}
+
+/// Delivers emails.
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    /// Backend name for logs ("file", "smtp")
+    fn backend(&self) -> &'static str;
+
+    /// Sends `email`.
+    async fn send(&self, email: &Email) -> Result<(), MailError>;
+}
+
+/// Builds the mailer configured through environment variables, or `None`
+/// when email is turned off.
+///
+/// # Errors
+///
+/// Returns an error if the backend is unknown or incompletely configured.
+pub fn mailer_from_env() -> Result<Option<Arc<dyn Mailer>>, MailError> {
+    let from = parse_mailbox(&std::env::var("MAIL_FROM").unwrap_or_else(|_| DEFAULT_FROM.to_string()))?;
+
+    match std::env::var("MAIL_BACKEND").unwrap_or_else(|_| "none".to_string()).as_str() {
+        "none" => Ok(None),
+        "file" => {
+            let dir = std::env::var("MAIL_FILE_DIR").unwrap_or_else(|_| "./data/mail".to_string());
+            Ok(Some(Arc::new(FileMailer::new(dir, from))))
+        }
+        "smtp" => Ok(Some(Arc::new(SmtpMailer::from_env(from)?))),
+        other => Err(MailError::Config(format!(
+            "unknown MAIL_BACKEND '{}', expected 'none', 'file' or 'smtp'",
+            other
+        ))),
+    }
+}
+
+fn parse_mailbox(address: &str) -> Result<Mailbox, MailError> {
+    address
+        .parse()
+        .map_err(|e| MailError::InvalidMessage(format!("{}: {}", address, e)))
+}
+
+fn build_message(from: &Mailbox, email: &Email) -> Result<Message, MailError> {
+    Message::builder()
+        .from(from.clone())
+        .to(parse_mailbox(&email.to)?)
+        .subject(&email.subject)
+        .header(ContentType::TEXT_PLAIN)
+        .body(email.body.clone())
+        .map_err(|e| MailError::InvalidMessage(e.to_string()))
+}
+
+/// Writes emails as `.eml` files instead of sending them.
+///
+/// # Example
+///
+/// ```
+/// use backend::mailer::{Email, FileMailer, Mailer};
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let dir = tempfile::tempdir().unwrap();
+/// let mailer = FileMailer::new(dir.path(), "CareerBridge <no-reply@careerbridge.local>".parse().unwrap());
+/// let email = Email {
+///     to: "rahim@example.com".to_string(),
+///     subject: "2 new jobs for your saved searches".to_string(),
+///     body: "Hi Rahim,\n\n2 new jobs matched your saved searches.\n".to_string(),
+/// };
+/// mailer.send(&email).await.unwrap();
+///
+/// let files: Vec<_> = std::fs::read_dir(dir.path()).unwrap().map(|entry| entry.unwrap().path()).collect();
+/// assert_eq!(files.len(), 1);
+/// assert_eq!(files[0].extension().unwrap(), "eml");
+/// let eml = std::fs::read_to_string(&files[0]).unwrap();
+/// assert!(eml.contains("To: rahim@example.com"));
+/// assert!(eml.contains("Subject: 2 new jobs for your saved searches"));
+/// assert!(eml.contains("2 new jobs matched your saved searches."));
+/// # });
+/// ```
+#[derive(Debug, Clone)]
+pub struct FileMailer {
+    dir: PathBuf,
+    from: Mailbox,
+}
+
+impl FileMailer {
+    /// Creates a sink writing to `dir`; the directory is created on first write.
+    pub fn new(dir: impl Into<PathBuf>, from: Mailbox) -> Self {
+        Self { dir: dir.into(), from }
+    }
+}
+
+#[async_trait]
+impl Mailer for FileMailer {
+    fn backend(&self) -> &'static str {
+        "file"
+    }
+
+    async fn send(&self, email: &Email) -> Result<(), MailError> {
+        let message = build_message(&self.from, email)?;
+        tokio::fs::create_dir_all(&self.dir).await?;
+        let name = format!(
+            "{}-{}.eml",
+            chrono::Utc::now().format("%Y%m%dT%H%M%S%.3fZ"),
+            uuid::Uuid::new_v4()
+        );
+        tokio::fs::write(self.dir.join(name), message.formatted()).await?;
+        Ok(())
+    }
+}
+
+/// Sends emails through an SMTP server.
+pub struct SmtpMailer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpMailer {
+    /// Configures the transport from `SMTP_HOST`, `SMTP_PORT`,
+    /// `SMTP_USERNAME` and `SMTP_PASSWORD`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `SMTP_HOST` is missing or the port is invalid.
+    pub fn from_env(from: Mailbox) -> Result<Self, MailError> {
+        let host = std::env::var("SMTP_HOST").map_err(|_| MailError::Config("SMTP_HOST must be set".to_string()))?;
+        let port = match std::env::var("SMTP_PORT") {
+            Ok(port) => port
+                .parse::<u16>()
+                .map_err(|_| MailError::Config(format!("invalid SMTP_PORT '{}'", port)))?,
+            Err(_) => 587,
+        };
+
+        let mut builder = AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&host)
+            .map_err(|e| MailError::Config(e.to_string()))?
+            .port(port);
+        if let (Ok(username), Ok(password)) = (std::env::var("SMTP_USERNAME"), std::env::var("SMTP_PASSWORD")) {
+            builder = builder.credentials(Credentials::new(username, password));
+        }
+
+        Ok(Self { transport: builder.build(), from })
+    }
+}
+
+#[async_trait]
+impl Mailer for SmtpMailer {
+    fn backend(&self) -> &'static str {
+        "smtp"
+    }
+
+    async fn send(&self, email: &Email) -> Result<(), MailError> {
+        let message = build_message(&self.from, email)?;
+        self.transport
+            .send(message)
+            .await
+            .map_err(|e| MailError::Transport(e.to_string()))?;
+        Ok(())
+    }
+}
@@ -13,9 +13,18 @@ use backend::{AppState, handlers};
 
 /// Main application entry point.
 /// 
+/// Runs as a CV extraction worker when started with the worker argument
+/// (see `backend::cv::sandbox`); otherwise starts the API server.
+fn main() {
+    if std::env::args().nth(1).as_deref() == Some(backend::cv::sandbox::WORKER_ARG) {
+        std::process::exit(backend::cv::sandbox::run_worker());
+    }
+    serve();
+}
+
 /// Initializes the database connection, sets up tracing, and starts the HTTP server.
 #[tokio::main]
-async fn main() {
+async fn serve() {
     // Load environment variables
     dotenv().ok();
     
@@ -69,10 +78,123 @@ async fn main() {
         None
     };
     
+    // Initialize OCR for image CV uploads
+    let ocr_engine: Option<std::sync::Arc<dyn backend::cv::extract::OcrEngine>> =
+        match backend::cv::extract::TesseractOcr::from_env() {
+            Some(engine) => {
+                info!("✓ OCR engine available (tesseract) - image CV uploads enabled");
+                Some(std::sync::Arc::new(engine))
+            }
+            None => {
+                info!("⚠ OCR engine not found - image CV uploads disabled");
+                info!("  Install tesseract or set TESSERACT_CMD to enable them");
+                None
+            }
+        };
+    
+    // Initialize storage for original CV files
+    let cv_storage = backend::cv::storage::storage_from_env()
+        .expect("Invalid CV storage configuration");
+    info!("✓ CV storage initialized ({})", cv_storage.backend());
+    
+    // Limits for CV uploads; extraction runs isolated from the server
+    let extraction_limits = backend::cv::sandbox::ExtractionLimits::from_env();
+    info!(
+        "✓ CV extraction: {:?} isolation, {}MB max upload, {}s timeout, {}MB memory ceiling",
+        extraction_limits.isolation,
+        extraction_limits.max_file_bytes / (1024 * 1024),
+        extraction_limits.timeout.as_secs(),
+        extraction_limits.memory_limit_bytes / (1024 * 1024)
+    );
+    if extraction_limits.isolation == backend::cv::sandbox::Isolation::Thread {
+        info!("⚠ Thread isolation does not enforce the memory ceiling");
+    }
+    let cv_extractor = std::sync::Arc::new(backend::cv::sandbox::CvExtractor::new(
+        extraction_limits,
+        ocr_engine,
+    ));
+    
+    // Email, used for saved search digests
+    let mailer = match backend::mailer::mailer_from_env() {
+        Ok(mailer) => mailer,
+        Err(e) => {
+            error!("Email disabled: {}", e);
+            None
+        }
+    };
+    match &mailer {
+        Some(mailer) => info!("✓ Email enabled ({})", mailer.backend()),
+        None => info!("⚠ Email not configured - saved search digests disabled (set MAIL_BACKEND)"),
+    }
+    
+    // External job sources, imported by a background job
+    let job_sources = match backend::external_jobs::sources::sources_from_env() {
+        Ok(sources) => {
+            if let Err(e) = backend::external_jobs::register_sources(&db_pool, &sources).await {
+                error!("Failed to record external job sources: {}", e);
+            }
+            sources
+        }
+        Err(e) => {
+            error!("External job import disabled: {}", e);
+            Vec::new()
+        }
+    };
+    let source_names: Vec<&str> = job_sources.iter().map(|source| source.name()).collect();
+    info!("✓ {} external job source(s) enabled: {}", job_sources.len(), source_names.join(", "));
+    if backend::external_jobs::sources::demo_mode() {
+        info!("⚠ External jobs demo mode: sample listings are imported");
+    }
+    
+    // Background jobs: account purge, external job import, notification checks, saved searches and digests
+    let worker_config = backend::queue::WorkerConfig::from_env();
+    if worker_config.workers > 0 {
+        let schedules = backend::queue::default_schedules(mailer.is_some());
+        let schedule_summary: Vec<String> = schedules
+            .iter()
+            .map(|scheduled| format!("{} ({})", scheduled.name, scheduled.schedule.describe()))
+            .collect();
+        backend::queue::spawn_scheduler(db_pool.clone(), schedules, worker_config.lease);
+        backend::queue::spawn_workers(
+            backend::queue::JobContext {
+                pool: db_pool.clone(),
+                storage: cv_storage.clone(),
+                mailer,
+                job_sources,
+                ai_service: ai_service.clone(),
+            },
+            &worker_config,
+        );
+        info!(
+            "✓ {} background worker(s) started ({} day account deletion grace period)",
+            worker_config.workers,
+            backend::account::deletion_grace_period().num_days()
+        );
+        for schedule in schedule_summary {
+            info!("  - {}", schedule);
+        }
+    } else {
+        info!("⚠ Background workers disabled (JOB_WORKERS=0) - another instance must run them");
+    }
+    
+    // Load quiz question banks; AI-generated questions are added through review
+    let quiz_bank_dir = backend::quiz::question_bank_dir();
+    if let Err(e) = backend::quiz::load_question_banks(&db_pool, &quiz_bank_dir).await {
+        error!("Failed to load quiz question banks: {}", e);
+    }
+    
+    // Deliver events published by any instance to this instance's clients
+    let events = backend::events::EventBus::new();
+    events.listen(db_pool.clone());
+    info!("✓ Real-time events enabled (channel {})", backend::events::EVENTS_CHANNEL);
+    
     // Create application state
     let app_state = AppState { 
         db_pool,
         ai_service,
+        cv_extractor,
+        cv_storage,
+        events,
     };
 
     // Create router
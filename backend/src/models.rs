@@ -6,7 +6,7 @@
 use serde::{Deserialize, Deserializer, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 
 /// Custom deserializer for case-insensitive enum matching
 fn deserialize_case_insensitive<'de, D, T>(deserializer: D) -> Result<T, D::Error>
@@ -21,8 +21,10 @@ where
         .map_err(serde::de::Error::custom)
 }
 
-/// Experience level of a user or required for a job.
-#[derive(Debug, Serialize, sqlx::Type)]
+/// Experience level of a user or required for a job, ordered from
+/// [`Fresher`](ExperienceLevel::Fresher) to
+/// [`Manager`](ExperienceLevel::Manager).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, sqlx::Type)]
 #[sqlx(type_name = "experience_level")]
 #[sqlx(rename_all = "lowercase")]
 #[serde(rename_all = "lowercase")]
@@ -33,6 +35,57 @@ pub enum ExperienceLevel {
     Junior,
     /// 3-5 years of experience
     Mid,
+    /// 5-8 years of experience
+    Senior,
+    /// 8+ years of experience, leading a team's technical work
+    Lead,
+    /// Manages people, projects or programs
+    Manager,
+}
+
+impl ExperienceLevel {
+    /// Value of the `experience_level` type.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ExperienceLevel::Fresher => "fresher",
+            ExperienceLevel::Junior => "junior",
+            ExperienceLevel::Mid => "mid",
+            ExperienceLevel::Senior => "senior",
+            ExperienceLevel::Lead => "lead",
+            ExperienceLevel::Manager => "manager",
+        }
+    }
+
+    /// Position in the order of levels, from 0 for
+    /// [`Fresher`](ExperienceLevel::Fresher).
+    pub fn rank(self) -> i32 {
+        self as i32
+    }
+
+    /// Level matching years of experience; management is not a matter of
+    /// years, so never [`Manager`](ExperienceLevel::Manager).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use backend::models::ExperienceLevel;
+    ///
+    /// assert_eq!(ExperienceLevel::from_years(0), ExperienceLevel::Fresher);
+    /// assert_eq!(ExperienceLevel::from_years(2), ExperienceLevel::Junior);
+    /// assert_eq!(ExperienceLevel::from_years(4), ExperienceLevel::Mid);
+    /// assert_eq!(ExperienceLevel::from_years(6), ExperienceLevel::Senior);
+    /// assert_eq!(ExperienceLevel::from_years(12), ExperienceLevel::Lead);
+    /// assert!(ExperienceLevel::Senior > ExperienceLevel::Mid);
+    /// ```
+    pub fn from_years(years: i32) -> Self {
+        match years {
+            ..=0 => ExperienceLevel::Fresher,
+            1..=2 => ExperienceLevel::Junior,
+            3..=4 => ExperienceLevel::Mid,
+            5..=7 => ExperienceLevel::Senior,
+            _ => ExperienceLevel::Lead,
+        }
+    }
 }
 
 impl std::str::FromStr for ExperienceLevel {
@@ -43,6 +96,9 @@ impl std::str::FromStr for ExperienceLevel {
             "fresher" => Ok(ExperienceLevel::Fresher),
             "junior" => Ok(ExperienceLevel::Junior),
             "mid" => Ok(ExperienceLevel::Mid),
+            "senior" => Ok(ExperienceLevel::Senior),
+            "lead" => Ok(ExperienceLevel::Lead),
+            "manager" => Ok(ExperienceLevel::Manager),
             _ => Err(format!("Unknown experience level: {}", s)),
         }
     }
@@ -58,7 +114,7 @@ impl<'de> Deserialize<'de> for ExperienceLevel {
 }
 
 /// Career track or specialization path.
-#[derive(Debug, Serialize, sqlx::Type)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, sqlx::Type)]
 #[sqlx(type_name = "career_track")]
 #[sqlx(rename_all = "snake_case")]
 #[serde(rename_all = "snake_case")]
@@ -77,7 +133,7 @@ impl std::str::FromStr for CareerTrack {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().replace('-', "_").replace(' ', "_").as_str() {
+        match s.to_lowercase().replace(['-', ' '], "_").as_str() {
             "web_development" | "webdevelopment" => Ok(CareerTrack::WebDevelopment),
             "data" => Ok(CareerTrack::Data),
             "design" => Ok(CareerTrack::Design),
@@ -97,7 +153,7 @@ impl<'de> Deserialize<'de> for CareerTrack {
 }
 
 /// Type of job or employment arrangement.
-#[derive(Debug, Serialize, sqlx::Type)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, sqlx::Type)]
 #[sqlx(type_name = "job_type")]
 #[sqlx(rename_all = "snake_case")]
 #[serde(rename_all = "snake_case")]
@@ -112,11 +168,23 @@ pub enum JobType {
     Freelance,
 }
 
+impl JobType {
+    /// Value of the `job_type` type.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            JobType::Internship => "internship",
+            JobType::PartTime => "part_time",
+            JobType::FullTime => "full_time",
+            JobType::Freelance => "freelance",
+        }
+    }
+}
+
 impl std::str::FromStr for JobType {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().replace('-', "_").replace(' ', "_").as_str() {
+        match s.to_lowercase().replace(['-', ' '], "_").as_str() {
             "internship" => Ok(JobType::Internship),
             "part_time" | "parttime" => Ok(JobType::PartTime),
             "full_time" | "fulltime" => Ok(JobType::FullTime),
@@ -182,6 +250,11 @@ pub struct User {
     /// Current experience level (nullable until profile completion)
     #[sqlx(rename = "experience_level")]
     pub experience_level: Option<ExperienceLevel>,
+    /// Fewest years of professional experience
+    pub experience_years_min: Option<i32>,
+    /// Most years of professional experience; equal to the minimum for an
+    /// exact figure
+    pub experience_years_max: Option<i32>,
     /// Preferred career track (nullable until profile completion)
     #[sqlx(rename = "preferred_track")]
     pub preferred_track: Option<CareerTrack>,
@@ -223,6 +296,10 @@ pub struct Job {
     /// Required experience level
     #[sqlx(rename = "experience_level")]
     pub experience_level: ExperienceLevel,
+    /// Fewest years of experience required (optional)
+    pub experience_years_min: Option<i32>,
+    /// Most years of experience sought (optional)
+    pub experience_years_max: Option<i32>,
     /// Type of employment
     #[sqlx(rename = "job_type")]
     pub job_type: JobType,
@@ -293,8 +370,7 @@ pub struct UserProgress {
     pub completed_at: Option<DateTime<Utc>>,
 }
 
-/// User notification.
-#[allow(dead_code)]
+/// User notification, created through [`crate::notifications::notify`].
 #[derive(Debug, FromRow, Serialize, Deserialize)]
 pub struct Notification {
     /// Unique notification identifier
@@ -305,17 +381,120 @@ pub struct Notification {
     pub title: String,
     /// Notification message content
     pub message: String,
-    /// Type of notification (e.g., "job_match", "resource_recommendation")
+    /// Type of notification: "job_match", "application_status", "roadmap_milestone",
+    /// "stalled_progress" or "saved_search"
     #[sqlx(rename = "type")]
     pub notification_type: String,
     /// Whether the notification has been read
     pub is_read: bool,
+    /// API path of the related resource
+    pub link: Option<String>,
     /// When the notification was created
     pub created_at: DateTime<Utc>,
 }
 
-/// Skill assessment record.
-#[allow(dead_code)]
+/// Saved job search with its alert and digest settings.
+#[derive(Debug, FromRow, Serialize)]
+pub struct SavedSearch {
+    /// Unique saved search identifier
+    pub id: i32,
+    /// User who saved the search
+    pub user_id: Uuid,
+    /// Name shown in alerts and digests
+    pub name: String,
+    /// Words that must all appear in the title, company or description
+    pub keywords: Option<String>,
+    /// Text the location must contain
+    pub location: Option<String>,
+    /// Required experience level
+    pub experience_level: Option<ExperienceLevel>,
+    /// Required job type
+    pub job_type: Option<JobType>,
+    /// The job must require at least one of these skills
+    pub skills: Vec<String>,
+    /// Minimum match score (0-100) against the user's profile
+    pub min_match_score: i32,
+    /// Whether external listings are searched too
+    pub include_external: bool,
+    /// Whether new matches create in-app notifications
+    pub alerts_enabled: bool,
+    /// Email digest: "none", "daily" or "weekly"
+    pub digest_frequency: String,
+    /// Jobs added after this time are checked next
+    pub last_checked_at: DateTime<Utc>,
+    /// When the last digest was due
+    pub last_digest_at: Option<DateTime<Utc>>,
+    /// When the search was saved
+    pub created_at: DateTime<Utc>,
+    /// When the search was last changed
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Job found by a saved search.
+#[derive(Debug, FromRow, Serialize)]
+pub struct SavedSearchMatch {
+    /// Unique match identifier
+    pub id: i32,
+    /// Saved search that found the job
+    pub saved_search_id: i32,
+    /// `job:<id>` for internal jobs, `external:<id>` for external listings
+    pub job_key: String,
+    /// Internal job ID
+    pub job_id: Option<i32>,
+    /// Job title
+    pub title: String,
+    /// Employer
+    pub company: String,
+    /// Job location
+    pub location: Option<String>,
+    /// Where to apply, for external listings
+    pub url: Option<String>,
+    /// "internal" or the external source's name
+    pub source: String,
+    /// Match score (0-100) when the job was found
+    pub match_score: f64,
+    /// When the job was found
+    pub matched_at: DateTime<Utc>,
+    /// When the job was included in an email digest
+    pub emailed_at: Option<DateTime<Utc>>,
+}
+
+/// Job in the background queue.
+#[derive(Debug, FromRow, Serialize)]
+pub struct BackgroundJob {
+    /// Unique job identifier
+    pub id: i64,
+    /// Task kind, e.g. "check_saved_searches"
+    pub kind: String,
+    /// Task with its payload
+    pub payload: serde_json::Value,
+    /// "pending", "running", "succeeded" or "dead"
+    pub status: String,
+    /// Attempts made so far
+    pub attempts: i32,
+    /// Attempts before the job is marked dead
+    pub max_attempts: i32,
+    /// Earliest time of the next attempt
+    pub run_at: DateTime<Utc>,
+    /// When a worker claimed the job
+    pub locked_at: Option<DateTime<Utc>>,
+    /// Worker running the job
+    pub locked_by: Option<String>,
+    /// Error of the last failed attempt
+    pub last_error: Option<String>,
+    /// Key allowing one pending or running job, e.g. `schedule:<name>`
+    pub dedup_key: Option<String>,
+    /// When the job was enqueued
+    pub created_at: DateTime<Utc>,
+    /// When the job last changed
+    pub updated_at: DateTime<Utc>,
+    /// When the job succeeded or died
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// Skill assessment record: a self-rating or a graded quiz.
+///
+/// Assessments are kept over time; the latest one of each kind counts.
 #[derive(Debug, FromRow, Serialize, Deserialize)]
 pub struct SkillAssessment {
     /// Unique assessment identifier
@@ -324,12 +503,279 @@ pub struct SkillAssessment {
     pub user_id: Uuid,
     /// Name of the skill assessed
     pub skill_name: String,
-    /// Proficiency level (typically 1-5)
+    /// Proficiency level (1-10)
     pub proficiency_level: i32,
+    /// How the level was obtained: 'self' or 'quiz'
+    pub source: String,
+    /// Quiz attempt that produced the level, for quiz results
+    pub quiz_attempt_id: Option<Uuid>,
     /// When the assessment was performed
     pub assessed_at: DateTime<Utc>,
 }
 
+/// Multiple-choice quiz question, including its answer.
+///
+/// Only returned to administrators; quiz takers get the question and options.
+#[derive(Debug, FromRow, Serialize, Deserialize)]
+pub struct QuizQuestion {
+    /// Unique question identifier
+    pub id: i32,
+    /// Skill the question tests
+    pub skill_name: String,
+    /// Question text
+    pub question: String,
+    /// Answer options
+    pub options: Vec<String>,
+    /// Index of the correct option
+    pub correct_option: i32,
+    /// Why the answer is correct, shown after grading
+    pub explanation: Option<String>,
+    /// Where the question came from: 'file' or 'ai'
+    pub source: String,
+    /// Review status: 'pending', 'approved' or 'rejected'
+    pub status: String,
+    /// When an administrator reviewed the question
+    pub reviewed_at: Option<DateTime<Utc>>,
+    /// When the question was added
+    pub created_at: DateTime<Utc>,
+}
+
+/// Structured CV data for a user, produced by the CV parser.
+#[derive(Debug, FromRow, Serialize, Deserialize)]
+pub struct CvParse {
+    /// Owner of the CV
+    pub user_id: Uuid,
+    /// Name found in the CV header
+    pub full_name: Option<String>,
+    /// Email found in the CV
+    pub email: Option<String>,
+    /// Phone number found in the CV
+    pub phone: Option<String>,
+    /// Address or city found in the CV
+    pub location: Option<String>,
+    /// Profile and portfolio links
+    pub links: Vec<String>,
+    /// Summary / objective paragraph
+    pub summary: Option<String>,
+    /// Skills listed in the CV
+    pub skills: Vec<String>,
+    /// When the user last reviewed and saved the parse
+    pub reviewed_at: Option<DateTime<Utc>>,
+    /// When AI enrichment last ran
+    pub enriched_at: Option<DateTime<Utc>>,
+    /// CV version the parse was produced from
+    pub cv_version_id: Option<i32>,
+    /// When the parse was created
+    pub created_at: Option<DateTime<Utc>>,
+    /// When the parse was last updated
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Education, experience, project or certification entry of a parsed CV.
+#[derive(Debug, FromRow, Serialize, Deserialize)]
+pub struct CvParseEntry {
+    /// Unique entry identifier
+    pub id: i32,
+    /// Owner of the CV
+    pub user_id: Uuid,
+    /// One of "education", "experience", "project", "certification"
+    pub section: String,
+    /// Order within the section
+    pub position: i32,
+    /// Degree, job title, project or certificate name
+    pub title: String,
+    /// Institution, employer or issuer
+    pub organization: Option<String>,
+    /// Location of the entry
+    pub location: Option<String>,
+    /// Start month
+    pub start_date: Option<NaiveDate>,
+    /// End month
+    pub end_date: Option<NaiveDate>,
+    /// Whether the entry is ongoing
+    pub is_current: bool,
+    /// Bullet points and details
+    pub description: Option<String>,
+    /// When the entry was created
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+/// Uploaded CV file. Each upload is kept as a numbered version.
+#[derive(Debug, FromRow, Serialize, Deserialize)]
+pub struct CvVersion {
+    /// Unique version identifier
+    pub id: i32,
+    /// Owner of the CV
+    pub user_id: Uuid,
+    /// Sequential number per user, starting at 1
+    pub version_number: i32,
+    /// File name given at upload
+    pub file_name: String,
+    /// MIME type of the stored file
+    pub content_type: String,
+    /// Detected format (pdf, docx, ...)
+    pub format: String,
+    /// File size in bytes
+    pub size_bytes: i64,
+    /// Hex SHA-256 of the file contents
+    pub sha256: String,
+    /// Storage backend holding the file ("local", "s3")
+    #[serde(skip_serializing)]
+    pub storage_backend: String,
+    /// Key of the file within the storage backend
+    #[serde(skip_serializing)]
+    pub storage_key: String,
+    /// Text extracted from the file
+    #[serde(skip_serializing)]
+    pub extracted_text: String,
+    /// Skills found in this version
+    pub skills: Vec<String>,
+    /// Whether this version feeds the profile
+    pub is_active: bool,
+    /// When the version was uploaded
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+/// Education history entry of a user.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct Education {
+    /// Unique entry identifier
+    pub id: i32,
+    /// Owner of the entry
+    pub user_id: Uuid,
+    /// School, college or university
+    pub institution: String,
+    /// Degree or qualification, e.g. "BSc"
+    pub degree: Option<String>,
+    /// Field of study, e.g. "Computer Science"
+    pub field_of_study: Option<String>,
+    /// First day of study
+    pub start_date: Option<NaiveDate>,
+    /// Graduation date
+    pub end_date: Option<NaiveDate>,
+    /// Whether the user is still studying here
+    pub is_current: bool,
+    /// Grade, GPA or class, as written by the user
+    pub grade: Option<String>,
+    /// When the entry was created
+    pub created_at: Option<DateTime<Utc>>,
+    /// When the entry was last updated
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Job a user has held.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct WorkExperience {
+    /// Unique entry identifier
+    pub id: i32,
+    /// Owner of the entry
+    pub user_id: Uuid,
+    /// Company or organization
+    pub employer: String,
+    /// Job title
+    pub title: String,
+    /// Where the job was based
+    pub location: Option<String>,
+    /// First day in the role
+    pub start_date: Option<NaiveDate>,
+    /// Last day in the role
+    pub end_date: Option<NaiveDate>,
+    /// Whether this is the user's current job
+    pub is_current: bool,
+    /// Achievements and responsibilities, one per bullet point
+    pub bullet_points: Vec<String>,
+    /// Skills used in the role
+    pub skills_used: Vec<String>,
+    /// When the entry was created
+    pub created_at: Option<DateTime<Utc>>,
+    /// When the entry was last updated
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Project a user has built.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct Project {
+    /// Unique project identifier
+    pub id: i32,
+    /// Owner of the project
+    pub user_id: Uuid,
+    /// Project name
+    pub title: String,
+    /// What the project does and the user's part in it
+    pub description: Option<String>,
+    /// Link to the repository or live project
+    pub url: Option<String>,
+    /// Languages, frameworks and tools used
+    pub tech_stack: Vec<String>,
+    /// When work on the project started
+    pub start_date: Option<NaiveDate>,
+    /// When work on the project ended
+    pub end_date: Option<NaiveDate>,
+    /// When the project was created
+    pub created_at: Option<DateTime<Utc>>,
+    /// When the project was last updated
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Cover letter written for an application. Generated by AI, editable by the user.
+#[derive(Debug, FromRow, Serialize, Deserialize)]
+pub struct CoverLetter {
+    /// Unique cover letter identifier
+    pub id: i32,
+    /// Application the letter belongs to
+    pub application_id: i32,
+    /// Owner of the letter
+    pub user_id: Uuid,
+    /// Letter text; paragraphs are separated by blank lines
+    pub content: String,
+    /// AI provider that generated the letter ("gemini", "groq")
+    pub ai_provider: Option<String>,
+    /// When the user last edited the letter
+    pub edited_at: Option<DateTime<Utc>>,
+    /// When the letter was first generated
+    pub created_at: Option<DateTime<Utc>>,
+    /// When the letter was last generated or edited
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// A recorded change to the user's skills or target roles.
+#[derive(Debug, FromRow, Serialize, Deserialize)]
+pub struct ProfileHistoryEntry {
+    /// Unique history entry identifier
+    pub id: i32,
+    /// Changed field: `skills` or `target_roles`
+    pub field: String,
+    /// Value before the change
+    pub old_value: Vec<String>,
+    /// Value after the change
+    pub new_value: Vec<String>,
+    /// Profile version the change produced
+    pub profile_version: i32,
+    /// When the change was made
+    pub changed_at: Option<DateTime<Utc>>,
+}
+
+/// Public profile settings: the slug and which fields are shown.
+#[derive(Debug, FromRow, Serialize, Deserialize)]
+pub struct PublicProfileSettings {
+    /// URL slug, lowercase (`/p/{slug}`)
+    pub slug: String,
+    /// Whether the profile is visible to anyone with the link
+    pub is_public: bool,
+    /// Show the email address
+    pub show_email: bool,
+    /// Show CV sections (summary, experience, education, projects) and allow downloading the CV
+    pub show_cv: bool,
+    /// Show skills and which of them are verified
+    pub show_skills: bool,
+    /// Show completed learning resources
+    pub show_learning: bool,
+    /// Show career roadmaps and their progress
+    pub show_roadmaps: bool,
+    /// When the settings were last changed
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
 /// OAuth provider user information.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct OAuthUser {
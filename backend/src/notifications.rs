@@ -0,0 +1,271 @@
+//! In-app notifications.
+//!
+//! Handlers call [`notify`] when something happens to a user (an application
+//! status change, a roadmap milestone). Background jobs
+//! ([`crate::queue::Task::NotifyJobMatches`],
+//! [`crate::queue::Task::NotifyStalledProgress`]) look for new jobs that match
+//! users' profiles well and for learning that has stalled. Users can turn each notification
+//! type off; a dedup key keeps repeated checks from notifying twice.
+
+use crate::ai_matching::{calculate_enhanced_match, load_skill_levels_by_user};
+use crate::events::{EventKind, publish_or_warn};
+use crate::models::Notification;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Match score from which a new job is worth a notification.
+pub const HIGH_MATCH_SCORE: f64 = 75.0;
+
+/// How far back the sweep looks for new jobs.
+const NEW_JOB_WINDOW_DAYS: i64 = 7;
+
+/// Days without progress after which started learning counts as stalled.
+pub const STALLED_AFTER_DAYS: i64 = 14;
+
+/// Roadmap progress percentages worth a notification.
+const ROADMAP_MILESTONES: [i32; 4] = [25, 50, 75, 100];
+
+/// Default interval of the background sweep.
+const DEFAULT_SWEEP_INTERVAL_SECS: u64 = 3600;
+
+/// Kind of notification; each can be turned off by the user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationType {
+    /// A new job matches the user's profile well
+    JobMatch,
+    /// An application's status changed
+    ApplicationStatus,
+    /// A career roadmap reached a milestone
+    RoadmapMilestone,
+    /// Started learning has had no progress for a while
+    StalledProgress,
+    /// New jobs match a saved search
+    SavedSearch,
+}
+
+impl NotificationType {
+    /// Value stored in `notifications.type`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            NotificationType::JobMatch => "job_match",
+            NotificationType::ApplicationStatus => "application_status",
+            NotificationType::RoadmapMilestone => "roadmap_milestone",
+            NotificationType::StalledProgress => "stalled_progress",
+            NotificationType::SavedSearch => "saved_search",
+        }
+    }
+}
+
+/// A notification to create.
+#[derive(Debug, Clone)]
+pub struct NewNotification {
+    /// Kind of notification
+    pub kind: NotificationType,
+    /// Short title
+    pub title: String,
+    /// Message body
+    pub message: String,
+    /// API path of the related resource, e.g. `/api/applications`
+    pub link: Option<String>,
+    /// A second notification with the same key is not created
+    pub dedup_key: Option<String>,
+}
+
+/// Creates a notification for a user and pushes it to their connected
+/// clients.
+///
+/// Returns `None` when the user turned the type off or an earlier
+/// notification has the same dedup key.
+///
+/// # Errors
+///
+/// Returns an error if a database operation fails.
+pub async fn notify(
+    pool: &PgPool,
+    user_id: Uuid,
+    notification: NewNotification,
+) -> Result<Option<Notification>, sqlx::Error> {
+    let created = sqlx::query_as!(
+        Notification,
+        r#"
+        INSERT INTO notifications (user_id, title, message, type, link, dedup_key)
+        SELECT $1, $2, $3, $4::TEXT, $5, $6
+        WHERE NOT EXISTS (
+            SELECT 1 FROM notification_preferences
+            WHERE user_id = $1 AND type = $4::TEXT AND NOT enabled
+        )
+        ON CONFLICT (user_id, dedup_key) WHERE dedup_key IS NOT NULL DO NOTHING
+        RETURNING id, user_id, title, message, type as notification_type, is_read, link, created_at
+        "#,
+        user_id,
+        &notification.title,
+        &notification.message,
+        notification.kind.as_str(),
+        notification.link.as_deref(),
+        notification.dedup_key.as_deref()
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    if let Some(created) = &created {
+        push(pool, created).await;
+    }
+    Ok(created)
+}
+
+async fn push(pool: &PgPool, notification: &Notification) {
+    let data = serde_json::to_value(notification).unwrap_or_default();
+    publish_or_warn(pool, notification.user_id, EventKind::Notification, data).await;
+}
+
+/// Highest roadmap milestone passed when progress goes from `old` to `new`
+/// percent.
+///
+/// # Example
+///
+/// ```
+/// use backend::notifications::crossed_milestone;
+///
+/// assert_eq!(crossed_milestone(10, 30), Some(25));
+/// assert_eq!(crossed_milestone(20, 80), Some(75));
+/// assert_eq!(crossed_milestone(50, 60), None);
+/// assert_eq!(crossed_milestone(80, 40), None);
+/// ```
+pub fn crossed_milestone(old: i32, new: i32) -> Option<i32> {
+    ROADMAP_MILESTONES
+        .iter()
+        .rev()
+        .find(|milestone| old < **milestone && new >= **milestone)
+        .copied()
+}
+
+/// Sweep interval from `NOTIFICATION_SWEEP_INTERVAL_SECS` (default one hour).
+pub fn sweep_interval() -> Duration {
+    let secs = std::env::var("NOTIFICATION_SWEEP_INTERVAL_SECS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|secs| *secs > 0)
+        .unwrap_or(DEFAULT_SWEEP_INTERVAL_SECS);
+    Duration::from_secs(secs)
+}
+
+/// Notifies users about jobs posted in the last week that match their
+/// profile with a score of at least [`HIGH_MATCH_SCORE`].
+///
+/// Each job is notified once per user. Returns how many notifications were
+/// created.
+///
+/// # Errors
+///
+/// Returns an error if a database operation fails.
+pub async fn notify_job_matches(pool: &PgPool) -> Result<usize, sqlx::Error> {
+    let jobs = sqlx::query!(
+        r#"
+        SELECT id, job_title, company, required_skills, experience_level::TEXT AS "experience_level!"
+        FROM jobs
+        WHERE created_at >= CURRENT_TIMESTAMP - make_interval(days => $1::INTEGER)
+        "#,
+        NEW_JOB_WINDOW_DAYS as i32
+    )
+    .fetch_all(pool)
+    .await?;
+    if jobs.is_empty() {
+        return Ok(0);
+    }
+
+    let users = sqlx::query!(
+        r#"
+        SELECT id, skills, experience_level::TEXT, preferred_track::TEXT
+        FROM users
+        WHERE cardinality(skills) > 0 AND deletion_scheduled_for IS NULL
+        "#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let levels_by_user = load_skill_levels_by_user(pool).await?;
+
+    let no_levels = HashMap::new();
+    let mut created = 0;
+    for user in &users {
+        let skill_levels = levels_by_user.get(&user.id).unwrap_or(&no_levels);
+        for job in &jobs {
+            let title = &job.job_title;
+            let analysis = calculate_enhanced_match(
+                &user.skills,
+                skill_levels,
+                &job.required_skills,
+                user.experience_level.as_deref(),
+                &job.experience_level,
+                user.preferred_track.as_deref(),
+                title,
+            );
+            if analysis.match_score < HIGH_MATCH_SCORE {
+                continue;
+            }
+            let notification = NewNotification {
+                kind: NotificationType::JobMatch,
+                title: format!("New job match: {}", title),
+                message: format!(
+                    "{} at {} matches your profile ({:.0}%).",
+                    title, job.company, analysis.match_score
+                ),
+                link: Some("/api/jobs/recommendations".to_string()),
+                dedup_key: Some(format!("job_match:{}", job.id)),
+            };
+            if notify(pool, user.id, notification).await?.is_some() {
+                created += 1;
+            }
+        }
+    }
+    Ok(created)
+}
+
+/// Reminds users of learning resources with no progress for
+/// [`STALLED_AFTER_DAYS`] days.
+///
+/// A resource is reminded about once per stall: new progress followed by
+/// another stall reminds again. Returns how many notifications were created.
+///
+/// # Errors
+///
+/// Returns an error if a database operation fails.
+pub async fn notify_stalled_progress(pool: &PgPool) -> Result<usize, sqlx::Error> {
+    let created = sqlx::query_as!(
+        Notification,
+        r#"
+        INSERT INTO notifications (user_id, title, message, type, link, dedup_key)
+        SELECT up.user_id,
+               'Pick up where you left off',
+               format('You are %s%% through "%s" with no progress for %s days.',
+                      COALESCE(up.completion_percentage, 0), lr.title,
+                      EXTRACT(DAY FROM CURRENT_TIMESTAMP - up.updated_at)::INTEGER),
+               $2::TEXT,
+               '/api/progress',
+               format('stalled_progress:%s:%s', up.id, extract(epoch FROM up.updated_at)::BIGINT)
+        FROM user_progress up
+        JOIN learning_resources lr ON lr.id = up.resource_id
+        WHERE up.completed_at IS NULL
+          AND up.updated_at < CURRENT_TIMESTAMP - make_interval(days => $1::INTEGER)
+          AND NOT EXISTS (
+              SELECT 1 FROM notification_preferences np
+              WHERE np.user_id = up.user_id AND np.type = $2::TEXT AND NOT np.enabled
+          )
+        ON CONFLICT (user_id, dedup_key) WHERE dedup_key IS NOT NULL DO NOTHING
+        RETURNING id, user_id, title, message, type as notification_type, is_read, link, created_at
+        "#,
+        STALLED_AFTER_DAYS as i32,
+        NotificationType::StalledProgress.as_str()
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for notification in &created {
+        push(pool, notification).await;
+    }
+    Ok(created.len())
+}
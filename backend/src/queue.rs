@@ -0,0 +1,805 @@
+//! Background job queue and scheduler.
+//!
+//! Work that should not run inside a request, and periodic work, is stored
+//! as a typed [`Task`] in the `background_jobs` table. Workers claim due jobs
+//! with `SELECT ... FOR UPDATE SKIP LOCKED`, so any number of workers on any
+//! number of instances can share the queue without running a job twice.
+//!
+//! - A failed job is retried after [`retry_delay`] (30s doubling up to an
+//!   hour) until it has used its `max_attempts`; it is then marked `dead`
+//!   and kept with its last error for inspection and manual retry.
+//! - A job whose worker stopped responding is claimed again once its lease
+//!   (`JOB_LEASE_SECS`, default 15 minutes) has expired.
+//! - Periodic jobs are [`ScheduledTask`]s, run every interval or on a cron
+//!   expression. Each run is enqueued by the instance that moves the
+//!   schedule's `next_run_at` forward in `job_schedules`, so it is enqueued
+//!   once however many instances are running.
+//!
+//! `JOB_WORKERS` (default 2) sets the workers per instance; with `0` the
+//! instance only serves requests and another instance must run the jobs.
+
+use crate::ai::AIService;
+use crate::cv::storage::DocumentStorage;
+use crate::errors::AppError;
+use crate::external_jobs::sources::JobSource;
+use crate::handlers::{CoverLetterJob, CvEnrichmentJob, RoadmapJob};
+use crate::mailer::{MailError, Mailer};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgExecutor, PgPool};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+/// Attempts a job gets unless enqueued with another limit.
+pub const DEFAULT_MAX_ATTEMPTS: i32 = 5;
+
+/// Delay before the first retry; doubled for each further attempt.
+const BASE_RETRY_DELAY_SECS: u64 = 30;
+
+/// Longest delay between retries.
+const MAX_RETRY_DELAY_SECS: u64 = 3600;
+
+/// Default number of workers per instance.
+const DEFAULT_WORKERS: usize = 2;
+
+/// How often an idle worker looks for due jobs.
+const DEFAULT_POLL_INTERVAL_SECS: u64 = 5;
+
+/// How long a job may run before it is failed and can be claimed again.
+const DEFAULT_LEASE_SECS: u64 = 900;
+
+/// How often the scheduler enqueues due periodic jobs.
+const SCHEDULER_TICK: Duration = Duration::from_secs(5);
+
+/// Days finished jobs are kept before the daily prune deletes them.
+const DEFAULT_RETENTION_DAYS: i64 = 7;
+
+/// Default cron expression of the prune (daily at 04:00 UTC).
+const DEFAULT_PRUNE_SCHEDULE: &str = "0 4 * * *";
+
+/// Kinds of the tasks run for a user's AI request; see [`Task::ai_job`].
+pub const AI_TASK_KINDS: [&str; 3] = ["generate_roadmap", "generate_cover_letter", "enrich_parsed_cv"];
+
+/// A unit of background work and its payload.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "payload", rename_all = "snake_case")]
+pub enum Task {
+    /// Purge accounts whose deletion grace period has passed
+    PurgeDeletedAccounts,
+    /// Notify users of new high-match jobs
+    NotifyJobMatches,
+    /// Notify users of learning with no recent progress
+    NotifyStalledProgress,
+    /// Check saved searches for new matching jobs
+    CheckSavedSearches,
+    /// Email the saved search digests that are due
+    SendSavedSearchDigests,
+    /// Import listings from the external job sources
+    ImportExternalJobs,
+    /// Delete succeeded jobs finished more than `older_than_days` ago
+    PruneFinishedJobs { older_than_days: i64 },
+    /// Generate a career roadmap with AI
+    GenerateRoadmap(RoadmapJob),
+    /// Generate an application's cover letter with AI
+    GenerateCoverLetter(CoverLetterJob),
+    /// Add AI-extracted skills and certifications to a parsed CV
+    EnrichParsedCv(CvEnrichmentJob),
+}
+
+impl Task {
+    /// Kind stored with the job, for filtering and logs.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Task::PurgeDeletedAccounts => "purge_deleted_accounts",
+            Task::NotifyJobMatches => "notify_job_matches",
+            Task::NotifyStalledProgress => "notify_stalled_progress",
+            Task::CheckSavedSearches => "check_saved_searches",
+            Task::SendSavedSearchDigests => "send_saved_search_digests",
+            Task::ImportExternalJobs => "import_external_jobs",
+            Task::PruneFinishedJobs { .. } => "prune_finished_jobs",
+            Task::GenerateRoadmap(_) => "generate_roadmap",
+            Task::GenerateCoverLetter(_) => "generate_cover_letter",
+            Task::EnrichParsedCv(_) => "enrich_parsed_cv",
+        }
+    }
+
+    /// Owner and AI job kind (`roadmap`, `cover_letter`, `cv_enrichment`)
+    /// of a task run for a user's AI request.
+    pub fn ai_job(&self) -> Option<(Uuid, &'static str)> {
+        match self {
+            Task::GenerateRoadmap(job) => Some((job.user_id, "roadmap")),
+            Task::GenerateCoverLetter(job) => Some((job.user_id, "cover_letter")),
+            Task::EnrichParsedCv(job) => Some((job.user_id, "cv_enrichment")),
+            _ => None,
+        }
+    }
+}
+
+/// State of a job in the queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    /// Waiting for its `run_at`, or for a retry
+    Pending,
+    /// Claimed by a worker
+    Running,
+    /// Finished successfully
+    Succeeded,
+    /// Out of attempts; kept until retried by an administrator
+    Dead,
+}
+
+impl JobStatus {
+    /// Status stored in the database.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            JobStatus::Pending => "pending",
+            JobStatus::Running => "running",
+            JobStatus::Succeeded => "succeeded",
+            JobStatus::Dead => "dead",
+        }
+    }
+}
+
+/// Errors failing a job.
+#[derive(Debug, Error)]
+pub enum TaskError {
+    /// A database operation failed
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+    /// Sending email failed
+    #[error("{0}")]
+    Mail(#[from] MailError),
+    /// Any other failure
+    #[error("{0}")]
+    Failed(String),
+    /// A failure retrying cannot fix; the job is marked dead at once
+    #[error("{0}")]
+    Permanent(String),
+}
+
+impl TaskError {
+    /// Whether the job should be marked dead instead of retried.
+    pub fn is_permanent(&self) -> bool {
+        matches!(self, TaskError::Permanent(_))
+    }
+}
+
+impl From<AppError> for TaskError {
+    fn from(e: AppError) -> Self {
+        match e {
+            AppError::DatabaseError(e) => TaskError::Database(e),
+            AppError::ExternalServiceError(message) => TaskError::Failed(message),
+            // The user, CV or application is gone, or the user opted out
+            // of AI: running the job again would fail the same way
+            e @ (AppError::NotFound
+            | AppError::Forbidden(_)
+            | AppError::ValidationError(_)
+            | AppError::ValidatorErrors(_)
+            | AppError::BadRequest(_)) => TaskError::Permanent(e.to_string()),
+            other => TaskError::Failed(other.to_string()),
+        }
+    }
+}
+
+/// What tasks need to run.
+#[derive(Clone)]
+pub struct JobContext {
+    /// Database connection pool
+    pub pool: PgPool,
+    /// Storage of original CV files
+    pub storage: Arc<dyn DocumentStorage>,
+    /// Mailer, when email is configured
+    pub mailer: Option<Arc<dyn Mailer>>,
+    /// Enabled external job sources
+    pub job_sources: Vec<Arc<dyn JobSource>>,
+    /// AI service, when a provider key is configured
+    pub ai_service: Option<Arc<AIService>>,
+}
+
+impl JobContext {
+    /// AI service, failing the task when no provider is configured.
+    fn ai_service(&self) -> Result<&AIService, TaskError> {
+        self.ai_service
+            .as_deref()
+            .ok_or_else(|| TaskError::Failed("AI service is not configured (set GEMINI_API_KEY or GROQ_API_KEY)".to_string()))
+    }
+}
+
+/// Runs `task`, the payload of job `job_id`.
+///
+/// # Errors
+///
+/// Returns an error if the task failed; the job is then retried.
+pub async fn run_task(job_id: i64, task: &Task, ctx: &JobContext) -> Result<(), TaskError> {
+    let pool = &ctx.pool;
+    match task {
+        Task::PurgeDeletedAccounts => {
+            let count = crate::account::purge_due_accounts(pool, ctx.storage.as_ref()).await?;
+            if count > 0 {
+                info!("Account purge removed {} account(s)", count);
+            }
+        }
+        Task::NotifyJobMatches => {
+            let count = crate::notifications::notify_job_matches(pool).await?;
+            if count > 0 {
+                info!("Created {} job match notification(s)", count);
+            }
+        }
+        Task::NotifyStalledProgress => {
+            let count = crate::notifications::notify_stalled_progress(pool).await?;
+            if count > 0 {
+                info!("Created {} stalled progress notification(s)", count);
+            }
+        }
+        Task::CheckSavedSearches => {
+            let count = crate::saved_searches::check_saved_searches(pool).await?;
+            if count > 0 {
+                info!("Recorded {} saved search match(es)", count);
+            }
+        }
+        Task::SendSavedSearchDigests => {
+            let mailer = ctx
+                .mailer
+                .as_ref()
+                .ok_or_else(|| TaskError::Failed("email is not configured (set MAIL_BACKEND)".to_string()))?;
+            let count = crate::saved_searches::send_due_digests(pool, mailer.as_ref()).await?;
+            if count > 0 {
+                info!("Sent {} saved search digest(s)", count);
+            }
+        }
+        Task::ImportExternalJobs => {
+            let report = crate::external_jobs::fetch_all_external_jobs(&ctx.job_sources).await;
+            let summary = crate::external_jobs::import_external_jobs(pool, &report).await?;
+            info!(
+                "Imported external jobs: {} new, {} updated, {} duplicate(s), {} stale, {} expired, {} source(s) failed",
+                summary.inserted, summary.updated, summary.duplicates, summary.stale, summary.expired, summary.failed
+            );
+        }
+        Task::PruneFinishedJobs { older_than_days } => {
+            let result = sqlx::query!(
+                r#"
+                DELETE FROM background_jobs
+                WHERE status = 'succeeded' AND finished_at < CURRENT_TIMESTAMP - make_interval(days => $1)
+                "#,
+                *older_than_days as i32
+            )
+            .execute(pool)
+            .await?;
+            if result.rows_affected() > 0 {
+                info!("Pruned {} finished background job(s)", result.rows_affected());
+            }
+        }
+        Task::GenerateRoadmap(job) => {
+            crate::handlers::run_roadmap_job(pool, ctx.ai_service()?, job_id, job).await?;
+        }
+        Task::GenerateCoverLetter(job) => {
+            crate::handlers::run_cover_letter_job(pool, ctx.ai_service()?, job_id, job).await?;
+        }
+        Task::EnrichParsedCv(job) => {
+            crate::handlers::run_cv_enrichment_job(pool, ctx.ai_service()?, job_id, job).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Delay before retrying a job that failed its `attempt`th attempt.
+///
+/// # Example
+///
+/// ```
+/// use backend::queue::retry_delay;
+/// use std::time::Duration;
+///
+/// assert_eq!(retry_delay(1), Duration::from_secs(30));
+/// assert_eq!(retry_delay(3), Duration::from_secs(120));
+/// assert_eq!(retry_delay(20), Duration::from_secs(3600));
+/// ```
+pub fn retry_delay(attempt: i32) -> Duration {
+    let exponent = attempt.saturating_sub(1).clamp(0, 16) as u32;
+    Duration::from_secs((BASE_RETRY_DELAY_SECS << exponent).min(MAX_RETRY_DELAY_SECS))
+}
+
+/// Options for [`enqueue_with`].
+#[derive(Debug, Clone, Default)]
+pub struct EnqueueOptions {
+    /// Earliest time the job runs (default: now)
+    pub run_at: Option<DateTime<Utc>>,
+    /// Attempts before the job is marked dead (default: [`DEFAULT_MAX_ATTEMPTS`])
+    pub max_attempts: Option<i32>,
+    /// Key allowing at most one pending or running job
+    pub dedup_key: Option<String>,
+}
+
+/// Enqueues `task` to run now.
+///
+/// # Errors
+///
+/// Returns an error if a database operation fails.
+pub async fn enqueue(pool: &PgPool, task: &Task) -> Result<i64, sqlx::Error> {
+    enqueue_with(pool, task, EnqueueOptions::default())
+        .await
+        .map(|id| id.unwrap_or_default())
+}
+
+/// Enqueues `task` with `options`, returning the job's ID, or `None` when a
+/// pending or running job already has the same dedup key.
+///
+/// # Errors
+///
+/// Returns an error if a database operation fails.
+pub async fn enqueue_with<'e, E: PgExecutor<'e>>(
+    executor: E,
+    task: &Task,
+    options: EnqueueOptions,
+) -> Result<Option<i64>, sqlx::Error> {
+    let payload = serde_json::to_value(task).map_err(|e| sqlx::Error::Encode(Box::new(e)))?;
+    sqlx::query_scalar!(
+        r#"
+        INSERT INTO background_jobs (kind, payload, max_attempts, run_at, dedup_key)
+        VALUES ($1, $2, $3, COALESCE($4, CURRENT_TIMESTAMP), $5)
+        ON CONFLICT (dedup_key) WHERE status IN ('pending', 'running') DO NOTHING
+        RETURNING id
+        "#,
+        task.kind(),
+        payload,
+        options.max_attempts.unwrap_or(DEFAULT_MAX_ATTEMPTS).max(1),
+        options.run_at,
+        options.dedup_key
+    )
+    .fetch_optional(executor)
+    .await
+}
+
+/// When a periodic task runs.
+#[derive(Debug, Clone)]
+pub enum Schedule {
+    /// Every interval, starting as soon as the schedule is first registered
+    Every(Duration),
+    /// On a cron expression, in UTC
+    Cron(Box<cron::Schedule>),
+}
+
+impl Schedule {
+    /// Parses a cron expression: the usual five fields (minute, hour, day of
+    /// month, month, day of week) or six with seconds first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use backend::queue::Schedule;
+    /// use chrono::{TimeZone, Utc};
+    ///
+    /// let daily = Schedule::cron("0 4 * * *").unwrap();
+    /// let now = Utc.with_ymd_and_hms(2025, 11, 3, 9, 30, 0).unwrap();
+    /// assert_eq!(daily.next_after(now), Some(Utc.with_ymd_and_hms(2025, 11, 4, 4, 0, 0).unwrap()));
+    ///
+    /// assert!(Schedule::cron("every day").is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the expression is invalid.
+    pub fn cron(expression: &str) -> Result<Self, String> {
+        let expression = expression.trim();
+        let with_seconds = if expression.split_whitespace().count() == 5 {
+            format!("0 {}", expression)
+        } else {
+            expression.to_string()
+        };
+        cron::Schedule::from_str(&with_seconds)
+            .map(|schedule| Schedule::Cron(Box::new(schedule)))
+            .map_err(|e| format!("invalid cron expression '{}': {}", expression, e))
+    }
+
+    /// Next run after `now`.
+    pub fn next_after(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Schedule::Every(interval) => chrono::Duration::from_std(*interval).ok().map(|interval| now + interval),
+            Schedule::Cron(schedule) => schedule.after(&now).next(),
+        }
+    }
+
+    /// Description stored with the schedule; a changed description resets
+    /// the next run.
+    pub fn describe(&self) -> String {
+        match self {
+            Schedule::Every(interval) => format!("every {}s", interval.as_secs()),
+            Schedule::Cron(schedule) => schedule.source().to_string(),
+        }
+    }
+
+    /// First run of a newly registered schedule: now for intervals, the
+    /// next occurrence for cron expressions.
+    fn first_run(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Schedule::Every(_) => Some(now),
+            Schedule::Cron(_) => self.next_after(now),
+        }
+    }
+}
+
+/// A task enqueued on a schedule.
+#[derive(Debug, Clone)]
+pub struct ScheduledTask {
+    /// Unique name of the schedule
+    pub name: &'static str,
+    /// When the task runs
+    pub schedule: Schedule,
+    /// Task enqueued on each run
+    pub task: Task,
+}
+
+/// The application's periodic tasks; digests are only sent with a mailer.
+///
+/// The prune of finished jobs runs on `JOB_PRUNE_SCHEDULE` (cron, default
+/// daily at 04:00 UTC) and keeps jobs for `JOB_RETENTION_DAYS` (default 7).
+pub fn default_schedules(mail_enabled: bool) -> Vec<ScheduledTask> {
+    let mut schedules = vec![
+        ScheduledTask {
+            name: "purge_deleted_accounts",
+            schedule: Schedule::Every(crate::account::purge_interval()),
+            task: Task::PurgeDeletedAccounts,
+        },
+        ScheduledTask {
+            name: "notify_job_matches",
+            schedule: Schedule::Every(crate::notifications::sweep_interval()),
+            task: Task::NotifyJobMatches,
+        },
+        ScheduledTask {
+            name: "notify_stalled_progress",
+            schedule: Schedule::Every(crate::notifications::sweep_interval()),
+            task: Task::NotifyStalledProgress,
+        },
+        ScheduledTask {
+            name: "import_external_jobs",
+            schedule: Schedule::Every(crate::external_jobs::import_interval()),
+            task: Task::ImportExternalJobs,
+        },
+        ScheduledTask {
+            name: "check_saved_searches",
+            schedule: Schedule::Every(crate::saved_searches::check_interval()),
+            task: Task::CheckSavedSearches,
+        },
+    ];
+    if mail_enabled {
+        schedules.push(ScheduledTask {
+            name: "send_saved_search_digests",
+            schedule: Schedule::Every(crate::saved_searches::check_interval()),
+            task: Task::SendSavedSearchDigests,
+        });
+    }
+
+    let prune = std::env::var("JOB_PRUNE_SCHEDULE").unwrap_or_else(|_| DEFAULT_PRUNE_SCHEDULE.to_string());
+    let prune = Schedule::cron(&prune).unwrap_or_else(|e| {
+        warn!("{}; using '{}'", e, DEFAULT_PRUNE_SCHEDULE);
+        Schedule::cron(DEFAULT_PRUNE_SCHEDULE).expect("default prune schedule is valid")
+    });
+    let older_than_days = std::env::var("JOB_RETENTION_DAYS")
+        .ok()
+        .and_then(|v| v.parse::<i64>().ok())
+        .filter(|days| *days >= 0)
+        .unwrap_or(DEFAULT_RETENTION_DAYS);
+    schedules.push(ScheduledTask {
+        name: "prune_finished_jobs",
+        schedule: prune,
+        task: Task::PruneFinishedJobs { older_than_days },
+    });
+    schedules
+}
+
+/// Worker settings.
+#[derive(Debug, Clone)]
+pub struct WorkerConfig {
+    /// Workers on this instance; `0` runs no jobs
+    pub workers: usize,
+    /// How often an idle worker looks for due jobs
+    pub poll_interval: Duration,
+    /// How long a job may run before it is failed and claimed again
+    pub lease: Duration,
+}
+
+impl WorkerConfig {
+    /// Settings from `JOB_WORKERS` (default 2), `JOB_POLL_INTERVAL_SECS`
+    /// (default 5) and `JOB_LEASE_SECS` (default 900).
+    pub fn from_env() -> Self {
+        let var = |name: &str| std::env::var(name).ok().and_then(|v| v.parse::<u64>().ok());
+        WorkerConfig {
+            workers: var("JOB_WORKERS").map(|n| n as usize).unwrap_or(DEFAULT_WORKERS),
+            poll_interval: Duration::from_secs(
+                var("JOB_POLL_INTERVAL_SECS").filter(|s| *s > 0).unwrap_or(DEFAULT_POLL_INTERVAL_SECS),
+            ),
+            lease: Duration::from_secs(var("JOB_LEASE_SECS").filter(|s| *s > 0).unwrap_or(DEFAULT_LEASE_SECS)),
+        }
+    }
+}
+
+/// A job claimed by a worker.
+#[derive(Debug, FromRow)]
+struct ClaimedJob {
+    id: i64,
+    kind: String,
+    payload: serde_json::Value,
+    attempts: i32,
+    max_attempts: i32,
+}
+
+/// Starts `config.workers` workers running due jobs.
+pub fn spawn_workers(ctx: JobContext, config: &WorkerConfig) {
+    let host = std::env::var("HOSTNAME").unwrap_or_else(|_| "worker".to_string());
+    for index in 0..config.workers {
+        let ctx = ctx.clone();
+        let config = config.clone();
+        let mut worker_id = format!("{}:{}:{}", host, std::process::id(), index);
+        worker_id.truncate(100);
+        tokio::spawn(async move {
+            loop {
+                match run_next_job(&ctx, &worker_id, config.lease).await {
+                    Ok(true) => {}
+                    Ok(false) => tokio::time::sleep(config.poll_interval).await,
+                    Err(e) => {
+                        error!("Background worker {} failed: {}", worker_id, e);
+                        tokio::time::sleep(config.poll_interval).await;
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Claims and runs the next due job. Returns `false` when none was due.
+async fn run_next_job(ctx: &JobContext, worker_id: &str, lease: Duration) -> Result<bool, sqlx::Error> {
+    let pool = &ctx.pool;
+    let Some(job) = sqlx::query_as!(
+        ClaimedJob,
+        r#"
+        UPDATE background_jobs
+        SET status = 'running', attempts = attempts + 1, locked_at = CURRENT_TIMESTAMP,
+            locked_by = $1, updated_at = CURRENT_TIMESTAMP
+        WHERE id = (
+            SELECT id FROM background_jobs
+            WHERE (status = 'pending' AND run_at <= CURRENT_TIMESTAMP)
+               OR (status = 'running' AND attempts < max_attempts
+                   AND locked_at < CURRENT_TIMESTAMP - make_interval(secs => $2))
+            ORDER BY run_at, id
+            FOR UPDATE SKIP LOCKED
+            LIMIT 1
+        )
+        RETURNING id, kind, payload, attempts, max_attempts
+        "#,
+        worker_id,
+        lease.as_secs_f64()
+    )
+    .fetch_optional(pool)
+    .await?
+    else {
+        return Ok(false);
+    };
+
+    let task = match serde_json::from_value::<Task>(job.payload.clone()) {
+        Ok(task) => task,
+        Err(e) => {
+            let message = format!("Unreadable {} payload: {}", job.kind, e);
+            fail_job(pool, &job, worker_id, &message, true).await?;
+            return Ok(true);
+        }
+    };
+
+    debug!("Worker {} running job {} ({}), attempt {}", worker_id, job.id, job.kind, job.attempts);
+    // Run on its own task so a panic fails the job instead of the worker
+    let handle = tokio::spawn({
+        let ctx = ctx.clone();
+        let job_id = job.id;
+        async move { run_task(job_id, &task, &ctx).await }
+    });
+    let abort = handle.abort_handle();
+    let outcome = match tokio::time::timeout(lease, handle).await {
+        Ok(Ok(Ok(()))) => Ok(()),
+        Ok(Ok(Err(e))) => Err((e.to_string(), e.is_permanent())),
+        Ok(Err(e)) => Err((format!("Job panicked: {}", e), false)),
+        Err(_) => {
+            abort.abort();
+            Err((format!("Job timed out after {}s", lease.as_secs()), false))
+        }
+    };
+
+    match outcome {
+        Ok(()) => {
+            sqlx::query!(
+                r#"
+                UPDATE background_jobs
+                SET status = 'succeeded', finished_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP,
+                    locked_at = NULL, locked_by = NULL, last_error = NULL
+                WHERE id = $1 AND status = 'running' AND locked_by = $2 AND attempts = $3
+                "#,
+                job.id,
+                worker_id,
+                job.attempts
+            )
+            .execute(pool)
+            .await?;
+        }
+        Err((message, permanent)) => fail_job(pool, &job, worker_id, &message, permanent).await?,
+    }
+    Ok(true)
+}
+
+/// Records a failed attempt: the job is retried after [`retry_delay`], or
+/// marked dead when it is out of attempts or `permanent`.
+async fn fail_job(
+    pool: &PgPool,
+    job: &ClaimedJob,
+    worker_id: &str,
+    message: &str,
+    permanent: bool,
+) -> Result<(), sqlx::Error> {
+    let dead = permanent || job.attempts >= job.max_attempts;
+    let delay = retry_delay(job.attempts);
+    let retry_at = Utc::now() + chrono::Duration::from_std(delay).unwrap_or_default();
+    sqlx::query!(
+        r#"
+        UPDATE background_jobs
+        SET status = CASE WHEN $4 THEN 'dead' ELSE 'pending' END,
+            run_at = CASE WHEN $4 THEN run_at ELSE $5 END,
+            finished_at = CASE WHEN $4 THEN CURRENT_TIMESTAMP END,
+            last_error = $6, locked_at = NULL, locked_by = NULL, updated_at = CURRENT_TIMESTAMP
+        WHERE id = $1 AND status = 'running' AND locked_by = $2 AND attempts = $3
+        "#,
+        job.id,
+        worker_id,
+        job.attempts,
+        dead,
+        retry_at,
+        message
+    )
+    .execute(pool)
+    .await?;
+
+    if dead {
+        error!(
+            "Background job {} ({}) is dead after {} attempt(s): {}",
+            job.id, job.kind, job.attempts, message
+        );
+        publish_ai_job_failed(pool, job.id, &job.payload, message).await;
+    } else {
+        warn!(
+            "Background job {} ({}) failed attempt {}/{}, retrying in {}s: {}",
+            job.id,
+            job.kind,
+            job.attempts,
+            job.max_attempts,
+            delay.as_secs(),
+            message
+        );
+    }
+    Ok(())
+}
+
+/// Registers `schedules` and enqueues their runs as they come due.
+///
+/// Also marks dead the jobs that used their last attempt on a worker that
+/// stopped responding (lease expired).
+pub fn spawn_scheduler(pool: PgPool, schedules: Vec<ScheduledTask>, lease: Duration) {
+    tokio::spawn(async move {
+        if let Err(e) = register_schedules(&pool, &schedules).await {
+            error!("Failed to register job schedules: {}", e);
+        }
+        let mut ticker = tokio::time::interval(SCHEDULER_TICK);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = enqueue_due_schedules(&pool, &schedules).await {
+                error!("Job scheduler failed: {}", e);
+            }
+            if let Err(e) = expire_abandoned_jobs(&pool, lease).await {
+                error!("Failed to expire abandoned jobs: {}", e);
+            }
+        }
+    });
+}
+
+/// Adds new schedules; a schedule whose description changed starts over.
+async fn register_schedules(pool: &PgPool, schedules: &[ScheduledTask]) -> Result<(), sqlx::Error> {
+    let now = Utc::now();
+    for scheduled in schedules {
+        let Some(first_run) = scheduled.schedule.first_run(now) else {
+            warn!("Schedule {} never runs", scheduled.name);
+            continue;
+        };
+        sqlx::query!(
+            r#"
+            INSERT INTO job_schedules (name, schedule, next_run_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (name) DO UPDATE
+            SET schedule = EXCLUDED.schedule, next_run_at = EXCLUDED.next_run_at, updated_at = CURRENT_TIMESTAMP
+            WHERE job_schedules.schedule <> EXCLUDED.schedule
+            "#,
+            scheduled.name,
+            scheduled.schedule.describe(),
+            first_run
+        )
+        .execute(pool)
+        .await?;
+    }
+    Ok(())
+}
+
+/// Enqueues the runs of due schedules, skipping a run while the previous
+/// one is still pending or running. Returns how many jobs were enqueued.
+async fn enqueue_due_schedules(pool: &PgPool, schedules: &[ScheduledTask]) -> Result<usize, sqlx::Error> {
+    let now = Utc::now();
+    let mut enqueued = 0;
+    for scheduled in schedules {
+        let Some(next_run) = scheduled.schedule.next_after(now) else {
+            continue;
+        };
+        let mut tx = pool.begin().await?;
+        let claimed: Option<String> = sqlx::query_scalar!(
+            r#"
+            UPDATE job_schedules
+            SET next_run_at = $2, last_run_at = $3, updated_at = CURRENT_TIMESTAMP
+            WHERE name = $1 AND next_run_at <= $3
+            RETURNING name
+            "#,
+            scheduled.name,
+            next_run,
+            now
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        if claimed.is_some() {
+            let options = EnqueueOptions {
+                dedup_key: Some(format!("schedule:{}", scheduled.name)),
+                ..EnqueueOptions::default()
+            };
+            match enqueue_with(&mut *tx, &scheduled.task, options).await? {
+                Some(_) => enqueued += 1,
+                None => debug!("Skipping {}: the previous run has not finished", scheduled.name),
+            }
+        }
+        tx.commit().await?;
+    }
+    Ok(enqueued)
+}
+
+/// Marks `dead` the running jobs whose lease has expired on their last
+/// attempt. A worker holds a job for `lease` from when it claimed it
+/// (`locked_at`) and gives up on it itself after that long, so a job still
+/// `running` past its lease belongs to a worker that stopped responding.
+/// With attempts left, [`run_next_job`] claims such a job again instead;
+/// without, nothing would, so it is failed here with "Worker stopped
+/// responding" and its owner told if it is an AI job.
+async fn expire_abandoned_jobs(pool: &PgPool, lease: Duration) -> Result<(), sqlx::Error> {
+    let expired = sqlx::query!(
+        r#"
+        UPDATE background_jobs
+        SET status = 'dead', finished_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP,
+            last_error = 'Worker stopped responding', locked_at = NULL, locked_by = NULL
+        WHERE status = 'running' AND attempts >= max_attempts
+          AND locked_at < CURRENT_TIMESTAMP - make_interval(secs => $1)
+        RETURNING id, kind, payload
+        "#,
+        lease.as_secs_f64()
+    )
+    .fetch_all(pool)
+    .await?;
+    for job in expired {
+        error!("Background job {} ({}) is dead: its worker stopped responding", job.id, job.kind);
+        publish_ai_job_failed(pool, job.id, &job.payload, "Worker stopped responding").await;
+    }
+    Ok(())
+}
+
+/// Tells the owner of a dead AI job that it failed; other jobs have no one
+/// to tell.
+async fn publish_ai_job_failed(pool: &PgPool, job_id: i64, payload: &serde_json::Value, message: &str) {
+    let Ok(task) = serde_json::from_value::<Task>(payload.clone()) else {
+        return;
+    };
+    if let Some((user_id, job)) = task.ai_job() {
+        crate::events::publish_ai_job_failed(pool, user_id, job_id, job, message).await;
+    }
+}
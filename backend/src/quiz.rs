@@ -0,0 +1,277 @@
+//! Skill quizzes.
+//!
+//! Short multiple-choice quizzes measure proficiency in a skill. Questions
+//! come from question bank files (`QUIZ_BANK_DIR`, default `assets/quizzes`),
+//! which are loaded as approved at startup, or are generated by an AI
+//! provider and wait for an administrator's review. Quizzes are graded on
+//! the server: the correct answers never leave it before an attempt is
+//! submitted, each attempt has a time limit and can be submitted only once.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// Questions per quiz.
+pub const QUIZ_LENGTH: i64 = 5;
+
+/// Time allowed per question.
+const SECONDS_PER_QUESTION: i64 = 60;
+
+/// Allowed lateness of a submission, for network latency.
+pub const SUBMIT_GRACE_SECS: i64 = 5;
+
+/// Default wait before the same skill's quiz can be taken again.
+const DEFAULT_RETAKE_COOLDOWN_MINS: i64 = 60;
+
+/// Answer options per question.
+const MIN_OPTIONS: usize = 2;
+const MAX_OPTIONS: usize = 6;
+
+/// A question bank file: questions for one skill.
+#[derive(Debug, Deserialize)]
+pub struct QuestionBank {
+    /// Skill the questions test
+    pub skill: String,
+    /// The questions
+    pub questions: Vec<BankQuestion>,
+}
+
+/// A multiple-choice question with its answer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BankQuestion {
+    /// Question text
+    pub question: String,
+    /// Answer options
+    pub options: Vec<String>,
+    /// Index of the correct option
+    pub answer: usize,
+    /// Why the answer is correct, shown after grading
+    #[serde(default)]
+    pub explanation: Option<String>,
+}
+
+impl BankQuestion {
+    /// Checks the question is well-formed: text, 2 to 6 distinct non-empty
+    /// options and an answer among them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use backend::quiz::BankQuestion;
+    ///
+    /// let mut q = BankQuestion {
+    ///     question: "Which keyword removes duplicate rows?".to_string(),
+    ///     options: vec!["UNIQUE".to_string(), "DISTINCT".to_string()],
+    ///     answer: 1,
+    ///     explanation: None,
+    /// };
+    /// assert!(q.validate().is_ok());
+    /// q.answer = 2;
+    /// assert!(q.validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<(), String> {
+        if self.question.trim().is_empty() {
+            return Err("Question text is empty".to_string());
+        }
+        if !(MIN_OPTIONS..=MAX_OPTIONS).contains(&self.options.len()) {
+            return Err(format!("A question needs {} to {} options", MIN_OPTIONS, MAX_OPTIONS));
+        }
+        let distinct: HashSet<_> = self.options.iter().map(|o| o.trim().to_lowercase()).collect();
+        if distinct.len() != self.options.len() || distinct.contains("") {
+            return Err("Options must be distinct and non-empty".to_string());
+        }
+        if self.answer >= self.options.len() {
+            return Err("The answer must be one of the options".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Wait before retaking a skill's quiz, from `QUIZ_RETAKE_COOLDOWN_MINS`
+/// (default 60).
+pub fn retake_cooldown() -> chrono::Duration {
+    let mins = std::env::var("QUIZ_RETAKE_COOLDOWN_MINS")
+        .ok()
+        .and_then(|v| v.parse::<i64>().ok())
+        .filter(|mins| *mins >= 0)
+        .unwrap_or(DEFAULT_RETAKE_COOLDOWN_MINS);
+    chrono::Duration::minutes(mins)
+}
+
+/// Time limit for a quiz with `questions` questions.
+pub fn time_limit(questions: usize) -> chrono::Duration {
+    chrono::Duration::seconds(SECONDS_PER_QUESTION * questions as i64)
+}
+
+/// Proficiency level (1-10) for a quiz score.
+///
+/// # Example
+///
+/// ```
+/// use backend::quiz::proficiency_from_score;
+///
+/// assert_eq!(proficiency_from_score(5, 5), 10);
+/// assert_eq!(proficiency_from_score(3, 5), 6);
+/// assert_eq!(proficiency_from_score(0, 5), 1);
+/// ```
+pub fn proficiency_from_score(correct: usize, total: usize) -> i32 {
+    if total == 0 {
+        return 1;
+    }
+    ((correct as f64 / total as f64 * 10.0).round() as i32).clamp(1, 10)
+}
+
+/// Grades answers against the correct options.
+///
+/// `questions` maps question IDs to the index of their correct option, in
+/// quiz order; `answers` maps question IDs to the chosen option. Unanswered
+/// questions count as wrong. Returns, per question, the chosen option and
+/// whether it was correct.
+///
+/// # Example
+///
+/// ```
+/// use backend::quiz::grade;
+/// use std::collections::HashMap;
+///
+/// let questions = [(7, 1), (9, 0), (12, 3)];
+/// let answers = HashMap::from([(7, 1), (9, 2)]);
+/// let graded = grade(&questions, &answers);
+/// assert_eq!(graded, vec![(Some(1), true), (Some(2), false), (None, false)]);
+/// ```
+pub fn grade(questions: &[(i32, i32)], answers: &HashMap<i32, i32>) -> Vec<(Option<i32>, bool)> {
+    questions
+        .iter()
+        .map(|(id, correct)| {
+            let chosen = answers.get(id).copied();
+            (chosen, chosen == Some(*correct))
+        })
+        .collect()
+}
+
+/// Questions from an AI response, dropping malformed ones.
+///
+/// Accepts a JSON array of questions, an object with a `questions` array, or
+/// a string holding either.
+///
+/// # Example
+///
+/// ```
+/// use backend::quiz::parse_generated_questions;
+/// use serde_json::json;
+///
+/// let content = json!(r#"{"questions": [
+///     {"question": "2 + 2?", "options": ["3", "4"], "answer": 1},
+///     {"question": "Broken", "options": ["only one"], "answer": 0}
+/// ]}"#);
+/// let questions = parse_generated_questions(&content);
+/// assert_eq!(questions.len(), 1);
+/// assert_eq!(questions[0].options[questions[0].answer], "4");
+/// ```
+pub fn parse_generated_questions(content: &Value) -> Vec<BankQuestion> {
+    let parsed;
+    let value = match content {
+        Value::String(text) => {
+            let text = text.trim().trim_start_matches("```json").trim_matches('`').trim();
+            match serde_json::from_str::<Value>(text) {
+                Ok(value) => {
+                    parsed = value;
+                    &parsed
+                }
+                Err(_) => return Vec::new(),
+            }
+        }
+        other => other,
+    };
+    let items = value.get("questions").unwrap_or(value);
+
+    items
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| serde_json::from_value::<BankQuestion>(item.clone()).ok())
+                .filter(|q| q.validate().is_ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Question bank directory from `QUIZ_BANK_DIR` (default `assets/quizzes`).
+pub fn question_bank_dir() -> PathBuf {
+    std::env::var("QUIZ_BANK_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("assets/quizzes"))
+}
+
+/// Loads every `*.json` question bank in `dir` as approved questions.
+///
+/// Existing questions from files are updated, so editing a bank file and
+/// restarting fixes a question. Malformed files and questions are skipped
+/// with a warning. Returns how many questions were loaded.
+///
+/// # Errors
+///
+/// Returns an error if a database operation fails.
+pub async fn load_question_banks(pool: &PgPool, dir: &Path) -> Result<usize, sqlx::Error> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            warn!("Question banks not loaded from {}: {}", dir.display(), e);
+            return Ok(0);
+        }
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+
+    let mut loaded = 0;
+    for path in paths {
+        let bank = match std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| serde_json::from_str::<QuestionBank>(&text).map_err(|e| e.to_string()))
+        {
+            Ok(bank) => bank,
+            Err(e) => {
+                warn!("Skipping question bank {}: {}", path.display(), e);
+                continue;
+            }
+        };
+
+        for question in &bank.questions {
+            if let Err(e) = question.validate() {
+                warn!("Skipping question in {}: {} ({})", path.display(), e, question.question);
+                continue;
+            }
+            sqlx::query!(
+                r#"
+                INSERT INTO quiz_questions
+                    (skill_name, question, options, correct_option, explanation, source, status, reviewed_at)
+                VALUES ($1, $2, $3, $4, $5, 'file', 'approved', CURRENT_TIMESTAMP)
+                ON CONFLICT (LOWER(skill_name), md5(question)) DO UPDATE
+                SET options = EXCLUDED.options,
+                    correct_option = EXCLUDED.correct_option,
+                    explanation = EXCLUDED.explanation
+                WHERE quiz_questions.source = 'file'
+                "#,
+                bank.skill.trim(),
+                question.question.trim(),
+                &question.options,
+                question.answer as i32,
+                question.explanation.as_deref()
+            )
+            .execute(pool)
+            .await?;
+            loaded += 1;
+        }
+    }
+
+    info!("Loaded {} quiz question(s) from {}", loaded, dir.display());
+    Ok(loaded)
+}
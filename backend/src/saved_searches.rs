@@ -0,0 +1,557 @@
+//! Saved job searches.
+//!
+//! Users save job filters with a minimum match score. A background job
+//! ([`crate::queue::Task::CheckSavedSearches`]) checks the jobs added since
+//! each search last ran (internal jobs and imported external listings),
+//! records the ones matching it and alerts the user in-app; another
+//! ([`crate::queue::Task::SendSavedSearchDigests`]) sends the daily or weekly
+//! email digests through the configured [`Mailer`].
+
+use crate::ai_matching::{calculate_enhanced_match, load_skill_levels_by_user};
+use crate::external_jobs::{StoredExternalJob, external_jobs_first_seen_between};
+use crate::mailer::{Email, Mailer};
+use crate::models::SavedSearchMatch;
+use crate::notifications::{NewNotification, NotificationType, notify};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::time::Duration;
+use tracing::warn;
+use uuid::Uuid;
+
+/// Saved searches per user.
+pub const MAX_SAVED_SEARCHES: i64 = 20;
+
+/// Default interval of the background check.
+const DEFAULT_CHECK_INTERVAL_SECS: u64 = 3600;
+
+/// How often a saved search's new matches are emailed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DigestFrequency {
+    /// No email; in-app alerts only
+    #[default]
+    None,
+    /// One email a day
+    Daily,
+    /// One email a week
+    Weekly,
+}
+
+impl DigestFrequency {
+    /// Value stored in `saved_searches.digest_frequency`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DigestFrequency::None => "none",
+            DigestFrequency::Daily => "daily",
+            DigestFrequency::Weekly => "weekly",
+        }
+    }
+}
+
+/// Filters of a saved search; unset filters match every job.
+#[derive(Debug, Default)]
+pub struct SearchFilters<'a> {
+    /// Words that must all appear in the title, company or description
+    pub keywords: Option<&'a str>,
+    /// Text the location must contain
+    pub location: Option<&'a str>,
+    /// Required experience level
+    pub experience_level: Option<&'a str>,
+    /// Required job type
+    pub job_type: Option<&'a str>,
+    /// The job must require at least one of these skills
+    pub skills: &'a [String],
+}
+
+/// A job considered by saved searches: an internal job or an external listing.
+#[derive(Debug, Clone)]
+pub struct SearchCandidate {
+    /// Unique key, `job:<id>` or `external:<id>`
+    pub key: String,
+    /// ID of an internal job
+    pub job_id: Option<i32>,
+    /// Job title
+    pub title: String,
+    /// Employer
+    pub company: String,
+    /// Location
+    pub location: String,
+    /// Description
+    pub description: String,
+    /// Required skills
+    pub skills: Vec<String>,
+    /// Experience level, e.g. `junior`
+    pub experience_level: Option<String>,
+    /// Job type, e.g. `full_time`
+    pub job_type: Option<String>,
+    /// Where to apply, for external listings
+    pub url: Option<String>,
+    /// `internal` or the external source's name
+    pub source: String,
+    /// When the job was added (internal) or first imported (external)
+    pub added_at: DateTime<Utc>,
+}
+
+impl SearchCandidate {
+    /// Candidate for an internal job.
+    #[allow(clippy::too_many_arguments)]
+    pub fn internal(
+        id: i32,
+        title: String,
+        company: String,
+        location: String,
+        description: String,
+        skills: Vec<String>,
+        experience_level: String,
+        job_type: String,
+        created_at: DateTime<Utc>,
+    ) -> Self {
+        SearchCandidate {
+            key: format!("job:{}", id),
+            job_id: Some(id),
+            title,
+            company,
+            location,
+            description,
+            skills,
+            experience_level: Some(experience_level),
+            job_type: Some(job_type),
+            url: None,
+            source: "internal".to_string(),
+            added_at: created_at,
+        }
+    }
+
+    /// Candidate for an imported external listing.
+    ///
+    /// Uses the normalized job type and experience level where the published
+    /// ones could be mapped, and the skills found in the description.
+    pub fn external(stored: StoredExternalJob) -> Self {
+        let skills = stored.skills();
+        let normalized = stored.normalized;
+        let job = stored.listing;
+        SearchCandidate {
+            key: format!("external:{}", job.id),
+            job_id: None,
+            title: job.title,
+            company: job.company,
+            location: job.location,
+            description: job.description,
+            skills,
+            experience_level: normalized
+                .experience_level
+                .map(|level| level.as_str().to_string())
+                .or(job.experience_level),
+            job_type: normalized.job_type.map(|job_type| job_type.as_str().to_string()).or(job.job_type),
+            url: Some(job.url),
+            source: job.source,
+            added_at: stored.first_seen_at,
+        }
+    }
+
+    /// Whether the job was added after `since`.
+    pub fn is_new_since(&self, since: DateTime<Utc>) -> bool {
+        self.added_at > since
+    }
+
+    /// Whether the job passes the filters; text is compared
+    /// case-insensitively and `Full-time` equals `full_time`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use backend::saved_searches::{SearchCandidate, SearchFilters};
+    ///
+    /// let job = SearchCandidate::internal(
+    ///     7,
+    ///     "Junior Data Analyst".to_string(),
+    ///     "Acme".to_string(),
+    ///     "Dhaka, Bangladesh".to_string(),
+    ///     "Reporting with SQL".to_string(),
+    ///     vec!["SQL".to_string(), "Excel".to_string()],
+    ///     "junior".to_string(),
+    ///     "full_time".to_string(),
+    ///     chrono::Utc::now(),
+    /// );
+    /// let skills = vec!["sql".to_string(), "Python".to_string()];
+    /// let filters = SearchFilters {
+    ///     keywords: Some("data analyst"),
+    ///     location: Some("dhaka"),
+    ///     job_type: Some("Full-time"),
+    ///     skills: &skills,
+    ///     ..Default::default()
+    /// };
+    /// assert!(job.matches(&filters));
+    /// assert!(!job.matches(&SearchFilters { experience_level: Some("mid"), ..filters }));
+    /// ```
+    pub fn matches(&self, filters: &SearchFilters) -> bool {
+        if let Some(keywords) = filters.keywords {
+            let text = format!("{} {} {}", self.title, self.company, self.description).to_lowercase();
+            if !keywords.split_whitespace().all(|word| text.contains(&word.to_lowercase())) {
+                return false;
+            }
+        }
+        if let Some(location) = filters.location
+            && !self.location.to_lowercase().contains(&location.trim().to_lowercase())
+        {
+            return false;
+        }
+        if !same_category(filters.experience_level, self.experience_level.as_deref())
+            || !same_category(filters.job_type, self.job_type.as_deref())
+        {
+            return false;
+        }
+        filters.skills.is_empty()
+            || filters
+                .skills
+                .iter()
+                .any(|wanted| self.skills.iter().any(|skill| skill.eq_ignore_ascii_case(wanted.trim())))
+    }
+}
+
+/// Whether a category value passes a filter on it.
+fn same_category(filter: Option<&str>, value: Option<&str>) -> bool {
+    let normalize = |s: &str| s.trim().to_lowercase().replace(['-', ' '], "_");
+    match (filter, value) {
+        (None, _) => true,
+        (Some(filter), Some(value)) => normalize(filter) == normalize(value),
+        (Some(_), None) => false,
+    }
+}
+
+/// Check interval from `SAVED_SEARCH_INTERVAL_SECS` (default one hour).
+pub fn check_interval() -> Duration {
+    let secs = std::env::var("SAVED_SEARCH_INTERVAL_SECS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|secs| *secs > 0)
+        .unwrap_or(DEFAULT_CHECK_INTERVAL_SECS);
+    Duration::from_secs(secs)
+}
+
+/// A saved search as the background check needs it.
+#[derive(Debug, FromRow)]
+struct ActiveSearch {
+    id: i32,
+    user_id: Uuid,
+    name: String,
+    keywords: Option<String>,
+    location: Option<String>,
+    experience_level: Option<String>,
+    job_type: Option<String>,
+    skills: Vec<String>,
+    min_match_score: i32,
+    include_external: bool,
+    alerts_enabled: bool,
+    last_checked_at: DateTime<Utc>,
+}
+
+/// Profile fields a user's matches are scored with.
+#[derive(Debug, FromRow)]
+struct MatchProfile {
+    id: Uuid,
+    skills: Vec<String>,
+    experience_level: Option<String>,
+    preferred_track: Option<String>,
+}
+
+/// Records the jobs added since each saved search last ran that pass its
+/// filters and minimum match score, and alerts users with alerts on.
+///
+/// Returns how many matches were recorded.
+///
+/// # Errors
+///
+/// Returns an error if a database operation fails.
+pub async fn check_saved_searches(pool: &PgPool) -> Result<usize, sqlx::Error> {
+    let searches = sqlx::query_as!(
+        ActiveSearch,
+        r#"
+        SELECT s.id, s.user_id, s.name, s.keywords, s.location, s.experience_level::TEXT,
+               s.job_type::TEXT, s.skills, s.min_match_score, s.include_external,
+               s.alerts_enabled, s.last_checked_at
+        FROM saved_searches s
+        JOIN users u ON u.id = s.user_id
+        WHERE u.deletion_scheduled_for IS NULL
+          AND (s.alerts_enabled OR s.digest_frequency <> 'none')
+        "#
+    )
+    .fetch_all(pool)
+    .await?;
+    let Some(since) = searches.iter().map(|s| s.last_checked_at).min() else {
+        return Ok(0);
+    };
+    let checked_at: DateTime<Utc> = sqlx::query_scalar!(r#"SELECT CURRENT_TIMESTAMP AS "now!""#).fetch_one(pool).await?;
+
+    let jobs = sqlx::query!(
+        r#"
+        SELECT id, job_title, company, location, job_description, required_skills,
+               experience_level::TEXT AS "experience_level!", job_type::TEXT AS "job_type!",
+               created_at AS "created_at!"
+        FROM jobs
+        WHERE created_at > $1 AND created_at <= $2
+        "#,
+        since,
+        checked_at
+    )
+    .fetch_all(pool)
+    .await?;
+    let mut candidates: Vec<SearchCandidate> = jobs
+        .into_iter()
+        .map(|job| {
+            SearchCandidate::internal(
+                job.id,
+                job.job_title,
+                job.company,
+                job.location,
+                job.job_description,
+                job.required_skills,
+                job.experience_level,
+                job.job_type,
+                job.created_at,
+            )
+        })
+        .collect();
+    if searches.iter().any(|s| s.include_external) {
+        let external = external_jobs_first_seen_between(pool, since, checked_at).await?;
+        candidates.extend(external.into_iter().map(SearchCandidate::external));
+    }
+
+    let mut user_ids: Vec<Uuid> = searches.iter().map(|s| s.user_id).collect();
+    user_ids.sort();
+    user_ids.dedup();
+    let profiles: HashMap<Uuid, MatchProfile> = sqlx::query_as!(
+        MatchProfile,
+        "SELECT id, skills, experience_level::TEXT, preferred_track::TEXT FROM users WHERE id = ANY($1)",
+        &user_ids
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|profile| (profile.id, profile))
+    .collect();
+    let levels_by_user = load_skill_levels_by_user(pool).await?;
+    let no_levels = HashMap::new();
+
+    let mut recorded = 0;
+    for search in &searches {
+        let Some(profile) = profiles.get(&search.user_id) else {
+            continue;
+        };
+        let skill_levels = levels_by_user.get(&search.user_id).unwrap_or(&no_levels);
+        let filters = SearchFilters {
+            keywords: search.keywords.as_deref(),
+            location: search.location.as_deref(),
+            experience_level: search.experience_level.as_deref(),
+            job_type: search.job_type.as_deref(),
+            skills: &search.skills,
+        };
+
+        let mut new_matches = Vec::new();
+        for candidate in &candidates {
+            if (candidate.job_id.is_none() && !search.include_external)
+                || !candidate.is_new_since(search.last_checked_at)
+                || !candidate.matches(&filters)
+            {
+                continue;
+            }
+            let analysis = calculate_enhanced_match(
+                &profile.skills,
+                skill_levels,
+                &candidate.skills,
+                profile.experience_level.as_deref(),
+                candidate.experience_level.as_deref().unwrap_or(""),
+                profile.preferred_track.as_deref(),
+                &candidate.title,
+            );
+            if analysis.match_score < search.min_match_score as f64 {
+                continue;
+            }
+            let inserted: Option<i32> = sqlx::query_scalar!(
+                r#"
+                INSERT INTO saved_search_matches
+                    (saved_search_id, user_id, job_key, job_id, title, company, location, url, source, match_score)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                ON CONFLICT (saved_search_id, job_key) DO NOTHING
+                RETURNING id
+                "#,
+                search.id,
+                search.user_id,
+                &candidate.key,
+                candidate.job_id,
+                &candidate.title,
+                &candidate.company,
+                &candidate.location,
+                candidate.url.as_deref(),
+                &candidate.source,
+                analysis.match_score
+            )
+            .fetch_optional(pool)
+            .await?;
+            if inserted.is_some() {
+                new_matches.push((candidate, analysis.match_score));
+            }
+        }
+
+        if search.alerts_enabled && !new_matches.is_empty() {
+            notify(pool, search.user_id, alert_for(search, &new_matches)).await?;
+        }
+        recorded += new_matches.len();
+
+        sqlx::query!(
+            "UPDATE saved_searches SET last_checked_at = $2 WHERE id = $1",
+            search.id,
+            checked_at
+        )
+        .execute(pool)
+        .await?;
+    }
+    Ok(recorded)
+}
+
+/// One in-app alert for all of a check's new matches of a search.
+fn alert_for(search: &ActiveSearch, matches: &[(&SearchCandidate, f64)]) -> NewNotification {
+    let message = match matches {
+        [(job, score)] => format!("{} at {} matches your profile ({:.0}%).", job.title, job.company, score),
+        _ => format!("{} new jobs match your saved search.", matches.len()),
+    };
+    NewNotification {
+        kind: NotificationType::SavedSearch,
+        title: format!("New jobs for \"{}\"", search.name),
+        message,
+        link: Some(format!("/api/saved-searches/{}/matches", search.id)),
+        dedup_key: None,
+    }
+}
+
+/// A saved search whose digest is due.
+#[derive(Debug, FromRow)]
+struct DueDigest {
+    id: i32,
+    user_id: Uuid,
+    name: String,
+    email: String,
+    full_name: String,
+}
+
+/// Emails each user whose daily or weekly digest is due the matches not
+/// emailed yet, one email covering all their due searches.
+///
+/// A digest without new matches sends nothing. A failed send is not retried
+/// right away: the digest waits for its next due date, and the matches it
+/// could not send go out with that one. Returns how many emails were sent.
+///
+/// # Errors
+///
+/// Returns an error if a database operation fails.
+pub async fn send_due_digests(pool: &PgPool, mailer: &dyn Mailer) -> Result<usize, sqlx::Error> {
+    let due = sqlx::query_as!(
+        DueDigest,
+        r#"
+        SELECT s.id, s.user_id, s.name, u.email, u.full_name
+        FROM saved_searches s
+        JOIN users u ON u.id = s.user_id
+        WHERE s.digest_frequency <> 'none'
+          AND u.deletion_scheduled_for IS NULL
+          AND COALESCE(s.last_digest_at, s.created_at) <= CURRENT_TIMESTAMP
+              - CASE s.digest_frequency WHEN 'daily' THEN INTERVAL '1 day' ELSE INTERVAL '7 days' END
+        ORDER BY s.user_id, s.name
+        "#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut by_user: Vec<(Uuid, Vec<&DueDigest>)> = Vec::new();
+    for digest in &due {
+        match by_user.last_mut() {
+            Some((user_id, searches)) if *user_id == digest.user_id => searches.push(digest),
+            _ => by_user.push((digest.user_id, vec![digest])),
+        }
+    }
+
+    let mut sent = 0;
+    for (user_id, searches) in by_user {
+        let search_ids: Vec<i32> = searches.iter().map(|s| s.id).collect();
+        let matches = sqlx::query_as!(
+            SavedSearchMatch,
+            r#"
+            SELECT id, saved_search_id, job_key, job_id, title, company, location, url, source,
+                   match_score, matched_at, emailed_at
+            FROM saved_search_matches
+            WHERE saved_search_id = ANY($1) AND emailed_at IS NULL
+            ORDER BY match_score DESC, matched_at DESC
+            "#,
+            &search_ids
+        )
+        .fetch_all(pool)
+        .await?;
+
+        if !matches.is_empty() {
+            let email = digest_email(&searches, &matches);
+            match mailer.send(&email).await {
+                Ok(()) => {
+                    let match_ids: Vec<i32> = matches.iter().map(|m| m.id).collect();
+                    sqlx::query!(
+                        "UPDATE saved_search_matches SET emailed_at = CURRENT_TIMESTAMP WHERE id = ANY($1)",
+                        &match_ids
+                    )
+                    .execute(pool)
+                    .await?;
+                    sent += 1;
+                }
+                // The matches stay unsent and go out with the next digest
+                Err(e) => warn!("Failed to send saved search digest to user {}: {}", user_id, e),
+            }
+        }
+
+        sqlx::query!(
+            "UPDATE saved_searches SET last_digest_at = CURRENT_TIMESTAMP WHERE id = ANY($1)",
+            &search_ids
+        )
+        .execute(pool)
+        .await?;
+    }
+    Ok(sent)
+}
+
+fn digest_email(searches: &[&DueDigest], matches: &[SavedSearchMatch]) -> Email {
+    let recipient = searches[0];
+    let mut body = format!(
+        "Hi {},\n\n{} new job{} matched your saved searches.\n",
+        recipient.full_name,
+        matches.len(),
+        if matches.len() == 1 { "" } else { "s" }
+    );
+    for search in searches {
+        let search_matches: Vec<&SavedSearchMatch> =
+            matches.iter().filter(|m| m.saved_search_id == search.id).collect();
+        if search_matches.is_empty() {
+            continue;
+        }
+        let _ = write!(body, "\n{}\n", search.name);
+        for job in search_matches {
+            let _ = write!(body, "- {} at {} ({:.0}% match)", job.title, job.company, job.match_score);
+            if let Some(location) = &job.location {
+                let _ = write!(body, ", {}", location);
+            }
+            if let Some(url) = &job.url {
+                let _ = write!(body, "\n  {}", url);
+            }
+            body.push('\n');
+        }
+    }
+    body.push_str("\nChange how often you get this email in your saved search settings.\n");
+
+    Email {
+        to: recipient.email.clone(),
+        subject: format!(
+            "{} new job{} for your saved searches",
+            matches.len(),
+            if matches.len() == 1 { "" } else { "s" }
+        ),
+        body,
+    }
+}
@@ -3,7 +3,7 @@
 import { useRouter } from "next/navigation"
 import { useState } from "react"
 import dynamic from "next/dynamic"
-import { profileApi } from "@/lib/api"
+import { profileApi, type ExperienceLevel } from "@/lib/api"
 import { toast } from "sonner"
 
 // Lazy load OnboardingFlow
@@ -24,11 +24,13 @@ export default function OnboardingPage() {
     
     try {
       // Map frontend values to API values
-      const experienceLevelMap: Record<string, 'fresher' | 'junior' | 'mid'> = {
+      const experienceLevelMap: Record<string, ExperienceLevel> = {
         'fresher': 'fresher',
         'junior': 'junior',
         'mid': 'mid',
-        'senior': 'mid', // Map senior to mid as API only accepts fresher, junior, mid
+        'senior': 'senior',
+        'lead': 'lead',
+        'manager': 'manager',
       }
 
       const trackMap: Record<string, 'web_development' | 'data' | 'design' | 'marketing'> = {
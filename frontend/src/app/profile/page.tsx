@@ -5,7 +5,7 @@ import dynamic from "next/dynamic"
 import { motion } from "framer-motion"
 import Navbar from "@/components/Navbar"
 import { Button } from "@/components/ui/button"
-import { profileApi } from "@/lib/api"
+import { profileApi, type ExperienceLevel } from "@/lib/api"
 import { toast } from "sonner"
 import { useRouter } from "next/navigation"
 import { CVUpload } from "@/components/CVUpload"
@@ -59,6 +59,9 @@ export default function ProfilePage() {
           'fresher': 'fresher',
           'junior': 'junior',
           'mid': 'mid',
+          'senior': 'senior',
+          'lead': 'lead',
+          'manager': 'manager',
         }
 
         const trackMap: Record<string, string> = {
@@ -135,11 +138,13 @@ export default function ProfilePage() {
 
     try {
       // Map frontend values to API values
-      const experienceLevelMap: Record<string, 'fresher' | 'junior' | 'mid'> = {
+      const experienceLevelMap: Record<string, ExperienceLevel> = {
         'fresher': 'fresher',
         'junior': 'junior',
         'mid': 'mid',
-        'senior': 'mid',
+        'senior': 'senior',
+        'lead': 'lead',
+        'manager': 'manager',
       }
 
       const trackMap: Record<string, 'web_development' | 'data' | 'design' | 'marketing'> = {
@@ -306,7 +311,9 @@ export default function ProfilePage() {
                         <SelectItem value="fresher">Fresher (0-1 years)</SelectItem>
                         <SelectItem value="junior">Junior (1-3 years)</SelectItem>
                         <SelectItem value="mid">Mid-level (3-5 years)</SelectItem>
-                        <SelectItem value="senior">Senior (5+ years)</SelectItem>
+                        <SelectItem value="senior">Senior (5-8 years)</SelectItem>
+                        <SelectItem value="lead">Lead (8+ years)</SelectItem>
+                        <SelectItem value="manager">Manager</SelectItem>
                       </SelectContent>
                     </Select>
                   </div>
@@ -100,22 +100,32 @@ export default function RoadmapPage() {
 
     setGenerating(true)
     try {
-      const result = await aiApi.generateRoadmap(
+      const role = targetRole.trim()
+      const { job_id } = await aiApi.generateRoadmap(
         targetRole, 
         timeframeMonths, 
         learningHours, 
         true, 
         'gemini'
       )
-      toast.success('Roadmap generated successfully!')
+      toast.info('Generating your roadmap...')
       setTargetRole("")
       setShowGenerateDialog(false)
-      await loadRoadmaps()
-      
-      // Auto-select the new roadmap
-      if (result.roadmap_id) {
-        const newRoadmap = await aiApi.getRoadmapById(result.roadmap_id)
-        setSelectedRoadmap(newRoadmap.roadmap)
+
+      // Generation runs as a background job; wait for it to finish
+      const job = await aiApi.waitForAiJob(job_id)
+      if (job.status === 'dead') {
+        throw new Error(job.error || 'Failed to generate roadmap')
+      }
+      toast.success('Roadmap generated successfully!')
+
+      // Auto-select the new roadmap (the list is newest first)
+      const data = await aiApi.getRoadmaps()
+      const list = data.roadmaps || []
+      setRoadmaps(list)
+      const newRoadmap = list.find((r: any) => r.target_role === role) || list[0]
+      if (newRoadmap) {
+        setSelectedRoadmap(newRoadmap)
       }
     } catch (err: any) {
       toast.error(err.message || 'Failed to generate roadmap')
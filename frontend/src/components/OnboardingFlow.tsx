@@ -32,7 +32,9 @@ const steps = [
       { value: "fresher", label: "Fresher (0-1 years)" },
       { value: "junior", label: "Junior (1-3 years)" },
       { value: "mid", label: "Mid-level (3-5 years)" },
-      { value: "senior", label: "Senior (5+ years)" },
+      { value: "senior", label: "Senior (5-8 years)" },
+      { value: "lead", label: "Lead (8+ years)" },
+      { value: "manager", label: "Manager" },
     ],
   },
   {
@@ -58,6 +58,8 @@ export interface RegisterResponse {
   user_id: string;
 }
 
+export type ExperienceLevel = 'fresher' | 'junior' | 'mid' | 'senior' | 'lead' | 'manager';
+
 export interface LoginResponse {
   token: string;
   user: {
@@ -81,12 +83,16 @@ export interface ProfileResponse {
   email: string;
   profile_completed: boolean;
   education_level: string | null;
-  experience_level: 'fresher' | 'junior' | 'mid' | null;
+  experience_level: ExperienceLevel | null;
+  experience_years_min: number | null;
+  experience_years_max: number | null;
   preferred_track: 'web_development' | 'data' | 'design' | 'marketing' | null;
   skills: string[];
   projects: string[];
   target_roles: string[];
   raw_cv_text: string | null;
+  /** Send back as `If-Match` to make a profile update conditional */
+  profile_version: number;
   oauth_provider: string | null;
   avatar_url: string | null;
   created_at: string;
@@ -263,7 +269,9 @@ export const profileApi = {
   // Complete profile (onboarding)
   completeProfile: async (data: {
     education_level: string;
-    experience_level: 'fresher' | 'junior' | 'mid';
+    experience_level: ExperienceLevel;
+    experience_years_min?: number;
+    experience_years_max?: number;
     preferred_track: 'web_development' | 'data' | 'design' | 'marketing';
     skills: string[];
     projects: string[];
@@ -293,7 +301,9 @@ export const profileApi = {
   updateProfile: async (updates: {
     full_name?: string;
     education_level?: string;
-    experience_level?: 'fresher' | 'junior' | 'mid';
+    experience_level?: ExperienceLevel;
+    experience_years_min?: number;
+    experience_years_max?: number;
     skills?: string[];
     projects?: string[];
     target_roles?: string[];
@@ -364,7 +374,7 @@ export const profileApi = {
 export const jobsApi = {
   // Get job recommendations with caching
   getRecommendations: async (filters?: {
-    experience_level?: 'fresher' | 'junior' | 'mid';
+    experience_level?: ExperienceLevel;
     job_type?: 'internship' | 'part_time' | 'full_time' | 'freelance';
     limit?: number;
   }): Promise<JobRecommendation[]> => {
@@ -422,6 +432,20 @@ export interface ExternalJob {
   salary: string | null;
 }
 
+// Whether a source's listings are current: live, cached from an earlier import, or unavailable
+export interface ExternalSourceStatus {
+  source: string;
+  category: string;
+  status: 'live' | 'cached' | 'unavailable';
+  synced_at: string | null;
+  demo: boolean;
+}
+
+export interface ExternalJobsResponse {
+  jobs: ExternalJob[];
+  sources: ExternalSourceStatus[];
+}
+
 export const externalJobsApi = {
   // Get all external jobs
   getAll: async (): Promise<ExternalJob[]> => {
@@ -440,7 +464,8 @@ export const externalJobsApi = {
       throw new Error(error.error || 'Failed to fetch external jobs');
     }
 
-    return await response.json();
+    const data: ExternalJobsResponse = await response.json();
+    return data.jobs;
   },
 
   // Get NGO jobs from ReliefWeb
@@ -460,7 +485,8 @@ export const externalJobsApi = {
       throw new Error(error.error || 'Failed to fetch NGO jobs');
     }
 
-    return await response.json();
+    const data: ExternalJobsResponse = await response.json();
+    return data.jobs;
   },
 
   // Get government portal jobs
@@ -480,7 +506,8 @@ export const externalJobsApi = {
       throw new Error(error.error || 'Failed to fetch government jobs');
     }
 
-    return await response.json();
+    const data: ExternalJobsResponse = await response.json();
+    return data.jobs;
   },
 
   // Get local job board listings
@@ -500,7 +527,8 @@ export const externalJobsApi = {
       throw new Error(error.error || 'Failed to fetch local jobs');
     }
 
-    return await response.json();
+    const data: ExternalJobsResponse = await response.json();
+    return data.jobs;
   },
 };
 
@@ -663,6 +691,25 @@ export const progressApi = {
 };
 
 // AI APIs
+export type AiJobKind = 'roadmap' | 'cover_letter' | 'cv_enrichment';
+
+// Returned with 202 by endpoints that run the AI call as a background job
+export interface AiJobAccepted {
+  job_id: number;
+  job: AiJobKind;
+}
+
+export interface AiJobStatus {
+  job_id: number;
+  job: AiJobKind;
+  status: 'pending' | 'running' | 'succeeded' | 'dead';
+  attempts: number;
+  max_attempts: number;
+  error: string | null;
+  created_at: string;
+  finished_at: string | null;
+}
+
 export const aiApi = {
   // Extract skills from CV text
   extractSkills: async (cvText: string, provider: 'gemini' | 'groq' = 'gemini', updateProfile: boolean = true): Promise<any> => {
@@ -692,7 +739,7 @@ export const aiApi = {
     learningHoursPerWeek: number = 10,
     includeCurrentSkills: boolean = true, 
     provider: 'gemini' | 'groq' = 'gemini'
-  ): Promise<any> => {
+  ): Promise<AiJobAccepted> => {
     const token = getToken();
     const response = await fetch(`${API_BASE_URL}/ai/roadmap`, {
       method: 'POST',
@@ -714,6 +761,38 @@ export const aiApi = {
     return await response.json();
   },
 
+  // Get the status of a background AI job
+  getAiJob: async (jobId: number): Promise<AiJobStatus> => {
+    const token = getToken();
+    const response = await fetch(`${API_BASE_URL}/ai/jobs/${jobId}`, {
+      headers: getHeaders(token),
+    });
+
+    if (!response.ok) {
+      const error = await response.json();
+      throw new Error(error.error || 'Failed to fetch AI job');
+    }
+
+    return await response.json();
+  },
+
+  // Poll a background AI job until it succeeds or dies
+  waitForAiJob: async (
+    jobId: number,
+    intervalMs: number = 2000,
+    timeoutMs: number = 5 * 60 * 1000
+  ): Promise<AiJobStatus> => {
+    const deadline = Date.now() + timeoutMs;
+    while (Date.now() < deadline) {
+      const job = await aiApi.getAiJob(jobId);
+      if (job.status === 'succeeded' || job.status === 'dead') {
+        return job;
+      }
+      await new Promise((resolve) => setTimeout(resolve, intervalMs));
+    }
+    throw new Error('The AI job is taking longer than expected. Check back later.');
+  },
+
   // Get all saved roadmaps
   getRoadmaps: async (): Promise<any> => {
     const token = getToken();